@@ -93,6 +93,22 @@ pub enum Direction {
     Backward,
 }
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RevertError {
+    #[error("Block #{0} is above the head of the chain")]
+    AboveHead(u32),
+    #[error("Block #{0} is below the last macro block #{1}, reverting requires force")]
+    Finalized(u32, u32),
+    #[error("Block #{0} is below the last election block #{1}")]
+    BelowElection(u32, u32),
+    #[error("Block #{0} is outside of the stored revert window")]
+    OutsideRevertWindow(u32),
+    #[error("The accounts tree is incomplete")]
+    IncompleteAccounts,
+    #[error("Blockchain error: {0}")]
+    BlockchainError(#[from] BlockchainError),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ChunksPushResult {
     EmptyChunks,
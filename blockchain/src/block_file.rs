@@ -0,0 +1,363 @@
+//! A portable file format to export ranges of blocks and replay them into another node.
+//!
+//! A block file starts with a header consisting of the magic bytes, the format version and the
+//! network id. The blocks follow in ascending order, each one serialized and prefixed with its
+//! length as a big-endian `u32`. A zero length marks the end of the blocks. It is followed by the
+//! number of blocks and a Blake2b checksum over all preceding bytes of the file.
+
+use std::io::{self, Read, Write};
+
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
+use nimiq_database::{traits::Database, DatabaseProxy, TransactionProxy};
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use parking_lot::RwLock;
+use thiserror::Error;
+
+use crate::{chain_store::ChainStore, Blockchain};
+
+/// The magic bytes at the start of every block file.
+pub const MAGIC: [u8; 4] = *b"NBLK";
+/// The version of the block file format.
+pub const VERSION: u16 = 1;
+/// The maximum serialized size of a single block that is accepted when reading a block file.
+const MAX_BLOCK_SIZE: u32 = 16 * 1024 * 1024;
+
+/// The errors that can occur when writing or reading block files.
+#[derive(Debug, Error)]
+pub enum BlockFileError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Not a block file")]
+    InvalidMagic,
+    #[error("Unsupported block file version {0}")]
+    UnsupportedVersion(u16),
+    #[error("Unknown network id {0}")]
+    UnknownNetwork(u8),
+    #[error("The block file belongs to network {0}, expected {1}")]
+    NetworkMismatch(NetworkId, NetworkId),
+    #[error("Block of {0} bytes exceeds the maximum block size")]
+    BlockTooLarge(u32),
+    #[error("Invalid block: {0}")]
+    InvalidBlock(#[from] DeserializeError),
+    #[error("Block count mismatch: the file claims {0} blocks, but contains {1}")]
+    CountMismatch(u32, u32),
+    #[error("Checksum mismatch")]
+    ChecksumMismatch,
+    #[error("Invalid block range #{0} to #{1}")]
+    InvalidRange(u32, u32),
+    #[error("Block #{0} or its body is not stored")]
+    BlockNotFound(u32),
+    #[error("Failed to push block #{0}: {1}")]
+    Push(u32, PushError),
+}
+
+/// Writes blocks to a block file. The file is only complete after calling [`finish`].
+///
+/// [`finish`]: BlockFileWriter::finish
+pub struct BlockFileWriter<W: Write> {
+    writer: W,
+    hasher: Blake2bHasher,
+    num_blocks: u32,
+}
+
+impl<W: Write> BlockFileWriter<W> {
+    /// Creates a writer and writes the header of the block file.
+    pub fn new(writer: W, network_id: NetworkId) -> Result<Self, BlockFileError> {
+        let mut file_writer = Self {
+            writer,
+            hasher: Blake2bHasher::new(),
+            num_blocks: 0,
+        };
+        file_writer.write_all(&MAGIC)?;
+        file_writer.write_all(&VERSION.to_be_bytes())?;
+        file_writer.write_all(&[network_id as u8])?;
+        Ok(file_writer)
+    }
+
+    /// Appends a block to the file.
+    pub fn write_block(&mut self, block: &Block) -> Result<(), BlockFileError> {
+        let data = block.serialize_to_vec();
+        let len = data.len() as u32;
+        if len > MAX_BLOCK_SIZE {
+            return Err(BlockFileError::BlockTooLarge(len));
+        }
+        self.write_all(&len.to_be_bytes())?;
+        self.write_all(&data)?;
+        self.num_blocks += 1;
+        Ok(())
+    }
+
+    /// Writes the end marker, the number of blocks and the checksum. Returns the inner writer.
+    pub fn finish(mut self) -> Result<W, BlockFileError> {
+        self.write_all(&0u32.to_be_bytes())?;
+        self.write_all(&self.num_blocks.to_be_bytes())?;
+        let checksum = self.hasher.finish();
+        self.writer.write_all(checksum.as_ref())?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.hasher.write_all(buf)?;
+        self.writer.write_all(buf)
+    }
+}
+
+/// Reads blocks from a block file. The checksum is verified once the end of the blocks is reached,
+/// so consumers must not rely on the blocks being intact before [`read_block`] returned `None`.
+///
+/// [`read_block`]: BlockFileReader::read_block
+pub struct BlockFileReader<R: Read> {
+    reader: R,
+    hasher: Blake2bHasher,
+    network_id: NetworkId,
+    num_blocks: u32,
+    finished: bool,
+}
+
+impl<R: Read> BlockFileReader<R> {
+    /// Creates a reader and reads the header of the block file.
+    pub fn new(reader: R) -> Result<Self, BlockFileError> {
+        let mut file_reader = Self {
+            reader,
+            hasher: Blake2bHasher::new(),
+            network_id: NetworkId::Main,
+            num_blocks: 0,
+            finished: false,
+        };
+
+        let mut magic = [0u8; 4];
+        file_reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(BlockFileError::InvalidMagic);
+        }
+
+        let version = file_reader.read_u16()?;
+        if version != VERSION {
+            return Err(BlockFileError::UnsupportedVersion(version));
+        }
+
+        let mut network_id = [0u8; 1];
+        file_reader.read_exact(&mut network_id)?;
+        file_reader.network_id = NetworkId::try_from(network_id[0])
+            .map_err(|_| BlockFileError::UnknownNetwork(network_id[0]))?;
+
+        Ok(file_reader)
+    }
+
+    /// The network the blocks in the file belong to.
+    pub fn network_id(&self) -> NetworkId {
+        self.network_id
+    }
+
+    /// Reads the next block. Returns `None` once all blocks were read and the checksum matched.
+    pub fn read_block(&mut self) -> Result<Option<Block>, BlockFileError> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let len = self.read_u32()?;
+        if len == 0 {
+            return self.read_trailer().map(|_| None);
+        }
+        if len > MAX_BLOCK_SIZE {
+            return Err(BlockFileError::BlockTooLarge(len));
+        }
+
+        let mut data = vec![0u8; len as usize];
+        self.read_exact(&mut data)?;
+        let block = Block::deserialize_from_vec(&data)?;
+        self.num_blocks += 1;
+
+        Ok(Some(block))
+    }
+
+    fn read_trailer(&mut self) -> Result<(), BlockFileError> {
+        let num_blocks = self.read_u32()?;
+        if num_blocks != self.num_blocks {
+            return Err(BlockFileError::CountMismatch(num_blocks, self.num_blocks));
+        }
+
+        let expected = std::mem::take(&mut self.hasher).finish();
+        let mut checksum = [0u8; Blake2bHash::SIZE];
+        self.reader.read_exact(&mut checksum)?;
+        if checksum != expected.0 {
+            return Err(BlockFileError::ChecksumMismatch);
+        }
+
+        self.finished = true;
+        Ok(())
+    }
+
+    fn read_u16(&mut self) -> io::Result<u16> {
+        let mut buf = [0u8; 2];
+        self.read_exact(&mut buf)?;
+        Ok(u16::from_be_bytes(buf))
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let mut buf = [0u8; 4];
+        self.read_exact(&mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.reader.read_exact(buf)?;
+        self.hasher.write_all(buf)
+    }
+}
+
+/// The progress of a block import.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportProgress {
+    /// The number of blocks read from the file.
+    pub blocks_read: u32,
+    /// The number of blocks that extended or rebranched the chain.
+    pub blocks_imported: u32,
+    /// The number of blocks that were already known.
+    pub blocks_known: u32,
+    /// The block number of the last block read.
+    pub block_number: u32,
+}
+
+/// Exports blocks straight from the chain store, without loading a `Blockchain`. This allows
+/// exporting from a database that is opened read-only, e.g. while the node is running.
+pub struct BlockExporter {
+    env: DatabaseProxy,
+    chain_store: ChainStore,
+    network_id: NetworkId,
+}
+
+impl BlockExporter {
+    pub fn new(env: DatabaseProxy, network_id: NetworkId) -> Self {
+        Self {
+            chain_store: ChainStore::new(env.clone()),
+            env,
+            network_id,
+        }
+    }
+
+    /// Returns the block number of the stored head block, if there is one.
+    pub fn head_block_number(&self) -> Option<u32> {
+        self.head_block_number_in(&self.env.read_transaction())
+    }
+
+    fn head_block_number_in(&self, txn: &TransactionProxy) -> Option<u32> {
+        let head_hash = self.chain_store.get_head(Some(txn))?;
+        self.chain_store
+            .get_chain_info(&head_hash, false, Some(txn))
+            .ok()
+            .map(|info| info.head.block_number())
+    }
+
+    /// Writes the main chain blocks from `from` to `to` (inclusive), including their bodies, to a
+    /// block file. Returns the number of blocks written.
+    pub fn export_blocks<W: Write>(
+        &self,
+        from: u32,
+        to: u32,
+        writer: W,
+    ) -> Result<u32, BlockFileError> {
+        let txn = self.env.read_transaction();
+        match self.head_block_number_in(&txn) {
+            Some(head_block_number) if to <= head_block_number => {}
+            _ => return Err(BlockFileError::InvalidRange(from, to)),
+        }
+        write_blocks(&self.chain_store, &txn, self.network_id, from, to, writer)
+    }
+}
+
+/// Writes the main chain blocks from `from` to `to` (inclusive) within a single read transaction.
+fn write_blocks<W: Write>(
+    chain_store: &ChainStore,
+    txn: &TransactionProxy,
+    network_id: NetworkId,
+    from: u32,
+    to: u32,
+    writer: W,
+) -> Result<u32, BlockFileError> {
+    if from > to {
+        return Err(BlockFileError::InvalidRange(from, to));
+    }
+
+    let mut file_writer = BlockFileWriter::new(writer, network_id)?;
+    for block_number in from..=to {
+        let block = chain_store
+            .get_block_at(block_number, true, Some(txn))
+            .map_err(|_| BlockFileError::BlockNotFound(block_number))?;
+        if block.body().is_none() {
+            return Err(BlockFileError::BlockNotFound(block_number));
+        }
+        file_writer.write_block(&block)?;
+    }
+    file_writer.finish()?;
+
+    Ok(to - from + 1)
+}
+
+impl Blockchain {
+    /// Writes the main chain blocks from `from` to `to` (inclusive), including their bodies, to a
+    /// block file. Returns the number of blocks written.
+    pub fn export_blocks<W: Write>(
+        &self,
+        from: u32,
+        to: u32,
+        writer: W,
+    ) -> Result<u32, BlockFileError> {
+        if from > to || to > self.block_number() {
+            return Err(BlockFileError::InvalidRange(from, to));
+        }
+
+        let txn = self.read_transaction();
+        write_blocks(&self.chain_store, &txn, self.network_id, from, to, writer)
+    }
+
+    /// Reads all blocks from a block file and pushes them with full verification. The `progress`
+    /// callback is invoked after every block. Blocks that are already known are skipped.
+    ///
+    /// Since the checksum is only known at the end of the file, blocks of a corrupted file may
+    /// already have been pushed when the error is returned. They passed verification regardless.
+    pub fn import_blocks<R: Read>(
+        this: &RwLock<Self>,
+        reader: R,
+        mut progress: impl FnMut(&ImportProgress),
+    ) -> Result<ImportProgress, BlockFileError> {
+        let mut file_reader = BlockFileReader::new(reader)?;
+        let network_id = this.read().network_id;
+        if file_reader.network_id() != network_id {
+            return Err(BlockFileError::NetworkMismatch(
+                file_reader.network_id(),
+                network_id,
+            ));
+        }
+
+        let mut import_progress = ImportProgress::default();
+        while let Some(block) = file_reader.read_block()? {
+            let block_number = block.block_number();
+            import_progress.blocks_read += 1;
+            import_progress.block_number = block_number;
+
+            match Blockchain::push(this.upgradable_read(), block) {
+                Ok(PushResult::Known) => import_progress.blocks_known += 1,
+                Ok(PushResult::Extended | PushResult::Rebranched) => {
+                    import_progress.blocks_imported += 1
+                }
+                Ok(result) => {
+                    warn!(
+                        block_number,
+                        ?result,
+                        "Imported block is not on the main chain"
+                    );
+                }
+                Err(error) => return Err(BlockFileError::Push(block_number, error)),
+            }
+
+            progress(&import_progress);
+        }
+
+        Ok(import_progress)
+    }
+}
@@ -1,16 +1,18 @@
 use nimiq_account::{
     Account, Accounts, BlockLogger, BlockState, RevertInfo, TransactionOperationReceipt,
+    TransactionSimulation,
 };
-use nimiq_block::{Block, BlockError, SkipBlockInfo};
-use nimiq_blockchain_interface::PushError;
+use nimiq_block::{Block, BlockError, MacroBlock, SkipBlockInfo};
+use nimiq_blockchain_interface::{AbstractBlockchain, PushError};
 use nimiq_database::{traits::Database, TransactionProxy};
 use nimiq_keys::Address;
 use nimiq_primitives::{
+    account::AccountError,
     key_nibbles::KeyNibbles,
     trie::{error::IncompleteTrie, trie_diff::TrieDiff, trie_proof::TrieProof},
 };
 use nimiq_serde::Deserialize;
-use nimiq_transaction::historic_transaction::HistoricTransaction;
+use nimiq_transaction::{historic_transaction::HistoricTransaction, Transaction};
 use nimiq_trie::WriteTransactionProxy;
 
 use crate::Blockchain;
@@ -55,8 +57,9 @@ impl Blockchain {
                 }
 
                 // Macro blocks are final and receipts for the previous batch are no longer necessary
-                // as rebranching across this block is not possible.
-                self.chain_store.clear_revert_infos(txn.raw());
+                // as rebranching across this block is not possible. They are only kept within the
+                // configured revert window.
+                self.prune_revert_infos(txn.raw(), macro_block.block_number());
 
                 // Store the transactions and the inherents into the History tree.
                 let hist_txs = HistoricTransaction::from(
@@ -159,8 +162,8 @@ impl Blockchain {
         }
     }
 
-    /// Reverts the accounts given a block. This only applies to micro blocks and skip blocks, since
-    /// macro blocks are final and can't be reverted.
+    /// Reverts the accounts given a block. Macro blocks can only be reverted within the configured
+    /// revert window, see `revert_to`. Rebranching never reverts macro blocks.
     pub(crate) fn revert_accounts(
         &self,
         accounts: &Accounts,
@@ -168,11 +171,12 @@ impl Blockchain {
         block: &Block,
         block_logger: &mut BlockLogger,
     ) -> Result<u64, PushError> {
-        if block.is_macro() {
-            panic!("Can't revert {block} - macro blocks are final");
-        }
-
-        let block = block.unwrap_micro_ref();
+        let block = match block {
+            Block::Macro(macro_block) => {
+                return self.revert_macro_accounts(accounts, txn, macro_block, block_logger)
+            }
+            Block::Micro(micro_block) => micro_block,
+        };
         let body = block.body.as_ref().unwrap();
 
         debug!(
@@ -239,6 +243,68 @@ impl Blockchain {
         Ok(total_size)
     }
 
+    /// Reverts the accounts given a macro block, using the accounts trie diff recorded when the
+    /// block was committed. The inherents finalizing a batch can't be reverted otherwise.
+    fn revert_macro_accounts(
+        &self,
+        accounts: &Accounts,
+        txn: &mut WriteTransactionProxy,
+        block: &MacroBlock,
+        block_logger: &mut BlockLogger,
+    ) -> Result<u64, PushError> {
+        debug!(block = %block, "Reverting macro block");
+
+        // Verify accounts hash if the tree is complete or changes only happened in the complete part.
+        if let Some(accounts_hash) = accounts.get_root_hash(Some(txn)) {
+            assert_eq!(
+                block.header.state_root, accounts_hash,
+                "Cannot revert {} - inconsistent state",
+                block,
+            );
+        }
+
+        // Get the revert info for this block.
+        let revert_info = self
+            .chain_store
+            .get_revert_info(block.block_number(), Some(txn))
+            .expect("Failed to revert - missing revert info");
+
+        // Revert the block from AccountsTree. The revert info holds the recorded diff, so neither
+        // transactions nor inherents are needed.
+        let block_state = BlockState::new(block.block_number(), block.header.timestamp);
+        let result = accounts.revert(txn, &[], &[], &block_state, revert_info, block_logger);
+        if let Err(e) = result {
+            panic!("Failed to revert {block} - {e:?}");
+        }
+
+        // Remove the inherents of the block from the History tree.
+        let inherents = self.create_macro_block_inherents(block);
+        let num_txs = HistoricTransaction::count(0, &inherents, vec![]);
+        let (_, total_size) = self
+            .history_store
+            .remove_partial_history(txn.raw(), block.epoch_number(), num_txs)
+            .expect("Failed to remove partial history");
+
+        Ok(total_size)
+    }
+
+    /// Executes the transaction on top of the current state as if it was included in a block at
+    /// the given height (defaults to the next block), without persisting any changes.
+    /// The accounts tree must be complete.
+    pub fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+        block_number: Option<u32>,
+    ) -> Result<TransactionSimulation, AccountError> {
+        let block_number = block_number.unwrap_or_else(|| self.block_number() + 1);
+        let timestamp = self.now().max(self.timestamp());
+        let block_state = BlockState::new(block_number, timestamp);
+
+        self.state
+            .accounts
+            .simulate_transaction(transaction, &block_state)
+    }
+
     /// Produces a Merkle proof of the inclusion of the given keys in the
     /// Merkle Radix Trie.
     pub fn get_accounts_proof(&self, keys: Vec<&KeyNibbles>) -> Result<TrieProof, IncompleteTrie> {
@@ -0,0 +1,245 @@
+use nimiq_block::{Block, BlockError};
+use nimiq_blockchain_interface::{BlockchainError, ChainInfo, PushError};
+use nimiq_database::{traits::WriteTransaction, TransactionProxy as DBTransaction};
+use nimiq_hash::Blake2bHash;
+use nimiq_mmr::mmr::position::leaf_number_to_index;
+use nimiq_primitives::policy::Policy;
+use nimiq_serde::Serialize;
+
+use crate::{interface::HistoryInterface, Blockchain};
+
+/// Implements methods to back-fill the blocks of finalized batches. Nodes that sync the chain
+/// through its macro blocks (e.g. history nodes) never see the micro blocks of past batches and
+/// only know some of their macro blocks. Archive nodes use these methods to retrieve them from
+/// other archive nodes, so that they can serve the full block-level chain of all past epochs.
+///
+/// Batches are back-filled from the newest to the oldest one: the blocks of a batch are
+/// authenticated by the hash chain ending in a block that is already known, i.e. either the macro
+/// block of the batch or the first micro block of the following batch.
+impl Blockchain {
+    /// Returns whether the micro blocks of the given finalized batch are stored. Batches are
+    /// always stored and pruned as a whole, so it suffices to check the first micro block.
+    pub fn has_batch_blocks(&self, batch_number: u32, txn: Option<&DBTransaction>) -> bool {
+        match Policy::first_block_of_batch(batch_number) {
+            Some(block_number) => self
+                .chain_store
+                .get_chain_info_at(block_number, false, txn)
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    /// Returns the newest finalized batch before the given batch whose blocks are not stored,
+    /// if any. Pass `u32::MAX` to start at the last finalized batch.
+    pub fn missing_batch_before(&self, batch_number: u32) -> Option<u32> {
+        let last_finalized_batch = Policy::batch_at(self.state.macro_info.head.block_number());
+        let txn = self.read_transaction();
+
+        (1..batch_number.min(last_finalized_batch + 1))
+            .rev()
+            .find(|&batch| !self.has_batch_blocks(batch, Some(&txn)))
+    }
+
+    /// Returns the blocks of the given finalized batch in ascending order, including their bodies.
+    /// These are the macro block preceding the batch, the micro blocks of the batch and the macro
+    /// block finalizing it.
+    pub fn get_batch_blocks(
+        &self,
+        batch_number: u32,
+        txn_option: Option<&DBTransaction>,
+    ) -> Result<Vec<Block>, BlockchainError> {
+        let read_txn: DBTransaction;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.read_transaction();
+                &read_txn
+            }
+        };
+
+        if batch_number == 0
+            || batch_number > Policy::batch_at(self.state.macro_info.head.block_number())
+        {
+            return Err(BlockchainError::BlockNotFound);
+        }
+
+        // Walk backwards from the macro block, such that only main chain blocks are returned.
+        let macro_block = self.chain_store.get_block_at(
+            Policy::macro_block_of(batch_number).unwrap(),
+            true,
+            Some(txn),
+        )?;
+
+        let mut hash = macro_block.parent_hash().clone();
+        let mut blocks = Vec::with_capacity(Policy::blocks_per_batch() as usize + 1);
+        blocks.push(macro_block);
+        for _ in 0..Policy::blocks_per_batch() {
+            let block = self.chain_store.get_block(&hash, true, Some(txn))?;
+            hash = block.parent_hash().clone();
+            blocks.push(block);
+        }
+        blocks.reverse();
+
+        Ok(blocks)
+    }
+
+    /// Stores the blocks of a finalized batch whose micro blocks were not stored before. The
+    /// blocks must be given as returned by `get_batch_blocks`. They are neither applied to the
+    /// accounts tree nor to the history store, since both already reflect the finalized batch.
+    pub fn push_batch_blocks(
+        &self,
+        batch_number: u32,
+        blocks: Vec<Block>,
+    ) -> Result<(), PushError> {
+        let (prev_info, chain_infos) = {
+            let txn = self.read_transaction();
+            if self.has_batch_blocks(batch_number, Some(&txn)) {
+                return Ok(());
+            }
+            self.verify_batch_blocks(batch_number, blocks, &txn)?
+        };
+
+        let mut txn = self.write_transaction();
+        for chain_info in &chain_infos {
+            self.chain_store
+                .put_chain_info(&mut txn, &chain_info.head.hash(), chain_info, true);
+        }
+        // Link the macro block preceding the batch to the first micro block if it is stored.
+        if let Some(mut prev_info) = prev_info {
+            prev_info.main_chain_successor = Some(chain_infos[0].head.hash());
+            self.chain_store
+                .put_chain_info(&mut txn, &prev_info.head.hash(), &prev_info, false);
+        }
+        txn.commit();
+
+        debug!(
+            batch_number,
+            num_blocks = chain_infos.len(),
+            "Stored blocks of finalized batch"
+        );
+
+        Ok(())
+    }
+
+    /// Verifies the blocks of a finalized batch and creates the chain infos of the blocks that
+    /// need to be stored. Also returns the chain info of the macro block preceding the batch if
+    /// it is stored.
+    fn verify_batch_blocks(
+        &self,
+        batch_number: u32,
+        mut blocks: Vec<Block>,
+        txn: &DBTransaction,
+    ) -> Result<(Option<ChainInfo>, Vec<ChainInfo>), PushError> {
+        if batch_number == 0
+            || batch_number > Policy::batch_at(self.state.macro_info.head.block_number())
+        {
+            return Err(PushError::Orphan);
+        }
+        if blocks.len() != Policy::blocks_per_batch() as usize + 1 {
+            return Err(PushError::InvalidSuccessor);
+        }
+
+        // Determine the hash of the macro block finalizing the batch from the blocks we know.
+        let macro_block_number = Policy::macro_block_of(batch_number).unwrap();
+        let (macro_hash, macro_successor): (Blake2bHash, Option<Blake2bHash>) = match self
+            .chain_store
+            .get_chain_info_at(macro_block_number, false, Some(txn))
+        {
+            Ok(macro_info) => (macro_info.head.hash(), None),
+            Err(_) => {
+                let successor = self
+                    .chain_store
+                    .get_chain_info_at(macro_block_number + 1, false, Some(txn))
+                    .map_err(|_| PushError::Orphan)?;
+                (
+                    successor.head.parent_hash().clone(),
+                    Some(successor.head.hash()),
+                )
+            }
+        };
+
+        // The hash chain must end in the known macro block. Since every block commits to its
+        // predecessor, this authenticates all blocks of the batch.
+        if blocks.last().unwrap().hash() != macro_hash {
+            return Err(PushError::InvalidSuccessor);
+        }
+        for (predecessor, block) in blocks.iter().zip(blocks.iter().skip(1)) {
+            if *block.parent_hash() != predecessor.hash() {
+                return Err(PushError::InvalidSuccessor);
+            }
+        }
+
+        // All blocks are expected to be full blocks with the right types.
+        let prev_macro_block = blocks.remove(0);
+        for block in std::iter::once(&prev_macro_block).chain(&blocks) {
+            if block.body().is_none() {
+                return Err(PushError::InvalidBlock(BlockError::MissingBody));
+            }
+            block.verify(self.network_id)?;
+        }
+        let prev_macro_block = prev_macro_block.unwrap_macro();
+
+        let validators =
+            self.get_validators_for_epoch(Policy::epoch_at(macro_block_number), Some(txn))?;
+
+        // Use the stored chain info of the preceding macro block if we know it.
+        let stored_prev_info = self
+            .chain_store
+            .get_chain_info(&prev_macro_block.hash(), false, Some(txn))
+            .ok();
+        let prev_info = stored_prev_info
+            .clone()
+            .unwrap_or_else(|| ChainInfo::new(Block::Macro(prev_macro_block.clone()), true));
+
+        let mut chain_infos: Vec<ChainInfo> = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let predecessor = chain_infos.last().unwrap_or(&prev_info);
+            block.verify_immediate_successor(&predecessor.head)?;
+
+            if block.is_micro() {
+                let proposer = self
+                    .get_proposer_after(
+                        &prev_macro_block,
+                        block.block_number(),
+                        block.vrf_offset(),
+                        predecessor.head.seed().entropy(),
+                        Some(txn),
+                    )?
+                    .validator;
+                block.verify_proposer(&proposer.signing_key, predecessor.head.seed())?;
+            }
+            block.verify_validators(&validators)?;
+
+            // The historic transactions of the block are already part of the history store.
+            let hist_tx_size = self
+                .history_store
+                .get_block_transactions(block.block_number(), Some(txn))
+                .iter()
+                .map(|hist_tx| hist_tx.serialized_size() as u64)
+                .sum();
+            let history_len = self
+                .history_store
+                .length_at(block.block_number(), Some(txn));
+
+            let mut chain_info = ChainInfo::from_block(block, predecessor, None);
+            chain_info.set_cumulative_hist_tx_size(predecessor, hist_tx_size);
+            chain_info.on_main_chain = true;
+            chain_info.history_tree_len = leaf_number_to_index(history_len as usize) as u64;
+
+            if let Some(last_info) = chain_infos.last_mut() {
+                last_info.main_chain_successor = Some(chain_info.head.hash());
+            }
+            chain_infos.push(chain_info);
+        }
+
+        // Only store the macro block finalizing the batch if we didn't know it before.
+        if let Some(successor) = macro_successor {
+            let macro_info = chain_infos.last_mut().unwrap();
+            macro_info.main_chain_successor = Some(successor);
+        } else {
+            chain_infos.pop();
+        }
+
+        Ok((stored_prev_info, chain_infos))
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use nimiq_account::{Accounts, BlockLog};
 use nimiq_block::Block;
@@ -9,6 +9,7 @@ use nimiq_database::{
 };
 use nimiq_genesis::NetworkInfo;
 use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
 use nimiq_primitives::{
     coin::Coin, networks::NetworkId, policy::Policy, slots_allocation::Validators, trie::TrieItem,
 };
@@ -18,8 +19,12 @@ use tokio::sync::broadcast::{channel as broadcast, Sender as BroadcastSender};
 #[cfg(feature = "metrics")]
 use crate::chain_metrics::BlockchainMetrics;
 use crate::{
-    blockchain_state::BlockchainState, chain_store::ChainStore, history::HistoryStore,
-    interface::HistoryInterface, reward::genesis_parameters,
+    blockchain_state::BlockchainState,
+    chain_store::ChainStore,
+    history::HistoryStore,
+    interface::HistoryInterface,
+    reward::genesis_parameters,
+    reward_attribution::{StakeSnapshot, StakeSnapshotStore},
 };
 
 const BROADCAST_MAX_CAPACITY: usize = 256;
@@ -45,6 +50,9 @@ pub struct Blockchain {
     pub chain_store: ChainStore,
     /// The history store is a database containing all of the history trees and transactions.
     pub history_store: Box<dyn HistoryInterface + Sync + Send>,
+    /// The stake snapshot store is a database containing the stake distribution at every election
+    /// block. It is only populated on history nodes.
+    pub(crate) stake_snapshot_store: StakeSnapshotStore,
     /// The current state of the blockchain.
     pub state: BlockchainState,
     /// A reference to a "function" to test whether a given transaction is known and valid.
@@ -69,6 +77,15 @@ pub struct BlockchainConfig {
     /// Maximum number of epochs (other than the current one) that the ChainStore will store fully.
     /// Epochs older than this number will be pruned.
     pub max_epochs_stored: u32,
+    /// Finer grained pruning of data that is not needed to follow the chain.
+    pub pruning: PruningPolicy,
+    /// Flag indicating if all blocks should be stored forever. If set, neither epochs nor parts
+    /// of batches are pruned from the ChainStore, regardless of the other settings.
+    pub archive: bool,
+    /// Number of finalized batches of the current epoch for which revert infos are kept, such that
+    /// the chain can be reverted across their macro blocks. Rebranching never reverts macro blocks,
+    /// so by default revert infos are dropped as soon as a batch is finalized.
+    pub revert_info_batches: u32,
 }
 
 impl Default for BlockchainConfig {
@@ -76,10 +93,57 @@ impl Default for BlockchainConfig {
         Self {
             keep_history: true,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
+            pruning: PruningPolicy::default(),
+            archive: false,
+            revert_info_batches: 0,
         }
     }
 }
 
+/// Determines which data is pruned once a batch or an epoch is finalized, on top of the epochs
+/// pruned according to `max_epochs_stored`. Revert infos are not covered, they are dropped once
+/// their batch is finalized, see `BlockchainConfig::revert_info_batches`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PruningPolicy {
+    /// Number of finalized batches for which the bodies of micro blocks are kept. The bodies of
+    /// older micro blocks are removed while their headers are kept. `None` keeps all bodies.
+    pub micro_body_batches: Option<u32>,
+    /// Number of finalized batches for which the accounts trie diffs are kept. `None` keeps the
+    /// diffs until their epoch is pruned.
+    pub accounts_diff_batches: Option<u32>,
+    /// Addresses whose historic transactions are kept when the history of an epoch is pruned
+    /// (i.e. if `keep_history` is not set). If empty, the whole history of the epoch is removed.
+    /// The history tree of the epoch is removed nonetheless, so no inclusion proofs can be
+    /// produced for the kept transactions anymore.
+    pub history_watched_addresses: HashSet<Address>,
+}
+
+impl PruningPolicy {
+    /// The minimum number of finalized batches for which block bodies and accounts trie diffs
+    /// are kept, such that peers catching up with the chain can still be served.
+    pub const MIN_BATCHES_STORED: u32 = 1;
+
+    /// Returns the batch whose data should be pruned after the given batch was finalized, if any.
+    fn batch_to_prune(batches_stored: Option<u32>, finalized_batch: u32) -> Option<u32> {
+        let batches_stored = batches_stored?.max(Self::MIN_BATCHES_STORED);
+        finalized_batch
+            .checked_sub(batches_stored)
+            .filter(|&batch| batch > 0)
+    }
+
+    /// Returns the batch whose micro block bodies should be pruned after the given batch was
+    /// finalized, if any.
+    pub(crate) fn micro_body_batch_to_prune(&self, finalized_batch: u32) -> Option<u32> {
+        Self::batch_to_prune(self.micro_body_batches, finalized_batch)
+    }
+
+    /// Returns the batch whose accounts trie diffs should be pruned after the given batch was
+    /// finalized, if any.
+    pub(crate) fn accounts_diff_batch_to_prune(&self, finalized_batch: u32) -> Option<u32> {
+        Self::batch_to_prune(self.accounts_diff_batches, finalized_batch)
+    }
+}
+
 /// Implements methods to start a Blockchain.
 impl Blockchain {
     /// Creates a new blockchain from a given environment and network ID.
@@ -134,6 +198,7 @@ impl Blockchain {
 
         let chain_store = ChainStore::new(env.clone());
         let history_store = HistoryStore::new(env.clone());
+        let stake_snapshot_store = StakeSnapshotStore::new(env.clone());
 
         Ok(match chain_store.get_head(None) {
             Some(head_hash) => Blockchain::load(
@@ -141,6 +206,7 @@ impl Blockchain {
                 config,
                 chain_store,
                 history_store,
+                stake_snapshot_store,
                 time,
                 network_id,
                 genesis_block,
@@ -151,6 +217,7 @@ impl Blockchain {
                 config,
                 chain_store,
                 history_store,
+                stake_snapshot_store,
                 time,
                 network_id,
                 genesis_block,
@@ -165,6 +232,7 @@ impl Blockchain {
         config: BlockchainConfig,
         chain_store: ChainStore,
         history_store: HistoryStore,
+        stake_snapshot_store: StakeSnapshotStore,
         time: Arc<OffsetTime>,
         network_id: NetworkId,
         genesis_block: Block,
@@ -275,6 +343,7 @@ impl Blockchain {
             log_notifier: tx_log,
             chain_store,
             history_store: Box::new(history_store) as Box<dyn HistoryInterface + Sync + Send>,
+            stake_snapshot_store,
             state: BlockchainState {
                 accounts,
                 main_chain,
@@ -302,6 +371,7 @@ impl Blockchain {
         config: BlockchainConfig,
         chain_store: ChainStore,
         history_store: HistoryStore,
+        stake_snapshot_store: StakeSnapshotStore,
         time: Arc<OffsetTime>,
         network_id: NetworkId,
         genesis_block: Block,
@@ -323,6 +393,17 @@ impl Blockchain {
         let mut txn = env.write_transaction();
         accounts.init(&mut (&mut txn).into(), genesis_accounts);
 
+        // Record the stake of the validators elected in the genesis block.
+        if config.keep_history {
+            if let Some(snapshot) = StakeSnapshot::from_accounts(&accounts, &txn) {
+                stake_snapshot_store.put(
+                    &mut txn,
+                    Policy::epoch_at(genesis_block_number) + 1,
+                    &snapshot,
+                );
+            }
+        }
+
         // Store genesis block.
         chain_store.put_chain_info(&mut txn, &head_hash, &main_chain, true);
         chain_store.set_head(&mut txn, &head_hash);
@@ -342,6 +423,7 @@ impl Blockchain {
             log_notifier: tx_log,
             chain_store,
             history_store: Box::new(history_store) as Box<dyn HistoryInterface + Sync + Send>,
+            stake_snapshot_store,
             state: BlockchainState {
                 accounts,
                 macro_info: main_chain.clone(),
@@ -378,6 +460,18 @@ impl Blockchain {
     pub fn write_transaction(&self) -> WriteTransactionProxy {
         self.env.write_transaction()
     }
+
+    /// Removes the history of the given epoch, keeping the transactions of the addresses watched
+    /// by the pruning policy if any.
+    pub(crate) fn prune_history(&self, txn: &mut WriteTransactionProxy, epoch_number: u32) {
+        let watched_addresses = &self.config.pruning.history_watched_addresses;
+        if watched_addresses.is_empty() {
+            self.history_store.remove_history(txn, epoch_number);
+        } else {
+            self.history_store
+                .prune_history(txn, epoch_number, watched_addresses);
+        }
+    }
 }
 
 pub trait TransactionVerificationCache: Send + Sync {
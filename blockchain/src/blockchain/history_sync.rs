@@ -421,6 +421,11 @@ impl Blockchain {
 
         let this = RwLockWriteGuard::downgrade_to_upgradable(this);
 
+        // Record the stake of the newly elected validators for the reward attribution.
+        if is_election_block {
+            this.snapshot_stake(block.block_number());
+        }
+
         debug!(
             %block,
             num_transactions = block.num_transactions(),
@@ -1,12 +1,15 @@
 mod abstract_blockchain;
 pub mod accounts;
+pub mod archive;
 #[allow(clippy::module_inception)]
 pub mod blockchain;
 pub mod history_sync;
 pub mod inherents;
 pub mod push;
 pub(super) mod rebranch_utils;
+pub mod revert;
 pub mod slots;
+pub mod snapshot;
 pub mod verify;
 pub mod wrappers;
 pub mod zkp_sync;
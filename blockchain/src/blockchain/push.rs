@@ -318,6 +318,22 @@ impl Blockchain {
             .put_chain_info(&mut txn, chain_info.head.parent_hash(), &prev_info, false);
         this.chain_store.set_head(&mut txn, &block_hash);
 
+        if is_macro_block && !this.config.archive {
+            let batch_number = Policy::batch_at(block_number);
+
+            // Prune micro block bodies and accounts trie diffs according to the pruning policy.
+            if let Some(batch) = this.config.pruning.micro_body_batch_to_prune(batch_number) {
+                this.chain_store.prune_micro_block_bodies(batch, &mut txn);
+            }
+            if let Some(batch) = this
+                .config
+                .pruning
+                .accounts_diff_batch_to_prune(batch_number)
+            {
+                this.chain_store.prune_accounts_diffs(batch, &mut txn);
+            }
+        }
+
         if is_election_block {
             let max_epochs_stored =
                 cmp::max(this.config.max_epochs_stored, Policy::MIN_EPOCHS_STORED);
@@ -325,13 +341,14 @@ impl Blockchain {
             // Calculate the epoch to be pruned. Saturate at zero.
             let pruned_epoch = Policy::epoch_at(block_number).saturating_sub(max_epochs_stored);
 
-            // Prune the Chain Store.
-            this.chain_store.prune_epoch(pruned_epoch, &mut txn);
+            // Prune the Chain Store. Archive nodes keep all blocks.
+            if !this.config.archive {
+                this.chain_store.prune_epoch(pruned_epoch, &mut txn);
+            }
 
             if !this.config.keep_history {
                 // Prune the History Store.
-                this.history_store
-                    .remove_history(&mut txn, Policy::epoch_at(block_number).saturating_sub(1));
+                this.prune_history(&mut txn, Policy::epoch_at(block_number).saturating_sub(1));
             }
         }
 
@@ -359,6 +376,11 @@ impl Blockchain {
         // Downgrade the lock again as the notify listeners might want to acquire read access themselves.
         let this = RwLockWriteGuard::downgrade_to_upgradable(this);
 
+        // Record the stake of the newly elected validators for the reward attribution.
+        if is_election_block {
+            this.snapshot_stake(block_number);
+        }
+
         // Try to apply any chunks we received.
         let chunk_result = this.commit_chunks(chunks, &block_hash);
 
@@ -578,7 +600,16 @@ impl Blockchain {
                 e
             })?;
             if is_complete {
-                let recorded_diff = txn.stop_recording().into_forward_diff();
+                let recorded_diff = txn.stop_recording();
+                // Macro blocks within the revert window are reverted using the recorded changes.
+                if block.is_macro() && self.keeps_macro_revert_info(block.block_number()) {
+                    self.chain_store.put_revert_info(
+                        txn.raw(),
+                        block.block_number(),
+                        &recorded_diff.clone().into_backward_diff().into(),
+                    );
+                }
+                let recorded_diff = recorded_diff.into_forward_diff();
                 self.chain_store
                     .put_accounts_diff(txn.raw(), &block.hash(), &recorded_diff);
             }
@@ -0,0 +1,176 @@
+use nimiq_account::BlockLogger;
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, ChainInfo, RevertError};
+use nimiq_database::WriteTransactionProxy;
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
+
+use crate::Blockchain;
+
+/// Implements methods to manually revert the chain, e.g. to recover from a bug or to reproduce an
+/// issue. Consensus syncs the chain forward again afterwards.
+impl Blockchain {
+    /// Returns whether the revert info of the given macro block is kept, i.e. whether the macro
+    /// block can be reverted later on. Election blocks are never reverted.
+    pub(crate) fn keeps_macro_revert_info(&self, block_number: u32) -> bool {
+        self.config.revert_info_batches > 0 && !Policy::is_election_block_at(block_number)
+    }
+
+    /// Removes the revert infos that fall out of the revert window once the given macro block was
+    /// committed.
+    pub(crate) fn prune_revert_infos(&self, txn: &mut WriteTransactionProxy, block_number: u32) {
+        if !self.keeps_macro_revert_info(block_number) {
+            self.chain_store.clear_revert_infos(txn);
+            return;
+        }
+
+        // Keep the revert infos of the last `revert_info_batches` finalized batches.
+        let batch_number = Policy::batch_at(block_number);
+        if let Some(last_pruned_block) = batch_number
+            .checked_sub(self.config.revert_info_batches)
+            .and_then(Policy::macro_block_of)
+        {
+            self.chain_store.prune_revert_infos(txn, last_pruned_block);
+        }
+    }
+
+    /// Reverts the main chain to the block with the given number, reverting the accounts tree and
+    /// the history store block by block. The reverted blocks are removed from the chain store, such
+    /// that they can be pushed again. Returns the reverted blocks in ascending order.
+    ///
+    /// Reverting below the last macro block requires `force` and is only possible within the
+    /// revert window configured by `revert_info_batches`. Election blocks are never reverted.
+    pub fn revert_to(
+        this: RwLockUpgradableReadGuard<Self>,
+        block_number: u32,
+        force: bool,
+    ) -> Result<Vec<(Blake2bHash, Block)>, RevertError> {
+        let head_number = this.block_number();
+        if block_number > head_number {
+            return Err(RevertError::AboveHead(block_number));
+        }
+        if block_number == head_number {
+            return Ok(vec![]);
+        }
+
+        let macro_block_number = this.state.macro_info.head.block_number();
+        if block_number < macro_block_number && !force {
+            return Err(RevertError::Finalized(block_number, macro_block_number));
+        }
+        let election_block_number = this.state.election_head.block_number();
+        if block_number < election_block_number {
+            return Err(RevertError::BelowElection(
+                block_number,
+                election_block_number,
+            ));
+        }
+        if !this.state.accounts.is_complete(None) {
+            return Err(RevertError::IncompleteAccounts);
+        }
+
+        // Collect the blocks to revert and check that all of them can be reverted.
+        let mut revert_chain: Vec<(Blake2bHash, ChainInfo)> = vec![];
+        let read_txn = this.read_transaction();
+        let mut current = (
+            this.state.head_hash.clone(),
+            this.chain_store
+                .get_chain_info(&this.state.head_hash, true, Some(&read_txn))?,
+        );
+        while current.1.head.block_number() > block_number {
+            if current.1.head.body().is_none()
+                || this
+                    .chain_store
+                    .get_revert_info(current.1.head.block_number(), Some(&read_txn))
+                    .is_none()
+            {
+                return Err(RevertError::OutsideRevertWindow(block_number));
+            }
+
+            let prev_hash = current.1.head.parent_hash().clone();
+            let mut prev_info =
+                this.chain_store
+                    .get_chain_info(&prev_hash, true, Some(&read_txn))?;
+            prev_info.main_chain_successor = None;
+
+            revert_chain.push(current);
+            current = (prev_hash, prev_info);
+        }
+        drop(read_txn);
+        let (target_hash, target_info) = current;
+
+        let mut this = RwLockUpgradableReadGuard::upgrade(this);
+        let mut txn = this.write_transaction();
+
+        let mut block_logs = Vec::with_capacity(revert_chain.len());
+        for (hash, chain_info) in &revert_chain {
+            let block = &chain_info.head;
+            let mut block_logger = BlockLogger::new_reverted(hash.clone(), block.block_number());
+            let total_tx_size = this
+                .revert_accounts(
+                    &this.state.accounts,
+                    &mut (&mut txn).into(),
+                    block,
+                    &mut block_logger,
+                )
+                .expect("Failed to revert block");
+            block_logs.push(block_logger.build(total_tx_size));
+
+            this.chain_store
+                .remove_chain_info(&mut txn, hash, block.block_number());
+        }
+
+        // The accounts tree must match the new head now.
+        assert_eq!(
+            target_info.head.state_root(),
+            &this.state.accounts.get_root_hash_assert(Some(&txn)),
+            "Inconsistent state after reverting to block {}",
+            target_info.head,
+        );
+
+        this.chain_store
+            .put_chain_info(&mut txn, &target_hash, &target_info, false);
+        this.chain_store.set_head(&mut txn, &target_hash);
+        txn.commit();
+
+        if target_info.head.block_number() < macro_block_number {
+            let macro_info = this
+                .chain_store
+                .get_chain_info_at(
+                    Policy::last_macro_block(target_info.head.block_number()),
+                    true,
+                    None,
+                )
+                .expect("Failed to load the macro block preceding the new head");
+            this.state.macro_head_hash = macro_info.head.hash();
+            this.state.macro_info = macro_info;
+        }
+        this.state.main_chain = target_info;
+        this.state.head_hash = target_hash;
+
+        // Downgrade the lock again as the notified listeners might want to acquire read themselves.
+        let this = RwLockWriteGuard::downgrade(this);
+
+        let reverted_blocks: Vec<(Blake2bHash, Block)> = revert_chain
+            .into_iter()
+            .rev()
+            .map(|(hash, chain_info)| (hash, chain_info.head))
+            .collect();
+
+        info!(
+            block = %this.state.main_chain.head,
+            num_reverted_blocks = reverted_blocks.len(),
+            "Reverted chain",
+        );
+
+        // We do not log errors if there are no listeners.
+        this.notifier
+            .send(BlockchainEvent::Rebranched(reverted_blocks.clone(), vec![]))
+            .ok();
+        for block_log in block_logs {
+            this.log_notifier.send(block_log).ok();
+        }
+
+        Ok(reverted_blocks)
+    }
+}
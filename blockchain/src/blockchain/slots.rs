@@ -1,3 +1,4 @@
+use nimiq_block::MacroBlock;
 use nimiq_blockchain_interface::BlockchainError;
 use nimiq_collections::BitSet;
 use nimiq_database::TransactionProxy;
@@ -65,11 +66,31 @@ impl Blockchain {
         //  An offline validator will thus continue to delay the chain as his slot(s) will still
         //  be selected until the end of the batch.
         let macro_block = self.get_block_at(Policy::macro_block_before(block_number), true, txn)?;
+        self.get_proposer_after(
+            &macro_block.unwrap_macro(),
+            block_number,
+            offset,
+            vrf_entropy,
+            txn,
+        )
+    }
+
+    /// Gets the proposer of the block at the given block number, using the disabled slots of the
+    /// given macro block, which must be the macro block preceding the block (including its body).
+    pub(crate) fn get_proposer_after(
+        &self,
+        macro_block: &MacroBlock,
+        block_number: u32,
+        offset: u32,
+        vrf_entropy: VrfEntropy,
+        txn: Option<&TransactionProxy>,
+    ) -> Result<Slot, BlockchainError> {
         let disabled_slots = macro_block
-            .unwrap_macro()
             .body
-            .unwrap()
-            .next_batch_initial_punished_set;
+            .as_ref()
+            .ok_or(BlockchainError::BlockBodyNotFound)?
+            .next_batch_initial_punished_set
+            .clone();
 
         // Compute the slot number of the next proposer.
         let slot_number = Self::compute_slot_number(offset, vrf_entropy, disabled_slots);
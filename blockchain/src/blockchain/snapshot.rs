@@ -0,0 +1,99 @@
+use nimiq_account::{Account, DataStore, StakingContract};
+use nimiq_database::TransactionProxy;
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::key_nibbles::KeyNibbles;
+
+use crate::{blockchain::accounts::AccountsChunk, Blockchain};
+
+/// A consistent view of the blockchain state at a single block. It pins a read transaction
+/// together with the head block that the transaction corresponds to, such that all reads from
+/// the accounts tree, the staking contract and the history store see the state after that block,
+/// even if blocks are committed in the meantime.
+pub struct BlockchainSnapshot<'a> {
+    blockchain: &'a Blockchain,
+    txn: TransactionProxy<'a>,
+    block_number: u32,
+    block_hash: Blake2bHash,
+}
+
+impl Blockchain {
+    /// Returns a snapshot of the state at the current head.
+    pub fn snapshot(&self) -> BlockchainSnapshot {
+        let txn = self.read_transaction();
+        // The head is read from the pinned transaction, since it is committed together with the
+        // state of the block.
+        let block_hash = self
+            .chain_store
+            .get_head(Some(&txn))
+            .expect("Failed to load the head");
+        let block_number = self
+            .chain_store
+            .get_chain_info(&block_hash, false, Some(&txn))
+            .expect("Failed to load the head")
+            .head
+            .block_number();
+
+        BlockchainSnapshot {
+            blockchain: self,
+            txn,
+            block_number,
+            block_hash,
+        }
+    }
+
+    /// Returns a snapshot of the state at the given block. This is head-only: the accounts tree
+    /// only stores the state at the head, so this returns `None` as soon as another block was
+    /// pushed. Older snapshots are deliberately not kept around, since every pinned read
+    /// transaction prevents the database from reusing the pages freed by later blocks. Callers
+    /// that combine several requests must retry them if the head moved in between.
+    pub fn snapshot_at(&self, block_hash: &Blake2bHash) -> Option<BlockchainSnapshot> {
+        let snapshot = self.snapshot();
+        (&snapshot.block_hash == block_hash).then_some(snapshot)
+    }
+}
+
+impl<'a> BlockchainSnapshot<'a> {
+    /// The number of the block the snapshot corresponds to.
+    pub fn block_number(&self) -> u32 {
+        self.block_number
+    }
+
+    /// The hash of the block the snapshot corresponds to.
+    pub fn block_hash(&self) -> &Blake2bHash {
+        &self.block_hash
+    }
+
+    /// The pinned read transaction. It can be passed to any method of the blockchain, the chain
+    /// store or the history store that accepts a transaction.
+    pub fn txn(&self) -> &TransactionProxy<'a> {
+        &self.txn
+    }
+
+    /// Returns the account at the given address, or `None` if that part of the accounts tree is
+    /// incomplete.
+    pub fn get_account(&self, address: &Address) -> Option<Account> {
+        self.blockchain
+            .state
+            .accounts
+            .get(address, Some(&self.txn))
+            .ok()
+    }
+
+    /// Returns the staking contract, or `None` if that part of the accounts tree is incomplete.
+    pub fn get_staking_contract(&self) -> Option<StakingContract> {
+        self.blockchain
+            .get_staking_contract_if_complete(Some(&self.txn))
+    }
+
+    /// Returns the data store of the staking contract. It must be read using `txn`.
+    pub fn get_staking_contract_store(&self) -> DataStore {
+        self.blockchain.get_staking_contract_store()
+    }
+
+    /// Gets an accounts chunk given a start key and a limit.
+    pub fn get_accounts_chunk(&self, start: KeyNibbles, limit: usize) -> AccountsChunk {
+        self.blockchain
+            .get_accounts_chunk(Some(&self.txn), start, limit)
+    }
+}
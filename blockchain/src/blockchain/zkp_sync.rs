@@ -289,13 +289,14 @@ impl Blockchain {
             // Calculate the epoch to be pruned. Saturate at zero.
             let pruned_epoch = Policy::epoch_at(block_number).saturating_sub(max_epochs_stored);
 
-            // Prune the Chain Store.
-            this.chain_store.prune_epoch(pruned_epoch, &mut txn);
+            // Prune the Chain Store. Archive nodes keep all blocks.
+            if !this.config.archive {
+                this.chain_store.prune_epoch(pruned_epoch, &mut txn);
+            }
 
             if !this.config.keep_history {
                 // Prune the History Store.
-                this.history_store
-                    .remove_history(&mut txn, Policy::epoch_at(block_number).saturating_sub(1));
+                this.prune_history(&mut txn, Policy::epoch_at(block_number).saturating_sub(1));
             }
         }
 
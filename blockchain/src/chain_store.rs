@@ -565,12 +565,50 @@ impl ChainStore {
                 if chain_info.prunable {
                     txn.remove(&self.chain_table, &hash);
                     txn.remove(&self.block_table, &hash);
+                    txn.remove(&self.accounts_diff_table, &hash);
                     txn.remove_item(&self.height_idx, &height, &hash);
                 }
             }
         }
     }
 
+    /// Removes the bodies of all micro blocks in the given batch. Their chain infos, and thus
+    /// their headers, are kept.
+    pub fn prune_micro_block_bodies(&self, batch_number: u32, txn: &mut WriteTransactionProxy) {
+        // The zero-th batch only contains the genesis block.
+        if batch_number == 0 {
+            return;
+        }
+
+        for height in Policy::first_block_of_batch(batch_number)
+            .expect("The supplied batch_number is out of bounds")
+            ..Policy::macro_block_of(batch_number)
+                .expect("The supplied batch_number is out of bounds")
+        {
+            for hash in self.get_block_hashes_at(height, Some(txn)) {
+                txn.remove(&self.block_table, &hash);
+            }
+        }
+    }
+
+    /// Removes the accounts trie diffs of all blocks in the given batch.
+    pub fn prune_accounts_diffs(&self, batch_number: u32, txn: &mut WriteTransactionProxy) {
+        // The zero-th batch only contains the genesis block.
+        if batch_number == 0 {
+            return;
+        }
+
+        for height in Policy::first_block_of_batch(batch_number)
+            .expect("The supplied batch_number is out of bounds")
+            ..=Policy::macro_block_of(batch_number)
+                .expect("The supplied batch_number is out of bounds")
+        {
+            for hash in self.get_block_hashes_at(height, Some(txn)) {
+                txn.remove(&self.accounts_diff_table, &hash);
+            }
+        }
+    }
+
     pub fn put_revert_info(
         &self,
         txn: &mut WriteTransactionProxy,
@@ -607,6 +645,21 @@ impl ChainStore {
         }
     }
 
+    /// Removes the revert infos of all blocks up to and including the given block number.
+    pub fn prune_revert_infos(&self, txn: &mut WriteTransactionProxy, block_number: u32) {
+        let mut cursor = WriteTransaction::cursor(txn, &self.revert_table);
+        let mut pos: Option<(u32, RevertInfo)> = cursor.first();
+
+        // The keys are sorted, so all revert infos to prune come first.
+        while let Some((block_height, _)) = pos {
+            if block_height > block_number {
+                break;
+            }
+            cursor.remove();
+            pos = cursor.next();
+        }
+    }
+
     pub fn put_accounts_diff(
         &self,
         txn: &mut WriteTransactionProxy,
@@ -0,0 +1,280 @@
+//! The lifecycle of HTLCs and vesting contracts.
+//!
+//! History nodes index the transactions that create HTLCs and vesting contracts by the addresses
+//! involved in the contracts, and the transactions sent from those contracts by the contract
+//! address. The current state of a contract is derived from the latter.
+//!
+//! Both indexes are only filled for blocks that are pushed after they were introduced. History
+//! nodes that were synced before need to rebuild them once, see
+//! [`HistoryStore::rebuild_indexes`](crate::HistoryStore::rebuild_indexes).
+
+use nimiq_database::TransactionProxy;
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::{account::AccountType, coin::Coin};
+use nimiq_transaction::{
+    account::{
+        htlc_contract::{
+            AnyHash, CreationTransactionData as HtlcCreationData, OutgoingHTLCTransactionProof,
+            PreImage,
+        },
+        vesting_contract::CreationTransactionData as VestingCreationData,
+    },
+    historic_transaction::HistoricTransactionData,
+    ExecutedTransaction,
+};
+
+use crate::Blockchain;
+
+/// The state of an HTLC, given by the last transaction sent from the contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HtlcStatus {
+    /// No funds were sent from the contract yet.
+    Funded,
+    /// The recipient redeemed (part of) the funds by revealing a pre-image.
+    Redeemed,
+    /// The sender recovered the funds after the timeout.
+    TimedOut,
+    /// The funds were sent with the consent of both the sender and the recipient.
+    EarlyResolved,
+}
+
+/// An HTLC together with its current status.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtlcLifecycle {
+    pub contract_address: Address,
+    pub creation_tx_hash: Blake2bHash,
+    pub creation_block_number: u32,
+    pub sender: Address,
+    pub recipient: Address,
+    pub hash_root: AnyHash,
+    pub hash_count: u8,
+    pub timeout: u64,
+    pub total_amount: Coin,
+    /// The balance left in the contract.
+    pub balance: Coin,
+    pub status: HtlcStatus,
+    /// The pre-image revealed by the last regular transfer, if any.
+    pub pre_image: Option<PreImage>,
+    /// The hash of the last transaction sent from the contract, if any.
+    pub resolution_tx_hash: Option<Blake2bHash>,
+    /// The block number of the last transaction sent from the contract, if any.
+    pub resolution_block_number: Option<u32>,
+}
+
+/// The state of a vesting contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VestingStatus {
+    /// The contract still holds funds.
+    Active,
+    /// All funds were withdrawn from the contract.
+    Withdrawn,
+}
+
+/// A vesting contract together with its current status.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VestingLifecycle {
+    pub contract_address: Address,
+    pub creation_tx_hash: Blake2bHash,
+    pub creation_block_number: u32,
+    pub owner: Address,
+    pub start_time: u64,
+    pub time_step: u64,
+    pub step_amount: Coin,
+    pub total_amount: Coin,
+    /// The balance left in the contract.
+    pub balance: Coin,
+    pub status: VestingStatus,
+    /// The number of withdrawals from the contract.
+    pub num_withdrawals: u32,
+    /// The block number of the last withdrawal, if any.
+    pub last_withdrawal_block_number: Option<u32>,
+}
+
+/// A transaction stored in the history store, together with its raw hash and block number.
+struct IndexedTransaction {
+    hash: Blake2bHash,
+    block_number: u32,
+    tx: ExecutedTransaction,
+}
+
+impl Blockchain {
+    /// Returns the HTLCs the given address created, sent or received, from most recent to least
+    /// recent up to the maximum number given.
+    pub fn get_htlcs_by_address(&self, address: &Address, max: u16) -> Vec<HtlcLifecycle> {
+        let txn = self.read_transaction();
+        self.get_contract_creations(address, &txn)
+            .into_iter()
+            .filter_map(|creation| self.get_htlc_lifecycle(creation, &txn))
+            .take(max as usize)
+            .collect()
+    }
+
+    /// Returns the vesting contracts owned by the given address, from most recent to least recent
+    /// up to the maximum number given.
+    pub fn get_vesting_contracts_by_owner(
+        &self,
+        owner: &Address,
+        max: u16,
+    ) -> Vec<VestingLifecycle> {
+        let txn = self.read_transaction();
+        self.get_contract_creations(owner, &txn)
+            .into_iter()
+            .filter_map(|creation| self.get_vesting_lifecycle(creation, &txn))
+            .filter(|vesting| &vesting.owner == owner)
+            .take(max as usize)
+            .collect()
+    }
+
+    fn get_htlc_lifecycle(
+        &self,
+        creation: IndexedTransaction,
+        txn: &TransactionProxy,
+    ) -> Option<HtlcLifecycle> {
+        let tx = creation.tx.get_raw_transaction();
+        if tx.recipient_type != AccountType::HTLC {
+            return None;
+        }
+        let data = HtlcCreationData::parse(tx).ok()?;
+
+        let mut htlc = HtlcLifecycle {
+            contract_address: tx.contract_creation_address(),
+            creation_tx_hash: creation.hash,
+            creation_block_number: creation.block_number,
+            sender: data.sender,
+            recipient: data.recipient,
+            hash_root: data.hash_root,
+            hash_count: data.hash_count,
+            timeout: data.timeout,
+            total_amount: tx.value,
+            balance: tx.value,
+            status: HtlcStatus::Funded,
+            pre_image: None,
+            resolution_tx_hash: None,
+            resolution_block_number: None,
+        };
+
+        for outgoing in self.get_outgoing_transactions(&htlc.contract_address, txn) {
+            htlc.balance = htlc.balance.saturating_sub(spent_amount(&outgoing.tx));
+            let tx = match outgoing.tx {
+                ExecutedTransaction::Ok(tx) => tx,
+                ExecutedTransaction::Err(_) => continue,
+            };
+
+            match OutgoingHTLCTransactionProof::parse(&tx) {
+                Ok(OutgoingHTLCTransactionProof::RegularTransfer { pre_image, .. }) => {
+                    htlc.status = HtlcStatus::Redeemed;
+                    htlc.pre_image = Some(pre_image);
+                }
+                Ok(OutgoingHTLCTransactionProof::TimeoutResolve { .. }) => {
+                    htlc.status = HtlcStatus::TimedOut;
+                }
+                Ok(OutgoingHTLCTransactionProof::EarlyResolve { .. }) => {
+                    htlc.status = HtlcStatus::EarlyResolved;
+                }
+                Err(_) => continue,
+            }
+            htlc.resolution_tx_hash = Some(outgoing.hash);
+            htlc.resolution_block_number = Some(outgoing.block_number);
+        }
+
+        Some(htlc)
+    }
+
+    fn get_vesting_lifecycle(
+        &self,
+        creation: IndexedTransaction,
+        txn: &TransactionProxy,
+    ) -> Option<VestingLifecycle> {
+        let tx = creation.tx.get_raw_transaction();
+        if tx.recipient_type != AccountType::Vesting {
+            return None;
+        }
+        let data = VestingCreationData::parse(tx).ok()?;
+
+        let mut vesting = VestingLifecycle {
+            contract_address: tx.contract_creation_address(),
+            creation_tx_hash: creation.hash,
+            creation_block_number: creation.block_number,
+            owner: data.owner,
+            start_time: data.start_time,
+            time_step: data.time_step,
+            step_amount: data.step_amount,
+            total_amount: data.total_amount,
+            balance: tx.value,
+            status: VestingStatus::Active,
+            num_withdrawals: 0,
+            last_withdrawal_block_number: None,
+        };
+
+        for outgoing in self.get_outgoing_transactions(&vesting.contract_address, txn) {
+            vesting.balance = vesting.balance.saturating_sub(spent_amount(&outgoing.tx));
+            if outgoing.tx.succeeded() {
+                vesting.num_withdrawals += 1;
+                vesting.last_withdrawal_block_number = Some(outgoing.block_number);
+            }
+        }
+        if vesting.balance.is_zero() {
+            vesting.status = VestingStatus::Withdrawn;
+        }
+
+        Some(vesting)
+    }
+
+    /// Returns the transactions that created a contract involving the given address, from most
+    /// recent to least recent.
+    fn get_contract_creations(
+        &self,
+        address: &Address,
+        txn: &TransactionProxy,
+    ) -> Vec<IndexedTransaction> {
+        self.history_store
+            .get_contract_creations_by_address(address, u16::MAX, Some(txn))
+            .into_iter()
+            .filter_map(|hash| self.get_indexed_transaction(hash, txn))
+            .filter(|creation| creation.tx.succeeded())
+            .collect()
+    }
+
+    /// Returns the transactions sent from the given contract, including failed ones since their
+    /// fees are paid by the contract, from least recent to most recent.
+    fn get_outgoing_transactions(
+        &self,
+        contract_address: &Address,
+        txn: &TransactionProxy,
+    ) -> Vec<IndexedTransaction> {
+        self.history_store
+            .get_txs_by_contract(contract_address, Some(txn))
+            .into_iter()
+            .filter_map(|hash| self.get_indexed_transaction(hash, txn))
+            .collect()
+    }
+
+    fn get_indexed_transaction(
+        &self,
+        hash: Blake2bHash,
+        txn: &TransactionProxy,
+    ) -> Option<IndexedTransaction> {
+        self.history_store
+            .get_hist_tx_by_hash(&hash, Some(txn))
+            .into_iter()
+            .find_map(|hist_tx| match hist_tx.data {
+                HistoricTransactionData::Basic(tx) => Some(IndexedTransaction {
+                    hash: hash.clone(),
+                    block_number: hist_tx.block_number,
+                    tx,
+                }),
+                _ => None,
+            })
+    }
+}
+
+/// The amount a transaction deducted from its sender. Failed transactions only pay the fee.
+fn spent_amount(tx: &ExecutedTransaction) -> Coin {
+    let raw_tx = tx.get_raw_transaction();
+    if tx.succeeded() {
+        raw_tx.total_value()
+    } else {
+        raw_tx.fee
+    }
+}
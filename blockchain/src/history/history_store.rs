@@ -20,17 +20,26 @@ use nimiq_mmr::{
     },
     store::memory::MemoryStore,
 };
-use nimiq_primitives::policy::Policy;
+use nimiq_primitives::{account::AccountType, policy::Policy};
 use nimiq_serde::Serialize;
 use nimiq_transaction::{
-    historic_transaction::{EquivocationEvent, HistoricTransaction, HistoricTransactionData},
+    account::{
+        htlc_contract::CreationTransactionData as HtlcCreationData,
+        vesting_contract::CreationTransactionData as VestingCreationData,
+    },
+    historic_transaction::{
+        EquivocationEvent, HistoricTransaction, HistoricTransactionData, RawTransactionHash,
+    },
     history_proof::HistoryTreeProof,
     inherent::Inherent,
-    EquivocationLocator,
+    EquivocationLocator, ExecutedTransaction, TransactionFlags,
 };
 
-use super::interface::HistoryInterface;
-use crate::history::{mmr_store::MMRStore, ordered_hash::OrderedHash, HistoryTreeChunk};
+use super::interface::{HistoryInterface, HistoryProofError};
+use crate::{
+    history::{mmr_store::MMRStore, ordered_hash::OrderedHash, HistoryTreeChunk},
+    staking_history::{StakingEvent, StakingEventData},
+};
 
 /// A struct that contains databases to store history trees (which are Merkle Mountain Ranges
 /// constructed from the list of historic transactions in an epoch) and historic transactions (which
@@ -55,6 +64,21 @@ pub struct HistoryStore {
     /// A database of all raw transaction (and reward inherent) hashes indexed by their sender and
     /// recipient addresses.
     address_table: TableProxy,
+    /// A database of the raw hashes of all transactions that created an HTLC or a vesting contract,
+    /// indexed by the addresses involved in the contract. The values are ordered by block number.
+    contract_table: TableProxy,
+    /// A database of the raw hashes of all transactions sent from an HTLC or a vesting contract,
+    /// including failed ones, indexed by the contract address. The values are ordered by block
+    /// number.
+    contract_tx_table: TableProxy,
+    /// A database of the raw hashes of all transactions and inherents that affected a validator or
+    /// a staker, indexed by the validator and staker addresses. The values are ordered by block
+    /// number.
+    staking_table: TableProxy,
+    /// A database of the validator a staker delegated to before each of its transactions that
+    /// carry the delegation, indexed by the raw transaction hash. There is no entry if the staker
+    /// didn't delegate.
+    staker_delegation_table: TableProxy,
 }
 
 impl HistoryStore {
@@ -63,6 +87,14 @@ impl HistoryStore {
     const TX_HASH_DB_NAME: &'static str = "LeafHashesByTxHash";
     const LAST_LEAF_DB_NAME: &'static str = "LastLeafIndexesByBlock";
     const ADDRESS_DB_NAME: &'static str = "TxHashesByAddress";
+    const CONTRACT_DB_NAME: &'static str = "ContractCreationsByAddress";
+    const CONTRACT_TX_DB_NAME: &'static str = "TxHashesByContract";
+    const STAKING_DB_NAME: &'static str = "StakingEventsByAddress";
+    const STAKER_DELEGATION_DB_NAME: &'static str = "StakerDelegationsByTxHash";
+
+    /// The leaf index stored for transactions that were kept when the history tree of their epoch
+    /// was pruned. These transactions aren't part of any history tree anymore.
+    const PRUNED_LEAF_INDEX: u32 = u32::MAX;
 
     /// Creates a new HistoryStore.
     pub fn new(db: DatabaseProxy) -> Self {
@@ -78,6 +110,19 @@ impl HistoryStore {
             Self::ADDRESS_DB_NAME.to_string(),
             TableFlags::DUPLICATE_KEYS | TableFlags::DUP_FIXED_SIZE_VALUES,
         );
+        let contract_table = db.open_table_with_flags(
+            Self::CONTRACT_DB_NAME.to_string(),
+            TableFlags::DUPLICATE_KEYS | TableFlags::DUP_FIXED_SIZE_VALUES,
+        );
+        let contract_tx_table = db.open_table_with_flags(
+            Self::CONTRACT_TX_DB_NAME.to_string(),
+            TableFlags::DUPLICATE_KEYS | TableFlags::DUP_FIXED_SIZE_VALUES,
+        );
+        let staking_table = db.open_table_with_flags(
+            Self::STAKING_DB_NAME.to_string(),
+            TableFlags::DUPLICATE_KEYS | TableFlags::DUP_FIXED_SIZE_VALUES,
+        );
+        let staker_delegation_table = db.open_table(Self::STAKER_DELEGATION_DB_NAME.to_string());
 
         HistoryStore {
             db,
@@ -86,6 +131,10 @@ impl HistoryStore {
             tx_hash_table,
             last_leaf_table,
             address_table,
+            contract_table,
+            contract_tx_table,
+            staking_table,
+            staker_delegation_table,
         }
     }
 
@@ -112,6 +161,7 @@ impl HistoryStore {
         &self,
         txn: &mut WriteTransactionProxy,
         hashes: Vec<(usize, Blake2bHash)>,
+        kept_txs: &HashSet<RawTransactionHash>,
     ) -> u64 {
         // Set to keep track of the txs we are removing to remove them later
         // from the address db in a single batch operation
@@ -150,15 +200,55 @@ impl HistoryStore {
             let block_number = hist_tx.block_number;
             let (start, end) = self.get_indexes_for_block(block_number, Some(txn));
 
+            // The block isn't indexed anymore if its whole epoch is being pruned.
             if end - start == 1 {
                 txn.remove(&self.last_leaf_table, &block_number);
-            } else {
+            } else if end - start > 1 {
                 txn.put(
                     &self.last_leaf_table,
                     &block_number,
                     &(leaf_index as u32 - 1),
                 );
             }
+            // Remove it from the contract databases.
+            for address in contract_addresses(&hist_tx) {
+                txn.remove_item(
+                    &self.contract_table,
+                    &address,
+                    &OrderedHash {
+                        index: block_number,
+                        hash: tx_hash.clone().into(),
+                    },
+                );
+            }
+            if let Some(contract_address) = contract_sender(&hist_tx) {
+                txn.remove_item(
+                    &self.contract_tx_table,
+                    contract_address,
+                    &OrderedHash {
+                        index: block_number,
+                        hash: tx_hash.clone().into(),
+                    },
+                );
+            }
+            // Remove it from the staking databases. The delegation recorded when indexing the
+            // transaction is used, since the preceding events of the staker might already be gone.
+            if let Some(data) = self.get_staking_event_data(&hist_tx, txn) {
+                for address in data.addresses() {
+                    txn.remove_item(
+                        &self.staking_table,
+                        &address,
+                        &OrderedHash {
+                            index: block_number,
+                            hash: tx_hash.clone().into(),
+                        },
+                    );
+                }
+                if data.has_delegation() {
+                    txn.remove(&self.staker_delegation_table, &tx_hash);
+                }
+            }
+
             removed_txs.insert(tx_hash);
 
             match &hist_tx.data {
@@ -186,7 +276,15 @@ impl HistoryStore {
             let mut duplicate = cursor.first_duplicate::<OrderedHash>();
 
             while let Some(v) = duplicate {
-                if !removed_txs.contains(&v.hash.into()) {
+                let tx_hash = v.hash.into();
+                if kept_txs.contains(&tx_hash) {
+                    // Skip over the transactions that are kept when pruning the history.
+                    duplicate = cursor
+                        .next_duplicate::<Address, OrderedHash>()
+                        .map(|(_, v)| v);
+                    continue;
+                }
+                if !removed_txs.contains(&tx_hash) {
                     break;
                 }
                 cursor.remove();
@@ -208,7 +306,7 @@ impl HistoryStore {
         positions: Vec<usize>,
         verifier_state: Option<usize>,
         txn_option: Option<&TransactionProxy>,
-    ) -> Option<HistoryTreeProof> {
+    ) -> Result<HistoryTreeProof, HistoryProofError> {
         let read_txn: TransactionProxy;
         let txn = match txn_option {
             Some(txn) => txn,
@@ -226,7 +324,9 @@ impl HistoryStore {
         ));
 
         // Create Merkle proof.
-        let proof = tree.prove(&positions, verifier_state).ok()?;
+        let proof = tree
+            .prove(&positions, verifier_state)
+            .map_err(HistoryProofError::Proof)?;
 
         // Get each historic transaction from the tree.
         let mut hist_txs = vec![];
@@ -236,7 +336,7 @@ impl HistoryStore {
             hist_txs.push(self.get_historic_tx(&leaf_hash, Some(txn)).unwrap());
         }
 
-        Some(HistoryTreeProof {
+        Ok(HistoryTreeProof {
             proof,
             positions,
             history: hist_txs,
@@ -260,6 +360,8 @@ impl HistoryStore {
 
         txn.put(&self.last_leaf_table, &hist_tx.block_number, &leaf_index);
 
+        self.put_staking_indexes(txn, hist_tx, &raw_tx_hash);
+
         match &hist_tx.data {
             HistoricTransactionData::Basic(tx) => {
                 let tx = tx.get_raw_transaction();
@@ -292,9 +394,11 @@ impl HistoryStore {
                     &tx.recipient,
                     &OrderedHash {
                         index: index_tx_recipient,
-                        hash: raw_tx_hash.into(),
+                        hash: raw_tx_hash.clone().into(),
                     },
                 );
+
+                self.put_contract_indexes(txn, hist_tx, &raw_tx_hash);
             }
             HistoricTransactionData::Reward(ev) => {
                 // We only add reward inherents to the address database.
@@ -337,6 +441,231 @@ impl HistoryStore {
         hist_tx.serialized_size()
     }
 
+    /// Adds a historic transaction to the databases of contract creations and of transactions sent
+    /// from contracts, if it is one of those.
+    fn put_contract_indexes(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        hist_tx: &HistoricTransaction,
+        raw_tx_hash: &RawTransactionHash,
+    ) {
+        for address in contract_addresses(hist_tx) {
+            txn.put(
+                &self.contract_table,
+                &address,
+                &OrderedHash {
+                    index: hist_tx.block_number,
+                    hash: raw_tx_hash.clone().into(),
+                },
+            );
+        }
+        if let Some(contract_address) = contract_sender(hist_tx) {
+            txn.put(
+                &self.contract_tx_table,
+                contract_address,
+                &OrderedHash {
+                    index: hist_tx.block_number,
+                    hash: raw_tx_hash.clone().into(),
+                },
+            );
+        }
+    }
+
+    /// Adds a historic transaction to the database of staking events under the validator and
+    /// staker addresses it affects. For the events of a staker, the delegation of the staker is
+    /// derived from its previous events and recorded, so this must be called in the order of the
+    /// chain.
+    fn put_staking_indexes(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        hist_tx: &HistoricTransaction,
+        raw_tx_hash: &RawTransactionHash,
+    ) {
+        let mut data = match StakingEventData::from_historic_transaction(hist_tx) {
+            Some(data) => data,
+            None => return,
+        };
+
+        if data.has_delegation() {
+            let delegation = data
+                .staker_address()
+                .and_then(|staker_address| self.get_staker_delegation(staker_address, txn));
+            if let Some(delegation) = &delegation {
+                txn.put(&self.staker_delegation_table, raw_tx_hash, delegation);
+            }
+            data.set_delegation(delegation);
+        }
+
+        for address in data.addresses() {
+            txn.put(
+                &self.staking_table,
+                &address,
+                &OrderedHash {
+                    index: hist_tx.block_number,
+                    hash: raw_tx_hash.clone().into(),
+                },
+            );
+        }
+    }
+
+    /// Decodes the staking event of a historic transaction, including the delegation of the
+    /// staker recorded when the transaction was indexed.
+    fn get_staking_event_data(
+        &self,
+        hist_tx: &HistoricTransaction,
+        txn: &TransactionProxy,
+    ) -> Option<StakingEventData> {
+        let mut data = StakingEventData::from_historic_transaction(hist_tx)?;
+        if data.has_delegation() {
+            data.set_delegation(txn.get(&self.staker_delegation_table, &hist_tx.tx_hash()));
+        }
+        Some(data)
+    }
+
+    /// Returns the staking event of the transaction or inherent with the given raw hash.
+    fn get_staking_event(
+        &self,
+        raw_tx_hash: &Blake2bHash,
+        txn: &TransactionProxy,
+    ) -> Option<StakingEvent> {
+        self.get_hist_tx_by_hash(raw_tx_hash, Some(txn))
+            .into_iter()
+            .find_map(|hist_tx| {
+                let data = self.get_staking_event_data(&hist_tx, txn)?;
+                Some(StakingEvent {
+                    tx_hash: raw_tx_hash.clone(),
+                    block_number: hist_tx.block_number,
+                    block_time: hist_tx.block_time,
+                    data,
+                })
+            })
+    }
+
+    /// Returns the validator the given staker delegates to according to its most recent stored
+    /// event that determines the delegation. Returns `None` if the staker doesn't delegate or if
+    /// there is no such event, e.g. for stakers created in the genesis block or whose history was
+    /// pruned.
+    fn get_staker_delegation(
+        &self,
+        staker_address: &Address,
+        txn: &TransactionProxy,
+    ) -> Option<Address> {
+        let mut cursor = txn.cursor(&self.staking_table);
+        cursor.seek_key::<Address, OrderedHash>(staker_address)?;
+
+        // The events of the same block are ordered by hash in the index, so among the events of
+        // the most recent block, the one with the highest leaf index is the last one.
+        let mut latest: Option<(u32, u32, Option<Address>)> = None;
+        let mut entry = cursor.last_duplicate::<OrderedHash>();
+        while let Some(v) = entry {
+            if matches!(&latest, Some((block_number, ..)) if v.index < *block_number) {
+                break;
+            }
+
+            let delegation = self.get_staking_event(&v.hash, txn).and_then(|event| {
+                if event.data.staker_address() != Some(staker_address) {
+                    return None;
+                }
+                event
+                    .data
+                    .delegation_after()
+                    .map(|delegation| delegation.cloned())
+            });
+            if let Some(delegation) = delegation {
+                let leaf_index = self
+                    .get_leaves_by_tx_hash(&v.hash, Some(txn))
+                    .last()
+                    .map(|leaf| leaf.index)
+                    .unwrap_or_default();
+                let is_latest = latest.as_ref().map_or(true, |(_, latest_leaf_index, _)| {
+                    leaf_index > *latest_leaf_index
+                });
+                if is_latest {
+                    latest = Some((v.index, leaf_index, delegation));
+                }
+            }
+
+            entry = cursor
+                .prev_duplicate::<Address, OrderedHash>()
+                .map(|(_, v)| v);
+        }
+
+        latest.and_then(|(_, _, delegation)| delegation)
+    }
+
+    /// Rebuilds the indexes that were added after the history was stored, i.e. the databases of
+    /// contract creations, of transactions sent from contracts and of staking events, from all
+    /// stored historic transactions. History nodes that were synced before these indexes existed
+    /// must run this once, otherwise the contract lifecycle and the validator and staker history
+    /// queries miss the older events. Returns the number of historic transactions that were
+    /// scanned.
+    pub fn rebuild_indexes(&self, txn: &mut WriteTransactionProxy) -> usize {
+        const BATCH_SIZE: usize = 1000;
+
+        txn.clear_database(&self.contract_table);
+        txn.clear_database(&self.contract_tx_table);
+        txn.clear_database(&self.staking_table);
+        txn.clear_database(&self.staker_delegation_table);
+
+        // The historic transactions are stored by leaf hash, but the staking events must be
+        // indexed in the order of the chain. So they are only collected while scanning.
+        let mut staking_txs: Vec<(u32, u32, Blake2bHash)> = vec![];
+
+        let mut num_hist_txs = 0;
+        let mut last_leaf_hash: Option<Blake2bHash> = None;
+        loop {
+            // The cursor borrows the transaction, so read a batch before writing it.
+            let mut batch: Vec<(Blake2bHash, HistoricTransaction)> = Vec::with_capacity(BATCH_SIZE);
+            {
+                let mut cursor = ReadTransaction::cursor(txn, &self.hist_tx_table);
+                let mut entry = match &last_leaf_hash {
+                    None => cursor.first(),
+                    Some(last_leaf_hash) => match cursor.seek_range_key(last_leaf_hash) {
+                        Some((leaf_hash, _)) if &leaf_hash == last_leaf_hash => cursor.next(),
+                        entry => entry,
+                    },
+                };
+                while let Some(kv) = entry {
+                    batch.push(kv);
+                    if batch.len() >= BATCH_SIZE {
+                        break;
+                    }
+                    entry = cursor.next();
+                }
+            }
+
+            for (leaf_hash, hist_tx) in &batch {
+                let raw_tx_hash = hist_tx.tx_hash();
+                self.put_contract_indexes(txn, hist_tx, &raw_tx_hash);
+
+                if StakingEventData::from_historic_transaction(hist_tx).is_some() {
+                    let leaf_index = self
+                        .get_leaves_by_tx_hash(&raw_tx_hash, Some(txn))
+                        .into_iter()
+                        .find(|leaf| &leaf.hash == leaf_hash)
+                        .map(|leaf| leaf.index)
+                        .unwrap_or_default();
+                    staking_txs.push((hist_tx.block_number, leaf_index, leaf_hash.clone()));
+                }
+            }
+            num_hist_txs += batch.len();
+
+            if batch.len() < BATCH_SIZE {
+                break;
+            }
+            last_leaf_hash = batch.pop().map(|(leaf_hash, _)| leaf_hash);
+        }
+
+        staking_txs.sort();
+        for (_, _, leaf_hash) in staking_txs {
+            if let Some(hist_tx) = self.get_historic_tx(&leaf_hash, Some(txn)) {
+                self.put_staking_indexes(txn, &hist_tx, &hist_tx.tx_hash());
+            }
+        }
+
+        num_hist_txs
+    }
+
     /// Returns a vector containing all leaf hashes and indexes corresponding to the given
     /// transaction hash.
     fn get_leaves_by_tx_hash(
@@ -455,6 +784,51 @@ impl HistoryStore {
     }
 }
 
+/// Returns the addresses under which the creation of a contract is indexed: the creator and the
+/// sender and recipient for HTLCs, the creator and the owner for vesting contracts. Returns no
+/// addresses if the historic transaction didn't successfully create such a contract.
+fn contract_addresses(hist_tx: &HistoricTransaction) -> Vec<Address> {
+    let tx = match &hist_tx.data {
+        HistoricTransactionData::Basic(ExecutedTransaction::Ok(tx))
+            if tx.flags.contains(TransactionFlags::CONTRACT_CREATION) =>
+        {
+            tx
+        }
+        _ => return vec![],
+    };
+
+    let mut addresses = vec![tx.sender.clone()];
+    match tx.recipient_type {
+        AccountType::HTLC => {
+            if let Ok(data) = HtlcCreationData::parse(tx) {
+                addresses.push(data.sender);
+                addresses.push(data.recipient);
+            }
+        }
+        AccountType::Vesting => {
+            if let Ok(data) = VestingCreationData::parse(tx) {
+                addresses.push(data.owner);
+            }
+        }
+        _ => return vec![],
+    }
+    addresses.sort();
+    addresses.dedup();
+    addresses
+}
+
+/// Returns the address of the HTLC or vesting contract that sent the historic transaction, if any.
+/// Failed transactions are included, since they pay their fee from the contract.
+fn contract_sender(hist_tx: &HistoricTransaction) -> Option<&Address> {
+    match &hist_tx.data {
+        HistoricTransactionData::Basic(tx) => {
+            let tx = tx.get_raw_transaction();
+            matches!(tx.sender_type, AccountType::HTLC | AccountType::Vesting).then_some(&tx.sender)
+        }
+        _ => None,
+    }
+}
+
 impl HistoryInterface for HistoryStore {
     fn clear(&self, txn: &mut WriteTransactionProxy) {
         txn.clear_database(&self.hist_tree_table);
@@ -462,6 +836,10 @@ impl HistoryInterface for HistoryStore {
         txn.clear_database(&self.tx_hash_table);
         txn.clear_database(&self.last_leaf_table);
         txn.clear_database(&self.address_table);
+        txn.clear_database(&self.contract_table);
+        txn.clear_database(&self.contract_tx_table);
+        txn.clear_database(&self.staking_table);
+        txn.clear_database(&self.staker_delegation_table);
     }
 
     /// Returns the length (i.e. the number of leaves) of the History Tree at a given block height.
@@ -599,7 +977,7 @@ impl HistoryInterface for HistoryStore {
 
         // Remove each of the historic transactions in the history tree from the extended
         // transaction database.
-        let txns_size = self.remove_txns_from_history(txn, hashes);
+        let txns_size = self.remove_txns_from_history(txn, hashes, &HashSet::new());
 
         // Return the history root.
         Some((root, txns_size))
@@ -624,7 +1002,91 @@ impl HistoryInterface for HistoryStore {
             hashes.push((i, leaf_hash));
         }
 
-        self.remove_txns_from_history(txn, hashes);
+        self.remove_txns_from_history(txn, hashes, &HashSet::new());
+
+        Some(())
+    }
+
+    /// Removes an existing history tree and all the historic transactions that were part of it,
+    /// except for the ones involving one of the kept addresses. The kept transactions can still be
+    /// retrieved by their hash or address, but they can't be proven anymore.
+    /// Returns None if there's no history tree corresponding to the given epoch number.
+    fn prune_history(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        epoch_number: u32,
+        kept_addresses: &HashSet<Address>,
+    ) -> Option<()> {
+        // Get the history tree.
+        let mut tree = MerkleMountainRange::new(MMRStore::with_write_transaction(
+            &self.hist_tree_table,
+            txn,
+            epoch_number,
+        ));
+
+        // Remove all leaves from the history tree and remember the respective hashes.
+        let mut hashes = Vec::with_capacity(tree.num_leaves());
+
+        for i in (0..tree.num_leaves()).rev() {
+            let leaf_hash = tree.get_leaf(i).unwrap();
+            tree.remove_back().ok()?;
+            hashes.push((i, leaf_hash));
+        }
+
+        // Keep the historic transactions that involve one of the kept addresses.
+        let mut kept_txs = HashSet::new();
+        let mut kept_leaves = vec![];
+        let mut block_numbers = HashSet::new();
+        hashes.retain(|(leaf_index, leaf_hash)| {
+            let hist_tx = match self.get_historic_tx(leaf_hash, Some(txn)) {
+                Some(hist_tx) => hist_tx,
+                None => return true,
+            };
+            block_numbers.insert(hist_tx.block_number);
+            let involves_kept_address = match &hist_tx.data {
+                HistoricTransactionData::Basic(tx) => {
+                    let tx = tx.get_raw_transaction();
+                    kept_addresses.contains(&tx.sender) || kept_addresses.contains(&tx.recipient)
+                }
+                HistoricTransactionData::Reward(ev) => kept_addresses.contains(&ev.reward_address),
+                HistoricTransactionData::Equivocation(_)
+                | HistoricTransactionData::Penalize(_)
+                | HistoricTransactionData::Jail(_) => false,
+            };
+            if involves_kept_address {
+                let tx_hash = hist_tx.tx_hash();
+                kept_leaves.push((tx_hash.clone(), *leaf_index as u32, leaf_hash.clone()));
+                kept_txs.insert(tx_hash);
+            }
+            !involves_kept_address
+        });
+
+        // The history tree is gone, so none of the blocks of the epoch has leaves anymore.
+        for block_number in block_numbers {
+            txn.remove(&self.last_leaf_table, &block_number);
+        }
+
+        // The kept transactions can't be proven anymore, mark their leaves as pruned.
+        for (tx_hash, leaf_index, leaf_hash) in kept_leaves {
+            txn.remove_item(
+                &self.tx_hash_table,
+                &tx_hash,
+                &OrderedHash {
+                    index: leaf_index,
+                    hash: leaf_hash.clone(),
+                },
+            );
+            txn.put(
+                &self.tx_hash_table,
+                &tx_hash,
+                &OrderedHash {
+                    index: Self::PRUNED_LEAF_INDEX,
+                    hash: leaf_hash,
+                },
+            );
+        }
+
+        self.remove_txns_from_history(txn, hashes, &kept_txs);
 
         Some(())
     }
@@ -971,6 +1433,123 @@ impl HistoryInterface for HistoryStore {
         tx_hashes
     }
 
+    /// Returns the hashes of all transactions that created an HTLC or a vesting contract involving
+    /// the given address. It fetches the transactions from most recent to least recent up to the
+    /// maximum number given.
+    fn get_contract_creations_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<Blake2bHash> {
+        let read_txn: TransactionProxy;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.db.read_transaction();
+                &read_txn
+            }
+        };
+
+        let mut cursor = txn.cursor(&self.contract_table);
+        if max == 0 || cursor.seek_key::<Address, OrderedHash>(address).is_none() {
+            return vec![];
+        }
+
+        let mut tx_hashes = vec![];
+        let mut entry = cursor.last_duplicate::<OrderedHash>();
+        while let Some(v) = entry {
+            tx_hashes.push(v.hash);
+            if tx_hashes.len() >= max as usize {
+                break;
+            }
+            entry = cursor
+                .prev_duplicate::<Address, OrderedHash>()
+                .map(|(_, v)| v);
+        }
+
+        tx_hashes
+    }
+
+    /// Returns the hashes of all transactions sent from the given HTLC or vesting contract,
+    /// including failed ones, from least recent to most recent.
+    fn get_txs_by_contract(
+        &self,
+        contract_address: &Address,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<Blake2bHash> {
+        let read_txn: TransactionProxy;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.db.read_transaction();
+                &read_txn
+            }
+        };
+
+        let mut cursor = txn.cursor(&self.contract_tx_table);
+        let mut entry = cursor.seek_key::<Address, OrderedHash>(contract_address);
+
+        let mut tx_hashes = vec![];
+        while let Some(v) = entry {
+            tx_hashes.push(v.hash);
+            entry = cursor
+                .next_duplicate::<Address, OrderedHash>()
+                .map(|(_, v)| v);
+        }
+
+        tx_hashes
+    }
+
+    /// Returns the staking events of all transactions and inherents that affected the validator
+    /// or the staker with the given address and match the given filter. It fetches the events from
+    /// most recent to least recent up to the maximum number given. If `start_at` is given, only
+    /// the events following it are returned.
+    fn get_staking_events_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<&Blake2bHash>,
+        filter: &dyn Fn(&StakingEventData) -> bool,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<StakingEvent> {
+        let read_txn: TransactionProxy;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.db.read_transaction();
+                &read_txn
+            }
+        };
+
+        let mut cursor = txn.cursor(&self.staking_table);
+        if max == 0 || cursor.seek_key::<Address, OrderedHash>(address).is_none() {
+            return vec![];
+        }
+
+        let mut events = vec![];
+        let mut skipping = start_at.is_some();
+        let mut entry = cursor.last_duplicate::<OrderedHash>();
+        while let Some(v) = entry {
+            if skipping {
+                // Skip all events up to and including the one to start at.
+                skipping = Some(&v.hash) != start_at;
+            } else if let Some(event) = self.get_staking_event(&v.hash, txn) {
+                if filter(&event.data) {
+                    events.push(event);
+                    if events.len() >= max as usize {
+                        break;
+                    }
+                }
+            }
+            entry = cursor
+                .prev_duplicate::<Address, OrderedHash>()
+                .map(|(_, v)| v);
+        }
+
+        events
+    }
+
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
     /// transactions.
     /// The verifier state is used for those cases where the verifier might have an incomplete MMR,
@@ -981,18 +1560,17 @@ impl HistoryInterface for HistoryStore {
         hashes: Vec<&Blake2bHash>,
         verifier_state: Option<usize>,
         txn_option: Option<&TransactionProxy>,
-    ) -> Option<HistoryTreeProof> {
+    ) -> Result<HistoryTreeProof, HistoryProofError> {
         // Get the leaf indexes.
         let mut positions = vec![];
 
         for hash in hashes {
-            let mut indices = self
-                .get_leaves_by_tx_hash(hash, txn_option)
-                .iter()
-                .map(|i| i.index as usize)
-                .collect();
-
-            positions.append(&mut indices)
+            for leaf in self.get_leaves_by_tx_hash(hash, txn_option) {
+                if leaf.index == Self::PRUNED_LEAF_INDEX {
+                    return Err(HistoryProofError::Pruned);
+                }
+                positions.push(leaf.index as usize);
+            }
         }
 
         self.prove_with_position(epoch_number, positions, verifier_state, txn_option)
@@ -1170,16 +1748,24 @@ impl HistoryInterface for HistoryStore {
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use nimiq_database::volatile::VolatileDatabase;
+    use nimiq_keys::{KeyPair, PrivateKey};
     use nimiq_primitives::{coin::Coin, networks::NetworkId};
     use nimiq_test_log::test;
     use nimiq_transaction::{
+        account::staking_contract::IncomingStakingTransactionData,
         historic_transaction::{JailEvent, PenalizeEvent, RewardEvent},
         ExecutedTransaction, ForkLocator, Transaction as BlockchainTransaction,
     };
+    use nimiq_transaction_builder::TransactionBuilder;
 
     use super::*;
 
+    const STAKER_SECRET_KEY: &str =
+        "6c9320ac201caf1f8eaa5b05f5d67a9e77826f3f6be266a0ecccc20416dc6587";
+
     #[test]
     fn prove_num_leaves_works() {
         // Initialize History Store.
@@ -1713,6 +2299,361 @@ mod tests {
         assert_eq!(query_4.len(), 0);
     }
 
+    #[test]
+    fn get_contract_creations_by_address_works() {
+        // Initialize History Store.
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        let creator = Address::from([1u8; 20]);
+        let recipient = Address::from([2u8; 20]);
+        let owner = Address::from([3u8; 20]);
+
+        // Create an HTLC, a plain transaction and a vesting contract.
+        let genesis_block_number = Policy::genesis_block_number();
+        let htlc_data = HtlcCreationData {
+            sender: creator.clone(),
+            recipient: recipient.clone(),
+            ..Default::default()
+        };
+        let vesting_data = VestingCreationData {
+            owner: owner.clone(),
+            ..Default::default()
+        };
+        let hist_txs = vec![
+            create_contract_creation(
+                genesis_block_number + 1,
+                &creator,
+                AccountType::HTLC,
+                htlc_data.serialize_to_vec(),
+            ),
+            create_transaction(genesis_block_number + 1, 1),
+            create_contract_creation(
+                genesis_block_number + 2,
+                &creator,
+                AccountType::Vesting,
+                vesting_data.serialize_to_vec(),
+            ),
+        ];
+        let hashes: Vec<_> = hist_txs.iter().map(|hist_tx| hist_tx.tx_hash()).collect();
+
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 0, &hist_txs);
+
+        // Verify method works.
+        let query_1 = history_store.get_contract_creations_by_address(&creator, 99, Some(&txn));
+        assert_eq!(query_1, vec![(*hashes[2]).clone(), (*hashes[0]).clone()]);
+
+        let query_2 = history_store.get_contract_creations_by_address(&creator, 1, Some(&txn));
+        assert_eq!(query_2, vec![(*hashes[2]).clone()]);
+
+        let query_3 = history_store.get_contract_creations_by_address(&recipient, 99, Some(&txn));
+        assert_eq!(query_3, vec![(*hashes[0]).clone()]);
+
+        let query_4 = history_store.get_contract_creations_by_address(&owner, 99, Some(&txn));
+        assert_eq!(query_4, vec![(*hashes[2]).clone()]);
+
+        let query_5 = history_store.get_contract_creations_by_address(
+            &Address::burn_address(),
+            99,
+            Some(&txn),
+        );
+        assert!(query_5.is_empty());
+
+        // Removing the vesting contract creation also removes it from the index.
+        history_store.remove_partial_history(&mut txn, 0, 1);
+
+        let query_6 = history_store.get_contract_creations_by_address(&creator, 99, Some(&txn));
+        assert_eq!(query_6, vec![(*hashes[0]).clone()]);
+
+        let query_7 = history_store.get_contract_creations_by_address(&owner, 99, Some(&txn));
+        assert!(query_7.is_empty());
+    }
+
+    #[test]
+    fn get_txs_by_contract_works() {
+        // Initialize History Store.
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        let contract = Address::from([1u8; 20]);
+
+        // Two transactions from the contract, one of them failed, and a plain transaction.
+        let genesis_block_number = Policy::genesis_block_number();
+        let hist_txs = vec![
+            create_contract_transaction(genesis_block_number + 1, &contract, true),
+            create_transaction(genesis_block_number + 1, 1),
+            create_contract_transaction(genesis_block_number + 2, &contract, false),
+        ];
+        let hashes: Vec<_> = hist_txs.iter().map(|hist_tx| hist_tx.tx_hash()).collect();
+
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 0, &hist_txs);
+
+        // Verify method works.
+        let query_1 = history_store.get_txs_by_contract(&contract, Some(&txn));
+        assert_eq!(query_1, vec![(*hashes[0]).clone(), (*hashes[2]).clone()]);
+
+        let query_2 = history_store.get_txs_by_contract(&Address::burn_address(), Some(&txn));
+        assert!(query_2.is_empty());
+
+        // Removing the failed transaction also removes it from the index.
+        history_store.remove_partial_history(&mut txn, 0, 1);
+
+        let query_3 = history_store.get_txs_by_contract(&contract, Some(&txn));
+        assert_eq!(query_3, vec![(*hashes[0]).clone()]);
+    }
+
+    #[test]
+    fn rebuild_indexes_works() {
+        // Initialize History Store.
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        let creator = Address::from([1u8; 20]);
+        let contract = Address::from([2u8; 20]);
+
+        let genesis_block_number = Policy::genesis_block_number();
+        let vesting_data = VestingCreationData {
+            owner: creator.clone(),
+            ..Default::default()
+        };
+        let hist_txs = vec![
+            create_contract_creation(
+                genesis_block_number + 1,
+                &creator,
+                AccountType::Vesting,
+                vesting_data.serialize_to_vec(),
+            ),
+            create_contract_transaction(genesis_block_number + 2, &contract, true),
+            create_transaction(genesis_block_number + 2, 1),
+        ];
+        let hashes: Vec<_> = hist_txs.iter().map(|hist_tx| hist_tx.tx_hash()).collect();
+
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 0, &hist_txs);
+
+        // Simulate a database that was synced before the indexes existed.
+        txn.clear_database(&history_store.contract_table);
+        txn.clear_database(&history_store.contract_tx_table);
+        assert!(history_store
+            .get_contract_creations_by_address(&creator, 99, Some(&txn))
+            .is_empty());
+
+        assert_eq!(history_store.rebuild_indexes(&mut txn), 3);
+
+        let query_1 = history_store.get_contract_creations_by_address(&creator, 99, Some(&txn));
+        assert_eq!(query_1, vec![(*hashes[0]).clone()]);
+
+        let query_2 = history_store.get_txs_by_contract(&contract, Some(&txn));
+        assert_eq!(query_2, vec![(*hashes[1]).clone()]);
+    }
+
+    #[test]
+    fn get_staking_events_by_address_works() {
+        // Initialize History Store.
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        let staker = Address::from([4u8; 20]);
+        let validator =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+
+        // Create two stake transactions as well as a jail and a penalize inherent.
+        let genesis_block_number = Policy::genesis_block_number();
+        let hist_txs = vec![
+            create_add_stake(genesis_block_number + 1, &staker, 1),
+            create_transaction(genesis_block_number + 1, 2),
+            create_add_stake(genesis_block_number + 2, &staker, 3),
+            create_jail_inherent(genesis_block_number + 2),
+            create_penalize_inherent(genesis_block_number + 2),
+        ];
+        let hashes: Vec<Blake2bHash> = hist_txs
+            .iter()
+            .map(|hist_tx| hist_tx.tx_hash().into())
+            .collect();
+
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 0, &hist_txs);
+
+        let get_hashes = |address: &Address,
+                          max: u16,
+                          start_at: Option<&Blake2bHash>,
+                          txn: &TransactionProxy| {
+            history_store
+                .get_staking_events_by_address(address, max, start_at, &|_| true, Some(txn))
+                .into_iter()
+                .map(|event| event.tx_hash)
+                .collect::<Vec<_>>()
+        };
+
+        // Verify method works.
+        let query_1 = get_hashes(&staker, 99, None, &txn);
+        assert_eq!(query_1, vec![hashes[2].clone(), hashes[0].clone()]);
+
+        // Paginate through the events.
+        let query_2 = get_hashes(&staker, 1, None, &txn);
+        assert_eq!(query_2, vec![hashes[2].clone()]);
+
+        let query_3 = get_hashes(&staker, 1, Some(&hashes[2]), &txn);
+        assert_eq!(query_3, vec![hashes[0].clone()]);
+
+        let query_4 = get_hashes(&staker, 1, Some(&hashes[0]), &txn);
+        assert!(query_4.is_empty());
+
+        // Jail and penalize inherents are indexed by the validator address.
+        let mut query_5 = get_hashes(&validator, 99, None, &txn);
+        query_5.sort();
+        let mut expected = vec![hashes[3].clone(), hashes[4].clone()];
+        expected.sort();
+        assert_eq!(query_5, expected);
+
+        let query_6 = get_hashes(&Address::burn_address(), 99, None, &txn);
+        assert!(query_6.is_empty());
+
+        // Removing the transactions also removes them from the index.
+        history_store.remove_partial_history(&mut txn, 0, 3);
+
+        let query_7 = get_hashes(&staker, 99, None, &txn);
+        assert_eq!(query_7, vec![hashes[0].clone()]);
+
+        let query_8 = get_hashes(&validator, 99, None, &txn);
+        assert!(query_8.is_empty());
+    }
+
+    #[test]
+    fn staker_events_are_indexed_by_delegation_works() {
+        // Initialize History Store.
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        let key_pair = KeyPair::from(PrivateKey::from_str(STAKER_SECRET_KEY).unwrap());
+        let staker = Address::from(&key_pair);
+        let validator_1 = Address::from([1u8; 20]);
+        let validator_2 = Address::from([2u8; 20]);
+
+        // Create a staker delegating to the first validator, redelegate it to the second one and
+        // then retire and remove its stake.
+        let genesis_block_number = Policy::genesis_block_number();
+        let hist_txs = vec![
+            create_staking_transaction(
+                genesis_block_number + 1,
+                TransactionBuilder::new_create_staker(
+                    &key_pair,
+                    &key_pair,
+                    Some(validator_1.clone()),
+                    Coin::from_u64_unchecked(100),
+                    Coin::ZERO,
+                    1,
+                    NetworkId::UnitAlbatross,
+                )
+                .unwrap(),
+            ),
+            create_staking_transaction(
+                genesis_block_number + 2,
+                TransactionBuilder::new_update_staker(
+                    None,
+                    &key_pair,
+                    Some(validator_2.clone()),
+                    false,
+                    Coin::ZERO,
+                    2,
+                    NetworkId::UnitAlbatross,
+                )
+                .unwrap(),
+            ),
+            create_staking_transaction(
+                genesis_block_number + 3,
+                TransactionBuilder::new_retire_stake(
+                    None,
+                    &key_pair,
+                    Coin::from_u64_unchecked(100),
+                    Coin::ZERO,
+                    3,
+                    NetworkId::UnitAlbatross,
+                )
+                .unwrap(),
+            ),
+            create_staking_transaction(
+                genesis_block_number + 4,
+                TransactionBuilder::new_remove_stake(
+                    &key_pair,
+                    staker.clone(),
+                    Coin::from_u64_unchecked(100),
+                    Coin::ZERO,
+                    4,
+                    NetworkId::UnitAlbatross,
+                )
+                .unwrap(),
+            ),
+        ];
+        let hashes: Vec<Blake2bHash> = hist_txs
+            .iter()
+            .map(|hist_tx| hist_tx.tx_hash().into())
+            .collect();
+
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 0, &hist_txs);
+
+        let get_events = |address: &Address, txn: &TransactionProxy| {
+            history_store.get_staking_events_by_address(address, 99, None, &|_| true, Some(txn))
+        };
+        let get_hashes = |address: &Address, txn: &TransactionProxy| {
+            get_events(address, txn)
+                .into_iter()
+                .map(|event| event.tx_hash)
+                .collect::<Vec<_>>()
+        };
+
+        // The redelegation is part of the history of both validators, the stake changes are part
+        // of the history of the validator the staker delegates to.
+        assert_eq!(
+            get_hashes(&validator_1, &txn),
+            vec![hashes[1].clone(), hashes[0].clone()]
+        );
+        assert_eq!(
+            get_hashes(&validator_2, &txn),
+            vec![hashes[3].clone(), hashes[2].clone(), hashes[1].clone()]
+        );
+        assert_eq!(get_hashes(&staker, &txn).len(), 4);
+
+        let events = get_events(&validator_2, &txn);
+        assert!(matches!(
+            &events[2].data,
+            StakingEventData::UpdateStaker { old_delegation, new_delegation, .. }
+                if old_delegation == &Some(validator_1.clone())
+                    && new_delegation == &Some(validator_2.clone())
+        ));
+        assert!(matches!(
+            &events[0].data,
+            StakingEventData::RemoveStake { delegation, .. }
+                if delegation == &Some(validator_2.clone())
+        ));
+
+        // The delegations are derived again when rebuilding the indexes.
+        txn.clear_database(&history_store.staking_table);
+        txn.clear_database(&history_store.staker_delegation_table);
+        assert!(get_hashes(&validator_2, &txn).is_empty());
+
+        assert_eq!(history_store.rebuild_indexes(&mut txn), 4);
+        assert_eq!(
+            get_hashes(&validator_2, &txn),
+            vec![hashes[3].clone(), hashes[2].clone(), hashes[1].clone()]
+        );
+        assert_eq!(get_events(&validator_2, &txn), events);
+
+        // Removing the transactions also removes them from the validator indexes.
+        history_store.remove_partial_history(&mut txn, 0, 2);
+
+        assert_eq!(get_hashes(&validator_2, &txn), vec![hashes[1].clone()]);
+        let delegation: Option<Address> = txn.get(
+            &history_store.staker_delegation_table,
+            &hist_txs[3].tx_hash(),
+        );
+        assert!(delegation.is_none());
+    }
+
     #[test]
     fn prove_works() {
         // Initialize History Store.
@@ -1800,6 +2741,64 @@ mod tests {
         assert!(proof.verify(root).unwrap());
     }
 
+    #[test]
+    fn prune_history_keeps_watched_transactions_works() {
+        let genesis_block_number = Policy::genesis_block_number();
+        // Initialize History Store.
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        // Create historic transactions.
+        let hist_txs = gen_hist_txs();
+
+        // Add historic transactions to History Store.
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 0, &hist_txs);
+
+        let hashes: Vec<_> = hist_txs.iter().map(|hist_tx| hist_tx.tx_hash()).collect();
+
+        // Keep the basic transactions, which are all sent from the same address.
+        let sender = hist_txs[0]
+            .unwrap_basic()
+            .get_raw_transaction()
+            .sender
+            .clone();
+        history_store
+            .prune_history(&mut txn, 0, &[sender.clone()].into())
+            .unwrap();
+
+        // The history tree and the other historic transactions are gone.
+        assert_eq!(history_store.num_epoch_transactions(0, Some(&txn)), 0);
+        assert!(history_store
+            .get_hist_tx_by_hash(&hashes[2], Some(&txn))
+            .is_empty());
+        for block_number in genesis_block_number..genesis_block_number + 3 {
+            assert!(history_store
+                .get_block_transactions(block_number, Some(&txn))
+                .is_empty());
+        }
+
+        // The kept transactions can still be retrieved.
+        assert_eq!(
+            history_store
+                .get_tx_hashes_by_address(&sender, u16::MAX, Some(&txn))
+                .len(),
+            5
+        );
+        let kept = history_store.get_hist_tx_by_hash(&hashes[3], Some(&txn));
+        assert_eq!(kept.len(), 1);
+        assert_eq!(
+            kept[0].unwrap_basic().get_raw_transaction().value,
+            Coin::from_u64_unchecked(3),
+        );
+
+        // But they can't be proven anymore.
+        assert!(matches!(
+            history_store.prove(0, vec![&hashes[3]], None, Some(&txn)),
+            Err(HistoryProofError::Pruned)
+        ));
+    }
+
     #[test]
     fn get_indexes_for_block_works() {
         let genesis_block_number = Policy::genesis_block_number();
@@ -1937,6 +2936,98 @@ mod tests {
         }
     }
 
+    fn create_contract_transaction(
+        block: u32,
+        contract: &Address,
+        succeeded: bool,
+    ) -> HistoricTransaction {
+        let tx = BlockchainTransaction::new_extended(
+            contract.clone(),
+            AccountType::HTLC,
+            vec![],
+            Address::burn_address(),
+            AccountType::Basic,
+            vec![],
+            Coin::from_u64_unchecked(10),
+            Coin::from_u64_unchecked(1),
+            0,
+            NetworkId::Dummy,
+        );
+        HistoricTransaction {
+            network_id: NetworkId::UnitAlbatross,
+            block_number: block,
+            block_time: 0,
+            data: HistoricTransactionData::Basic(if succeeded {
+                ExecutedTransaction::Ok(tx)
+            } else {
+                ExecutedTransaction::Err(tx)
+            }),
+        }
+    }
+
+    fn create_contract_creation(
+        block: u32,
+        sender: &Address,
+        recipient_type: AccountType,
+        recipient_data: Vec<u8>,
+    ) -> HistoricTransaction {
+        HistoricTransaction {
+            network_id: NetworkId::UnitAlbatross,
+            block_number: block,
+            block_time: 0,
+            data: HistoricTransactionData::Basic(ExecutedTransaction::Ok(
+                BlockchainTransaction::new_contract_creation(
+                    sender.clone(),
+                    AccountType::Basic,
+                    vec![],
+                    recipient_type,
+                    recipient_data,
+                    Coin::from_u64_unchecked(100),
+                    Coin::from_u64_unchecked(0),
+                    0,
+                    NetworkId::Dummy,
+                ),
+            )),
+        }
+    }
+
+    fn create_add_stake(block: u32, staker_address: &Address, value: u64) -> HistoricTransaction {
+        let data = IncomingStakingTransactionData::AddStake {
+            staker_address: staker_address.clone(),
+        };
+        HistoricTransaction {
+            network_id: NetworkId::UnitAlbatross,
+            block_number: block,
+            block_time: 0,
+            data: HistoricTransactionData::Basic(ExecutedTransaction::Ok(
+                BlockchainTransaction::new_extended(
+                    Address::from_user_friendly_address(
+                        "NQ09 VF5Y 1PKV MRM4 5LE1 55KV P6R2 GXYJ XYQF",
+                    )
+                    .unwrap(),
+                    AccountType::Basic,
+                    vec![],
+                    Policy::STAKING_CONTRACT_ADDRESS,
+                    AccountType::Staking,
+                    data.serialize_to_vec(),
+                    Coin::from_u64_unchecked(value),
+                    Coin::from_u64_unchecked(0),
+                    0,
+                    NetworkId::Dummy,
+                ),
+            )),
+        }
+    }
+
+    fn create_staking_transaction(block: u32, tx: BlockchainTransaction) -> HistoricTransaction {
+        HistoricTransaction {
+            network_id: NetworkId::UnitAlbatross,
+            block_number: block,
+            block_time: 0,
+            data: HistoricTransactionData::Basic(ExecutedTransaction::Ok(tx)),
+        }
+    }
+
     fn gen_hist_txs() -> Vec<HistoricTransaction> {
         let genesis_block_number = Policy::genesis_block_number();
         let ext_0 = create_transaction(genesis_block_number + 0, 0);
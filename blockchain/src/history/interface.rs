@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use nimiq_block::Block;
 use nimiq_database::{TransactionProxy, WriteTransactionProxy};
 use nimiq_hash::Blake2bHash;
@@ -10,8 +12,22 @@ use nimiq_transaction::{
     historic_transaction::HistoricTransaction, history_proof::HistoryTreeProof, inherent::Inherent,
     EquivocationLocator,
 };
+use thiserror::Error;
+
+use crate::{
+    staking_history::{StakingEvent, StakingEventData},
+    HistoryTreeChunk,
+};
 
-use crate::HistoryTreeChunk;
+/// Errors that can occur when proving historic transactions.
+#[derive(Debug, Error)]
+pub enum HistoryProofError {
+    /// The history tree of the transaction's epoch was pruned, only the transaction was kept.
+    #[error("The history of the transaction was pruned")]
+    Pruned,
+    #[error("Failed to prove the transactions: {0:?}")]
+    Proof(MMRError),
+}
 
 /// Defines several methods to interact with a history store.
 pub trait HistoryInterface {
@@ -29,6 +45,16 @@ pub trait HistoryInterface {
     /// Removes the full history associated with a given epoch.
     fn remove_history(&self, txn: &mut WriteTransactionProxy, epoch_number: u32) -> Option<()>;
 
+    /// Removes the history tree associated with a given epoch and all of its historic
+    /// transactions, except for those involving one of the given addresses. The kept transactions
+    /// aren't part of a history tree anymore, so they can't be proven.
+    fn prune_history(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        epoch_number: u32,
+        kept_addresses: &HashSet<Address>,
+    ) -> Option<()>;
+
     /// Obtains the current history root at the given epoch.
     fn get_history_tree_root(
         &self,
@@ -134,17 +160,52 @@ pub trait HistoryInterface {
         txn_option: Option<&TransactionProxy>,
     ) -> Vec<Blake2bHash>;
 
+    /// Returns the hashes of all transactions that created an HTLC or a vesting contract involving
+    /// the given address, i.e. contracts it created, HTLCs it is the sender or recipient of and
+    /// vesting contracts it owns. It fetches the transactions from most recent to least recent up
+    /// to the maximum number given.
+    fn get_contract_creations_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<Blake2bHash>;
+
+    /// Returns the hashes of all transactions sent from the given HTLC or vesting contract,
+    /// including failed ones since they pay their fee from the contract, from least recent to most
+    /// recent.
+    fn get_txs_by_contract(
+        &self,
+        contract_address: &Address,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<Blake2bHash>;
+
+    /// Returns the staking events of all transactions and inherents that affected the validator
+    /// or the staker with the given address, including the events of the stakers delegating to a
+    /// validator, that match the given filter. It fetches the events from most recent to least
+    /// recent up to the maximum number given. If `start_at` is given, only the events following
+    /// the one with that hash are returned, which allows paginating through the results.
+    fn get_staking_events_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<&Blake2bHash>,
+        filter: &dyn Fn(&StakingEventData) -> bool,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<StakingEvent>;
+
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
     /// transactions.
     /// The verifier state is used for those cases where the verifier might have an incomplete MMR,
     /// for instance this could occur where we want to create transaction inclusion proofs of incomplete epochs.
+    /// Transactions that were kept when pruning the history of their epoch can't be proven anymore.
     fn prove(
         &self,
         epoch_number: u32,
         hashes: Vec<&Blake2bHash>,
         verifier_state: Option<usize>,
         txn_option: Option<&TransactionProxy>,
-    ) -> Option<HistoryTreeProof>;
+    ) -> Result<HistoryTreeProof, HistoryProofError>;
 
     /// Returns the `chunk_index`th chunk of size `chunk_size` for a given epoch.
     /// The return value consists of a vector of all the historic transactions in that chunk
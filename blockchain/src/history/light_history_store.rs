@@ -1,17 +1,21 @@
+use std::collections::HashSet;
+
 use nimiq_database::{
     traits::{Database, WriteTransaction},
     DatabaseProxy, TableProxy, TransactionProxy, WriteTransactionProxy,
 };
 use nimiq_genesis::NetworkId;
 use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
 use nimiq_mmr::mmr::PeaksMerkleMountainRange;
 use nimiq_transaction::{historic_transaction::HistoricTransaction, inherent::Inherent};
 
 use super::{
-    interface::HistoryInterface,
+    interface::{HistoryInterface, HistoryProofError},
     mmr_store::{remove_block_from_store, LightMMRStore},
     validity_store::ValidityStore,
 };
+use crate::staking_history::{StakingEvent, StakingEventData};
 
 /// The LightHistoryStore is a simplified version of the history store.
 /// Internally it uses a Peaks-only MMR
@@ -101,6 +105,16 @@ impl HistoryInterface for LightHistoryStore {
         todo!()
     }
 
+    fn prune_history(
+        &self,
+        _txn: &mut WriteTransactionProxy,
+        _epoch_number: u32,
+        _kept_addresses: &HashSet<Address>,
+    ) -> Option<()> {
+        // The light history store doesn't keep historic transactions, so there is nothing to prune.
+        None
+    }
+
     fn get_history_tree_root(
         &self,
         epoch_number: u32,
@@ -271,13 +285,43 @@ impl HistoryInterface for LightHistoryStore {
         unimplemented!()
     }
 
+    fn get_contract_creations_by_address(
+        &self,
+        _address: &nimiq_keys::Address,
+        _max: u16,
+        _txn_option: Option<&TransactionProxy>,
+    ) -> Vec<nimiq_hash::Blake2bHash> {
+        // Light nodes don't index contracts.
+        vec![]
+    }
+
+    fn get_txs_by_contract(
+        &self,
+        _contract_address: &nimiq_keys::Address,
+        _txn_option: Option<&TransactionProxy>,
+    ) -> Vec<nimiq_hash::Blake2bHash> {
+        vec![]
+    }
+
+    fn get_staking_events_by_address(
+        &self,
+        _address: &nimiq_keys::Address,
+        _max: u16,
+        _start_at: Option<&nimiq_hash::Blake2bHash>,
+        _filter: &dyn Fn(&StakingEventData) -> bool,
+        _txn_option: Option<&TransactionProxy>,
+    ) -> Vec<StakingEvent> {
+        // Light nodes don't index staking events.
+        vec![]
+    }
+
     fn prove(
         &self,
         _epoch_number: u32,
         _hashes: Vec<&nimiq_hash::Blake2bHash>,
         _verifier_state: Option<usize>,
         _txn_option: Option<&TransactionProxy>,
-    ) -> Option<nimiq_transaction::history_proof::HistoryTreeProof> {
+    ) -> Result<nimiq_transaction::history_proof::HistoryTreeProof, HistoryProofError> {
         unimplemented!()
     }
 
@@ -0,0 +1,500 @@
+//! Offline consistency checks of the blockchain database.
+//!
+//! The checks operate directly on the stores instead of a loaded `Blockchain`, since loading
+//! fails on exactly the inconsistencies these checks are meant to diagnose.
+
+use std::fmt;
+
+use nimiq_account::Accounts;
+use nimiq_block::Block;
+use nimiq_blockchain_interface::ChainInfo;
+use nimiq_database::{
+    traits::{Database, WriteTransaction},
+    DatabaseProxy,
+};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+use thiserror::Error;
+
+use crate::{chain_store::ChainStore, history::HistoryStore, interface::HistoryInterface};
+
+/// The outcome of a single check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// The invariant holds.
+    Ok,
+    /// The invariant could not be checked, e.g. because the data was pruned.
+    Skipped,
+    /// The invariant is violated.
+    Failed,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckStatus::Ok => write!(f, "OK"),
+            CheckStatus::Skipped => write!(f, "SKIPPED"),
+            CheckStatus::Failed => write!(f, "FAILED"),
+        }
+    }
+}
+
+/// The result of a single check together with a human readable diagnosis.
+#[derive(Clone, Debug)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl CheckResult {
+    fn ok(name: &'static str, message: String) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            message,
+        }
+    }
+
+    fn skipped(name: &'static str, message: String) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skipped,
+            message,
+        }
+    }
+
+    fn failed(name: &'static str, message: String) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Failed,
+            message,
+        }
+    }
+}
+
+/// The results of all checks.
+#[derive(Clone, Debug)]
+pub struct IntegrityReport {
+    /// The block number and hash of the stored head, if any.
+    pub head: Option<(u32, Blake2bHash)>,
+    pub checks: Vec<CheckResult>,
+}
+
+impl IntegrityReport {
+    /// Returns true if none of the checks failed.
+    pub fn is_consistent(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != CheckStatus::Failed)
+    }
+}
+
+/// The errors that prevent a repair of the database.
+#[derive(Debug, Error)]
+pub enum RepairError {
+    #[error("No head block stored")]
+    NoHead,
+    #[error("The accounts tree is incomplete")]
+    IncompleteAccounts,
+    #[error("No block since the last macro block #{0} matches the accounts tree")]
+    NoConsistentBlock(u32),
+    #[error("History root mismatch at block #{0} after rolling back")]
+    HistoryMismatch(u32),
+}
+
+/// Checks the invariants between the chain store, the history store and the accounts tree.
+pub struct IntegrityChecker {
+    env: DatabaseProxy,
+    chain_store: ChainStore,
+    history_store: HistoryStore,
+    accounts: Accounts,
+}
+
+impl IntegrityChecker {
+    const HEAD: &'static str = "chain head";
+    const LINKAGE: &'static str = "chain linkage";
+    const ELECTION_CHAIN: &'static str = "election chain";
+    const ACCOUNTS: &'static str = "accounts root";
+    const HISTORY: &'static str = "history roots";
+    const VALIDITY_WINDOW: &'static str = "validity window";
+
+    pub fn new(env: DatabaseProxy) -> Self {
+        Self {
+            chain_store: ChainStore::new(env.clone()),
+            history_store: HistoryStore::new(env.clone()),
+            accounts: Accounts::new(env.clone()),
+            env,
+        }
+    }
+
+    /// Runs all checks and returns their results.
+    pub fn check(&self) -> IntegrityReport {
+        let head = match self.head() {
+            Ok(head) => head,
+            Err(check) => {
+                return IntegrityReport {
+                    head: None,
+                    checks: vec![check],
+                }
+            }
+        };
+        let head_hash = head.head.hash();
+
+        let mut checks = vec![CheckResult::ok(
+            Self::HEAD,
+            format!("#{} {}", head.head.block_number(), head_hash),
+        )];
+        checks.push(self.check_linkage(&head));
+        let (election_check, election_blocks) = self.check_election_chain(&head);
+        checks.push(election_check);
+        checks.push(self.check_accounts(&head));
+        checks.push(self.check_history(&head, &election_blocks));
+        checks.push(self.check_validity_window(&head));
+
+        IntegrityReport {
+            head: Some((head.head.block_number(), head_hash)),
+            checks,
+        }
+    }
+
+    /// Rolls the chain back to the most recent block since the last macro block whose state
+    /// root matches the accounts tree. Returns the new head or `None` if the head already
+    /// matches the accounts tree.
+    pub fn repair(&self) -> Result<Option<Block>, RepairError> {
+        let head = self.head().map_err(|_| RepairError::NoHead)?;
+        let accounts_root = self
+            .accounts
+            .get_root_hash(None)
+            .ok_or(RepairError::IncompleteAccounts)?;
+        if head.head.state_root() == &accounts_root {
+            return Ok(None);
+        }
+
+        // Find the most recent consistent block, only reverting micro blocks.
+        let last_macro_block = Policy::last_macro_block(head.head.block_number());
+        let mut reverted = vec![];
+        let mut target = head;
+        while target.head.state_root() != &accounts_root {
+            if target.head.block_number() <= last_macro_block {
+                return Err(RepairError::NoConsistentBlock(last_macro_block));
+            }
+            let parent = self
+                .chain_store
+                .get_chain_info(target.head.parent_hash(), false, None)
+                .map_err(|_| RepairError::NoConsistentBlock(last_macro_block))?;
+            reverted.push(target);
+            target = parent;
+        }
+
+        let mut txn = self.env.write_transaction();
+        let target_hash = target.head.hash();
+        let reverted_hist_txs = reverted
+            .first()
+            .map_or(0, |head| head.history_tree_len - target.history_tree_len);
+        if Policy::is_election_block_at(target.head.block_number()) {
+            // The reverted blocks all belong to the next epoch.
+            self.history_store
+                .remove_history(&mut txn, target.head.epoch_number() + 1);
+        } else {
+            self.history_store.remove_partial_history(
+                &mut txn,
+                target.head.epoch_number(),
+                reverted_hist_txs as usize,
+            );
+        }
+
+        for mut info in reverted {
+            info.on_main_chain = false;
+            info.main_chain_successor = None;
+            self.chain_store
+                .put_chain_info(&mut txn, &info.head.hash(), &info, false);
+        }
+        target.main_chain_successor = None;
+        self.chain_store
+            .put_chain_info(&mut txn, &target_hash, &target, false);
+        self.chain_store.set_head(&mut txn, &target_hash);
+
+        if !Policy::is_election_block_at(target.head.block_number())
+            && self
+                .history_store
+                .get_history_tree_root(target.head.epoch_number(), Some(&txn))
+                .is_some_and(|root| &root != target.head.history_root())
+        {
+            txn.abort();
+            return Err(RepairError::HistoryMismatch(target.head.block_number()));
+        }
+
+        txn.commit();
+        Ok(Some(target.head))
+    }
+
+    fn head(&self) -> Result<ChainInfo, CheckResult> {
+        let head_hash = self
+            .chain_store
+            .get_head(None)
+            .ok_or_else(|| CheckResult::failed(Self::HEAD, "No head block stored".to_string()))?;
+        let head = self
+            .chain_store
+            .get_chain_info(&head_hash, true, None)
+            .map_err(|_| {
+                CheckResult::failed(
+                    Self::HEAD,
+                    format!("The chain info of the head block {head_hash} is missing"),
+                )
+            })?;
+        if !head.on_main_chain {
+            return Err(CheckResult::failed(
+                Self::HEAD,
+                format!("The head block {head_hash} is not on the main chain"),
+            ));
+        }
+        Ok(head)
+    }
+
+    /// Walks from the head back to the last election block and checks that every block is
+    /// linked to its parent and indexed at its height.
+    fn check_linkage(&self, head: &ChainInfo) -> CheckResult {
+        let last_election_block = Policy::last_election_block(head.head.block_number());
+        let mut info = head.clone();
+        let mut num_blocks = 1;
+
+        while info.head.block_number() > last_election_block {
+            let block_number = info.head.block_number();
+            let hash = info.head.hash();
+
+            match self
+                .chain_store
+                .get_chain_info_at(block_number, false, None)
+            {
+                Ok(indexed) if indexed.head.hash() == hash => {}
+                _ => {
+                    return CheckResult::failed(
+                        Self::LINKAGE,
+                        format!("Block #{block_number} {hash} is not indexed as main chain block"),
+                    )
+                }
+            }
+
+            let parent = match self
+                .chain_store
+                .get_chain_info(info.head.parent_hash(), false, None)
+            {
+                Ok(parent) => parent,
+                Err(_) => {
+                    return CheckResult::failed(
+                        Self::LINKAGE,
+                        format!("The parent of block #{block_number} {hash} is missing"),
+                    )
+                }
+            };
+            if !parent.on_main_chain
+                || parent.main_chain_successor.as_ref() != Some(&hash)
+                || parent.head.block_number() + 1 != block_number
+            {
+                return CheckResult::failed(
+                    Self::LINKAGE,
+                    format!(
+                        "Block #{} {} does not link to its successor #{block_number} {hash}",
+                        parent.head.block_number(),
+                        info.head.parent_hash(),
+                    ),
+                );
+            }
+
+            info = parent;
+            num_blocks += 1;
+        }
+
+        CheckResult::ok(
+            Self::LINKAGE,
+            format!("{num_blocks} blocks back to election block #{last_election_block}"),
+        )
+    }
+
+    /// Follows the election blocks back until they are no longer stored. Returns the check
+    /// result and the visited election blocks.
+    fn check_election_chain(&self, head: &ChainInfo) -> (CheckResult, Vec<Block>) {
+        let last_election_block = Policy::last_election_block(head.head.block_number());
+        let mut election_blocks = vec![];
+        let mut info = match self
+            .chain_store
+            .get_chain_info_at(last_election_block, false, None)
+        {
+            Ok(info) => info,
+            Err(_) => {
+                return (
+                    CheckResult::failed(
+                        Self::ELECTION_CHAIN,
+                        format!("Election block #{last_election_block} is missing"),
+                    ),
+                    election_blocks,
+                )
+            }
+        };
+
+        loop {
+            if !info.head.is_election() {
+                return (
+                    CheckResult::failed(
+                        Self::ELECTION_CHAIN,
+                        format!(
+                            "Block #{} is not an election block",
+                            info.head.block_number()
+                        ),
+                    ),
+                    election_blocks,
+                );
+            }
+
+            let parent_hash = info.head.parent_election_hash().cloned();
+            election_blocks.push(info.head);
+
+            match parent_hash
+                .and_then(|hash| self.chain_store.get_chain_info(&hash, false, None).ok())
+            {
+                Some(parent) if parent.on_main_chain => info = parent,
+                Some(parent) => {
+                    return (
+                        CheckResult::failed(
+                            Self::ELECTION_CHAIN,
+                            format!(
+                                "Election block #{} is not on the main chain",
+                                parent.head.block_number()
+                            ),
+                        ),
+                        election_blocks,
+                    )
+                }
+                None => break,
+            }
+        }
+
+        (
+            CheckResult::ok(
+                Self::ELECTION_CHAIN,
+                format!("{} election blocks stored", election_blocks.len()),
+            ),
+            election_blocks,
+        )
+    }
+
+    fn check_accounts(&self, head: &ChainInfo) -> CheckResult {
+        match self.accounts.get_root_hash(None) {
+            None => CheckResult::skipped(
+                Self::ACCOUNTS,
+                "The accounts tree is incomplete (state sync in progress)".to_string(),
+            ),
+            Some(root) if head.head.state_root() == &root => {
+                CheckResult::ok(Self::ACCOUNTS, format!("Matches the head block: {root}"))
+            }
+            Some(root) => CheckResult::failed(
+                Self::ACCOUNTS,
+                format!(
+                    "Accounts tree root {root} does not match the state root {} of the head block #{}",
+                    head.head.state_root(),
+                    head.head.block_number(),
+                ),
+            ),
+        }
+    }
+
+    /// Compares the history tree roots of the current and of all stored past epochs with the
+    /// history roots of the respective blocks.
+    fn check_history(&self, head: &ChainInfo, election_blocks: &[Block]) -> CheckResult {
+        let mut num_checked = 0;
+        let mut num_pruned = 0;
+
+        let head_block = &head.head;
+        let current = (!head_block.is_election()).then_some(head_block);
+        for block in current.into_iter().chain(election_blocks) {
+            match self
+                .history_store
+                .get_history_tree_root(block.epoch_number(), None)
+            {
+                Some(root) if &root == block.history_root() => num_checked += 1,
+                Some(root) => {
+                    return CheckResult::failed(
+                        Self::HISTORY,
+                        format!(
+                            "History root {root} of epoch {} does not match the history root {} of block #{}",
+                            block.epoch_number(),
+                            block.history_root(),
+                            block.block_number(),
+                        ),
+                    )
+                }
+                None => num_pruned += 1,
+            }
+        }
+
+        if num_checked == 0 {
+            return CheckResult::skipped(Self::HISTORY, "No history stored".to_string());
+        }
+        CheckResult::ok(
+            Self::HISTORY,
+            format!("{num_checked} epochs match, {num_pruned} epochs without history"),
+        )
+    }
+
+    /// Checks that all transactions of the blocks within the validity window are indexed by
+    /// hash, which is required to reject replayed transactions.
+    fn check_validity_window(&self, head: &ChainInfo) -> CheckResult {
+        let head_number = head.head.block_number();
+        let window_start = head_number
+            .saturating_sub(Policy::transaction_validity_window_blocks())
+            .max(Policy::genesis_block_number());
+
+        let mut num_transactions = 0;
+        let mut num_missing_bodies = 0;
+        let txn = self.env.read_transaction();
+        for block_number in (window_start + 1)..=head_number {
+            let info = match self
+                .chain_store
+                .get_chain_info_at(block_number, true, Some(&txn))
+            {
+                Ok(info) => info,
+                Err(_) => {
+                    num_missing_bodies += 1;
+                    continue;
+                }
+            };
+            if !info.head.is_micro() {
+                continue;
+            }
+            let Some(transactions) = info.head.transactions() else {
+                num_missing_bodies += 1;
+                continue;
+            };
+
+            for transaction in transactions {
+                let hash: Blake2bHash = transaction.raw_tx_hash().into();
+                if !self
+                    .history_store
+                    .get_hist_tx_by_hash(&hash, Some(&txn))
+                    .iter()
+                    .any(|hist_tx| hist_tx.block_number == block_number)
+                {
+                    return CheckResult::failed(
+                        Self::VALIDITY_WINDOW,
+                        format!("Transaction {hash} of block #{block_number} is not indexed"),
+                    );
+                }
+                num_transactions += 1;
+            }
+        }
+
+        if num_missing_bodies > 0 && num_transactions == 0 {
+            return CheckResult::skipped(
+                Self::VALIDITY_WINDOW,
+                format!("{num_missing_bodies} blocks of the validity window are not stored"),
+            );
+        }
+        CheckResult::ok(
+            Self::VALIDITY_WINDOW,
+            format!(
+                "{num_transactions} transactions indexed, {num_missing_bodies} blocks not stored"
+            ),
+        )
+    }
+}
@@ -2,14 +2,22 @@
 extern crate log;
 
 pub use block_production::BlockProducer;
-pub use blockchain::blockchain::{Blockchain, BlockchainConfig, TransactionVerificationCache};
+pub use blockchain::{
+    blockchain::{Blockchain, BlockchainConfig, PruningPolicy, TransactionVerificationCache},
+    snapshot::BlockchainSnapshot,
+};
 pub use history::*;
 
+pub mod block_file;
 pub(crate) mod block_production;
 pub(crate) mod blockchain;
 pub(crate) mod blockchain_state;
 #[cfg(feature = "metrics")]
 pub mod chain_metrics;
 pub(crate) mod chain_store;
+pub mod contract_lifecycle;
 pub(crate) mod history;
+pub mod integrity;
 pub mod reward;
+pub mod reward_attribution;
+pub mod staking_history;
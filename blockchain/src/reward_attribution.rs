@@ -0,0 +1,400 @@
+//! Attribution of validator rewards to the stakers delegating to them.
+//!
+//! Rewards are only ever paid to the reward address of a validator. To allow staking pools to
+//! pay out their stakers, history nodes record the active stake delegated to every active
+//! validator at each election block. The rewards a validator earned during the following epoch
+//! can then be split proportionally among its deposit and its stakers.
+
+use std::{borrow::Cow, collections::BTreeMap, io};
+
+use nimiq_account::{Account, Accounts};
+use nimiq_database::{
+    traits::{Database, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableFlags, TableProxy, TransactionProxy, WriteTransactionProxy,
+};
+use nimiq_database_value::{AsDatabaseBytes, FromDatabaseValue};
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_serde::{Deserialize as _, Serialize as _};
+use nimiq_transaction::historic_transaction::HistoricTransactionData;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Blockchain;
+
+/// The commission is given in basis points, i.e. 10000 corresponds to 100%.
+pub const MAX_COMMISSION: u16 = 10_000;
+
+/// The stake of a validator and its stakers at an election block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorStake {
+    pub validator_address: Address,
+    /// The deposit of the validator itself.
+    pub deposit: Coin,
+    /// The active balance of every staker delegating to the validator.
+    pub stakers: Vec<(Address, Coin)>,
+}
+
+impl ValidatorStake {
+    /// The total stake of the validator, i.e. its deposit and the active balance of its stakers.
+    pub fn total_stake(&self) -> Coin {
+        self.deposit + self.stakers.iter().map(|(_, balance)| *balance).sum()
+    }
+}
+
+/// The stake of all active validators at the election block preceding an epoch.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeSnapshot {
+    pub validators: Vec<ValidatorStake>,
+}
+
+impl StakeSnapshot {
+    /// Creates the snapshot from the current state of the staking contract.
+    /// Returns `None` if the accounts tree is incomplete.
+    /// IMPORTANT: This is a very expensive operation, iterating over all existing stakers in the contract.
+    pub fn from_accounts(accounts: &Accounts, txn: &TransactionProxy) -> Option<Self> {
+        let staking_contract = match accounts
+            .get(&Policy::STAKING_CONTRACT_ADDRESS, Some(txn))
+            .ok()?
+        {
+            Account::Staking(staking_contract) => staking_contract,
+            _ => unreachable!(),
+        };
+        let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+        let data_store = data_store.read(txn);
+
+        let mut validators: BTreeMap<Address, ValidatorStake> = BTreeMap::new();
+        for address in staking_contract.active_validators.keys() {
+            let validator = staking_contract.get_validator(&data_store, address)?;
+            validators.insert(
+                address.clone(),
+                ValidatorStake {
+                    validator_address: address.clone(),
+                    deposit: validator.deposit,
+                    stakers: vec![],
+                },
+            );
+        }
+
+        for staker in staking_contract.get_stakers(&data_store) {
+            if staker.active_balance.is_zero() {
+                continue;
+            }
+            if let Some(validator) = staker
+                .delegation
+                .as_ref()
+                .and_then(|address| validators.get_mut(address))
+            {
+                validator
+                    .stakers
+                    .push((staker.address, staker.active_balance));
+            }
+        }
+
+        Some(Self {
+            validators: validators.into_values().collect(),
+        })
+    }
+
+    pub fn get_validator(&self, validator_address: &Address) -> Option<&ValidatorStake> {
+        self.validators
+            .iter()
+            .find(|validator| &validator.validator_address == validator_address)
+    }
+}
+
+impl AsDatabaseBytes for StakeSnapshot {
+    fn as_database_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(self.serialize_to_vec())
+    }
+}
+
+impl FromDatabaseValue for StakeSnapshot {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::deserialize_from_vec(bytes).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+/// A database of stake snapshots indexed by the epoch in which the snapshotted stake was active.
+#[derive(Debug)]
+pub struct StakeSnapshotStore {
+    db: DatabaseProxy,
+    snapshot_table: TableProxy,
+}
+
+impl StakeSnapshotStore {
+    const SNAPSHOT_DB_NAME: &'static str = "StakeSnapshots";
+
+    pub fn new(db: DatabaseProxy) -> Self {
+        let snapshot_table =
+            db.open_table_with_flags(Self::SNAPSHOT_DB_NAME.to_string(), TableFlags::UINT_KEYS);
+        Self { db, snapshot_table }
+    }
+
+    pub fn put(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        epoch_number: u32,
+        snapshot: &StakeSnapshot,
+    ) {
+        txn.put(&self.snapshot_table, &epoch_number, snapshot);
+    }
+
+    pub fn get(
+        &self,
+        epoch_number: u32,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<StakeSnapshot> {
+        match txn_option {
+            Some(txn) => txn.get(&self.snapshot_table, &epoch_number),
+            None => self
+                .db
+                .read_transaction()
+                .get(&self.snapshot_table, &epoch_number),
+        }
+    }
+}
+
+/// The share of a staker in the rewards of a validator for an epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakerReward {
+    pub staker_address: Address,
+    /// The active balance of the staker at the election block preceding the epoch.
+    pub active_balance: Coin,
+    /// The reward of the staker after deducting the commission.
+    pub reward: Coin,
+}
+
+/// The rewards of a validator for an epoch, attributed to its stakers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakerRewards {
+    pub validator_address: Address,
+    pub epoch_number: u32,
+    /// The total stake of the validator at the election block preceding the epoch.
+    pub total_stake: Coin,
+    /// The total rewards the validator received for the epoch.
+    pub total_reward: Coin,
+    /// The commission deducted from the stakers' rewards.
+    pub commission: Coin,
+    /// The rewards attributed to the validator itself, i.e. the share of its deposit, the
+    /// commission and the remainder of the integer division.
+    pub validator_reward: Coin,
+    pub stakers: Vec<StakerReward>,
+}
+
+impl StakerRewards {
+    /// Splits the rewards of a validator proportionally to the given stake. The commission is
+    /// given in basis points and deducted from the share of every staker.
+    pub fn attribute(
+        validator: &ValidatorStake,
+        epoch_number: u32,
+        total_reward: Coin,
+        commission: u16,
+    ) -> Self {
+        let total_stake = validator.total_stake();
+        let commission = u128::from(commission.min(MAX_COMMISSION));
+
+        let mut total_commission = 0u128;
+        let mut stakers_reward = 0u128;
+        let stakers = validator
+            .stakers
+            .iter()
+            .map(|(staker_address, active_balance)| {
+                let share = if total_stake.is_zero() {
+                    0
+                } else {
+                    u128::from(u64::from(total_reward)) * u128::from(u64::from(*active_balance))
+                        / u128::from(u64::from(total_stake))
+                };
+                let staker_commission = share * commission / u128::from(MAX_COMMISSION);
+                total_commission += staker_commission;
+                stakers_reward += share - staker_commission;
+
+                StakerReward {
+                    staker_address: staker_address.clone(),
+                    active_balance: *active_balance,
+                    reward: Coin::from_u64_unchecked((share - staker_commission) as u64),
+                }
+            })
+            .collect();
+
+        Self {
+            validator_address: validator.validator_address.clone(),
+            epoch_number,
+            total_stake,
+            total_reward,
+            commission: Coin::from_u64_unchecked(total_commission as u64),
+            validator_reward: total_reward - Coin::from_u64_unchecked(stakers_reward as u64),
+            stakers,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RewardAttributionError {
+    #[error("Reward attribution is only available on history nodes")]
+    NoHistory,
+    #[error("No stake snapshot for epoch {0}")]
+    SnapshotNotFound(u32),
+    #[error("Validator {0} was not active in epoch {1}")]
+    ValidatorNotActive(Address, u32),
+}
+
+impl Blockchain {
+    /// Records the stake of all active validators at the given election block, so that the
+    /// rewards of the following epoch can later be attributed to the stakers.
+    /// This is only done on history nodes.
+    ///
+    /// This must be called after the election block was committed, while the accounts still
+    /// reflect it. Since iterating over all stakers is expensive, the snapshot is built in a read
+    /// transaction and only stored in a short write transaction of its own, instead of prolonging
+    /// the write transaction that pushes the block. If the node stops in between, the snapshot of
+    /// that epoch is missing and its rewards can't be attributed.
+    pub(crate) fn snapshot_stake(&self, block_number: u32) {
+        if !self.config.keep_history {
+            return;
+        }
+
+        let snapshot = {
+            let txn = self.read_transaction();
+            StakeSnapshot::from_accounts(&self.state.accounts, &txn)
+        };
+
+        match snapshot {
+            Some(snapshot) => {
+                let mut txn = self.write_transaction();
+                self.stake_snapshot_store.put(
+                    &mut txn,
+                    Policy::epoch_at(block_number) + 1,
+                    &snapshot,
+                );
+                txn.commit();
+            }
+            None => debug!(block_number, "Accounts incomplete, skipping stake snapshot"),
+        }
+    }
+
+    /// Attributes the rewards a validator received for the given epoch to its stakers, according
+    /// to their active balance at the election block preceding the epoch. The commission is given
+    /// in basis points and deducted from the stakers' shares.
+    ///
+    /// The rewards for the last batch of an epoch are paid in the first batch of the following
+    /// epoch. Thus, the attribution is incomplete until that batch is finalized.
+    pub fn get_staker_rewards(
+        &self,
+        validator_address: &Address,
+        epoch_number: u32,
+        commission: u16,
+    ) -> Result<StakerRewards, RewardAttributionError> {
+        if !self.config.keep_history {
+            return Err(RewardAttributionError::NoHistory);
+        }
+
+        let txn = self.read_transaction();
+        let snapshot = self
+            .stake_snapshot_store
+            .get(epoch_number, Some(&txn))
+            .ok_or(RewardAttributionError::SnapshotNotFound(epoch_number))?;
+        let validator = snapshot.get_validator(validator_address).ok_or_else(|| {
+            RewardAttributionError::ValidatorNotActive(validator_address.clone(), epoch_number)
+        })?;
+
+        // Rewards are paid in the macro block following the batch they are paid for.
+        let mut hist_txs = self
+            .history_store
+            .get_epoch_transactions(epoch_number, Some(&txn));
+        if let Some(election_block) = Policy::election_block_of(epoch_number) {
+            hist_txs.extend(
+                self.history_store.get_block_transactions(
+                    election_block + Policy::blocks_per_batch(),
+                    Some(&txn),
+                ),
+            );
+        }
+
+        let total_reward = hist_txs
+            .iter()
+            .filter(|hist_tx| {
+                Policy::epoch_at(
+                    hist_tx
+                        .block_number
+                        .saturating_sub(Policy::blocks_per_batch()),
+                ) == epoch_number
+            })
+            .filter_map(|hist_tx| match &hist_tx.data {
+                HistoricTransactionData::Reward(event)
+                    if &event.validator_address == validator_address =>
+                {
+                    Some(event.value)
+                }
+                _ => None,
+            })
+            .sum();
+
+        Ok(StakerRewards::attribute(
+            validator,
+            epoch_number,
+            total_reward,
+            commission,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; 20])
+    }
+
+    #[test]
+    fn it_attributes_rewards_proportionally() {
+        let validator = ValidatorStake {
+            validator_address: address(1),
+            deposit: Coin::from_u64_unchecked(1_000),
+            stakers: vec![
+                (address(2), Coin::from_u64_unchecked(3_000)),
+                (address(3), Coin::from_u64_unchecked(6_000)),
+            ],
+        };
+
+        let rewards = StakerRewards::attribute(&validator, 3, Coin::from_u64_unchecked(1_000), 0);
+        assert_eq!(rewards.total_stake, Coin::from_u64_unchecked(10_000));
+        assert_eq!(rewards.stakers[0].reward, Coin::from_u64_unchecked(300));
+        assert_eq!(rewards.stakers[1].reward, Coin::from_u64_unchecked(600));
+        assert_eq!(rewards.commission, Coin::ZERO);
+        assert_eq!(rewards.validator_reward, Coin::from_u64_unchecked(100));
+
+        // A commission of 10% is deducted from every staker and goes to the validator.
+        let rewards =
+            StakerRewards::attribute(&validator, 3, Coin::from_u64_unchecked(1_000), 1_000);
+        assert_eq!(rewards.stakers[0].reward, Coin::from_u64_unchecked(270));
+        assert_eq!(rewards.stakers[1].reward, Coin::from_u64_unchecked(540));
+        assert_eq!(rewards.commission, Coin::from_u64_unchecked(90));
+        assert_eq!(rewards.validator_reward, Coin::from_u64_unchecked(190));
+    }
+
+    #[test]
+    fn it_keeps_rounding_remainders_with_the_validator() {
+        let validator = ValidatorStake {
+            validator_address: address(1),
+            deposit: Coin::ZERO,
+            stakers: vec![
+                (address(2), Coin::from_u64_unchecked(1)),
+                (address(3), Coin::from_u64_unchecked(1)),
+                (address(4), Coin::from_u64_unchecked(1)),
+            ],
+        };
+
+        let rewards = StakerRewards::attribute(&validator, 3, Coin::from_u64_unchecked(100), 0);
+        assert!(rewards
+            .stakers
+            .iter()
+            .all(|staker| staker.reward == Coin::from_u64_unchecked(33)));
+        assert_eq!(rewards.validator_reward, Coin::from_u64_unchecked(1));
+    }
+}
@@ -0,0 +1,641 @@
+//! The history of validators and stakers.
+//!
+//! History nodes index the transactions sent to and from the staking contract, as well as the jail
+//! and penalty events, by the validator and staker addresses they affect. This allows listing the
+//! timeline of a validator or a staker without scanning the whole history. The events of a staker
+//! are also part of the timeline of the validators it delegates to before and after the event.
+//! Since the delegation of a staker is only known to the staking contract, the history store
+//! derives it from the previous events of the staker when indexing them. For stakers created in
+//! the genesis block, the delegation is only known after their first update.
+//!
+//! The index is only filled for blocks that are pushed after it was introduced. History nodes that
+//! were synced before need to rebuild it once, see
+//! [`HistoryStore::rebuild_indexes`](crate::HistoryStore::rebuild_indexes).
+
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_bls::CompressedPublicKey;
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::{Address, Ed25519PublicKey};
+use nimiq_primitives::{account::AccountType, coin::Coin, policy::Policy};
+use nimiq_serde::Deserialize;
+use nimiq_transaction::{
+    account::staking_contract::{IncomingStakingTransactionData, OutgoingStakingTransactionData},
+    historic_transaction::{HistoricTransaction, HistoricTransactionData},
+    ExecutedTransaction, SignatureProof,
+};
+
+use crate::Blockchain;
+
+/// A change to a validator or a staker in the staking contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StakingEventData {
+    CreateValidator {
+        validator_address: Address,
+        signing_key: Ed25519PublicKey,
+        voting_key: CompressedPublicKey,
+        reward_address: Address,
+        signal_data: Option<Blake2bHash>,
+        deposit: Coin,
+    },
+    UpdateValidator {
+        validator_address: Address,
+        new_signing_key: Option<Ed25519PublicKey>,
+        new_voting_key: Option<CompressedPublicKey>,
+        new_reward_address: Option<Address>,
+        /// Set if the signal data is updated. An inner `None` removes the signal data.
+        new_signal_data: Option<Option<Blake2bHash>>,
+    },
+    DeactivateValidator {
+        validator_address: Address,
+    },
+    ReactivateValidator {
+        validator_address: Address,
+    },
+    RetireValidator {
+        validator_address: Address,
+    },
+    DeleteValidator {
+        validator_address: Address,
+        recipient: Address,
+        value: Coin,
+    },
+    /// The fee of a failed transaction sent from the deposit of a validator.
+    ValidatorFeeDeduction {
+        validator_address: Address,
+        fee: Coin,
+    },
+    JailValidator {
+        validator_address: Address,
+        offense_event_block: u32,
+    },
+    PenalizeValidator {
+        validator_address: Address,
+        slot: u16,
+        offense_event_block: u32,
+    },
+    CreateStaker {
+        staker_address: Address,
+        delegation: Option<Address>,
+        value: Coin,
+    },
+    AddStake {
+        staker_address: Address,
+        value: Coin,
+    },
+    UpdateStaker {
+        staker_address: Address,
+        /// The delegation before the update, as derived by the history store.
+        old_delegation: Option<Address>,
+        new_delegation: Option<Address>,
+        reactivate_all_stake: bool,
+    },
+    SetActiveStake {
+        staker_address: Address,
+        /// The delegation of the staker, as derived by the history store.
+        delegation: Option<Address>,
+        new_active_balance: Coin,
+    },
+    RetireStake {
+        staker_address: Address,
+        /// The delegation of the staker, as derived by the history store.
+        delegation: Option<Address>,
+        retire_stake: Coin,
+    },
+    RemoveStake {
+        staker_address: Address,
+        /// The delegation of the staker, as derived by the history store.
+        delegation: Option<Address>,
+        recipient: Address,
+        value: Coin,
+    },
+    /// The fee of a failed transaction sent from the stake of a staker.
+    StakerFeeDeduction {
+        staker_address: Address,
+        fee: Coin,
+    },
+}
+
+impl StakingEventData {
+    /// Decodes the staking event of a historic transaction. Returns `None` if the historic
+    /// transaction doesn't affect a validator or a staker. The delegation of the staker isn't part
+    /// of the transaction, so it is always `None` and must be set with `set_delegation`.
+    pub fn from_historic_transaction(hist_tx: &HistoricTransaction) -> Option<Self> {
+        match &hist_tx.data {
+            HistoricTransactionData::Basic(tx) => Self::from_transaction(tx),
+            HistoricTransactionData::Jail(ev) => Some(StakingEventData::JailValidator {
+                validator_address: ev.validator_address.clone(),
+                offense_event_block: ev.offense_event_block,
+            }),
+            HistoricTransactionData::Penalize(ev) => Some(StakingEventData::PenalizeValidator {
+                validator_address: ev.validator_address.clone(),
+                slot: ev.slot,
+                offense_event_block: ev.offense_event_block,
+            }),
+            HistoricTransactionData::Reward(_) | HistoricTransactionData::Equivocation(_) => None,
+        }
+    }
+
+    fn from_transaction(executed_tx: &ExecutedTransaction) -> Option<Self> {
+        let tx = executed_tx.get_raw_transaction();
+
+        if tx.recipient_type == AccountType::Staking {
+            // Failed incoming transactions don't change the staking contract, their fee is paid by
+            // the sender.
+            if executed_tx.failed() {
+                return None;
+            }
+
+            let data = IncomingStakingTransactionData::parse(tx).ok()?;
+            let event = match data {
+                IncomingStakingTransactionData::CreateValidator {
+                    signing_key,
+                    voting_key,
+                    reward_address,
+                    signal_data,
+                    proof,
+                    ..
+                } => StakingEventData::CreateValidator {
+                    validator_address: proof.compute_signer(),
+                    signing_key,
+                    voting_key,
+                    reward_address,
+                    signal_data,
+                    deposit: tx.value,
+                },
+                IncomingStakingTransactionData::UpdateValidator {
+                    new_signing_key,
+                    new_voting_key,
+                    new_reward_address,
+                    new_signal_data,
+                    proof,
+                    ..
+                } => StakingEventData::UpdateValidator {
+                    validator_address: proof.compute_signer(),
+                    new_signing_key,
+                    new_voting_key,
+                    new_reward_address,
+                    new_signal_data,
+                },
+                IncomingStakingTransactionData::DeactivateValidator {
+                    validator_address, ..
+                } => StakingEventData::DeactivateValidator { validator_address },
+                IncomingStakingTransactionData::ReactivateValidator {
+                    validator_address, ..
+                } => StakingEventData::ReactivateValidator { validator_address },
+                IncomingStakingTransactionData::RetireValidator { proof } => {
+                    StakingEventData::RetireValidator {
+                        validator_address: proof.compute_signer(),
+                    }
+                }
+                IncomingStakingTransactionData::CreateStaker { delegation, proof } => {
+                    StakingEventData::CreateStaker {
+                        staker_address: proof.compute_signer(),
+                        delegation,
+                        value: tx.value,
+                    }
+                }
+                IncomingStakingTransactionData::AddStake { staker_address } => {
+                    StakingEventData::AddStake {
+                        staker_address,
+                        value: tx.value,
+                    }
+                }
+                IncomingStakingTransactionData::UpdateStaker {
+                    new_delegation,
+                    reactivate_all_stake,
+                    proof,
+                } => StakingEventData::UpdateStaker {
+                    staker_address: proof.compute_signer(),
+                    old_delegation: None,
+                    new_delegation,
+                    reactivate_all_stake,
+                },
+                IncomingStakingTransactionData::SetActiveStake {
+                    new_active_balance,
+                    proof,
+                } => StakingEventData::SetActiveStake {
+                    staker_address: proof.compute_signer(),
+                    delegation: None,
+                    new_active_balance,
+                },
+                IncomingStakingTransactionData::RetireStake {
+                    retire_stake,
+                    proof,
+                } => StakingEventData::RetireStake {
+                    staker_address: proof.compute_signer(),
+                    delegation: None,
+                    retire_stake,
+                },
+            };
+            return Some(event);
+        }
+
+        if tx.sender_type == AccountType::Staking {
+            // Outgoing transactions are signed by the validator or the staker they are sent from.
+            let signer = SignatureProof::deserialize_from_vec(&tx.proof)
+                .ok()?
+                .compute_signer();
+
+            let data = OutgoingStakingTransactionData::parse(tx).ok()?;
+            let event = match (data, executed_tx.succeeded()) {
+                (OutgoingStakingTransactionData::DeleteValidator, true) => {
+                    StakingEventData::DeleteValidator {
+                        validator_address: signer,
+                        recipient: tx.recipient.clone(),
+                        value: tx.value,
+                    }
+                }
+                (OutgoingStakingTransactionData::DeleteValidator, false) => {
+                    StakingEventData::ValidatorFeeDeduction {
+                        validator_address: signer,
+                        fee: tx.fee,
+                    }
+                }
+                (OutgoingStakingTransactionData::RemoveStake, true) => {
+                    StakingEventData::RemoveStake {
+                        staker_address: signer,
+                        delegation: None,
+                        recipient: tx.recipient.clone(),
+                        value: tx.value,
+                    }
+                }
+                (OutgoingStakingTransactionData::RemoveStake, false) => {
+                    StakingEventData::StakerFeeDeduction {
+                        staker_address: signer,
+                        fee: tx.fee,
+                    }
+                }
+            };
+            return Some(event);
+        }
+
+        None
+    }
+
+    /// The validators whose timeline the event is part of. For stakers, these are the validators
+    /// they delegate to before and after the event.
+    pub fn validator_addresses(&self) -> Vec<&Address> {
+        match self {
+            StakingEventData::CreateValidator {
+                validator_address, ..
+            }
+            | StakingEventData::UpdateValidator {
+                validator_address, ..
+            }
+            | StakingEventData::DeactivateValidator { validator_address }
+            | StakingEventData::ReactivateValidator { validator_address }
+            | StakingEventData::RetireValidator { validator_address }
+            | StakingEventData::DeleteValidator {
+                validator_address, ..
+            }
+            | StakingEventData::ValidatorFeeDeduction {
+                validator_address, ..
+            }
+            | StakingEventData::JailValidator {
+                validator_address, ..
+            }
+            | StakingEventData::PenalizeValidator {
+                validator_address, ..
+            } => vec![validator_address],
+            StakingEventData::CreateStaker { delegation, .. }
+            | StakingEventData::SetActiveStake { delegation, .. }
+            | StakingEventData::RetireStake { delegation, .. }
+            | StakingEventData::RemoveStake { delegation, .. } => delegation.iter().collect(),
+            StakingEventData::UpdateStaker {
+                old_delegation,
+                new_delegation,
+                ..
+            } => old_delegation.iter().chain(new_delegation).collect(),
+            StakingEventData::AddStake { .. } | StakingEventData::StakerFeeDeduction { .. } => {
+                vec![]
+            }
+        }
+    }
+
+    /// The staker whose timeline the event is part of.
+    pub fn staker_address(&self) -> Option<&Address> {
+        match self {
+            StakingEventData::CreateStaker { staker_address, .. }
+            | StakingEventData::AddStake { staker_address, .. }
+            | StakingEventData::UpdateStaker { staker_address, .. }
+            | StakingEventData::SetActiveStake { staker_address, .. }
+            | StakingEventData::RetireStake { staker_address, .. }
+            | StakingEventData::RemoveStake { staker_address, .. }
+            | StakingEventData::StakerFeeDeduction { staker_address, .. } => Some(staker_address),
+            _ => None,
+        }
+    }
+
+    /// The addresses under which the event is indexed.
+    pub fn addresses(&self) -> Vec<Address> {
+        let mut addresses: Vec<Address> = self
+            .validator_addresses()
+            .into_iter()
+            .chain(self.staker_address())
+            .cloned()
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+        addresses
+    }
+
+    /// Whether the event carries the delegation of the staker before the event.
+    pub(crate) fn has_delegation(&self) -> bool {
+        matches!(
+            self,
+            StakingEventData::UpdateStaker { .. }
+                | StakingEventData::SetActiveStake { .. }
+                | StakingEventData::RetireStake { .. }
+                | StakingEventData::RemoveStake { .. }
+        )
+    }
+
+    /// Sets the delegation of the staker before the event. Does nothing if the event doesn't
+    /// carry the delegation.
+    pub(crate) fn set_delegation(&mut self, delegation: Option<Address>) {
+        match self {
+            StakingEventData::UpdateStaker { old_delegation, .. } => *old_delegation = delegation,
+            StakingEventData::SetActiveStake {
+                delegation: event_delegation,
+                ..
+            }
+            | StakingEventData::RetireStake {
+                delegation: event_delegation,
+                ..
+            }
+            | StakingEventData::RemoveStake {
+                delegation: event_delegation,
+                ..
+            } => *event_delegation = delegation,
+            _ => {}
+        }
+    }
+
+    /// The delegation of the staker after the event, if the event determines it.
+    pub(crate) fn delegation_after(&self) -> Option<Option<&Address>> {
+        match self {
+            StakingEventData::CreateStaker { delegation, .. }
+            | StakingEventData::SetActiveStake { delegation, .. }
+            | StakingEventData::RetireStake { delegation, .. }
+            | StakingEventData::RemoveStake { delegation, .. } => Some(delegation.as_ref()),
+            StakingEventData::UpdateStaker { new_delegation, .. } => Some(new_delegation.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// A staking event together with the transaction or inherent it originates from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakingEvent {
+    /// The raw hash of the transaction, or the hash of the jail or penalty inherent.
+    pub tx_hash: Blake2bHash,
+    pub block_number: u32,
+    pub block_time: u64,
+    pub data: StakingEventData,
+}
+
+/// The stakers delegating active stake to a validator during an epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidatorStakers {
+    pub epoch_number: u32,
+    pub num_stakers: usize,
+    /// The total stake of the validator, i.e. its deposit and the active balance of its stakers.
+    pub total_stake: Coin,
+}
+
+impl Blockchain {
+    /// Returns the events of the given validator, including the events of the stakers delegating
+    /// to it, from most recent to least recent up to the maximum number given. If `start_at` is
+    /// given, only the events preceding the event with that hash are returned, which allows
+    /// paginating through the history.
+    pub fn get_validator_history(
+        &self,
+        validator_address: &Address,
+        max: u16,
+        start_at: Option<&Blake2bHash>,
+    ) -> Vec<StakingEvent> {
+        self.get_staking_events(validator_address, max, start_at, &|data| {
+            data.validator_addresses().contains(&validator_address)
+        })
+    }
+
+    /// Returns the events of the given staker from most recent to least recent up to the maximum
+    /// number given. If `start_at` is given, only the events preceding the event with that hash
+    /// are returned, which allows paginating through the history.
+    pub fn get_staker_history(
+        &self,
+        staker_address: &Address,
+        max: u16,
+        start_at: Option<&Blake2bHash>,
+    ) -> Vec<StakingEvent> {
+        self.get_staking_events(staker_address, max, start_at, &|data| {
+            data.staker_address() == Some(staker_address)
+        })
+    }
+
+    /// Returns the number of stakers delegating active stake to the given validator for each of
+    /// the last `max_epochs` epochs, from most recent to least recent. The numbers are taken from
+    /// the stake snapshots of the election blocks, so they are only available on history nodes.
+    /// Epochs in which the validator wasn't active are skipped.
+    pub fn get_validator_stakers_history(
+        &self,
+        validator_address: &Address,
+        max_epochs: u16,
+    ) -> Vec<ValidatorStakers> {
+        let txn = self.read_transaction();
+        let current_epoch = Policy::epoch_at(self.block_number());
+
+        (1..=current_epoch)
+            .rev()
+            .take(max_epochs as usize)
+            .filter_map(|epoch_number| {
+                let snapshot = self.stake_snapshot_store.get(epoch_number, Some(&txn))?;
+                let validator = snapshot.get_validator(validator_address)?;
+                Some(ValidatorStakers {
+                    epoch_number,
+                    num_stakers: validator.stakers.len(),
+                    total_stake: validator.total_stake(),
+                })
+            })
+            .collect()
+    }
+
+    fn get_staking_events(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<&Blake2bHash>,
+        filter: &dyn Fn(&StakingEventData) -> bool,
+    ) -> Vec<StakingEvent> {
+        let txn = self.read_transaction();
+        // An address can be both a validator and a staker, so the events are filtered while
+        // iterating over the index.
+        self.history_store
+            .get_staking_events_by_address(address, max, start_at, filter, Some(&txn))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use nimiq_keys::{KeyPair, PrivateKey};
+    use nimiq_primitives::networks::NetworkId;
+    use nimiq_transaction_builder::TransactionBuilder;
+
+    use super::*;
+
+    const SECRET_KEY: &str = "6c9320ac201caf1f8eaa5b05f5d67a9e77826f3f6be266a0ecccc20416dc6587";
+
+    fn key_pair() -> KeyPair {
+        KeyPair::from(PrivateKey::from_str(SECRET_KEY).unwrap())
+    }
+
+    fn decode(executed_tx: ExecutedTransaction) -> Option<StakingEventData> {
+        StakingEventData::from_historic_transaction(&HistoricTransaction {
+            network_id: NetworkId::UnitAlbatross,
+            block_number: 1,
+            block_time: 0,
+            data: HistoricTransactionData::Basic(executed_tx),
+        })
+    }
+
+    #[test]
+    fn it_decodes_the_signer_of_outgoing_transactions() {
+        let key_pair = key_pair();
+        let recipient = Address::from([1u8; 20]);
+
+        // The sender of the transaction is the staking contract, the staker is its signer.
+        let tx = TransactionBuilder::new_remove_stake(
+            &key_pair,
+            recipient.clone(),
+            Coin::from_u64_unchecked(100),
+            Coin::from_u64_unchecked(1),
+            1,
+            NetworkId::UnitAlbatross,
+        )
+        .unwrap();
+        assert_eq!(
+            decode(ExecutedTransaction::Ok(tx)),
+            Some(StakingEventData::RemoveStake {
+                staker_address: Address::from(&key_pair),
+                delegation: None,
+                recipient: recipient.clone(),
+                value: Coin::from_u64_unchecked(100),
+            })
+        );
+
+        let tx = TransactionBuilder::new_delete_validator(
+            recipient.clone(),
+            &key_pair,
+            Coin::from_u64_unchecked(1),
+            Coin::from_u64_unchecked(100),
+            1,
+            NetworkId::UnitAlbatross,
+        )
+        .unwrap();
+        assert_eq!(
+            decode(ExecutedTransaction::Ok(tx)),
+            Some(StakingEventData::DeleteValidator {
+                validator_address: Address::from(&key_pair),
+                recipient,
+                value: Coin::from_u64_unchecked(100),
+            })
+        );
+    }
+
+    #[test]
+    fn it_decodes_redelegations() {
+        let key_pair = key_pair();
+        let staker_address = Address::from(&key_pair);
+        let validator_1 = Address::from([1u8; 20]);
+        let validator_2 = Address::from([2u8; 20]);
+
+        let tx = TransactionBuilder::new_update_staker(
+            None,
+            &key_pair,
+            Some(validator_2.clone()),
+            true,
+            Coin::ZERO,
+            1,
+            NetworkId::UnitAlbatross,
+        )
+        .unwrap();
+        let mut data = decode(ExecutedTransaction::Ok(tx)).unwrap();
+        assert_eq!(
+            data,
+            StakingEventData::UpdateStaker {
+                staker_address: staker_address.clone(),
+                old_delegation: None,
+                new_delegation: Some(validator_2.clone()),
+                reactivate_all_stake: true,
+            }
+        );
+        assert_eq!(data.validator_addresses(), vec![&validator_2]);
+
+        // The previous delegation is derived by the history store.
+        data.set_delegation(Some(validator_1.clone()));
+        assert_eq!(data.validator_addresses(), vec![&validator_1, &validator_2]);
+        assert_eq!(data.delegation_after(), Some(Some(&validator_2)));
+
+        let mut addresses = vec![staker_address, validator_1, validator_2];
+        addresses.sort();
+        assert_eq!(data.addresses(), addresses);
+    }
+
+    #[test]
+    fn it_decodes_fee_deductions() {
+        let key_pair = key_pair();
+        let validator_address = Address::from([1u8; 20]);
+
+        // Failed outgoing transactions deduct the fee from the stake or the deposit.
+        let tx = TransactionBuilder::new_remove_stake(
+            &key_pair,
+            Address::from([2u8; 20]),
+            Coin::from_u64_unchecked(100),
+            Coin::from_u64_unchecked(2),
+            1,
+            NetworkId::UnitAlbatross,
+        )
+        .unwrap();
+        let data = decode(ExecutedTransaction::Err(tx)).unwrap();
+        assert_eq!(
+            data,
+            StakingEventData::StakerFeeDeduction {
+                staker_address: Address::from(&key_pair),
+                fee: Coin::from_u64_unchecked(2),
+            }
+        );
+        assert!(data.validator_addresses().is_empty());
+
+        let tx = TransactionBuilder::new_delete_validator(
+            Address::from([2u8; 20]),
+            &key_pair,
+            Coin::from_u64_unchecked(3),
+            Coin::from_u64_unchecked(100),
+            1,
+            NetworkId::UnitAlbatross,
+        )
+        .unwrap();
+        assert_eq!(
+            decode(ExecutedTransaction::Err(tx)),
+            Some(StakingEventData::ValidatorFeeDeduction {
+                validator_address: Address::from(&key_pair),
+                fee: Coin::from_u64_unchecked(3),
+            })
+        );
+
+        // Failed incoming transactions don't change the staking contract.
+        let tx = TransactionBuilder::new_update_staker(
+            None,
+            &key_pair,
+            Some(validator_address),
+            false,
+            Coin::from_u64_unchecked(1),
+            1,
+            NetworkId::UnitAlbatross,
+        )
+        .unwrap();
+        assert_eq!(decode(ExecutedTransaction::Err(tx)), None);
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use nimiq_blockchain::{interface::HistoryInterface, BlockProducer, Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis::NetworkId;
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::blockchain::{produce_macro_blocks, signing_key, voting_key};
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+fn archive_blockchain() -> Arc<RwLock<Blockchain>> {
+    let config = BlockchainConfig {
+        archive: true,
+        ..Default::default()
+    };
+    Arc::new(RwLock::new(
+        Blockchain::new(
+            VolatileDatabase::new(20).unwrap(),
+            config,
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ))
+}
+
+// Tests that an archive node keeps the blocks of all past epochs.
+#[test]
+fn archive_keeps_all_blocks() {
+    let blockchain = archive_blockchain();
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks(
+        &producer,
+        &blockchain,
+        (2 * Policy::batches_per_epoch()) as usize,
+    );
+
+    let blockchain = blockchain.read();
+    let last_batch = Policy::batch_at(blockchain.block_number());
+    assert_eq!(blockchain.missing_batch_before(u32::MAX), None);
+    for batch_number in 1..=last_batch {
+        let blocks = blockchain.get_batch_blocks(batch_number, None).unwrap();
+        assert_eq!(blocks.len(), Policy::blocks_per_batch() as usize + 1);
+        assert!(blocks.iter().all(|block| block.body().is_some()));
+    }
+}
+
+// Tests that an archive node that history synced can back-fill the blocks of all past batches.
+#[test]
+fn archive_backfills_history_synced_batches() {
+    let num_macro_blocks = (Policy::batches_per_epoch() + 2) as usize;
+
+    // Produce the blocks on a first archive node.
+    let blockchain = archive_blockchain();
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks(&producer, &blockchain, num_macro_blocks);
+    let blockchain = blockchain.read();
+
+    // History sync a second archive node to the election block and the last checkpoint block.
+    let election_block = blockchain
+        .chain_store
+        .get_block_at(Policy::election_block_of(1).unwrap(), true, None)
+        .unwrap();
+    let election_txs = blockchain.history_store.get_epoch_transactions(1, None);
+    let checkpoint_block = blockchain
+        .chain_store
+        .get_block_at(blockchain.block_number(), true, None)
+        .unwrap();
+    let checkpoint_txs = blockchain.history_store.get_epoch_transactions(2, None);
+
+    let blockchain2 = archive_blockchain();
+    assert_eq!(
+        Blockchain::push_history_sync(blockchain2.upgradable_read(), election_block, &election_txs),
+        Ok(PushResult::Extended)
+    );
+    assert_eq!(
+        Blockchain::push_history_sync(
+            blockchain2.upgradable_read(),
+            checkpoint_block,
+            &checkpoint_txs
+        ),
+        Ok(PushResult::Extended)
+    );
+
+    let blockchain2 = blockchain2.read();
+    let last_batch = Policy::batch_at(blockchain2.block_number());
+    assert_eq!(blockchain2.missing_batch_before(u32::MAX), Some(last_batch));
+
+    // Batches can't be back-filled out of order.
+    assert_eq!(
+        blockchain2.push_batch_blocks(
+            last_batch - 1,
+            blockchain.get_batch_blocks(last_batch - 1, None).unwrap()
+        ),
+        Err(PushError::Orphan)
+    );
+
+    // Blocks of a different batch are rejected.
+    assert_eq!(
+        blockchain2.push_batch_blocks(
+            last_batch,
+            blockchain.get_batch_blocks(last_batch - 1, None).unwrap()
+        ),
+        Err(PushError::InvalidSuccessor)
+    );
+
+    // Back-fill all batches from the newest to the oldest one.
+    let mut cursor = u32::MAX;
+    while let Some(batch_number) = blockchain2.missing_batch_before(cursor) {
+        let blocks = blockchain.get_batch_blocks(batch_number, None).unwrap();
+        assert_eq!(blockchain2.push_batch_blocks(batch_number, blocks), Ok(()));
+        cursor = batch_number;
+    }
+    assert_eq!(cursor, 1);
+
+    // All blocks are now stored and linked on the main chain.
+    assert_eq!(blockchain2.head_hash(), blockchain.head_hash());
+    for block_number in Policy::genesis_block_number()..=blockchain.block_number() {
+        let block = blockchain2
+            .chain_store
+            .get_block_at(block_number, true, None)
+            .unwrap();
+        assert_eq!(
+            block,
+            blockchain
+                .chain_store
+                .get_block_at(block_number, true, None)
+                .unwrap()
+        );
+    }
+    for batch_number in 1..=last_batch {
+        assert_eq!(
+            blockchain2.get_batch_blocks(batch_number, None),
+            blockchain.get_batch_blocks(batch_number, None)
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use nimiq_blockchain::{
+    block_file::{BlockExporter, BlockFileError, BlockFileReader, ImportProgress},
+    BlockProducer, Blockchain, BlockchainConfig,
+};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis::NetworkId;
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::blockchain::{produce_macro_blocks, signing_key, voting_key};
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+fn new_blockchain() -> Arc<RwLock<Blockchain>> {
+    Arc::new(RwLock::new(
+        Blockchain::new(
+            VolatileDatabase::new(20).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ))
+}
+
+fn produce_chain() -> Arc<RwLock<Blockchain>> {
+    let blockchain = new_blockchain();
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks(&producer, &blockchain, 2);
+    blockchain
+}
+
+fn export_chain(blockchain: &Arc<RwLock<Blockchain>>) -> Vec<u8> {
+    let blockchain = blockchain.read();
+    let mut file = vec![];
+    let num_blocks = blockchain
+        .export_blocks(
+            Policy::genesis_block_number() + 1,
+            blockchain.block_number(),
+            &mut file,
+        )
+        .unwrap();
+    assert_eq!(num_blocks, 2 * Policy::blocks_per_batch());
+    file
+}
+
+#[test]
+fn it_can_export_and_import_blocks() {
+    let blockchain = produce_chain();
+    let file = export_chain(&blockchain);
+
+    // All blocks are read back in order.
+    let mut reader = BlockFileReader::new(&file[..]).unwrap();
+    assert_eq!(reader.network_id(), NetworkId::UnitAlbatross);
+    let mut block_number = Policy::genesis_block_number();
+    while let Some(block) = reader.read_block().unwrap() {
+        block_number += 1;
+        assert_eq!(block.block_number(), block_number);
+    }
+    assert_eq!(block_number, blockchain.read().block_number());
+
+    // Importing into a fresh blockchain reproduces the chain.
+    let blockchain2 = new_blockchain();
+    let mut num_reports = 0;
+    let progress =
+        Blockchain::import_blocks(&blockchain2, &file[..], |_| num_reports += 1).unwrap();
+    assert_eq!(
+        progress,
+        ImportProgress {
+            blocks_read: 2 * Policy::blocks_per_batch(),
+            blocks_imported: 2 * Policy::blocks_per_batch(),
+            blocks_known: 0,
+            block_number: blockchain.read().block_number(),
+        }
+    );
+    assert_eq!(num_reports, progress.blocks_read);
+    assert_eq!(
+        blockchain2.read().head_hash(),
+        blockchain.read().head_hash()
+    );
+    assert_eq!(
+        blockchain2.read().state.accounts.get_root_hash_assert(None),
+        blockchain.read().state.accounts.get_root_hash_assert(None)
+    );
+
+    // Importing again only finds known blocks.
+    let progress = Blockchain::import_blocks(&blockchain2, &file[..], |_| {}).unwrap();
+    assert_eq!(progress.blocks_imported, 0);
+    assert_eq!(progress.blocks_known, 2 * Policy::blocks_per_batch());
+}
+
+#[test]
+fn it_can_export_blocks_without_loading_the_blockchain() {
+    let env = VolatileDatabase::new(20).unwrap();
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            env.clone(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks(&producer, &blockchain, 2);
+
+    let exporter = BlockExporter::new(env, NetworkId::UnitAlbatross);
+    let head_block_number = blockchain.read().block_number();
+    assert_eq!(exporter.head_block_number(), Some(head_block_number));
+
+    // The exporter writes the same file as the blockchain.
+    let mut file = vec![];
+    let num_blocks = exporter
+        .export_blocks(
+            Policy::genesis_block_number() + 1,
+            head_block_number,
+            &mut file,
+        )
+        .unwrap();
+    assert_eq!(num_blocks, 2 * Policy::blocks_per_batch());
+    assert_eq!(file, export_chain(&blockchain));
+
+    assert!(matches!(
+        exporter.export_blocks(
+            Policy::genesis_block_number() + 1,
+            head_block_number + 1,
+            vec![]
+        ),
+        Err(BlockFileError::InvalidRange(..))
+    ));
+}
+
+#[test]
+fn it_rejects_invalid_block_files() {
+    let blockchain = produce_chain();
+    let file = export_chain(&blockchain);
+
+    // A flipped bit in the checksum is detected at the end of the file.
+    let mut corrupted = file.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    let mut reader = BlockFileReader::new(&corrupted[..]).unwrap();
+    let result = loop {
+        match reader.read_block() {
+            Ok(Some(_)) => continue,
+            result => break result,
+        }
+    };
+    assert!(matches!(result, Err(BlockFileError::ChecksumMismatch)));
+
+    // A truncated file can't be read to the end.
+    let truncated = &file[..file.len() / 2];
+    assert!(matches!(
+        Blockchain::import_blocks(&new_blockchain(), truncated, |_| {}),
+        Err(BlockFileError::Io(_))
+    ));
+
+    // Files of other networks are rejected.
+    let mut other_network = file.clone();
+    other_network[6] = NetworkId::TestAlbatross as u8;
+    assert!(matches!(
+        Blockchain::import_blocks(&new_blockchain(), &other_network[..], |_| {}),
+        Err(BlockFileError::NetworkMismatch(
+            NetworkId::TestAlbatross,
+            NetworkId::UnitAlbatross
+        ))
+    ));
+
+    // Anything else is not a block file.
+    assert!(matches!(
+        BlockFileReader::new(&b"not a block file"[..]),
+        Err(BlockFileError::InvalidMagic)
+    ));
+
+    // Blocks with broken links can't be pushed.
+    let blockchain2 = new_blockchain();
+    let mut skipped = vec![];
+    blockchain
+        .read()
+        .export_blocks(
+            Policy::genesis_block_number() + 2,
+            Policy::genesis_block_number() + 2,
+            &mut skipped,
+        )
+        .unwrap();
+    assert!(matches!(
+        Blockchain::import_blocks(&blockchain2, &skipped[..], |_| {}),
+        Err(BlockFileError::Push(_, _))
+    ));
+}
@@ -1,15 +1,19 @@
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 
 use nimiq_block::{Block, BlockError};
-use nimiq_blockchain::Blockchain;
-use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
+use nimiq_blockchain::{interface::HistoryInterface, Blockchain, BlockchainConfig, PruningPolicy};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, PushError, PushResult};
+use nimiq_genesis::NetworkId;
 use nimiq_hash::Hash;
+use nimiq_keys::{KeyPair, PrivateKey};
 use nimiq_primitives::{policy::Policy, trie::trie_diff::TrieDiff};
 use nimiq_tendermint::ProposalMessage;
 use nimiq_test_log::test;
 use nimiq_test_utils::{
     block_production::TemporaryBlockProducer,
-    blockchain::produce_macro_blocks,
+    blockchain::{
+        generate_transactions, produce_macro_blocks, produce_macro_blocks_with_txns, REWARD_KEY,
+    },
     test_custom_block::{finalize_macro_block, next_macro_block_proposal},
 };
 
@@ -257,3 +261,155 @@ fn can_detect_invalid_punished_set() {
         Err(PushError::InvalidBlock(BlockError::InvalidValidators))
     );
 }
+
+#[test]
+fn prunes_micro_block_bodies_according_to_policy() {
+    let temp_producer = TemporaryBlockProducer::with_config(BlockchainConfig {
+        pruning: PruningPolicy {
+            micro_body_batches: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let blockchain = &temp_producer.blockchain;
+
+    // Finalize three batches. Only the bodies of the micro blocks in the last one are kept.
+    produce_macro_blocks(&temp_producer.producer, blockchain, 3);
+
+    let bc_read = blockchain.read();
+    for batch in 1..=3 {
+        let micro_block = bc_read
+            .chain_store
+            .get_block_at(Policy::first_block_of_batch(batch).unwrap(), false, None)
+            .unwrap();
+        let macro_block = bc_read
+            .chain_store
+            .get_block_at(Policy::macro_block_of(batch).unwrap(), false, None)
+            .unwrap();
+
+        // The headers and the macro blocks are always kept.
+        assert!(bc_read
+            .chain_store
+            .get_block(&macro_block.hash(), true, None)
+            .is_ok());
+        assert_eq!(
+            bc_read
+                .chain_store
+                .get_block(&micro_block.hash(), true, None)
+                .is_ok(),
+            batch == 3
+        );
+    }
+}
+
+#[test]
+fn prunes_accounts_diffs_according_to_policy() {
+    let temp_producer = TemporaryBlockProducer::with_config(BlockchainConfig {
+        pruning: PruningPolicy {
+            accounts_diff_batches: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let blockchain = &temp_producer.blockchain;
+
+    produce_macro_blocks(&temp_producer.producer, blockchain, 3);
+
+    let bc_read = blockchain.read();
+    for batch in 1..=3 {
+        let block = bc_read
+            .chain_store
+            .get_block_at(Policy::first_block_of_batch(batch).unwrap(), false, None)
+            .unwrap();
+        let diff = bc_read.chain_store.get_accounts_diff(&block.hash(), None);
+
+        if batch == 3 {
+            assert!(diff.is_ok());
+        } else {
+            assert_eq!(diff, Err(BlockchainError::AccountsDiffNotFound));
+        }
+    }
+}
+
+#[test]
+fn keeps_history_of_watched_addresses() {
+    let rng_seed = 42;
+    let key_pair = KeyPair::from(PrivateKey::from_str(REWARD_KEY).unwrap());
+    // All transactions are sent by the same key, but every block pays the same two recipients.
+    let recipients: Vec<_> =
+        generate_transactions(&key_pair, 0, NetworkId::UnitAlbatross, 2, rng_seed)
+            .into_iter()
+            .map(|tx| tx.recipient)
+            .collect();
+
+    let temp_producer = TemporaryBlockProducer::with_config(BlockchainConfig {
+        keep_history: false,
+        pruning: PruningPolicy {
+            history_watched_addresses: [recipients[0].clone()].into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let blockchain = &temp_producer.blockchain;
+
+    // Finalize two epochs, which prunes the history of the first one.
+    produce_macro_blocks_with_txns(
+        &temp_producer.producer,
+        blockchain,
+        2 * Policy::batches_per_epoch() as usize,
+        2,
+        rng_seed,
+    );
+
+    let bc_read = blockchain.read();
+    let first_epoch_txs = |address| {
+        bc_read
+            .history_store
+            .get_tx_hashes_by_address(address, u16::MAX, None)
+            .iter()
+            .map(|tx_hash| {
+                let hist_txs = bc_read.history_store.get_hist_tx_by_hash(tx_hash, None);
+                assert_eq!(hist_txs.len(), 1);
+                hist_txs[0].block_number
+            })
+            .filter(|&block_number| Policy::epoch_at(block_number) == 1)
+            .count()
+    };
+
+    assert!(first_epoch_txs(&recipients[0]) > 0);
+    assert_eq!(first_epoch_txs(&recipients[1]), 0);
+}
+
+#[test]
+fn snapshots_refer_to_the_head() {
+    let temp_producer = TemporaryBlockProducer::new();
+    let block = temp_producer.next_block(vec![], false);
+
+    {
+        let bc_read = temp_producer.blockchain.read();
+        let snapshot = bc_read.snapshot();
+        assert_eq!(snapshot.block_number(), block.block_number());
+        assert_eq!(snapshot.block_hash(), &block.hash());
+        assert_eq!(
+            snapshot.get_staking_contract(),
+            Some(bc_read.get_staking_contract())
+        );
+        assert_eq!(
+            snapshot.get_account(&Policy::STAKING_CONTRACT_ADDRESS),
+            bc_read.get_account_if_complete(&Policy::STAKING_CONTRACT_ADDRESS)
+        );
+        assert!(bc_read.snapshot_at(&block.hash()).is_some());
+    }
+
+    // Once the head moved on, the state at the previous block isn't available anymore.
+    let next_block = temp_producer.next_block(vec![], false);
+    let bc_read = temp_producer.blockchain.read();
+    assert!(bc_read.snapshot_at(&block.hash()).is_none());
+    assert_eq!(
+        bc_read
+            .snapshot_at(&next_block.hash())
+            .unwrap()
+            .block_number(),
+        next_block.block_number()
+    );
+}
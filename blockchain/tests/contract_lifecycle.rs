@@ -0,0 +1,374 @@
+use std::sync::Arc;
+
+use nimiq_block::Block;
+use nimiq_blockchain::{
+    contract_lifecycle::{HtlcLifecycle, HtlcStatus, VestingStatus},
+    BlockProducer, Blockchain, BlockchainConfig,
+};
+use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis::NetworkId;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hash, Hasher};
+use nimiq_keys::{Address, KeyPair, PrivateKey, SecureGenerate};
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_serde::Deserialize;
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    blockchain::{signing_key, voting_key},
+    test_rng::test_rng,
+};
+use nimiq_transaction::{
+    account::htlc_contract::{AnyHash, PreImage},
+    ExecutedTransaction, Transaction,
+};
+use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+const ACCOUNT_SECRET_KEY: &str = "6c9320ac201caf1f8eaa5b05f5d67a9e77826f3f6be266a0ecccc20416dc6587";
+
+fn key_pair(secret_key: &str) -> KeyPair {
+    let priv_key: PrivateKey =
+        Deserialize::deserialize_from_vec(&hex::decode(secret_key).unwrap()[..]).unwrap();
+    priv_key.into()
+}
+
+fn new_blockchain() -> Arc<RwLock<Blockchain>> {
+    Arc::new(RwLock::new(
+        Blockchain::new(
+            VolatileDatabase::new(20).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ))
+}
+
+/// The timestamp of the next micro block pushed with `push_transactions`.
+fn next_timestamp(blockchain: &Arc<RwLock<Blockchain>>) -> u64 {
+    blockchain.read().head().timestamp() + Policy::BLOCK_SEPARATION_TIME
+}
+
+/// Pushes a micro block with the given transactions and returns the executed transactions.
+fn push_transactions(
+    producer: &BlockProducer,
+    blockchain: &Arc<RwLock<Blockchain>>,
+    transactions: Vec<Transaction>,
+) -> Vec<ExecutedTransaction> {
+    let timestamp = next_timestamp(blockchain);
+    let bc = blockchain.upgradable_read();
+    let block = producer.next_micro_block(&bc, timestamp, vec![], transactions, vec![0x41], None);
+    let executed_txs = block.body.as_ref().unwrap().transactions.clone();
+    assert_eq!(
+        Blockchain::push(bc, Block::Micro(block)),
+        Ok(PushResult::Extended)
+    );
+    executed_txs
+}
+
+fn find_htlc(htlcs: &[HtlcLifecycle], contract_address: &Address) -> HtlcLifecycle {
+    htlcs
+        .iter()
+        .find(|htlc| &htlc.contract_address == contract_address)
+        .cloned()
+        .expect("HTLC not found")
+}
+
+#[test]
+fn it_tracks_the_lifecycle_of_htlcs() {
+    let blockchain = new_blockchain();
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    let sender_key_pair = key_pair(ACCOUNT_SECRET_KEY);
+    let recipient_key_pair = KeyPair::generate(&mut test_rng(false));
+    let sender = Address::from(&sender_key_pair);
+    let recipient = Address::from(&recipient_key_pair);
+
+    // The hash root is the pre-image hashed twice. Revealing the pre-image hashed once only
+    // allows redeeming half of the funds.
+    let pre_image = Blake2bHasher::default().digest(b"pre-image");
+    let half_pre_image = Blake2bHasher::default().digest(pre_image.as_bytes());
+    let hash_root = AnyHash::from(Blake2bHasher::default().digest(half_pre_image.as_bytes()));
+
+    let value = Coin::from_u64_unchecked(1000);
+    let fee = Coin::from_u64_unchecked(10);
+    let create_htlc = |timeout: u64, validity_start_height: u32| {
+        TransactionBuilder::new_create_htlc(
+            &sender_key_pair,
+            sender.clone(),
+            recipient.clone(),
+            hash_root.clone(),
+            2,
+            timeout,
+            value,
+            Coin::ZERO,
+            validity_start_height,
+            NetworkId::UnitAlbatross,
+        )
+        .unwrap()
+    };
+
+    // #1: Create four HTLCs, one of which times out with the next block.
+    let block_number = blockchain.read().block_number() + 1;
+    let redeemed_tx = create_htlc(u64::MAX, block_number);
+    let partial_tx = create_htlc(u64::MAX - 1, block_number);
+    let timed_out_tx = create_htlc(next_timestamp(&blockchain), block_number);
+    let early_tx = create_htlc(u64::MAX - 2, block_number);
+    push_transactions(
+        &producer,
+        &blockchain,
+        vec![
+            redeemed_tx.clone(),
+            partial_tx.clone(),
+            timed_out_tx.clone(),
+            early_tx.clone(),
+        ],
+    );
+
+    let htlcs = blockchain.read().get_htlcs_by_address(&recipient, 10);
+    assert_eq!(htlcs.len(), 4);
+    let htlc = find_htlc(&htlcs, &redeemed_tx.contract_creation_address());
+    assert_eq!(htlc.status, HtlcStatus::Funded);
+    assert_eq!(htlc.balance, value);
+    assert_eq!(htlc.creation_block_number, block_number);
+    assert_eq!(htlc.resolution_tx_hash, None);
+
+    // #2: Resolve the HTLCs in all possible ways.
+    let block_number = blockchain.read().block_number() + 1;
+    let redeem_tx = TransactionBuilder::new_redeem_htlc_regular(
+        &recipient_key_pair,
+        redeemed_tx.contract_creation_address(),
+        recipient.clone(),
+        PreImage::from(pre_image.clone()),
+        hash_root.clone(),
+        2,
+        value - fee,
+        fee,
+        block_number,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let partial_redeem_tx = TransactionBuilder::new_redeem_htlc_regular(
+        &recipient_key_pair,
+        partial_tx.contract_creation_address(),
+        recipient.clone(),
+        PreImage::from(half_pre_image.clone()),
+        hash_root.clone(),
+        1,
+        Coin::from_u64_unchecked(490),
+        fee,
+        block_number,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let timeout_tx = TransactionBuilder::new_redeem_htlc_timeout(
+        &sender_key_pair,
+        timed_out_tx.contract_creation_address(),
+        sender.clone(),
+        value - fee,
+        fee,
+        block_number,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let sign_early = |key_pair: &KeyPair| {
+        TransactionBuilder::sign_htlc_early(
+            key_pair,
+            early_tx.contract_creation_address(),
+            sender.clone(),
+            value - fee,
+            fee,
+            block_number,
+            NetworkId::UnitAlbatross,
+        )
+        .unwrap()
+    };
+    let early_resolve_tx = TransactionBuilder::new_redeem_htlc_early(
+        early_tx.contract_creation_address(),
+        sender.clone(),
+        sign_early(&sender_key_pair),
+        sign_early(&recipient_key_pair),
+        value - fee,
+        fee,
+        block_number,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let executed_txs = push_transactions(
+        &producer,
+        &blockchain,
+        vec![
+            redeem_tx.clone(),
+            partial_redeem_tx.clone(),
+            timeout_tx.clone(),
+            early_resolve_tx.clone(),
+        ],
+    );
+    assert!(executed_txs.iter().all(|tx| tx.succeeded()));
+
+    let htlcs = blockchain.read().get_htlcs_by_address(&sender, 10);
+    assert_eq!(htlcs.len(), 4);
+
+    let htlc = find_htlc(&htlcs, &redeemed_tx.contract_creation_address());
+    assert_eq!(htlc.status, HtlcStatus::Redeemed);
+    assert_eq!(htlc.balance, Coin::ZERO);
+    assert_eq!(htlc.pre_image, Some(PreImage::from(pre_image)));
+    assert_eq!(
+        htlc.resolution_tx_hash,
+        Some(redeem_tx.hash::<Blake2bHash>())
+    );
+    assert_eq!(htlc.resolution_block_number, Some(block_number));
+
+    let htlc = find_htlc(&htlcs, &partial_tx.contract_creation_address());
+    assert_eq!(htlc.status, HtlcStatus::Redeemed);
+    assert_eq!(htlc.balance, Coin::from_u64_unchecked(500));
+    assert_eq!(htlc.pre_image, Some(PreImage::from(half_pre_image.clone())));
+    assert_eq!(
+        htlc.resolution_tx_hash,
+        Some(partial_redeem_tx.hash::<Blake2bHash>())
+    );
+
+    let htlc = find_htlc(&htlcs, &timed_out_tx.contract_creation_address());
+    assert_eq!(htlc.status, HtlcStatus::TimedOut);
+    assert_eq!(htlc.balance, Coin::ZERO);
+    assert_eq!(htlc.pre_image, None);
+    assert_eq!(
+        htlc.resolution_tx_hash,
+        Some(timeout_tx.hash::<Blake2bHash>())
+    );
+
+    let htlc = find_htlc(&htlcs, &early_tx.contract_creation_address());
+    assert_eq!(htlc.status, HtlcStatus::EarlyResolved);
+    assert_eq!(htlc.balance, Coin::ZERO);
+    assert_eq!(
+        htlc.resolution_tx_hash,
+        Some(early_resolve_tx.hash::<Blake2bHash>())
+    );
+
+    // #3: Redeeming more than the remaining half of the partially redeemed HTLC fails, but the
+    // fee is still paid by the contract.
+    let failed_tx = TransactionBuilder::new_redeem_htlc_regular(
+        &recipient_key_pair,
+        partial_tx.contract_creation_address(),
+        recipient.clone(),
+        PreImage::from(half_pre_image),
+        hash_root,
+        1,
+        Coin::from_u64_unchecked(400),
+        fee,
+        blockchain.read().block_number() + 1,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let executed_txs = push_transactions(&producer, &blockchain, vec![failed_tx.clone()]);
+    assert_eq!(executed_txs, vec![ExecutedTransaction::Err(failed_tx)]);
+
+    let htlcs = blockchain.read().get_htlcs_by_address(&recipient, 10);
+    let htlc = find_htlc(&htlcs, &partial_tx.contract_creation_address());
+    assert_eq!(htlc.status, HtlcStatus::Redeemed);
+    assert_eq!(htlc.balance, Coin::from_u64_unchecked(490));
+    assert_eq!(
+        htlc.resolution_tx_hash,
+        Some(partial_redeem_tx.hash::<Blake2bHash>())
+    );
+    assert_eq!(htlc.resolution_block_number, Some(block_number));
+
+    // The maximum number of HTLCs is respected.
+    assert_eq!(
+        blockchain.read().get_htlcs_by_address(&recipient, 2).len(),
+        2
+    );
+    assert!(blockchain
+        .read()
+        .get_htlcs_by_address(&Address::burn_address(), 10)
+        .is_empty());
+}
+
+#[test]
+fn it_tracks_the_lifecycle_of_vesting_contracts() {
+    let blockchain = new_blockchain();
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    let key_pair = key_pair(ACCOUNT_SECRET_KEY);
+    let owner = Address::from(&key_pair);
+    let fee = Coin::from_u64_unchecked(100);
+
+    // #1: Create a vesting contract that is fully vested right away.
+    let block_number = blockchain.read().block_number() + 1;
+    let create_tx = TransactionBuilder::new_create_vesting(
+        &key_pair,
+        owner.clone(),
+        1,
+        1,
+        1,
+        Coin::from_u64_unchecked(1000),
+        fee,
+        block_number,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let contract_address = create_tx.contract_creation_address();
+    push_transactions(&producer, &blockchain, vec![create_tx.clone()]);
+
+    let vesting_contracts = blockchain.read().get_vesting_contracts_by_owner(&owner, 10);
+    assert_eq!(vesting_contracts.len(), 1);
+    assert_eq!(vesting_contracts[0].contract_address, contract_address);
+    assert_eq!(
+        vesting_contracts[0].creation_tx_hash,
+        create_tx.hash::<Blake2bHash>()
+    );
+    assert_eq!(vesting_contracts[0].creation_block_number, block_number);
+    assert_eq!(vesting_contracts[0].status, VestingStatus::Active);
+    assert_eq!(vesting_contracts[0].balance, Coin::from_u64_unchecked(1000));
+
+    // #2: A withdrawal of more than the balance fails, but pays the fee from the contract.
+    let failed_tx = TransactionBuilder::new_redeem_vesting(
+        &key_pair,
+        contract_address.clone(),
+        owner.clone(),
+        Coin::from_u64_unchecked(2000),
+        fee,
+        blockchain.read().block_number() + 1,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let executed_txs = push_transactions(&producer, &blockchain, vec![failed_tx.clone()]);
+    assert_eq!(executed_txs, vec![ExecutedTransaction::Err(failed_tx)]);
+
+    let vesting_contracts = blockchain.read().get_vesting_contracts_by_owner(&owner, 10);
+    assert_eq!(vesting_contracts[0].status, VestingStatus::Active);
+    assert_eq!(vesting_contracts[0].balance, Coin::from_u64_unchecked(900));
+    assert_eq!(vesting_contracts[0].num_withdrawals, 0);
+    assert_eq!(vesting_contracts[0].last_withdrawal_block_number, None);
+
+    // #3: Withdrawing the remaining balance empties the contract.
+    let block_number = blockchain.read().block_number() + 1;
+    let withdraw_tx = TransactionBuilder::new_redeem_vesting(
+        &key_pair,
+        contract_address,
+        owner.clone(),
+        Coin::from_u64_unchecked(800),
+        fee,
+        block_number,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let executed_txs = push_transactions(&producer, &blockchain, vec![withdraw_tx]);
+    assert!(executed_txs[0].succeeded());
+
+    let vesting_contracts = blockchain.read().get_vesting_contracts_by_owner(&owner, 10);
+    assert_eq!(vesting_contracts[0].status, VestingStatus::Withdrawn);
+    assert_eq!(vesting_contracts[0].balance, Coin::ZERO);
+    assert_eq!(vesting_contracts[0].num_withdrawals, 1);
+    assert_eq!(
+        vesting_contracts[0].last_withdrawal_block_number,
+        Some(block_number)
+    );
+
+    // Only the owner's vesting contracts are returned.
+    assert!(blockchain
+        .read()
+        .get_vesting_contracts_by_owner(&Address::burn_address(), 10)
+        .is_empty());
+}
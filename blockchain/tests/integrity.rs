@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use nimiq_blockchain::{
+    integrity::{CheckStatus, IntegrityChecker, RepairError},
+    BlockProducer, Blockchain, BlockchainConfig,
+};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_database::{traits::WriteTransaction, volatile::VolatileDatabase};
+use nimiq_genesis::NetworkId;
+use nimiq_test_log::test;
+use nimiq_test_utils::blockchain::{
+    fill_micro_blocks_with_txns, produce_macro_blocks, signing_key, voting_key,
+};
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+#[test]
+fn it_detects_inconsistent_head() {
+    let env = VolatileDatabase::new(20).unwrap();
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            env.clone(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks(&producer, &blockchain, 1);
+    fill_micro_blocks_with_txns(&producer, &blockchain, 1, 1);
+
+    let checker = IntegrityChecker::new(env.clone());
+    let report = checker.check();
+    assert!(report.is_consistent(), "{report:?}");
+    assert_eq!(
+        report.head,
+        Some((
+            blockchain.read().block_number(),
+            blockchain.read().head_hash()
+        ))
+    );
+    assert!(matches!(checker.repair(), Ok(None)));
+
+    // Point the head to its parent without reverting the accounts.
+    {
+        let blockchain = blockchain.read();
+        let mut txn = blockchain.write_transaction();
+        blockchain
+            .chain_store
+            .set_head(&mut txn, blockchain.head().parent_hash());
+        txn.commit();
+    }
+
+    let report = checker.check();
+    assert!(!report.is_consistent());
+    let accounts_check = report
+        .checks
+        .iter()
+        .find(|check| check.name == "accounts root")
+        .unwrap();
+    assert_eq!(accounts_check.status, CheckStatus::Failed);
+
+    // The accounts are ahead of the head, so there is no block to roll back to.
+    assert!(matches!(
+        checker.repair(),
+        Err(RepairError::NoConsistentBlock(_))
+    ));
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::{AbstractBlockchain, PushResult, RevertError};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis::NetworkId;
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::blockchain::{
+    produce_macro_blocks, push_micro_block, signing_key, voting_key,
+};
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+fn new_blockchain(revert_info_batches: u32) -> Arc<RwLock<Blockchain>> {
+    let config = BlockchainConfig {
+        revert_info_batches,
+        ..Default::default()
+    };
+    Arc::new(RwLock::new(
+        Blockchain::new(
+            VolatileDatabase::new(20).unwrap(),
+            config,
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ))
+}
+
+// Reverts the chain to the given block, checks the resulting state and pushes the reverted blocks
+// again.
+fn revert_and_resync(blockchain: &Arc<RwLock<Blockchain>>, block_number: u32, force: bool) {
+    let head_hash = blockchain.read().head_hash();
+    let head_block_number = blockchain.read().block_number();
+    let target = blockchain
+        .read()
+        .chain_store
+        .get_block_at(block_number, false, None)
+        .unwrap();
+
+    let reverted_blocks =
+        Blockchain::revert_to(blockchain.upgradable_read(), block_number, force).unwrap();
+    assert_eq!(
+        reverted_blocks.len() as u32,
+        head_block_number - block_number
+    );
+    assert_eq!(
+        reverted_blocks.first().unwrap().1.block_number(),
+        block_number + 1
+    );
+
+    {
+        let blockchain = blockchain.read();
+        assert_eq!(blockchain.block_number(), block_number);
+        assert_eq!(blockchain.head_hash(), target.hash());
+        assert_eq!(
+            blockchain.state.accounts.get_root_hash_assert(None),
+            *target.state_root()
+        );
+        assert_eq!(
+            blockchain.macro_head().block_number(),
+            Policy::last_macro_block(block_number)
+        );
+        // The reverted blocks are forgotten.
+        assert!(blockchain
+            .chain_store
+            .get_chain_info(&reverted_blocks[0].0, false, None)
+            .is_err());
+    }
+
+    for (_, block) in reverted_blocks {
+        assert_eq!(
+            Blockchain::push(blockchain.upgradable_read(), block),
+            Ok(PushResult::Extended)
+        );
+    }
+    assert_eq!(blockchain.read().head_hash(), head_hash);
+}
+
+#[test]
+fn it_can_revert_micro_blocks() {
+    let blockchain = new_blockchain(0);
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks(&producer, &blockchain, 1);
+    for _ in 0..3 {
+        push_micro_block(&producer, &blockchain);
+    }
+
+    let macro_block_number = blockchain.read().macro_head().block_number();
+    revert_and_resync(&blockchain, macro_block_number + 1, false);
+    revert_and_resync(&blockchain, macro_block_number, false);
+
+    // Reverting to the head is a no-op.
+    let head = blockchain.read().block_number();
+    assert_eq!(
+        Blockchain::revert_to(blockchain.upgradable_read(), head, false),
+        Ok(vec![])
+    );
+}
+
+#[test]
+fn it_refuses_invalid_reverts() {
+    let blockchain = new_blockchain(0);
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks(&producer, &blockchain, 1);
+    push_micro_block(&producer, &blockchain);
+
+    let head = blockchain.read().block_number();
+    let macro_block_number = blockchain.read().macro_head().block_number();
+
+    assert_eq!(
+        Blockchain::revert_to(blockchain.upgradable_read(), head + 1, false),
+        Err(RevertError::AboveHead(head + 1))
+    );
+    assert_eq!(
+        Blockchain::revert_to(blockchain.upgradable_read(), macro_block_number - 1, false),
+        Err(RevertError::Finalized(
+            macro_block_number - 1,
+            macro_block_number
+        ))
+    );
+
+    // Without a revert window, macro blocks can't be reverted even if forced.
+    assert_eq!(
+        Blockchain::revert_to(blockchain.upgradable_read(), macro_block_number - 1, true),
+        Err(RevertError::OutsideRevertWindow(macro_block_number - 1))
+    );
+    assert_eq!(blockchain.read().block_number(), head);
+}
+
+#[test]
+fn it_can_revert_macro_blocks_within_the_revert_window() {
+    let blockchain = new_blockchain(1);
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks(&producer, &blockchain, 2);
+    push_micro_block(&producer, &blockchain);
+
+    // The last batch can be reverted, including its macro block.
+    let first_batch_block = Policy::macro_block_of(1).unwrap();
+    revert_and_resync(&blockchain, first_batch_block + 1, true);
+    revert_and_resync(&blockchain, first_batch_block, true);
+
+    // Older batches are outside of the revert window.
+    assert_eq!(
+        Blockchain::revert_to(blockchain.upgradable_read(), first_batch_block - 1, true),
+        Err(RevertError::OutsideRevertWindow(first_batch_block - 1))
+    );
+}
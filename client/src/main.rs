@@ -1,7 +1,7 @@
 use std::time::Duration;
 
 use log::info;
-use nimiq::prover::prover_main;
+use nimiq::prover::{prover_main, prover_server_main};
 pub use nimiq::{
     client::{Client, Consensus},
     config::{command_line::CommandLine, config::ClientConfig, config_file::ConfigFile},
@@ -59,6 +59,20 @@ async fn main_inner() -> Result<(), Error> {
     let config = builder.build()?;
     log::debug!("Final configuration: {:#?}", config);
 
+    // Early return in case of a remote prover service.
+    if let Some(address) = command_line.prover_server.clone() {
+        let auth_key = config
+            .zkp
+            .remote_prover
+            .as_ref()
+            .map(|remote_prover| remote_prover.auth_key.clone())
+            .ok_or_else(|| {
+                Error::config_error("A remote prover service requires [zkp.remote_prover] auth_key")
+            })?;
+        info!(%address, "Starting remote prover service.");
+        return Ok(prover_server_main(address, auth_key, config.zkp.prover_keys_path).await?);
+    }
+
     // Clone config for RPC and metrics server
     let rpc_config = config.rpc_server.clone();
     let metrics_config = config.metrics_server.clone();
@@ -100,6 +114,7 @@ async fn main_inner() -> Result<(), Error> {
     }
     let consensus = client.consensus_proxy();
     let mempool = client.mempool();
+    let aggregation_trackers = client.aggregation_trackers();
 
     let zkp_component = client.take_zkp_component().unwrap();
     tokio::spawn(zkp_component); //ITODO get metrics on this? ask JD
@@ -132,6 +147,7 @@ async fn main_inner() -> Result<(), Error> {
             metrics_config.addr,
             client.blockchain(),
             mempool,
+            aggregation_trackers,
             client.consensus_proxy(),
             client.network(),
             &nimiq_task_metric,
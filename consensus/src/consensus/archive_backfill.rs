@@ -0,0 +1,117 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::PushError;
+use nimiq_network_interface::{
+    network::{CloseReason, Network},
+    peer_info::Services,
+};
+use parking_lot::RwLock;
+use tokio::time::sleep;
+
+use crate::messages::RequestBatchBlocks;
+
+/// Time to wait before retrying if no archive peer could provide the blocks of a batch.
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+/// Time to wait before checking for missing batches again once all batches are stored.
+const IDLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Back-fills the blocks of all finalized batches that are not stored locally, from the newest
+/// to the oldest one. The blocks are requested from peers providing the `ARCHIVE` service.
+///
+/// This runs for the whole lifetime of an archive node: batches finalized while the node is
+/// history syncing are picked up once it checks for missing batches again. The node only starts
+/// providing the `ARCHIVE` service once consensus is established and no batch is missing.
+pub(crate) async fn archive_backfill<N: Network>(
+    network: Arc<N>,
+    blockchain: Arc<RwLock<Blockchain>>,
+    established_flag: Arc<AtomicBool>,
+) {
+    // The batch below which we search for the next missing batch.
+    let mut cursor = u32::MAX;
+    let mut providing_archive = false;
+
+    loop {
+        let missing_batch = blockchain.read().missing_batch_before(cursor);
+        let Some(batch_number) = missing_batch else {
+            if cursor != u32::MAX {
+                // Batches might have been finalized in the meantime, so check again from the top.
+                cursor = u32::MAX;
+                continue;
+            }
+
+            // Before consensus is established, our chain might still be far behind.
+            if !providing_archive && established_flag.load(Ordering::Acquire) {
+                info!("Archive back-fill complete, providing the archive service");
+                network.add_provided_services(Services::ARCHIVE).await;
+                providing_archive = true;
+            }
+            sleep(IDLE_INTERVAL).await;
+            continue;
+        };
+
+        if request_batch_blocks(&network, &blockchain, batch_number).await {
+            cursor = batch_number;
+        } else {
+            sleep(RETRY_INTERVAL).await;
+        }
+    }
+}
+
+/// Requests the blocks of the given batch from archive peers until one of them provides valid
+/// blocks. Returns whether the blocks were stored.
+async fn request_batch_blocks<N: Network>(
+    network: &Arc<N>,
+    blockchain: &Arc<RwLock<Blockchain>>,
+    batch_number: u32,
+) -> bool {
+    let peers = match network.get_peers_by_services(Services::ARCHIVE, 1).await {
+        Ok(peers) => peers,
+        Err(error) => {
+            debug!(%error, batch_number, "No archive peers to back-fill batch from");
+            return false;
+        }
+    };
+
+    for peer_id in peers {
+        let blocks = match network
+            .request(RequestBatchBlocks { batch_number }, peer_id)
+            .await
+        {
+            Ok(Ok(response)) => response.blocks,
+            Ok(Err(error)) => {
+                debug!(%error, batch_number, %peer_id, "Peer could not provide batch blocks");
+                continue;
+            }
+            Err(error) => {
+                debug!(?error, batch_number, %peer_id, "Failed to request batch blocks");
+                continue;
+            }
+        };
+
+        let result = blockchain.read().push_batch_blocks(batch_number, blocks);
+        match result {
+            Ok(()) => return true,
+            // Our own chain changed such that the batch can't be connected (yet).
+            Err(PushError::Orphan) => return false,
+            Err(PushError::BlockchainError(error)) => {
+                debug!(%error, batch_number, "Could not verify batch blocks");
+                return false;
+            }
+            Err(error) => {
+                warn!(%error, batch_number, %peer_id, "Peer sent invalid batch blocks");
+                network
+                    .disconnect_peer(peer_id, CloseReason::MaliciousPeer)
+                    .await;
+            }
+        }
+    }
+
+    false
+}
@@ -40,12 +40,14 @@ use crate::{
 #[cfg(feature = "full")]
 use crate::{
     messages::{
-        RequestBatchSet, RequestBlocksProof, RequestHistoryChunk,
+        RequestBatchBlocks, RequestBatchSet, RequestBlocksProof, RequestHistoryChunk,
         RequestTransactionReceiptsByAddress, RequestTransactionsProof, RequestTrieProof,
     },
     sync::live::{diff_queue::RequestTrieDiff, state_queue::RequestChunk},
 };
 
+#[cfg(feature = "full")]
+mod archive_backfill;
 pub mod consensus_proxy;
 mod head_requests;
 mod remote_data_store;
@@ -210,6 +212,9 @@ impl<N: Network> Consensus<N> {
 
         let established_flag = Arc::new(AtomicBool::new(false));
 
+        #[cfg(feature = "full")]
+        Self::init_archive_backfill(&network, &blockchain, &established_flag);
+
         #[cfg(not(target_family = "wasm"))]
         let timer = Box::pin(sleep(Self::CONSENSUS_POLL_TIMER));
 
@@ -248,6 +253,24 @@ impl<N: Network> Consensus<N> {
         }
     }
 
+    #[cfg(feature = "full")]
+    fn init_archive_backfill(
+        network: &Arc<N>,
+        blockchain: &BlockchainProxy,
+        established_flag: &Arc<AtomicBool>,
+    ) {
+        // Archive nodes back-fill the blocks of past batches that they skipped while syncing.
+        if let BlockchainProxy::Full(blockchain) = blockchain {
+            if blockchain.read().config.archive {
+                tokio::spawn(archive_backfill::archive_backfill(
+                    Arc::clone(network),
+                    Arc::clone(blockchain),
+                    Arc::clone(established_flag),
+                ));
+            }
+        }
+    }
+
     fn init_network_request_receivers(
         network: &Arc<N>,
         blockchain: &BlockchainProxy,
@@ -290,6 +313,12 @@ impl<N: Network> Consensus<N> {
 
                 let stream = network.receive_requests::<RequestBlocksProof>();
                 executor.exec(Box::pin(request_handler(network, stream, blockchain)));
+
+                // Only archive nodes serve the blocks of past batches.
+                if blockchain.read().config.archive {
+                    let stream = network.receive_requests::<RequestBatchBlocks>();
+                    executor.exec(Box::pin(request_handler(network, stream, blockchain)));
+                }
             }
             BlockchainProxy::Light(_) => {}
         }
@@ -6,7 +6,7 @@ use nimiq_block::Block;
 #[cfg(feature = "full")]
 use nimiq_block::BlockInclusionProof;
 #[cfg(feature = "full")]
-use nimiq_blockchain::{Blockchain, CHUNK_SIZE};
+use nimiq_blockchain::{interface::HistoryProofError, Blockchain, CHUNK_SIZE};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, Direction};
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_network_interface::{network::Network, request::Handle};
@@ -179,6 +179,11 @@ impl<N: Network> Handle<N, BlockchainProxy> for RequestBlock {
     ) -> Result<Block, BlockError> {
         let blockchain = blockchain.read();
         if let Ok(block) = blockchain.get_block(&self.hash, self.include_micro_bodies) {
+            // The body might have been pruned.
+            if self.include_micro_bodies && is_missing_micro_body(&block) {
+                return Err(BlockError::TargetHashNotFound);
+            }
+
             // Macro bodies are always needed, do we have it already?
             let block = if block.is_macro() && !self.include_micro_bodies {
                 match blockchain.get_block(&self.hash, true) {
@@ -250,7 +255,16 @@ impl RequestMissingBlocks {
                     Block::Micro(_) => {
                         if self.include_micro_bodies {
                             match blockchain.get_block(&block_hash, true) {
-                                Ok(block) => block,
+                                Ok(block) if !is_missing_micro_body(&block) => block,
+                                // The body was pruned.
+                                Ok(_) => {
+                                    debug!(
+                                        blocks_found = blocks.len(),
+                                        block_hash = %block_hash,
+                                        "ResponseBlocks - Micro block body not found",
+                                    );
+                                    return Err(ResponseBlocksError::TargetHashNotFound);
+                                }
                                 Err(error) => {
                                     debug!(
                                         %error,
@@ -386,10 +400,24 @@ impl RequestMissingBlocks {
             blocks.push(block);
         }
 
+        // Micro block bodies might have been pruned, don't serve them as empty blocks.
+        if self.include_micro_bodies && blocks.iter().any(is_missing_micro_body) {
+            debug!(
+                start_hash = %start_hash,
+                "ResponseBlocks - Micro block bodies not found",
+            );
+            return Err(ResponseBlocksError::TargetHashNotFound);
+        }
+
         Ok(ResponseBlocks { blocks })
     }
 }
 
+/// Returns whether the block is a micro block without its body, e.g. because it was pruned.
+fn is_missing_micro_body(block: &Block) -> bool {
+    block.is_micro() && block.transactions().is_none()
+}
+
 impl<N: Network> Handle<N, BlockchainProxy> for RequestHead {
     fn handle(&self, _peer_id: N::PeerId, blockchain: &BlockchainProxy) -> ResponseHead {
         let blockchain = blockchain.read();
@@ -401,6 +429,22 @@ impl<N: Network> Handle<N, BlockchainProxy> for RequestHead {
     }
 }
 
+#[cfg(feature = "full")]
+impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestBatchBlocks {
+    fn handle(
+        &self,
+        _peer_id: N::PeerId,
+        blockchain: &Arc<RwLock<Blockchain>>,
+    ) -> Result<ResponseBlocks, BatchBlocksError> {
+        let blocks = blockchain
+            .read()
+            .get_batch_blocks(self.batch_number, None)
+            .map_err(|_| BatchBlocksError::BatchNotFound)?;
+
+        Ok(ResponseBlocks { blocks })
+    }
+}
+
 #[cfg(feature = "full")]
 impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestChunk {
     fn handle(&self, _peer_id: N::PeerId, blockchain: &Arc<RwLock<Blockchain>>) -> ResponseChunk {
@@ -555,8 +599,11 @@ fn prove_txns_with_block_number(
         verifier_state,
         None,
     ) {
-        Some(proof) => proof,
-        None => {
+        Ok(proof) => proof,
+        Err(HistoryProofError::Pruned) => {
+            return Err(ResponseTransactionProofError::HistoryPruned);
+        }
+        Err(_) => {
             log::info!("Could not generate the txn inclusion proof");
             return Err(ResponseTransactionProofError::CouldntProveInclusion);
         }
@@ -623,8 +670,11 @@ fn prove_transaction(
             verifier_state,
             None,
         ) {
-            Some(proof) => proof,
-            None => {
+            Ok(proof) => proof,
+            Err(HistoryProofError::Pruned) => {
+                return Err(ResponseTransactionProofError::HistoryPruned);
+            }
+            Err(_) => {
                 log::info!("Could not generate the txn inclusion proof");
                 return Err(ResponseTransactionProofError::CouldntProveInclusion);
             }
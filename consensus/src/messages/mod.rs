@@ -54,6 +54,8 @@ pub const MAX_REQUEST_BLOCKS_PROOF: u32 = 1000;
 pub const MAX_REQUEST_SUBSCRIBE_BY_ADDRESS: u32 = 10;
 /// The max number of Address notifications per peer.
 pub const MAX_ADDRESS_NOTIFICATIONS: u32 = 100;
+/// The max number of BatchBlocks requests per peer.
+pub const MAX_REQUEST_RESPONSE_BATCH_BLOCKS: u32 = 100;
 
 /// Part of [`MacroChain`].
 ///
@@ -339,6 +341,35 @@ impl RequestCommon for RequestMissingBlocks {
     const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_MISSING_BLOCKS;
 }
 
+/// Request all blocks of a finalized batch, including their bodies, from an archive node.
+///
+/// The response contains the macro block preceding the batch, the micro blocks of the batch and
+/// the macro block finalizing it, in ascending order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestBatchBlocks {
+    /// The number of the requested batch.
+    pub batch_number: u32,
+}
+
+/// Error response to [`RequestBatchBlocks`].
+#[derive(Clone, Debug, Deserialize, Error, Serialize)]
+pub enum BatchBlocksError {
+    /// The batch is not finalized or its blocks are not stored by the responder.
+    #[error("batch not found")]
+    BatchNotFound,
+    /// Error not understood by the recipient, is never sent explicitly.
+    #[error("unknown error")]
+    #[serde(other)]
+    Other,
+}
+
+impl RequestCommon for RequestBatchBlocks {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 220;
+    type Response = Result<ResponseBlocks, BatchBlocksError>;
+    const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_BATCH_BLOCKS;
+}
+
 /// Request the current blockchain head block hash.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RequestHead {}
@@ -379,6 +410,8 @@ pub enum ResponseTransactionProofError {
     CouldntProveInclusion,
     #[error("transaction not found")]
     TransactionNotFound,
+    #[error("the history of the transaction was pruned")]
+    HistoryPruned,
     #[error("unknown error")]
     #[serde(other)]
     Other,
@@ -1,9 +1,18 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Couldn't create directory: {0}")]
     CreateDirectory(#[from] std::io::Error),
+    #[error("Backup target is not empty: {0}")]
+    BackupTargetExists(PathBuf),
     #[error("Mdbx error: {0}")]
     Mdbx(#[from] libmdbx::Error),
+    #[error("Operation not supported by the {0} backend")]
+    NotSupported(&'static str),
+    #[cfg(feature = "redb")]
+    #[error("Redb error: {0}")]
+    Redb(#[from] redb::Error),
 }
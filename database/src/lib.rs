@@ -4,6 +4,8 @@ mod error;
 pub mod mdbx;
 /// Database implementation that can handle volatile and persistent storage.
 pub mod proxy;
+#[cfg(feature = "redb")]
+pub mod redb;
 /// Abstraction for methods related to the database.
 pub mod traits;
 pub mod volatile;
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, fs, path::Path, sync::Arc};
+use std::{borrow::Cow, ffi::CString, fs, io, path::Path, sync::Arc};
 
 use libmdbx::NoWriteMap;
 use log::info;
@@ -13,14 +13,17 @@ pub(super) type DbKvPair<'a> = (Cow<'a, [u8]>, Cow<'a, [u8]>);
 #[derive(Clone, Debug)]
 pub struct MdbxDatabase {
     pub(super) db: Arc<libmdbx::Database<NoWriteMap>>,
+    read_only: bool,
 }
 
 impl Database for MdbxDatabase {
     type Table = MdbxTable;
-    type ReadTransaction<'db> = MdbxReadTransaction<'db>
+    type ReadTransaction<'db>
+        = MdbxReadTransaction<'db>
     where
         Self: 'db;
-    type WriteTransaction<'db> = MdbxWriteTransaction<'db>
+    type WriteTransaction<'db>
+        = MdbxWriteTransaction<'db>
     where
         Self: 'db;
 
@@ -29,6 +32,11 @@ impl Database for MdbxDatabase {
     }
 
     fn open_table_with_flags(&self, name: String, flags: TableFlags) -> Self::Table {
+        // Tables cannot be created in a read-only database, they are expected to exist.
+        if self.read_only {
+            return MdbxTable { name };
+        }
+
         // This is an implicit transaction, so take the lock first.
         let mut table_flags = libmdbx::TableFlags::CREATE;
 
@@ -91,6 +99,26 @@ impl MdbxDatabase {
         )?))
     }
 
+    /// Opens an existing database without write access, e.g. to inspect the database of a
+    /// node that is not running. Tables are not created when opened and must already exist.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new_read_only<P: AsRef<Path>>(path: P, max_tables: u32) -> Result<DatabaseProxy, Error> {
+        let db = libmdbx::Database::open_with_options(
+            path.as_ref(),
+            libmdbx::DatabaseOptions {
+                max_tables: Some(max_tables.into()),
+                no_rdahead: true,
+                mode: libmdbx::Mode::ReadOnly,
+                ..Default::default()
+            },
+        )?;
+
+        Ok(DatabaseProxy::Persistent(MdbxDatabase {
+            db: Arc::new(db),
+            read_only: true,
+        }))
+    }
+
     pub(crate) fn new_mdbx_database(
         path: &Path,
         size: usize,
@@ -119,7 +147,10 @@ impl MdbxDatabase {
         let cur_mapsize = info.map_size();
         info!(cur_mapsize, "MDBX memory map size");
 
-        let mdbx = MdbxDatabase { db: Arc::new(db) };
+        let mdbx = MdbxDatabase {
+            db: Arc::new(db),
+            read_only: false,
+        };
         if mdbx.need_resize(0) {
             info!("MDBX memory needs to be resized.");
         }
@@ -127,6 +158,84 @@ impl MdbxDatabase {
         Ok(mdbx)
     }
 
+    /// Returns the storage statistics of all tables in the database.
+    pub fn table_stats(&self) -> Result<Vec<TableStats>, Error> {
+        let txn = self.db.begin_ro_txn()?;
+        let main_table = txn.open_table(None)?;
+
+        let mut stats = vec![];
+        for item in txn.cursor(&main_table)?.iter_start::<Cow<[u8]>, ()>() {
+            let (name, _) = item?;
+            let name = String::from_utf8_lossy(&name).into_owned();
+            let table = txn.open_table(Some(&name))?;
+            let stat = txn.table_stat(&table)?;
+            let pages = stat.branch_pages() + stat.leaf_pages() + stat.overflow_pages();
+            stats.push(TableStats {
+                name,
+                entries: stat.entries(),
+                depth: stat.depth(),
+                branch_pages: stat.branch_pages(),
+                leaf_pages: stat.leaf_pages(),
+                overflow_pages: stat.overflow_pages(),
+                size: pages * stat.page_size() as usize,
+            });
+        }
+
+        Ok(stats)
+    }
+
+    /// Returns the page usage statistics of the database file.
+    pub fn stats(&self) -> Result<DatabaseStats, Error> {
+        let info = self.db.info()?;
+        let stat = self.db.stat()?;
+        let page_size = stat.page_size() as usize;
+        let used_pages = info.last_pgno() + 1;
+        let free_pages = self.db.freelist()?;
+
+        Ok(DatabaseStats {
+            page_size,
+            map_size: info.map_size(),
+            used_pages,
+            used_size: used_pages * page_size,
+            free_pages,
+            free_size: free_pages * page_size,
+        })
+    }
+
+    /// Copies a consistent snapshot of the database to the given directory, which must not exist
+    /// or be empty. The copy is compacted, i.e. it does not contain any free pages.
+    ///
+    /// This uses MDBX's native compacting copy, so the database can be written to concurrently.
+    pub fn backup_to<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        if path.exists() && fs::read_dir(path)?.next().is_some() {
+            return Err(Error::BackupTargetExists(path.to_path_buf()));
+        }
+        fs::create_dir_all(path).map_err(Error::CreateDirectory)?;
+
+        // MDBX stores the data file as `mdbx.dat` inside the database directory.
+        let target = path.join("mdbx.dat");
+        let target = target
+            .to_str()
+            .and_then(|target| CString::new(target).ok())
+            .ok_or_else(|| {
+                Error::CreateDirectory(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Invalid backup path",
+                ))
+            })?;
+
+        // Safety: The environment pointer is valid for as long as `self.db` is alive and the
+        // target path is a valid NUL-terminated string.
+        let result = unsafe {
+            mdbx_sys::mdbx_env_copy(self.db.ptr().0, target.as_ptr(), mdbx_sys::MDBX_CP_COMPACT)
+        };
+        match result {
+            mdbx_sys::MDBX_SUCCESS => Ok(()),
+            code => Err(libmdbx::Error::from_err_code(code).into()),
+        }
+    }
+
     pub fn need_resize(&self, threshold_size: usize) -> bool {
         let info = self.db.info().unwrap();
         let stat = self.db.stat().unwrap();
@@ -169,3 +278,42 @@ impl MdbxDatabase {
 pub struct MdbxTable {
     pub(super) name: String,
 }
+
+/// Page usage statistics of the database file.
+#[derive(Clone, Debug)]
+pub struct DatabaseStats {
+    pub page_size: usize,
+    /// The size of the memory map, i.e. the maximum size of the database in bytes.
+    pub map_size: usize,
+    /// The number of pages allocated in the database file.
+    pub used_pages: usize,
+    pub used_size: usize,
+    /// The number of allocated pages that are free for reuse.
+    pub free_pages: usize,
+    pub free_size: usize,
+}
+
+impl DatabaseStats {
+    /// The share of allocated pages that are free, between 0 and 1.
+    pub fn fragmentation(&self) -> f64 {
+        if self.used_pages == 0 {
+            return 0.0;
+        }
+        self.free_pages as f64 / self.used_pages as f64
+    }
+}
+
+/// Storage statistics of a single table.
+#[derive(Clone, Debug)]
+pub struct TableStats {
+    pub name: String,
+    /// The number of entries (key-value pairs) in the table.
+    pub entries: usize,
+    /// The depth of the B-tree.
+    pub depth: u32,
+    pub branch_pages: usize,
+    pub leaf_pages: usize,
+    pub overflow_pages: usize,
+    /// The size of all pages of the table in bytes.
+    pub size: usize,
+}
@@ -78,6 +78,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_can_open_read_only_and_report_table_stats() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("test5");
+        {
+            let db = MdbxDatabase::new(&path, 0, 2).unwrap();
+            let table = db.open_table("test".to_string());
+            db.open_table("empty".to_string());
+
+            let mut tx = db.write_transaction();
+            tx.put_reserve(&table, "test1", "one");
+            tx.put_reserve(&table, "test2", "two");
+            tx.commit();
+        }
+
+        let db = MdbxDatabase::new_read_only(&path, 2).unwrap();
+        let table = db.open_table("test".to_string());
+        let tx = db.read_transaction();
+        assert_eq!(
+            tx.get::<str, String>(&table, "test1"),
+            Some("one".to_string())
+        );
+
+        let stats = db.table_stats().unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name, "empty");
+        assert_eq!(stats[0].entries, 0);
+        assert_eq!(stats[1].name, "test");
+        assert_eq!(stats[1].entries, 2);
+        assert!(stats[1].size > 0);
+    }
+
+    #[test]
+    fn it_can_backup_and_report_stats() {
+        let tempdir = tempdir().unwrap();
+        let db = MdbxDatabase::new(tempdir.path().join("test6"), 0, 2).unwrap();
+        let table = db.open_table("test".to_string());
+        let dup_table = db.open_table_with_flags("dup".to_string(), TableFlags::DUPLICATE_KEYS);
+
+        let mut tx = db.write_transaction();
+        for i in 0..100u32 {
+            tx.put_reserve(&table, &i, "value");
+            tx.put::<str, u32>(&dup_table, "test", &i);
+        }
+        tx.commit();
+
+        let backup_path = tempdir.path().join("backup");
+        db.backup_to(&backup_path).unwrap();
+        assert!(matches!(
+            db.backup_to(&backup_path),
+            Err(crate::Error::BackupTargetExists(_))
+        ));
+
+        let stats = db.stats().unwrap();
+        assert!(stats.used_pages > 0);
+        assert!(stats.fragmentation() <= 1.0);
+
+        let backup = MdbxDatabase::new(&backup_path, 0, 2).unwrap();
+        let table = backup.open_table("test".to_string());
+        let dup_table = backup.open_table_with_flags("dup".to_string(), TableFlags::DUPLICATE_KEYS);
+        let tx = backup.read_transaction();
+        assert_eq!(tx.get::<u32, String>(&table, &0), Some("value".to_string()));
+        assert_eq!(
+            tx.cursor(&table).into_iter_start::<u32, String>().count(),
+            100
+        );
+        let mut cursor = tx.cursor(&dup_table);
+        assert_eq!(cursor.seek_key::<str, u32>("test"), Some(0));
+        assert_eq!(cursor.count_duplicates(), 100);
+    }
+
     #[test]
     fn isolation_test() {
         let tempdir = tempdir().unwrap();
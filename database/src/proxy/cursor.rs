@@ -1,9 +1,11 @@
 use libmdbx::{RO, RW};
 use nimiq_database_value::{AsDatabaseBytes, FromDatabaseValue};
 
+#[cfg(feature = "redb")]
+use crate::redb::{RedbIntoIter, RedbReadCursor, RedbWriteCursor};
 use crate::{
     mdbx::{IntoIter, MdbxReadCursor, MdbxWriteCursor},
-    traits::ReadCursor,
+    traits::{ReadCursor, WriteCursor},
 };
 
 /// A cursor for navigating the entries within a table.
@@ -11,6 +13,10 @@ use crate::{
 pub enum CursorProxy<'txn> {
     ReadCursor(MdbxReadCursor<'txn>),
     WriteCursor(MdbxWriteCursor<'txn>),
+    #[cfg(feature = "redb")]
+    RedbReadCursor(RedbReadCursor<'txn>),
+    #[cfg(feature = "redb")]
+    RedbWriteCursor(RedbWriteCursor<'txn>),
 }
 
 impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
@@ -27,6 +33,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.first(),
             CursorProxy::WriteCursor(cursor) => cursor.first(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.first(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.first(),
         }
     }
 
@@ -37,6 +47,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.first_duplicate(),
             CursorProxy::WriteCursor(cursor) => cursor.first_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.first_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.first_duplicate(),
         }
     }
 
@@ -48,6 +62,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.last(),
             CursorProxy::WriteCursor(cursor) => cursor.last(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.last(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.last(),
         }
     }
 
@@ -58,6 +76,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.last_duplicate(),
             CursorProxy::WriteCursor(cursor) => cursor.last_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.last_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.last_duplicate(),
         }
     }
 
@@ -69,6 +91,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.get_current(),
             CursorProxy::WriteCursor(cursor) => cursor.get_current(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.get_current(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.get_current(),
         }
     }
 
@@ -80,6 +106,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.next(),
             CursorProxy::WriteCursor(cursor) => cursor.next(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.next(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.next(),
         }
     }
 
@@ -91,6 +121,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.next_duplicate(),
             CursorProxy::WriteCursor(cursor) => cursor.next_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.next_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.next_duplicate(),
         }
     }
 
@@ -102,6 +136,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.next_no_duplicate(),
             CursorProxy::WriteCursor(cursor) => cursor.next_no_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.next_no_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.next_no_duplicate(),
         }
     }
 
@@ -113,6 +151,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.prev(),
             CursorProxy::WriteCursor(cursor) => cursor.prev(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.prev(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.prev(),
         }
     }
 
@@ -124,6 +166,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.prev_duplicate(),
             CursorProxy::WriteCursor(cursor) => cursor.prev_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.prev_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.prev_duplicate(),
         }
     }
 
@@ -135,6 +181,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.prev_no_duplicate(),
             CursorProxy::WriteCursor(cursor) => cursor.prev_no_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.prev_no_duplicate(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.prev_no_duplicate(),
         }
     }
 
@@ -146,6 +196,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.seek_key(key),
             CursorProxy::WriteCursor(cursor) => cursor.seek_key(key),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.seek_key(key),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.seek_key(key),
         }
     }
 
@@ -157,6 +211,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.seek_range_key(key),
             CursorProxy::WriteCursor(cursor) => cursor.seek_range_key(key),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.seek_range_key(key),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.seek_range_key(key),
         }
     }
 
@@ -164,6 +222,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.count_duplicates(),
             CursorProxy::WriteCursor(cursor) => cursor.count_duplicates(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => cursor.count_duplicates(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.count_duplicates(),
         }
     }
 
@@ -175,6 +237,14 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         match self {
             CursorProxy::ReadCursor(cursor) => IntoIterProxy::ReadIter(cursor.into_iter_start()),
             CursorProxy::WriteCursor(cursor) => IntoIterProxy::WriteIter(cursor.into_iter_start()),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => {
+                IntoIterProxy::RedbReadIter(cursor.into_iter_start())
+            }
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => {
+                IntoIterProxy::RedbWriteIter(cursor.into_iter_start())
+            }
         }
     }
 
@@ -190,6 +260,14 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
             CursorProxy::WriteCursor(cursor) => {
                 IntoIterProxy::WriteIter(cursor.into_iter_dup_of(key))
             }
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => {
+                IntoIterProxy::RedbReadIter(cursor.into_iter_dup_of(key))
+            }
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => {
+                IntoIterProxy::RedbWriteIter(cursor.into_iter_dup_of(key))
+            }
         }
     }
 
@@ -203,6 +281,25 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
             CursorProxy::WriteCursor(cursor) => {
                 IntoIterProxy::WriteIter(cursor.into_iter_from(key))
             }
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbReadCursor(cursor) => {
+                IntoIterProxy::RedbReadIter(cursor.into_iter_from(key))
+            }
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => {
+                IntoIterProxy::RedbWriteIter(cursor.into_iter_from(key))
+            }
+        }
+    }
+}
+
+impl<'txn> WriteCursor<'txn> for CursorProxy<'txn> {
+    fn remove(&mut self) {
+        match self {
+            CursorProxy::WriteCursor(cursor) => cursor.remove(),
+            #[cfg(feature = "redb")]
+            CursorProxy::RedbWriteCursor(cursor) => cursor.remove(),
+            _ => unreachable!(),
         }
     }
 }
@@ -211,6 +308,10 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
 pub enum IntoIterProxy<'txn, K: FromDatabaseValue, V: FromDatabaseValue> {
     ReadIter(IntoIter<'txn, RO, K, V>),
     WriteIter(IntoIter<'txn, RW, K, V>),
+    #[cfg(feature = "redb")]
+    RedbReadIter(RedbIntoIter<'txn, redb::ReadTransaction, K, V>),
+    #[cfg(feature = "redb")]
+    RedbWriteIter(RedbIntoIter<'txn, redb::WriteTransaction, K, V>),
 }
 
 impl<'txn, K: FromDatabaseValue, V: FromDatabaseValue> Iterator for IntoIterProxy<'txn, K, V> {
@@ -220,6 +321,10 @@ impl<'txn, K: FromDatabaseValue, V: FromDatabaseValue> Iterator for IntoIterProx
         match self {
             IntoIterProxy::ReadIter(iter) => iter.next(),
             IntoIterProxy::WriteIter(iter) => iter.next(),
+            #[cfg(feature = "redb")]
+            IntoIterProxy::RedbReadIter(iter) => iter.next(),
+            #[cfg(feature = "redb")]
+            IntoIterProxy::RedbWriteIter(iter) => iter.next(),
         }
     }
 }
@@ -1,5 +1,9 @@
+use std::path::Path;
+
 use crate::{
-    mdbx, traits::Database, volatile, TableProxy, TransactionProxy, WriteTransactionProxy,
+    mdbx::{self, DatabaseStats, TableStats},
+    traits::Database,
+    volatile, Error, TableProxy, TransactionProxy, WriteTransactionProxy,
 };
 
 /// A database handle that can hold multiple tables.
@@ -7,6 +11,8 @@ use crate::{
 pub enum DatabaseProxy {
     Volatile(volatile::VolatileDatabase),
     Persistent(mdbx::MdbxDatabase),
+    #[cfg(feature = "redb")]
+    Redb(crate::redb::RedbDatabase),
 }
 
 impl Database for DatabaseProxy {
@@ -22,15 +28,23 @@ impl Database for DatabaseProxy {
 
     fn open_table(&self, name: String) -> Self::Table {
         match self {
-            DatabaseProxy::Volatile(ref db) => db.open_table(name),
-            DatabaseProxy::Persistent(ref db) => db.open_table(name),
+            DatabaseProxy::Volatile(ref db) => TableProxy::Mdbx(db.open_table(name)),
+            DatabaseProxy::Persistent(ref db) => TableProxy::Mdbx(db.open_table(name)),
+            #[cfg(feature = "redb")]
+            DatabaseProxy::Redb(ref db) => TableProxy::Redb(db.open_table(name)),
         }
     }
 
     fn open_table_with_flags(&self, name: String, flags: crate::TableFlags) -> Self::Table {
         match self {
-            DatabaseProxy::Volatile(ref db) => db.open_table_with_flags(name, flags),
-            DatabaseProxy::Persistent(ref db) => db.open_table_with_flags(name, flags),
+            DatabaseProxy::Volatile(ref db) => {
+                TableProxy::Mdbx(db.open_table_with_flags(name, flags))
+            }
+            DatabaseProxy::Persistent(ref db) => {
+                TableProxy::Mdbx(db.open_table_with_flags(name, flags))
+            }
+            #[cfg(feature = "redb")]
+            DatabaseProxy::Redb(ref db) => TableProxy::Redb(db.open_table_with_flags(name, flags)),
         }
     }
 
@@ -42,13 +56,58 @@ impl Database for DatabaseProxy {
             DatabaseProxy::Persistent(ref db) => {
                 TransactionProxy::ReadTransaction(db.read_transaction())
             }
+            #[cfg(feature = "redb")]
+            DatabaseProxy::Redb(ref db) => {
+                TransactionProxy::RedbReadTransaction(db.read_transaction())
+            }
         }
     }
 
     fn write_transaction(&self) -> Self::WriteTransaction<'_> {
         match self {
-            DatabaseProxy::Volatile(ref db) => WriteTransactionProxy::new(db.write_transaction()),
-            DatabaseProxy::Persistent(ref db) => WriteTransactionProxy::new(db.write_transaction()),
+            DatabaseProxy::Volatile(ref db) => WriteTransactionProxy::new(
+                TransactionProxy::WriteTransaction(db.write_transaction()),
+            ),
+            DatabaseProxy::Persistent(ref db) => WriteTransactionProxy::new(
+                TransactionProxy::WriteTransaction(db.write_transaction()),
+            ),
+            #[cfg(feature = "redb")]
+            DatabaseProxy::Redb(ref db) => WriteTransactionProxy::new(
+                TransactionProxy::RedbWriteTransaction(db.write_transaction()),
+            ),
+        }
+    }
+}
+
+impl DatabaseProxy {
+    /// Returns the page usage statistics of the database file.
+    pub fn stats(&self) -> Result<DatabaseStats, Error> {
+        match self {
+            DatabaseProxy::Volatile(ref db) => db.stats(),
+            DatabaseProxy::Persistent(ref db) => db.stats(),
+            #[cfg(feature = "redb")]
+            DatabaseProxy::Redb(_) => Err(Error::NotSupported("redb")),
+        }
+    }
+
+    /// Returns the storage statistics of all tables in the database.
+    pub fn table_stats(&self) -> Result<Vec<TableStats>, Error> {
+        match self {
+            DatabaseProxy::Volatile(ref db) => db.table_stats(),
+            DatabaseProxy::Persistent(ref db) => db.table_stats(),
+            #[cfg(feature = "redb")]
+            DatabaseProxy::Redb(_) => Err(Error::NotSupported("redb")),
+        }
+    }
+
+    /// Copies a compacted, consistent snapshot of the database to the given directory, which
+    /// must not exist or be empty. Writes to the database may continue while the copy is taken.
+    pub fn backup_to<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        match self {
+            DatabaseProxy::Volatile(ref db) => db.backup_to(path),
+            DatabaseProxy::Persistent(ref db) => db.backup_to(path),
+            #[cfg(feature = "redb")]
+            DatabaseProxy::Redb(_) => Err(Error::NotSupported("redb")),
         }
     }
 }
@@ -7,6 +7,31 @@ pub use database::*;
 pub use transaction::*;
 
 use crate::mdbx::MdbxTable;
+#[cfg(feature = "redb")]
+use crate::redb::RedbTable;
 
 /// A table handle that is used to reference tables during transactions.
-pub type TableProxy = MdbxTable;
+#[derive(Debug)]
+pub enum TableProxy {
+    Mdbx(MdbxTable),
+    #[cfg(feature = "redb")]
+    Redb(RedbTable),
+}
+
+impl TableProxy {
+    pub(crate) fn mdbx(&self) -> &MdbxTable {
+        match self {
+            TableProxy::Mdbx(table) => table,
+            #[cfg(feature = "redb")]
+            _ => panic!("Table of another database backend used in an MDBX transaction"),
+        }
+    }
+
+    #[cfg(feature = "redb")]
+    pub(crate) fn redb(&self) -> &RedbTable {
+        match self {
+            TableProxy::Redb(table) => table,
+            _ => panic!("Table of another database backend used in a redb transaction"),
+        }
+    }
+}
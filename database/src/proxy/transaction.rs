@@ -1,7 +1,9 @@
 use std::ops::Deref;
 
+#[cfg(feature = "redb")]
+use crate::redb::{RedbReadTransaction, RedbWriteTransaction};
 use crate::{
-    mdbx::{MdbxReadTransaction, MdbxWriteCursor, MdbxWriteTransaction},
+    mdbx::{MdbxReadTransaction, MdbxWriteTransaction},
     traits::{ReadTransaction, WriteTransaction},
     CursorProxy, TableProxy,
 };
@@ -12,6 +14,10 @@ use crate::{
 pub enum TransactionProxy<'db> {
     ReadTransaction(MdbxReadTransaction<'db>),
     WriteTransaction(MdbxWriteTransaction<'db>),
+    #[cfg(feature = "redb")]
+    RedbReadTransaction(RedbReadTransaction),
+    #[cfg(feature = "redb")]
+    RedbWriteTransaction(RedbWriteTransaction),
 }
 
 /// A transaction handle for read-write transactions.
@@ -33,26 +39,38 @@ impl<'db> ReadTransaction<'db> for TransactionProxy<'db> {
         V: nimiq_database_value::FromDatabaseValue,
     {
         match self {
-            TransactionProxy::ReadTransaction(txn) => txn.get(table, key),
-            TransactionProxy::WriteTransaction(txn) => txn.get(table, key),
+            TransactionProxy::ReadTransaction(txn) => txn.get(table.mdbx(), key),
+            TransactionProxy::WriteTransaction(txn) => txn.get(table.mdbx(), key),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(txn) => txn.get(table.redb(), key),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(txn) => txn.get(table.redb(), key),
         }
     }
 
     fn cursor<'txn>(&'txn self, table: &Self::Table) -> Self::Cursor<'txn> {
         match self {
-            TransactionProxy::ReadTransaction(txn) => CursorProxy::ReadCursor(txn.cursor(table)),
+            TransactionProxy::ReadTransaction(txn) => {
+                CursorProxy::ReadCursor(txn.cursor(table.mdbx()))
+            }
             TransactionProxy::WriteTransaction(txn) => {
-                CursorProxy::WriteCursor(ReadTransaction::cursor(txn, table))
+                CursorProxy::WriteCursor(ReadTransaction::cursor(txn, table.mdbx()))
+            }
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(txn) => {
+                CursorProxy::RedbReadCursor(txn.cursor(table.redb()))
+            }
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(txn) => {
+                CursorProxy::RedbWriteCursor(ReadTransaction::cursor(txn, table.redb()))
             }
         }
     }
 }
 
 impl<'db> WriteTransactionProxy<'db> {
-    pub(super) fn new(txn: MdbxWriteTransaction<'db>) -> Self {
-        Self {
-            txn: TransactionProxy::WriteTransaction(txn),
-        }
+    pub(super) fn new(txn: TransactionProxy<'db>) -> Self {
+        Self { txn }
     }
 }
 
@@ -85,7 +103,7 @@ impl<'db> ReadTransaction<'db> for WriteTransactionProxy<'db> {
 }
 
 impl<'db> WriteTransaction<'db> for WriteTransactionProxy<'db> {
-    type WriteCursor<'txn> = MdbxWriteCursor<'txn>
+    type WriteCursor<'txn> = CursorProxy<'txn>
     where
         Self: 'txn;
 
@@ -96,7 +114,15 @@ impl<'db> WriteTransaction<'db> for WriteTransactionProxy<'db> {
     {
         match self.txn {
             TransactionProxy::ReadTransaction(_) => unreachable!(),
-            TransactionProxy::WriteTransaction(ref mut txn) => txn.put_reserve(table, key, value),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(_) => unreachable!(),
+            TransactionProxy::WriteTransaction(ref mut txn) => {
+                txn.put_reserve(table.mdbx(), key, value)
+            }
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(ref mut txn) => {
+                txn.put_reserve(table.redb(), key, value)
+            }
         }
     }
 
@@ -107,7 +133,13 @@ impl<'db> WriteTransaction<'db> for WriteTransactionProxy<'db> {
     {
         match self.txn {
             TransactionProxy::ReadTransaction(_) => unreachable!(),
-            TransactionProxy::WriteTransaction(ref mut txn) => txn.put(table, key, value),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(_) => unreachable!(),
+            TransactionProxy::WriteTransaction(ref mut txn) => txn.put(table.mdbx(), key, value),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(ref mut txn) => {
+                txn.put(table.redb(), key, value)
+            }
         }
     }
 
@@ -117,7 +149,11 @@ impl<'db> WriteTransaction<'db> for WriteTransactionProxy<'db> {
     {
         match self.txn {
             TransactionProxy::ReadTransaction(_) => unreachable!(),
-            TransactionProxy::WriteTransaction(ref mut txn) => txn.remove(table, key),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(_) => unreachable!(),
+            TransactionProxy::WriteTransaction(ref mut txn) => txn.remove(table.mdbx(), key),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(ref mut txn) => txn.remove(table.redb(), key),
         }
     }
 
@@ -128,28 +164,63 @@ impl<'db> WriteTransaction<'db> for WriteTransactionProxy<'db> {
     {
         match self.txn {
             TransactionProxy::ReadTransaction(_) => unreachable!(),
-            TransactionProxy::WriteTransaction(ref mut txn) => txn.remove_item(table, key, value),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(_) => unreachable!(),
+            TransactionProxy::WriteTransaction(ref mut txn) => {
+                txn.remove_item(table.mdbx(), key, value)
+            }
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(ref mut txn) => {
+                txn.remove_item(table.redb(), key, value)
+            }
         }
     }
 
     fn commit(self) {
         match self.txn {
             TransactionProxy::ReadTransaction(_) => unreachable!(),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(_) => unreachable!(),
             TransactionProxy::WriteTransaction(txn) => txn.commit(),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(txn) => txn.commit(),
+        }
+    }
+
+    fn abort(self) {
+        match self.txn {
+            TransactionProxy::ReadTransaction(_) => unreachable!(),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(_) => unreachable!(),
+            TransactionProxy::WriteTransaction(txn) => txn.abort(),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(txn) => txn.abort(),
         }
     }
 
     fn cursor<'txn>(&'txn self, table: &Self::Table) -> Self::WriteCursor<'txn> {
         match self.txn {
             TransactionProxy::ReadTransaction(_) => unreachable!(),
-            TransactionProxy::WriteTransaction(ref txn) => WriteTransaction::cursor(txn, table),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(_) => unreachable!(),
+            TransactionProxy::WriteTransaction(ref txn) => {
+                CursorProxy::WriteCursor(WriteTransaction::cursor(txn, table.mdbx()))
+            }
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(ref txn) => {
+                CursorProxy::RedbWriteCursor(WriteTransaction::cursor(txn, table.redb()))
+            }
         }
     }
 
     fn clear_database(&mut self, table: &Self::Table) {
         match self.txn {
             TransactionProxy::ReadTransaction(_) => unreachable!(),
-            TransactionProxy::WriteTransaction(ref mut txn) => txn.clear_database(table),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbReadTransaction(_) => unreachable!(),
+            TransactionProxy::WriteTransaction(ref mut txn) => txn.clear_database(table.mdbx()),
+            #[cfg(feature = "redb")]
+            TransactionProxy::RedbWriteTransaction(ref mut txn) => txn.clear_database(table.redb()),
         }
     }
 }
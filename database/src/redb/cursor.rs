@@ -0,0 +1,322 @@
+use std::{marker::PhantomData, ops::Bound};
+
+use nimiq_database_value::{AsDatabaseBytes, FromDatabaseValue};
+
+use super::{
+    encoding::{self, Entry, Range},
+    RedbTable, TableAccess,
+};
+use crate::traits::{ReadCursor, WriteCursor};
+
+/// A cursor for navigating the entries within a table.
+/// Redb has no cursors, so the cursor remembers the stored key of the current entry and looks up
+/// its neighbours on every move.
+pub struct RedbCursor<'txn, T: TableAccess> {
+    txn: &'txn T,
+    table: RedbTable,
+    position: Option<Vec<u8>>,
+}
+/// Instantiation of the `RedbCursor` for read transactions.
+pub type RedbReadCursor<'txn> = RedbCursor<'txn, redb::ReadTransaction>;
+/// Instantiation of the `RedbCursor` for write transactions.
+pub type RedbWriteCursor<'txn> = RedbCursor<'txn, redb::WriteTransaction>;
+
+fn convert<K, V>((key, value): Entry) -> (K, V)
+where
+    K: FromDatabaseValue,
+    V: FromDatabaseValue,
+{
+    (
+        FromDatabaseValue::copy_from_database(&key).unwrap(),
+        FromDatabaseValue::copy_from_database(&value).unwrap(),
+    )
+}
+
+/// Turns an upper bound into the lower bound of the complementary range and vice versa.
+fn complement(bound: Bound<Vec<u8>>) -> Option<Bound<Vec<u8>>> {
+    match bound {
+        Bound::Included(key) => Some(Bound::Excluded(key)),
+        Bound::Excluded(key) => Some(Bound::Included(key)),
+        Bound::Unbounded => None,
+    }
+}
+
+impl<'txn, T: TableAccess> RedbCursor<'txn, T> {
+    pub(crate) fn new(txn: &'txn T, table: RedbTable) -> Self {
+        RedbCursor {
+            txn,
+            table,
+            position: None,
+        }
+    }
+
+    /// Moves the cursor to the first (or last if `rev` is set) entry within the range.
+    /// The cursor is not moved if there is no such entry.
+    fn seek(&mut self, range: Range, rev: bool) -> Option<Entry> {
+        let (stored_key, stored_value) = self.txn.find(&self.table, &range, rev)?;
+        let entry = encoding::decode(&self.table, &stored_key, &stored_value);
+        self.position = Some(stored_key);
+        Some(entry)
+    }
+
+    fn current(&self) -> Option<Entry> {
+        let position = self.position.clone()?;
+        let range = (Bound::Included(position.clone()), Bound::Included(position));
+        let (stored_key, stored_value) = self.txn.find(&self.table, &range, false)?;
+        Some(encoding::decode(&self.table, &stored_key, &stored_value))
+    }
+
+    /// Returns the range of stored keys of all duplicates of the current key.
+    fn current_key_range(&self) -> Option<Range> {
+        let (key, _) = self.current()?;
+        Some(encoding::key_range(&self.table, &key))
+    }
+}
+
+impl<'txn, T: TableAccess> ReadCursor<'txn> for RedbCursor<'txn, T> {
+    type IntoIter<K, V> = RedbIntoIter<'txn, T, K, V>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue;
+
+    fn first<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        self.seek((Bound::Unbounded, Bound::Unbounded), false)
+            .map(convert)
+    }
+
+    fn first_duplicate<V>(&mut self) -> Option<V>
+    where
+        V: FromDatabaseValue,
+    {
+        let range = self.current_key_range()?;
+        let (_, value) = self.seek(range, false)?;
+        Some(FromDatabaseValue::copy_from_database(&value).unwrap())
+    }
+
+    fn last<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        self.seek((Bound::Unbounded, Bound::Unbounded), true)
+            .map(convert)
+    }
+
+    fn last_duplicate<V>(&mut self) -> Option<V>
+    where
+        V: FromDatabaseValue,
+    {
+        let range = self.current_key_range()?;
+        let (_, value) = self.seek(range, true)?;
+        Some(FromDatabaseValue::copy_from_database(&value).unwrap())
+    }
+
+    fn get_current<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        self.current().map(convert)
+    }
+
+    fn next<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        match self.position.clone() {
+            Some(position) => self
+                .seek((Bound::Excluded(position), Bound::Unbounded), false)
+                .map(convert),
+            None => self.first(),
+        }
+    }
+
+    fn next_duplicate<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        let position = self.position.clone()?;
+        let (_, end) = self.current_key_range()?;
+        self.seek((Bound::Excluded(position), end), false)
+            .map(convert)
+    }
+
+    fn next_no_duplicate<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        if self.position.is_none() {
+            return self.first();
+        }
+        let (_, end) = self.current_key_range()?;
+        self.seek((complement(end)?, Bound::Unbounded), false)
+            .map(convert)
+    }
+
+    fn prev<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        match self.position.clone() {
+            Some(position) => self
+                .seek((Bound::Unbounded, Bound::Excluded(position)), true)
+                .map(convert),
+            None => self.last(),
+        }
+    }
+
+    fn prev_duplicate<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        let position = self.position.clone()?;
+        let (start, _) = self.current_key_range()?;
+        self.seek((start, Bound::Excluded(position)), true)
+            .map(convert)
+    }
+
+    fn prev_no_duplicate<K, V>(&mut self) -> Option<(K, V)>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        if self.position.is_none() {
+            return self.last();
+        }
+        let (start, _) = self.current_key_range()?;
+        self.seek((Bound::Unbounded, complement(start)?), true)
+            .map(convert)
+    }
+
+    fn seek_key<K, V>(&mut self, key: &K) -> Option<V>
+    where
+        K: AsDatabaseBytes + ?Sized,
+        V: FromDatabaseValue,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let range = encoding::key_range(&self.table, &key);
+        let (_, value) = self.seek(range, false)?;
+        Some(FromDatabaseValue::copy_from_database(&value).unwrap())
+    }
+
+    fn seek_range_key<K, V>(&mut self, key: &K) -> Option<(K, V)>
+    where
+        K: AsDatabaseBytes + FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let range = encoding::range_from(&self.table, &key);
+        self.seek(range, false).map(convert)
+    }
+
+    fn count_duplicates(&mut self) -> usize {
+        match self.current_key_range() {
+            Some(range) => self.txn.count(&self.table, &range),
+            None => 0,
+        }
+    }
+
+    fn into_iter_start<K, V>(self) -> Self::IntoIter<K, V>
+    where
+        K: FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        RedbIntoIter::new(self, (Bound::Unbounded, Bound::Unbounded))
+    }
+
+    fn into_iter_dup_of<K, V>(self, key: &K) -> Self::IntoIter<K, V>
+    where
+        K: AsDatabaseBytes + FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let range = encoding::key_range(&self.table, &key);
+        RedbIntoIter::new(self, range)
+    }
+
+    fn into_iter_from<K, V>(self, key: &K) -> Self::IntoIter<K, V>
+    where
+        K: AsDatabaseBytes + FromDatabaseValue,
+        V: FromDatabaseValue,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let range = encoding::range_from(&self.table, &key);
+        RedbIntoIter::new(self, range)
+    }
+}
+
+impl<'txn, T: TableAccess> Clone for RedbCursor<'txn, T> {
+    fn clone(&self) -> Self {
+        Self {
+            txn: self.txn,
+            table: self.table.clone(),
+            position: self.position.clone(),
+        }
+    }
+}
+
+impl<'txn> WriteCursor<'txn> for RedbWriteCursor<'txn> {
+    fn remove(&mut self) {
+        // The position is kept, so that moving the cursor continues from the removed entry.
+        if let Some(ref position) = self.position {
+            self.txn
+                .open_table(self.table.definition())
+                .unwrap()
+                .remove(position.as_slice())
+                .unwrap();
+        }
+    }
+}
+
+/// Iterates over database entries (key, value pairs).
+/// Can be instantiated for both read and write transactions.
+pub struct RedbIntoIter<'txn, T: TableAccess, K: FromDatabaseValue, V: FromDatabaseValue> {
+    txn: &'txn T,
+    table: RedbTable,
+    /// The range of stored keys that have not been visited yet.
+    range: Range,
+    _k: PhantomData<K>,
+    _v: PhantomData<V>,
+}
+
+impl<'txn, T, K, V> RedbIntoIter<'txn, T, K, V>
+where
+    T: TableAccess,
+    K: FromDatabaseValue,
+    V: FromDatabaseValue,
+{
+    fn new(cursor: RedbCursor<'txn, T>, range: Range) -> Self {
+        RedbIntoIter {
+            txn: cursor.txn,
+            table: cursor.table,
+            range,
+            _k: PhantomData,
+            _v: PhantomData,
+        }
+    }
+}
+
+impl<'txn, T, K, V> Iterator for RedbIntoIter<'txn, T, K, V>
+where
+    T: TableAccess,
+    K: FromDatabaseValue,
+    V: FromDatabaseValue,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (stored_key, stored_value) = self.txn.find(&self.table, &self.range, false)?;
+        let entry = encoding::decode(&self.table, &stored_key, &stored_value);
+        self.range.0 = Bound::Excluded(stored_key);
+        Some(convert(entry))
+    }
+}
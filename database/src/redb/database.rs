@@ -0,0 +1,92 @@
+use std::{fs, path::Path, sync::Arc};
+
+use log::info;
+
+use super::{RedbReadTransaction, RedbWriteTransaction};
+use crate::{traits::Database, DatabaseProxy, Error, TableFlags};
+
+/// The name of the database file within the database directory.
+const DB_FILE_NAME: &str = "data.redb";
+
+/// Wrapper around the redb database handle.
+/// A database can hold multiple tables.
+#[derive(Clone)]
+pub struct RedbDatabase {
+    pub(super) db: Arc<redb::Database>,
+}
+
+impl std::fmt::Debug for RedbDatabase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedbDatabase").finish_non_exhaustive()
+    }
+}
+
+impl Database for RedbDatabase {
+    type Table = RedbTable;
+    type ReadTransaction<'db> = RedbReadTransaction
+    where
+        Self: 'db;
+    type WriteTransaction<'db> = RedbWriteTransaction
+    where
+        Self: 'db;
+
+    fn open_table(&self, name: String) -> Self::Table {
+        self.open_table_with_flags(name, TableFlags::empty())
+    }
+
+    fn open_table_with_flags(&self, name: String, flags: TableFlags) -> Self::Table {
+        let table = RedbTable { name, flags };
+
+        // Create the table.
+        let txn = self.db.begin_write().unwrap();
+        txn.open_table(table.definition()).unwrap();
+        txn.commit().unwrap();
+
+        table
+    }
+
+    fn read_transaction(&self) -> Self::ReadTransaction<'_> {
+        RedbReadTransaction::new(self.db.begin_read().unwrap())
+    }
+
+    fn write_transaction(&self) -> Self::WriteTransaction<'_> {
+        RedbWriteTransaction::new(self.db.begin_write().unwrap())
+    }
+}
+
+impl RedbDatabase {
+    /// Opens or creates the database in the given directory. Unlike MDBX, the database file grows
+    /// as needed and does not require a map size.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<DatabaseProxy, Error> {
+        let path = path.as_ref();
+        fs::create_dir_all(path).map_err(Error::CreateDirectory)?;
+
+        let db = redb::Database::create(path.join(DB_FILE_NAME)).map_err(redb::Error::from)?;
+        info!(path = %path.display(), "Opened redb database");
+
+        Ok(DatabaseProxy::Redb(RedbDatabase { db: Arc::new(db) }))
+    }
+}
+
+/// A table handle for the redb database.
+/// It is used to reference tables during transactions.
+///
+/// Redb has no notion of duplicate or integer keys, so they are emulated by encoding keys and
+/// values into the stored keys (see `encoding`). The flags must thus be the same every time a
+/// table is opened.
+#[derive(Clone, Debug)]
+pub struct RedbTable {
+    pub(super) name: String,
+    pub(super) flags: TableFlags,
+}
+
+impl RedbTable {
+    pub(super) fn definition(&self) -> redb::TableDefinition<'_, &'static [u8], &'static [u8]> {
+        redb::TableDefinition::new(&self.name)
+    }
+
+    pub(super) fn has_duplicates(&self) -> bool {
+        self.flags.contains(TableFlags::DUPLICATE_KEYS)
+    }
+}
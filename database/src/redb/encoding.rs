@@ -0,0 +1,131 @@
+//! Encoding of keys and values into the keys stored in redb.
+//!
+//! Redb compares keys byte-wise and does not support duplicate keys. To match the semantics of
+//! MDBX tables:
+//! * integer keys (stored in native byte order) are converted to big endian, so that they are
+//!   sorted numerically;
+//! * entries of tables with duplicate keys are stored as `escape(key) | 0x00 0x00 | value` with
+//!   an empty value, where `escape` replaces every `0x00` by `0x00 0xff`. This keeps the entries
+//!   sorted by key first and value second, as MDBX does.
+
+use std::ops::Bound;
+
+use super::RedbTable;
+use crate::TableFlags;
+
+const ESCAPE: u8 = 0xff;
+const TERMINATOR: [u8; 2] = [0x00, 0x00];
+
+fn encode_key(table: &RedbTable, key: &[u8]) -> Vec<u8> {
+    let mut key = key.to_vec();
+    if cfg!(target_endian = "little") && table.flags.contains(TableFlags::UINT_KEYS) {
+        key.reverse();
+    }
+    key
+}
+
+fn decode_key(table: &RedbTable, key: &[u8]) -> Vec<u8> {
+    // Reversing the byte order is its own inverse.
+    encode_key(table, key)
+}
+
+fn escape(key: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(key.len() + TERMINATOR.len());
+    for &byte in key {
+        escaped.push(byte);
+        if byte == 0x00 {
+            escaped.push(ESCAPE);
+        }
+    }
+    escaped
+}
+
+/// Returns the prefix shared by all stored keys of the given key.
+fn prefix(table: &RedbTable, key: &[u8]) -> Vec<u8> {
+    let mut prefix = escape(&encode_key(table, key));
+    prefix.extend_from_slice(&TERMINATOR);
+    prefix
+}
+
+/// Returns the stored key of an entry.
+pub(super) fn stored_key(table: &RedbTable, key: &[u8], value: &[u8]) -> Vec<u8> {
+    if table.has_duplicates() {
+        let mut stored_key = prefix(table, key);
+        stored_key.extend_from_slice(value);
+        stored_key
+    } else {
+        encode_key(table, key)
+    }
+}
+
+/// Returns the stored value of an entry.
+pub(super) fn stored_value<'a>(table: &RedbTable, value: &'a [u8]) -> &'a [u8] {
+    if table.has_duplicates() {
+        &[]
+    } else {
+        value
+    }
+}
+
+/// Splits a stored entry into its key and value.
+pub(super) fn decode(table: &RedbTable, stored_key: &[u8], stored_value: &[u8]) -> Entry {
+    if !table.has_duplicates() {
+        return (decode_key(table, stored_key), stored_value.to_vec());
+    }
+
+    let mut key = vec![];
+    let mut i = 0;
+    while i < stored_key.len() {
+        let byte = stored_key[i];
+        if byte == 0x00 {
+            if stored_key[i + 1] == TERMINATOR[1] {
+                break;
+            }
+            // Skip the escape byte.
+            i += 1;
+        }
+        key.push(byte);
+        i += 1;
+    }
+    (
+        decode_key(table, &key),
+        stored_key[i + TERMINATOR.len()..].to_vec(),
+    )
+}
+
+/// Returns the stored key range of all entries of the given key.
+pub(super) fn key_range(table: &RedbTable, key: &[u8]) -> Range {
+    if table.has_duplicates() {
+        let start = prefix(table, key);
+        // `0x00 0x01` sorts directly after the terminator and is never produced by escaping.
+        let mut end = start.clone();
+        *end.last_mut().unwrap() = 0x01;
+        (Bound::Included(start), Bound::Excluded(end))
+    } else {
+        let key = encode_key(table, key);
+        (Bound::Included(key.clone()), Bound::Included(key))
+    }
+}
+
+/// Returns the stored key range of all entries with keys greater than or equal to the given key.
+pub(super) fn range_from(table: &RedbTable, key: &[u8]) -> Range {
+    // For tables with duplicates, the escaped key sorts before all of its entries and after
+    // the entries of all smaller keys.
+    (
+        Bound::Included(escape_if_duplicates(table, &encode_key(table, key))),
+        Bound::Unbounded,
+    )
+}
+
+fn escape_if_duplicates(table: &RedbTable, key: &[u8]) -> Vec<u8> {
+    if table.has_duplicates() {
+        escape(key)
+    } else {
+        key.to_vec()
+    }
+}
+
+/// A decoded key and value.
+pub(super) type Entry = (Vec<u8>, Vec<u8>);
+/// A range of stored keys.
+pub(super) type Range = (Bound<Vec<u8>>, Bound<Vec<u8>>);
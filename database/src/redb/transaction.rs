@@ -0,0 +1,226 @@
+use std::{fmt, ops::Bound};
+
+use nimiq_database_value::{AsDatabaseBytes, FromDatabaseValue, IntoDatabaseValue};
+use redb::ReadableTable;
+
+use super::{
+    encoding::{self, Range},
+    RedbCursor, RedbTable, RedbWriteCursor,
+};
+use crate::traits::{ReadTransaction, WriteTransaction};
+
+/// Read access to the tables of a redb transaction, shared by read and write transactions.
+pub trait TableAccess {
+    /// Returns the first (or last if `rev` is set) stored entry within the given range of
+    /// stored keys.
+    fn find(&self, table: &RedbTable, range: &Range, rev: bool) -> Option<(Vec<u8>, Vec<u8>)>;
+
+    /// Returns the number of stored entries within the given range of stored keys.
+    fn count(&self, table: &RedbTable, range: &Range) -> usize;
+}
+
+impl TableAccess for redb::ReadTransaction {
+    fn find(&self, table: &RedbTable, range: &Range, rev: bool) -> Option<(Vec<u8>, Vec<u8>)> {
+        find_in(&self.open_table(table.definition()).unwrap(), range, rev)
+    }
+
+    fn count(&self, table: &RedbTable, range: &Range) -> usize {
+        count_in(&self.open_table(table.definition()).unwrap(), range)
+    }
+}
+
+impl TableAccess for redb::WriteTransaction {
+    fn find(&self, table: &RedbTable, range: &Range, rev: bool) -> Option<(Vec<u8>, Vec<u8>)> {
+        find_in(&self.open_table(table.definition()).unwrap(), range, rev)
+    }
+
+    fn count(&self, table: &RedbTable, range: &Range) -> usize {
+        count_in(&self.open_table(table.definition()).unwrap(), range)
+    }
+}
+
+/// Returns true if the range cannot contain any key. Redb rejects such ranges.
+fn is_empty(range: &Range) -> bool {
+    match range {
+        (Bound::Included(start), Bound::Included(end)) => start > end,
+        (Bound::Included(start), Bound::Excluded(end))
+        | (Bound::Excluded(start), Bound::Included(end))
+        | (Bound::Excluded(start), Bound::Excluded(end)) => start >= end,
+        _ => false,
+    }
+}
+
+fn as_slices(range: &Range) -> (Bound<&[u8]>, Bound<&[u8]>) {
+    (
+        range.0.as_ref().map(Vec::as_slice),
+        range.1.as_ref().map(Vec::as_slice),
+    )
+}
+
+fn find_in<T>(table: &T, range: &Range, rev: bool) -> Option<(Vec<u8>, Vec<u8>)>
+where
+    T: ReadableTable<&'static [u8], &'static [u8]>,
+{
+    if is_empty(range) {
+        return None;
+    }
+
+    let mut entries = table.range::<&[u8]>(as_slices(range)).unwrap();
+    let entry = if rev {
+        entries.next_back()
+    } else {
+        entries.next()
+    };
+    let (key, value) = entry?.unwrap();
+    Some((key.value().to_vec(), value.value().to_vec()))
+}
+
+fn count_in<T>(table: &T, range: &Range) -> usize
+where
+    T: ReadableTable<&'static [u8], &'static [u8]>,
+{
+    if is_empty(range) {
+        return 0;
+    }
+    table.range::<&[u8]>(as_slices(range)).unwrap().count()
+}
+
+/// Wrapper around redb transactions that only exposes our own traits.
+pub struct RedbTransaction<T: TableAccess> {
+    txn: T,
+}
+/// Instantiation for read-only transactions.
+pub type RedbReadTransaction = RedbTransaction<redb::ReadTransaction>;
+/// Instantiation for read-write transactions.
+pub type RedbWriteTransaction = RedbTransaction<redb::WriteTransaction>;
+
+impl<T: TableAccess> RedbTransaction<T> {
+    pub(crate) fn new(txn: T) -> Self {
+        RedbTransaction { txn }
+    }
+}
+
+impl<T: TableAccess> fmt::Debug for RedbTransaction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedbTransaction").finish_non_exhaustive()
+    }
+}
+
+impl<'db, T: TableAccess> ReadTransaction<'db> for RedbTransaction<T> {
+    type Table = RedbTable;
+    type Cursor<'txn> = RedbCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    fn get<K, V>(&self, table: &RedbTable, key: &K) -> Option<V>
+    where
+        K: AsDatabaseBytes + ?Sized,
+        V: FromDatabaseValue,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let range = encoding::key_range(table, &key);
+
+        let (stored_key, stored_value) = self.txn.find(table, &range, false)?;
+        let (_, value) = encoding::decode(table, &stored_key, &stored_value);
+        Some(FromDatabaseValue::copy_from_database(&value).unwrap())
+    }
+
+    fn cursor<'txn>(&'txn self, table: &RedbTable) -> RedbCursor<'txn, T> {
+        RedbCursor::new(&self.txn, table.clone())
+    }
+}
+
+impl RedbWriteTransaction {
+    fn open_table(&self, table: &RedbTable) -> redb::Table<'_, &'static [u8], &'static [u8]> {
+        self.txn.open_table(table.definition()).unwrap()
+    }
+}
+
+impl<'db> WriteTransaction<'db> for RedbWriteTransaction {
+    type WriteCursor<'txn> = RedbWriteCursor<'txn>
+    where
+        Self: 'txn;
+
+    fn put_reserve<K, V>(&mut self, table: &RedbTable, key: &K, value: &V)
+    where
+        K: AsDatabaseBytes + ?Sized,
+        V: IntoDatabaseValue + ?Sized,
+    {
+        // Redb has no way to reserve space for a value, so the value is serialized up front.
+        let mut bytes = vec![0u8; IntoDatabaseValue::database_byte_size(value)];
+        IntoDatabaseValue::copy_into_database(value, &mut bytes);
+
+        self.put(table, key, bytes.as_slice());
+    }
+
+    fn put<K, V>(&mut self, table: &RedbTable, key: &K, value: &V)
+    where
+        K: AsDatabaseBytes + ?Sized,
+        V: AsDatabaseBytes + ?Sized,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let value = AsDatabaseBytes::as_database_bytes(value);
+
+        let stored_key = encoding::stored_key(table, &key, &value);
+        self.open_table(table)
+            .insert(stored_key.as_slice(), encoding::stored_value(table, &value))
+            .unwrap();
+    }
+
+    fn remove<K>(&mut self, table: &RedbTable, key: &K)
+    where
+        K: AsDatabaseBytes + ?Sized,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let range = encoding::key_range(table, &key);
+
+        // Removes all duplicates of the key.
+        let mut redb_table = self.open_table(table);
+        let stored_keys: Vec<Vec<u8>> = redb_table
+            .range::<&[u8]>(as_slices(&range))
+            .unwrap()
+            .map(|entry| entry.unwrap().0.value().to_vec())
+            .collect();
+        for stored_key in stored_keys {
+            redb_table.remove(stored_key.as_slice()).unwrap();
+        }
+    }
+
+    fn remove_item<K, V>(&mut self, table: &RedbTable, key: &K, value: &V)
+    where
+        K: AsDatabaseBytes + ?Sized,
+        V: AsDatabaseBytes + ?Sized,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let value = AsDatabaseBytes::as_database_bytes(value);
+        let stored_key = encoding::stored_key(table, &key, &value);
+
+        let mut redb_table = self.open_table(table);
+        let matches = redb_table
+            .get(stored_key.as_slice())
+            .unwrap()
+            .is_some_and(|stored_value| {
+                stored_value.value() == encoding::stored_value(table, &value)
+            });
+        if matches {
+            redb_table.remove(stored_key.as_slice()).unwrap();
+        }
+    }
+
+    fn commit(self) {
+        self.txn.commit().unwrap();
+    }
+
+    fn abort(self) {
+        self.txn.abort().unwrap();
+    }
+
+    fn cursor<'txn>(&'txn self, table: &RedbTable) -> RedbWriteCursor<'txn> {
+        RedbCursor::new(&self.txn, table.clone())
+    }
+
+    fn clear_database(&mut self, table: &RedbTable) {
+        self.txn.delete_table(table.definition()).unwrap();
+        self.open_table(table);
+    }
+}
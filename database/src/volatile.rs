@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use tempfile::TempDir;
 
@@ -78,6 +78,21 @@ impl VolatileDatabase {
             db,
         }))
     }
+
+    /// Returns the page usage statistics of the database file.
+    pub fn stats(&self) -> Result<DatabaseStats, Error> {
+        self.db.stats()
+    }
+
+    /// Returns the storage statistics of all tables in the database.
+    pub fn table_stats(&self) -> Result<Vec<TableStats>, Error> {
+        self.db.table_stats()
+    }
+
+    /// Copies a compacted, consistent snapshot of the database to the given directory.
+    pub fn backup_to<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.db.backup_to(path)
+    }
 }
 
 pub type VolatileTable = MdbxTable;
@@ -1,6 +1,7 @@
 use std::{
     fmt::Debug,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -9,6 +10,7 @@ use futures::{
     ready, select,
     stream::{BoxStream, Stream, StreamExt},
 };
+use parking_lot::Mutex;
 use tokio::time::{interval_at, Instant};
 use tokio_stream::wrappers::IntervalStream;
 
@@ -22,6 +24,8 @@ use crate::{
     protocol::Protocol,
     store::ContributionStore,
     todo::TodoList,
+    trace::{AggregationTrace, LevelStart},
+    tracker::AggregationTracker,
     update::LevelUpdate,
 };
 
@@ -64,6 +68,9 @@ struct NextAggregation<
 
     /// the level which needs activation next
     next_level_timeout: usize,
+
+    /// Record of how this aggregation progresses
+    trace: Arc<Mutex<AggregationTrace>>,
 }
 
 impl<
@@ -86,7 +93,9 @@ impl<
         let mut todos = TodoList::new(protocol.identify(), protocol.evaluator(), input_stream);
 
         // Add our own contribution to the todo list.
-        todos.add_contribution(own_contribution.clone(), 0);
+        todos.add_contribution(own_contribution.clone(), 0, protocol.node_id());
+
+        let trace = Arc::new(Mutex::new(AggregationTrace::new(&levels)));
 
         // Regardless of level completion consecutive levels need to be activated at some point. Activate Levels every time this interval ticks,
         // if the level has not already been activated due to level completion
@@ -111,11 +120,12 @@ impl<
             start_level_interval,
             periodic_update_interval,
             next_level_timeout: 0,
+            trace,
         }
     }
 
     /// Starts level `level`
-    fn start_level(&mut self, level: usize) {
+    fn start_level(&mut self, level: usize, reason: LevelStart) {
         let level = self
             .levels
             .get(level)
@@ -128,6 +138,8 @@ impl<
 
         // Try to Start the level
         if level.start() {
+            self.trace.lock().level_started(level.id, reason);
+
             // In case the level was not started previously send the best contribution to peers on the level
 
             // Don't do anything for level 0 as it only contains this node
@@ -212,10 +224,12 @@ impl<
                     .write()
                     .receive_completed = true;
             }
+            self.trace.lock().level_completed(level_id);
+
             // if there is a level with a higher id than the completed one it needs to be activated.
             if level_id + 1 < self.levels.len() {
                 // activate next level
-                self.start_level(level_id + 1);
+                self.start_level(level_id + 1, LevelStart::PreviousLevelCompleted);
             }
         }
 
@@ -273,12 +287,14 @@ impl<
             );
 
             // Send the level update to every peer_id in peer_ids
+            let mut trace = self.trace.lock();
             for peer_id in peer_ids {
                 // This should always be the case
                 if peer_id < self.protocol.partitioner().size() {
                     self.sender
                         // `send` is not a future and thus will not block execution.
                         .send((update.clone(), peer_id));
+                    trace.update_sent(level_id, peer_id);
                 }
             }
         }
@@ -327,7 +343,7 @@ impl<
             self.next_level_timeout += 1;
 
             // finally start the level.
-            self.start_level(level);
+            self.start_level(level, LevelStart::Timeout);
         }
     }
 
@@ -348,8 +364,15 @@ impl<
                             let result = self.protocol.verify(&todo.contribution).await;
 
                             if result.is_ok() {
+                                self.trace.lock().contribution_verified(todo.level, todo.origin);
+
                                 // special case of full contributions
                                 if todo.level == self.protocol.partitioner().levels() {
+                                    let num_contributors = self.num_contributors(&todo.contribution);
+                                    self.trace.lock().aggregate_produced(
+                                        num_contributors,
+                                        self.is_complete_aggregate(&todo.contribution),
+                                    );
                                     return (todo.contribution, Some(self));
                                 }
 
@@ -366,7 +389,7 @@ impl<
                                 }
 
                                 // in case the level of this todo has not started, start it now as we have already contributions on it.
-                                self.start_level(todo.level);
+                                self.start_level(todo.level, LevelStart::Contribution);
                                 // check if a level was completed by the addition of the contribution
                                 self.check_completed_level(todo.level);
 
@@ -379,13 +402,22 @@ impl<
                                 };
 
                                 if let Some(best) = best {
+                                    let num_contributors = self.num_contributors(&best);
+                                    self.trace.lock().aggregate_produced(
+                                        num_contributors,
+                                        self.is_complete_aggregate(&best),
+                                    );
                                     return (best, Some(self));
                                 }
                             } else {
+                                self.trace.lock().contribution_invalid(todo.level, todo.origin);
+
                                 // Invalid contributions create a warning, but do not terminate. -> Continue with the next best todo item.
                                 warn!(
                                     id = ?self.protocol.identify(),
                                     ?result,
+                                    level = todo.level,
+                                    origin = todo.origin,
                                     "Invalid signature",
                                 );
                             }
@@ -468,6 +500,12 @@ pub struct Aggregation<
 > {
     next_aggregation:
         Option<BoxFuture<'static, (P::Contribution, Option<NextAggregation<TId, P, N>>)>>,
+
+    /// Record of how this aggregation progresses
+    trace: Arc<Mutex<AggregationTrace>>,
+
+    /// Tracker the trace is handed to once the aggregation is dropped
+    tracker: Option<Arc<AggregationTracker>>,
 }
 
 impl<
@@ -487,12 +525,43 @@ impl<
         let sender = LevelUpdateSender::new(protocol.partitioner().size(), network);
 
         let next_aggregation =
-            NextAggregation::new(protocol, config, own_contribution, input_stream, sender)
-                .next()
-                .boxed();
+            NextAggregation::new(protocol, config, own_contribution, input_stream, sender);
+        let trace = Arc::clone(&next_aggregation.trace);
 
         Self {
-            next_aggregation: Some(next_aggregation),
+            next_aggregation: Some(next_aggregation.next().boxed()),
+            trace,
+            tracker: None,
+        }
+    }
+
+    /// Hands the trace of this aggregation to the given tracker once the aggregation is dropped.
+    ///
+    /// The aggregation should have been created with the config returned by
+    /// [`AggregationTracker::config`] for the tracker to adapt timeouts of future aggregations.
+    pub fn with_tracker(mut self, tracker: Arc<AggregationTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Returns a snapshot of the trace of this aggregation.
+    pub fn trace(&self) -> AggregationTrace {
+        self.trace.lock().clone()
+    }
+}
+
+impl<
+        TId: Debug + Clone + Unpin + Send + 'static,
+        P: Protocol<TId>,
+        N: Network<Contribution = P::Contribution>,
+    > Drop for Aggregation<TId, P, N>
+{
+    fn drop(&mut self) {
+        let mut trace = self.trace.lock();
+        trace.finish();
+
+        if let Some(tracker) = &self.tracker {
+            tracker.record(&trace);
         }
     }
 }
@@ -15,10 +15,14 @@ pub mod contribution;
 pub mod evaluator;
 pub mod identity;
 pub mod level;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod network;
 pub mod partitioner;
 pub mod protocol;
 pub mod store;
 pub(crate) mod todo;
+pub mod trace;
+pub mod tracker;
 pub mod update;
 pub mod verifier;
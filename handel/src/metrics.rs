@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use prometheus_client::{
+    encoding::{EncodeLabelSet, EncodeLabelValue},
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::Gauge,
+        histogram::{exponential_buckets, Histogram},
+    },
+    registry::Registry,
+};
+
+use crate::{
+    config::Config,
+    trace::{AggregationTrace, LevelStart},
+};
+
+fn duration_histogram() -> Histogram {
+    // 10ms up to ~20s
+    Histogram::new(exponential_buckets(0.01, 2.0, 12))
+}
+
+/// Metrics about the aggregations recorded by an
+/// [`AggregationTracker`](crate::tracker::AggregationTracker).
+#[derive(Clone)]
+pub struct HandelMetrics {
+    aggregations: Counter,
+    completed_aggregations: Counter,
+    aggregation_duration: Histogram,
+    level_completion_time: Family<LevelLabels, Histogram, fn() -> Histogram>,
+    level_starts: Family<LevelStartLabels, Counter>,
+    signatures_verified: Counter,
+    invalid_contributions: Counter,
+    slow_peers: Counter,
+    level_timeout: Gauge,
+    update_interval: Gauge,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct LevelLabels {
+    level: u32,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct LevelStartLabels {
+    reason: LevelStartReason,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+enum LevelStartReason {
+    Timeout,
+    PreviousLevelCompleted,
+    Contribution,
+}
+
+impl From<LevelStart> for LevelStartReason {
+    fn from(reason: LevelStart) -> Self {
+        match reason {
+            LevelStart::Timeout => LevelStartReason::Timeout,
+            LevelStart::PreviousLevelCompleted => LevelStartReason::PreviousLevelCompleted,
+            LevelStart::Contribution => LevelStartReason::Contribution,
+        }
+    }
+}
+
+impl Default for HandelMetrics {
+    fn default() -> Self {
+        Self {
+            aggregations: Counter::default(),
+            completed_aggregations: Counter::default(),
+            aggregation_duration: duration_histogram(),
+            level_completion_time: Family::new_with_constructor(duration_histogram),
+            level_starts: Family::default(),
+            signatures_verified: Counter::default(),
+            invalid_contributions: Counter::default(),
+            slow_peers: Counter::default(),
+            level_timeout: Gauge::default(),
+            update_interval: Gauge::default(),
+        }
+    }
+}
+
+impl HandelMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "aggregations",
+            "Number of aggregations",
+            self.aggregations.clone(),
+        );
+        registry.register(
+            "completed_aggregations",
+            "Number of aggregations that collected all contributions",
+            self.completed_aggregations.clone(),
+        );
+        registry.register(
+            "aggregation_duration_seconds",
+            "Time aggregations ran for",
+            self.aggregation_duration.clone(),
+        );
+        registry.register(
+            "level_completion_seconds",
+            "Time it took to complete a level after it was started",
+            self.level_completion_time.clone(),
+        );
+        registry.register(
+            "level_starts",
+            "Number of started levels by reason",
+            self.level_starts.clone(),
+        );
+        registry.register(
+            "signatures_verified",
+            "Number of successfully verified contributions",
+            self.signatures_verified.clone(),
+        );
+        registry.register(
+            "invalid_contributions",
+            "Number of contributions that failed verification",
+            self.invalid_contributions.clone(),
+        );
+        registry.register(
+            "slow_peers",
+            "Number of contacted peers that contributed late or not at all",
+            self.slow_peers.clone(),
+        );
+        registry.register(
+            "level_timeout_ms",
+            "Current level timeout",
+            self.level_timeout.clone(),
+        );
+        registry.register(
+            "update_interval_ms",
+            "Current update interval",
+            self.update_interval.clone(),
+        );
+    }
+
+    pub(crate) fn note_aggregation(&self, trace: &AggregationTrace, slow_peers: usize) {
+        self.aggregations.inc();
+        if trace.completed_after.is_some() {
+            self.completed_aggregations.inc();
+        }
+        if let Some(duration) = trace.duration {
+            self.aggregation_duration.observe(duration.as_secs_f64());
+        }
+
+        for (level_id, level) in trace.levels.iter().enumerate() {
+            if let Some(completion_time) = level.completion_time() {
+                self.level_completion_time
+                    .get_or_create(&LevelLabels {
+                        level: level_id as u32,
+                    })
+                    .observe(completion_time.as_secs_f64());
+            }
+            if let Some(reason) = level.start_reason {
+                self.level_starts
+                    .get_or_create(&LevelStartLabels {
+                        reason: reason.into(),
+                    })
+                    .inc();
+            }
+        }
+
+        self.signatures_verified
+            .inc_by(trace.signatures_verified as u64);
+        self.invalid_contributions
+            .inc_by(trace.invalid_contributions as u64);
+        self.slow_peers.inc_by(slow_peers as u64);
+    }
+
+    pub(crate) fn note_config(&self, config: &Config) {
+        self.level_timeout.set(duration_millis(config.timeout));
+        self.update_interval
+            .set(duration_millis(config.update_interval));
+    }
+}
+
+fn duration_millis(duration: Duration) -> i64 {
+    duration.as_millis().try_into().unwrap_or(i64::MAX)
+}
@@ -19,12 +19,15 @@ pub(crate) struct TodoItem<C: AggregatableContribution> {
     pub contribution: C,
     /// The level the contribution of this TodoItem belongs to.
     pub level: usize,
+    /// The id of the peer the contribution was received from.
+    pub origin: usize,
 }
 
 impl<C: AggregatableContribution> fmt::Debug for TodoItem<C> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut dbg = f.debug_struct("TodoItem");
         dbg.field("level", &self.level);
+        dbg.field("origin", &self.origin);
         dbg.field("signers", &self.contribution.contributors());
         dbg.finish()
     }
@@ -106,10 +109,16 @@ where
         }
     }
 
-    pub fn add_contribution(&mut self, contribution: TProtocol::Contribution, level: usize) {
+    pub fn add_contribution(
+        &mut self,
+        contribution: TProtocol::Contribution,
+        level: usize,
+        origin: usize,
+    ) {
         self.list.insert(TodoItem {
             contribution,
             level,
+            origin,
         });
         self.wake();
     }
@@ -194,6 +203,7 @@ where
                 let aggregate_todo = TodoItem {
                     contribution: msg.aggregate,
                     level: msg.level as usize,
+                    origin: msg.origin as usize,
                 };
                 // Score the newly created TodoItem for the aggregate of the LevelUpdate
                 let score = aggregate_todo
@@ -225,6 +235,7 @@ where
                     let individual_todo = TodoItem {
                         contribution: individual,
                         level: msg.level as usize,
+                        origin: msg.origin as usize,
                     };
                     // Score the newly created TodoItem for the individual contribution of the LevelUpdate.
                     let score = individual_todo
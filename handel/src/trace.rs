@@ -0,0 +1,209 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::level::Level;
+
+/// The reason a level was started.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LevelStart {
+    /// The level timeout expired before the previous level was completed.
+    Timeout,
+    /// The previous level was completed.
+    PreviousLevelCompleted,
+    /// A contribution for the level was received.
+    Contribution,
+}
+
+/// The progress of a single level of an aggregation.
+#[derive(Clone, Debug, Default)]
+pub struct LevelTrace {
+    /// The number of peers on this level.
+    pub num_peers: usize,
+    /// The time after the start of the aggregation at which the level was started.
+    pub started_after: Option<Duration>,
+    /// Why the level was started.
+    pub start_reason: Option<LevelStart>,
+    /// The time after the start of the aggregation at which all contributions of the level were
+    /// received.
+    pub completed_after: Option<Duration>,
+    /// The number of valid contributions received for this level.
+    pub contributions_verified: usize,
+    /// The number of invalid contributions received for this level.
+    pub invalid_contributions: usize,
+    /// The number of level updates sent to peers on this level.
+    pub updates_sent: usize,
+}
+
+impl LevelTrace {
+    /// Returns the time it took to complete the level after it was started.
+    pub fn completion_time(&self) -> Option<Duration> {
+        Some(self.completed_after?.saturating_sub(self.started_after?))
+    }
+}
+
+/// The interaction with a single peer during an aggregation.
+#[derive(Clone, Debug, Default)]
+pub struct PeerTrace {
+    /// The level the peer is on.
+    pub level: usize,
+    /// The number of level updates sent to the peer.
+    pub updates_sent: usize,
+    /// The number of valid contributions received from the peer.
+    pub contributions_verified: usize,
+    /// The number of invalid contributions received from the peer.
+    pub invalid_contributions: usize,
+    /// The time after the start of the aggregation at which the first valid contribution of the
+    /// peer was received.
+    pub first_contribution_after: Option<Duration>,
+}
+
+/// A record of how an aggregation progressed.
+#[derive(Clone, Debug)]
+pub struct AggregationTrace {
+    started_at: Instant,
+    /// The time the aggregation ran for. Only set once the aggregation has ended.
+    pub duration: Option<Duration>,
+    /// The progress of every level, indexed by level id.
+    pub levels: Vec<LevelTrace>,
+    /// The peers that were contacted or sent contributions, indexed by their id.
+    pub peers: BTreeMap<usize, PeerTrace>,
+    /// The total number of contributions verified successfully.
+    pub signatures_verified: usize,
+    /// The total number of contributions that failed verification.
+    pub invalid_contributions: usize,
+    /// The number of contributors of the best aggregate produced so far.
+    pub best_contributors: usize,
+    /// The time after the start of the aggregation at which the best aggregate was produced.
+    pub best_aggregate_after: Option<Duration>,
+    /// The time after the start of the aggregation at which the aggregate contained all
+    /// contributors.
+    pub completed_after: Option<Duration>,
+}
+
+impl AggregationTrace {
+    pub(crate) fn new(levels: &[Level]) -> Self {
+        let mut peers = BTreeMap::new();
+        for level in levels {
+            for &peer_id in &level.peer_ids {
+                peers.insert(
+                    peer_id,
+                    PeerTrace {
+                        level: level.id,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        Self {
+            started_at: Instant::now(),
+            duration: None,
+            levels: levels
+                .iter()
+                .map(|level| LevelTrace {
+                    num_peers: level.num_peers(),
+                    ..Default::default()
+                })
+                .collect(),
+            peers,
+            signatures_verified: 0,
+            invalid_contributions: 0,
+            best_contributors: 0,
+            best_aggregate_after: None,
+            completed_after: None,
+        }
+    }
+
+    /// Returns the time that has passed since the start of the aggregation.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Returns the completion times of all completed levels, skipping levels without peers.
+    pub fn level_completion_times(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.levels
+            .iter()
+            .filter(|level| level.num_peers > 0)
+            .filter_map(LevelTrace::completion_time)
+    }
+
+    /// Returns the peers that were contacted but whose first valid contribution didn't arrive
+    /// within `threshold` after their level was started, including peers that never contributed.
+    pub fn slow_peers(&self, threshold: Duration) -> Vec<usize> {
+        self.peers
+            .iter()
+            .filter(|(_, peer)| peer.updates_sent > 0)
+            .filter(|(_, peer)| {
+                let started_after = self.levels[peer.level].started_after.unwrap_or_default();
+                match peer.first_contribution_after {
+                    Some(after) => after.saturating_sub(started_after) > threshold,
+                    None => true,
+                }
+            })
+            .map(|(&peer_id, _)| peer_id)
+            .collect()
+    }
+
+    pub(crate) fn level_started(&mut self, level_id: usize, reason: LevelStart) {
+        let elapsed = self.elapsed();
+        if let Some(level) = self.levels.get_mut(level_id) {
+            if level.started_after.is_none() {
+                level.started_after = Some(elapsed);
+                level.start_reason = Some(reason);
+            }
+        }
+    }
+
+    pub(crate) fn level_completed(&mut self, level_id: usize) {
+        let elapsed = self.elapsed();
+        if let Some(level) = self.levels.get_mut(level_id) {
+            level.completed_after.get_or_insert(elapsed);
+        }
+    }
+
+    pub(crate) fn update_sent(&mut self, level_id: usize, peer_id: usize) {
+        if let Some(level) = self.levels.get_mut(level_id) {
+            level.updates_sent += 1;
+        }
+        self.peers.entry(peer_id).or_default().updates_sent += 1;
+    }
+
+    pub(crate) fn contribution_verified(&mut self, level_id: usize, origin: usize) {
+        let elapsed = self.elapsed();
+        self.signatures_verified += 1;
+        if let Some(level) = self.levels.get_mut(level_id) {
+            level.contributions_verified += 1;
+        }
+        if let Some(peer) = self.peers.get_mut(&origin) {
+            peer.contributions_verified += 1;
+            peer.first_contribution_after.get_or_insert(elapsed);
+        }
+    }
+
+    pub(crate) fn contribution_invalid(&mut self, level_id: usize, origin: usize) {
+        self.invalid_contributions += 1;
+        if let Some(level) = self.levels.get_mut(level_id) {
+            level.invalid_contributions += 1;
+        }
+        if let Some(peer) = self.peers.get_mut(&origin) {
+            peer.invalid_contributions += 1;
+        }
+    }
+
+    pub(crate) fn aggregate_produced(&mut self, contributors: usize, complete: bool) {
+        let elapsed = self.elapsed();
+        if contributors > self.best_contributors {
+            self.best_contributors = contributors;
+            self.best_aggregate_after = Some(elapsed);
+        }
+        if complete {
+            self.completed_after.get_or_insert(elapsed);
+        }
+    }
+
+    pub(crate) fn finish(&mut self) {
+        let elapsed = self.elapsed();
+        self.duration.get_or_insert(elapsed);
+    }
+}
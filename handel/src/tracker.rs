@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+#[cfg(feature = "metrics")]
+use crate::metrics::HandelMetrics;
+use crate::{config::Config, trace::AggregationTrace};
+
+/// Collects the traces of consecutive aggregations of the same kind (e.g. skip block or macro
+/// block votes) and adapts the configuration of future aggregations to the observed level
+/// completion times.
+///
+/// The level timeout follows a moving average of the level completion times, scaled by
+/// [`TIMEOUT_FACTOR`](Self::TIMEOUT_FACTOR), and the update interval is scaled along with it.
+/// Both stay within [`MIN_SCALE`](Self::MIN_SCALE) and [`MAX_SCALE`](Self::MAX_SCALE) of the
+/// base configuration. They are never shortened below the base configuration, since a few fast
+/// aggregations must not make the next slow one time out prematurely.
+pub struct AggregationTracker {
+    /// Name of the kind of aggregations tracked, used for logging.
+    name: &'static str,
+    /// The configuration the adaptive values are derived from.
+    base: Config,
+    /// Moving average of the level completion times, if any were observed yet.
+    level_completion_estimate: Mutex<Option<Duration>>,
+    #[cfg(feature = "metrics")]
+    metrics: HandelMetrics,
+}
+
+impl AggregationTracker {
+    /// Weight of a new observation in the moving average of level completion times.
+    pub const SMOOTHING: f64 = 0.2;
+    /// Factor between the estimated level completion time and the level timeout.
+    pub const TIMEOUT_FACTOR: f64 = 2.0;
+    /// Lower bound of the adaptive values relative to the base configuration.
+    pub const MIN_SCALE: f64 = 1.0;
+    /// Upper bound of the adaptive values relative to the base configuration.
+    pub const MAX_SCALE: f64 = 4.0;
+
+    pub fn new(name: &'static str, base: Config) -> Self {
+        Self {
+            name,
+            base,
+            level_completion_estimate: Mutex::new(None),
+            #[cfg(feature = "metrics")]
+            metrics: HandelMetrics::default(),
+        }
+    }
+
+    /// Returns the configuration to use for the next aggregation.
+    pub fn config(&self) -> Config {
+        let estimate = match *self.level_completion_estimate.lock() {
+            Some(estimate) => estimate,
+            None => return self.base.clone(),
+        };
+
+        let base_timeout = self.base.timeout.as_secs_f64();
+        if base_timeout == 0.0 {
+            return self.base.clone();
+        }
+
+        let scale = (estimate.as_secs_f64() * Self::TIMEOUT_FACTOR / base_timeout)
+            .clamp(Self::MIN_SCALE, Self::MAX_SCALE);
+
+        Config {
+            timeout: self.base.timeout.mul_f64(scale),
+            update_interval: self.base.update_interval.mul_f64(scale),
+            ..self.base.clone()
+        }
+    }
+
+    /// Returns the moving average of the level completion times observed so far.
+    pub fn level_completion_estimate(&self) -> Option<Duration> {
+        *self.level_completion_estimate.lock()
+    }
+
+    /// Records the trace of a finished aggregation.
+    pub fn record(&self, trace: &AggregationTrace) {
+        let config = self.config();
+        let slow_peers = trace.slow_peers(config.timeout);
+
+        debug!(
+            aggregation = self.name,
+            duration = ?trace.duration,
+            completed_after = ?trace.completed_after,
+            best_contributors = trace.best_contributors,
+            signatures_verified = trace.signatures_verified,
+            invalid_contributions = trace.invalid_contributions,
+            levels_completed = trace.level_completion_times().count(),
+            ?slow_peers,
+            "Aggregation finished",
+        );
+        for (level_id, level) in trace.levels.iter().enumerate() {
+            trace!(
+                aggregation = self.name,
+                level_id,
+                num_peers = level.num_peers,
+                started_after = ?level.started_after,
+                start_reason = ?level.start_reason,
+                completed_after = ?level.completed_after,
+                contributions_verified = level.contributions_verified,
+                invalid_contributions = level.invalid_contributions,
+                updates_sent = level.updates_sent,
+                "Aggregation level",
+            );
+        }
+
+        #[cfg(feature = "metrics")]
+        self.metrics.note_aggregation(trace, slow_peers.len());
+
+        // Update the estimate with the average completion time of the levels of this aggregation.
+        let completion_times: Vec<Duration> = trace.level_completion_times().collect();
+        if completion_times.is_empty() {
+            return;
+        }
+        let average = completion_times.iter().sum::<Duration>() / completion_times.len() as u32;
+
+        {
+            let mut estimate = self.level_completion_estimate.lock();
+            *estimate = Some(match *estimate {
+                Some(estimate) => {
+                    estimate.mul_f64(1.0 - Self::SMOOTHING) + average.mul_f64(Self::SMOOTHING)
+                }
+                None => average,
+            });
+        }
+
+        #[cfg(feature = "metrics")]
+        self.metrics.note_config(&self.config());
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &HandelMetrics {
+        &self.metrics
+    }
+
+    /// Returns the name of the kind of aggregations tracked.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nimiq_test_log::test;
+
+    use super::*;
+    use crate::level::Level;
+
+    fn trace_with_level_completion(completion_time: Duration) -> AggregationTrace {
+        let levels = vec![
+            Level::new(0, vec![0], 1),
+            Level::new(1, vec![1], 2),
+            Level::new(2, vec![2, 3], 4),
+        ];
+        let mut trace = AggregationTrace::new(&levels);
+        for level in trace.levels.iter_mut().skip(1) {
+            level.started_after = Some(Duration::ZERO);
+            level.completed_after = Some(completion_time);
+        }
+        trace.finish();
+        trace
+    }
+
+    #[test]
+    fn it_adapts_timeouts_to_level_completion_times() {
+        let base = Config::default();
+        let tracker = AggregationTracker::new("test", base.clone());
+
+        // Without any observations, the base config is used.
+        assert_eq!(tracker.config().timeout, base.timeout);
+        assert_eq!(tracker.config().update_interval, base.update_interval);
+
+        // Slow levels increase the timeouts, but not beyond the upper bound.
+        tracker.record(&trace_with_level_completion(Duration::from_secs(60)));
+        assert_eq!(
+            tracker.level_completion_estimate(),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            tracker.config().timeout,
+            base.timeout.mul_f64(AggregationTracker::MAX_SCALE)
+        );
+        assert_eq!(
+            tracker.config().update_interval,
+            base.update_interval.mul_f64(AggregationTracker::MAX_SCALE)
+        );
+
+        // The estimate follows the moving average of the observed completion times.
+        let tracker = AggregationTracker::new("test", base.clone());
+        tracker.record(&trace_with_level_completion(base.timeout));
+        tracker.record(&trace_with_level_completion(base.timeout / 2));
+        let estimate = tracker.level_completion_estimate().unwrap();
+        assert!(estimate < base.timeout && estimate > base.timeout / 2);
+        assert!(
+            tracker.config().timeout < base.timeout.mul_f64(AggregationTracker::TIMEOUT_FACTOR)
+        );
+
+        // Fast levels never shorten the timeouts below the base config.
+        let tracker = AggregationTracker::new("test", base.clone());
+        tracker.record(&trace_with_level_completion(Duration::from_millis(1)));
+        assert_eq!(tracker.config().timeout, base.timeout);
+        assert_eq!(tracker.config().update_interval, base.update_interval);
+    }
+
+    #[test]
+    fn it_finds_slow_peers() {
+        let levels = vec![Level::new(0, vec![0], 1), Level::new(1, vec![1, 2, 3], 4)];
+        let mut trace = AggregationTrace::new(&levels);
+        trace.levels[1].started_after = Some(Duration::from_millis(100));
+
+        // Peer 1 responds quickly, peer 2 late and peer 3 never.
+        for peer_id in 1..=3 {
+            trace.update_sent(1, peer_id);
+        }
+        trace.peers.get_mut(&1).unwrap().first_contribution_after =
+            Some(Duration::from_millis(200));
+        trace.peers.get_mut(&2).unwrap().first_contribution_after = Some(Duration::from_secs(2));
+
+        assert_eq!(trace.slow_peers(Duration::from_secs(1)), vec![2, 3]);
+        assert_eq!(trace.levels[1].updates_sent, 3);
+    }
+}
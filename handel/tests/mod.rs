@@ -314,6 +314,14 @@ async fn it_can_aggregate() {
         }
     }
 
+    // The trace must reflect the completed aggregation.
+    let trace = aggregation.trace();
+    assert_eq!(trace.best_contributors, contributor_num + 1);
+    assert!(trace.completed_after.is_some());
+    assert!(trace.signatures_verified > 0);
+    assert_eq!(trace.invalid_contributions, 0);
+    assert!(trace.levels[0].started_after.is_some());
+
     drop(aggregation);
     net.disconnect();
 
@@ -2,7 +2,7 @@ use argon2::Config;
 pub use argon2::{Error as Argon2Error, Variant as Argon2Variant};
 
 // Taken from https://github.com/nimiq/core-js/blob/c98d56b2dd967d9a9c9a97fe4c54bfaac743aa0c/src/main/generic/utils/crypto/CryptoWorkerImpl.js#L146
-const MEMORY_COST: u32 = 512;
+pub const MEMORY_COST: u32 = 512;
 
 pub fn compute_argon2_kdf(
     password: &[u8],
@@ -10,11 +10,30 @@ pub fn compute_argon2_kdf(
     iterations: u32,
     derived_key_length: usize,
     variant: Argon2Variant,
+) -> Result<Vec<u8>, Argon2Error> {
+    compute_argon2_kdf_with_memory_cost(
+        password,
+        salt,
+        iterations,
+        MEMORY_COST,
+        derived_key_length,
+        variant,
+    )
+}
+
+/// Same as `compute_argon2_kdf`, but with a custom memory cost in KiB.
+pub fn compute_argon2_kdf_with_memory_cost(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    memory_cost: u32,
+    derived_key_length: usize,
+    variant: Argon2Variant,
 ) -> Result<Vec<u8>, Argon2Error> {
     let config = Config {
         time_cost: iterations,
         hash_length: derived_key_length as u32,
-        mem_cost: MEMORY_COST,
+        mem_cost: memory_cost,
         variant,
         ..Default::default()
     };
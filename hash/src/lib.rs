@@ -135,6 +135,12 @@ impl Blake2bHasher {
     pub fn new() -> Self {
         Blake2bHasher(Blake2b::new(BLAKE2B_LENGTH))
     }
+
+    /// Creates a keyed hasher that can be used as a message authentication code.
+    /// The key must not be longer than 64 bytes.
+    pub fn with_key(key: &[u8]) -> Self {
+        Blake2bHasher(Blake2b::with_key(BLAKE2B_LENGTH, key))
+    }
 }
 
 impl Default for Blake2bHasher {
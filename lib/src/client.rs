@@ -2,7 +2,7 @@ use std::{fs, num::NonZeroU8, sync::Arc};
 
 use nimiq_block::Block;
 #[cfg(feature = "full-consensus")]
-use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_blockchain::{Blockchain, BlockchainConfig, PruningPolicy};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_bls::cache::PublicKeyCache;
@@ -11,9 +11,13 @@ use nimiq_consensus::{
     Consensus as AbstractConsensus, ConsensusProxy as AbstractConsensusProxy,
     Error::BlockchainError,
 };
+#[cfg(feature = "database-storage")]
+use nimiq_database::DatabaseProxy;
 #[cfg(feature = "zkp-prover")]
 use nimiq_genesis::NetworkId;
 use nimiq_genesis::NetworkInfo;
+#[cfg(feature = "validator")]
+use nimiq_handel::tracker::AggregationTracker;
 use nimiq_light_blockchain::LightBlockchain;
 #[cfg(feature = "validator")]
 use nimiq_mempool::mempool::Mempool;
@@ -45,6 +49,11 @@ use nimiq_zkp_component::zkp_component::{
     ZKPComponent as AbstractZKPComponent, ZKPComponentProxy as AbstractZKPComponentProxy,
 };
 #[cfg(feature = "zkp-prover")]
+use nimiq_zkp_component::{
+    remote_prover::{RemoteProver, RemoteProverConfig},
+    zkp_prover::ProverBackend,
+};
+#[cfg(feature = "zkp-prover")]
 use nimiq_zkp_primitives::NanoZKPError;
 use parking_lot::{Mutex, RwLock};
 #[cfg(feature = "zkp-prover")]
@@ -54,7 +63,7 @@ use rand_chacha::ChaCha20Rng;
 use rustls_pemfile::Item;
 
 use crate::{
-    config::config::{ClientConfig, SyncMode},
+    config::config::{ClientConfig, ConsensusConfig, SyncMode},
     error::Error,
 };
 
@@ -94,11 +103,17 @@ pub(crate) struct ClientInner {
     wallet_store: Arc<WalletStore>,
 
     zkp_component: ZKPComponentProxy,
+
+    /// The database holding the consensus state and the wallets.
+    #[cfg(feature = "database-storage")]
+    environment: DatabaseProxy,
 }
 
 /// This function is used to generate the services flags (provided, needed) based upon the configured sync mode
-pub fn generate_service_flags(sync_mode: SyncMode) -> (Services, Services) {
-    let provided_services = match sync_mode {
+/// and pruning settings
+pub fn generate_service_flags(consensus: &ConsensusConfig) -> (Services, Services) {
+    let sync_mode = consensus.sync_mode;
+    let mut provided_services = match sync_mode {
         // Services provided by history nodes
         crate::config::config::SyncMode::History => {
             log::info!("Client configured as a history node");
@@ -114,8 +129,27 @@ pub fn generate_service_flags(sync_mode: SyncMode) -> (Services, Services) {
             log::info!("Client configured as a light node");
             Services::provided(NodeType::Light)
         }
+        // Services provided by archive nodes
+        crate::config::config::SyncMode::Archive => {
+            log::info!("Client configured as an archive node");
+            Services::provided(NodeType::Archive)
+        }
     };
 
+    // Archive nodes only provide the blocks of all past batches once they were back-filled, the
+    // service is added by consensus then.
+    provided_services.remove(Services::ARCHIVE);
+
+    // Nodes that prune micro block bodies within an epoch can't serve the full blocks of the epoch.
+    if consensus.prunes_epoch_bodies() {
+        log::info!("Micro block bodies are pruned, not providing full blocks");
+        provided_services.remove(Services::FULL_BLOCKS);
+    }
+    // Nodes that prune the transaction history don't provide a (complete) transaction index.
+    if consensus.prunes_history() {
+        provided_services.remove(Services::HISTORY | Services::TRANSACTION_INDEX);
+    }
+
     let required_services = match sync_mode {
         // Services required by history nodes
         crate::config::config::SyncMode::History => Services::required(NodeType::History),
@@ -123,10 +157,25 @@ pub fn generate_service_flags(sync_mode: SyncMode) -> (Services, Services) {
         crate::config::config::SyncMode::Full => Services::required(NodeType::Full),
         // Services required by light nodes
         crate::config::config::SyncMode::Light => Services::required(NodeType::Light),
+        // Services required by archive nodes
+        crate::config::config::SyncMode::Archive => Services::required(NodeType::Archive),
     };
     (provided_services, required_services)
 }
 
+/// Returns the backend generating the zk proofs: the remote prover service if one is configured
+/// and a local prover process otherwise.
+#[cfg(feature = "zkp-prover")]
+fn prover_backend(remote_prover: Option<RemoteProverConfig>) -> ProverBackend {
+    match remote_prover {
+        Some(config) => {
+            log::info!(address = %config.address, "Using remote prover service");
+            ProverBackend::Remote(Arc::new(RemoteProver::new(config)))
+        }
+        None => ProverBackend::Process { prover_path: None },
+    }
+}
+
 impl ClientInner {
     async fn from_config(
         config: ClientConfig,
@@ -210,8 +259,7 @@ impl ClientInner {
             identity_keypair.public().to_peer_id().to_base58()
         );
 
-        let (mut provided_services, required_services) =
-            generate_service_flags(config.consensus.sync_mode);
+        let (mut provided_services, required_services) = generate_service_flags(&config.consensus);
 
         // We update the services flags depending on our validator configuration
         #[cfg(feature = "validator")]
@@ -335,12 +383,24 @@ impl ClientInner {
         #[cfg(feature = "full-consensus")]
         let mut blockchain_config = BlockchainConfig {
             max_epochs_stored: config.consensus.max_epochs_stored,
+            pruning: PruningPolicy {
+                micro_body_batches: config.consensus.micro_body_batches_stored,
+                accounts_diff_batches: config.consensus.accounts_diff_batches_stored,
+                history_watched_addresses: config
+                    .consensus
+                    .history_watched_addresses
+                    .iter()
+                    .cloned()
+                    .collect(),
+            },
+            revert_info_batches: config.consensus.revert_info_batches_stored,
             ..Default::default()
         };
 
         #[cfg(feature = "database-storage")]
-        let zkp_storage: Option<Box<dyn ProofStore>> =
-            Some(Box::new(DBProofStore::new(environment.clone())));
+        let zkp_storage: Option<Box<dyn ProofStore>> = Some(Box::new(
+            DBProofStore::with_retention(environment.clone(), config.zkp.proof_retention),
+        ));
         #[cfg(not(feature = "database-storage"))]
         let zkp_storage = None;
 
@@ -353,9 +413,15 @@ impl ClientInner {
             SyncMode::Full => {
                 panic!("Can't build a full node without the full-consensus feature enabled")
             }
+            #[cfg(not(feature = "full-consensus"))]
+            SyncMode::Archive => {
+                panic!("Can't build an archive node without the full-consensus feature enabled")
+            }
             #[cfg(feature = "full-consensus")]
-            SyncMode::History => {
+            SyncMode::History | SyncMode::Archive => {
+                // Archive nodes sync like history nodes and back-fill the blocks of past epochs.
                 blockchain_config.keep_history = true;
+                blockchain_config.archive = config.consensus.sync_mode == SyncMode::Archive;
                 let blockchain = match Blockchain::new(
                     environment.clone(),
                     blockchain_config,
@@ -371,12 +437,12 @@ impl ClientInner {
                 let blockchain_proxy = BlockchainProxy::from(&blockchain);
                 #[cfg(feature = "zkp-prover")]
                 let zkp_component = if config.zkp.prover_active {
-                    ZKPComponent::with_prover(
+                    ZKPComponent::with_prover_backend(
                         blockchain_proxy.clone(),
                         Arc::clone(&network),
                         executor.clone(),
                         config.zkp.prover_active,
-                        None,
+                        prover_backend(config.zkp.remote_prover),
                         config.zkp.prover_keys_path,
                         zkp_storage,
                     )
@@ -411,7 +477,7 @@ impl ClientInner {
             SyncMode::Full => {
                 // TODO this is a temporary fix for the issue of full nodes taking forever pruning the epoch history.
                 // should be set to false when the light history store is implemented.
-                blockchain_config.keep_history = true;
+                blockchain_config.keep_history = !config.consensus.prunes_history();
                 let blockchain = match Blockchain::new(
                     environment.clone(),
                     blockchain_config,
@@ -427,12 +493,12 @@ impl ClientInner {
                 let blockchain_proxy = BlockchainProxy::from(&blockchain);
                 #[cfg(feature = "zkp-prover")]
                 let zkp_component = if config.zkp.prover_active {
-                    ZKPComponent::with_prover(
+                    ZKPComponent::with_prover_backend(
                         blockchain_proxy.clone(),
                         Arc::clone(&network),
                         executor.clone(),
                         config.zkp.prover_active,
-                        None,
+                        prover_backend(config.zkp.remote_prover),
                         config.zkp.prover_keys_path,
                         zkp_storage,
                     )
@@ -536,6 +602,7 @@ impl ClientInner {
                         voting_key,
                         fee_key,
                         config.mempool,
+                        validator_config.block_building,
                     );
 
                     // Use the validator's mempool as TransactionVerificationCache in the blockchain.
@@ -565,6 +632,8 @@ impl ClientInner {
                 #[cfg(feature = "wallet")]
                 wallet_store,
                 zkp_component: zkp_component.proxy(),
+                #[cfg(feature = "database-storage")]
+                environment,
             }),
             consensus: Some(consensus),
             #[cfg(feature = "validator")]
@@ -656,6 +725,15 @@ impl Client {
             .map(|validator| Arc::clone(&validator.mempool))
     }
 
+    #[cfg(feature = "validator")]
+    /// Returns the trackers of the validator's signature aggregations, if there is a validator.
+    pub fn aggregation_trackers(&self) -> Vec<Arc<AggregationTracker>> {
+        self.validator
+            .as_ref()
+            .map(|validator| validator.aggregation_trackers())
+            .unwrap_or_default()
+    }
+
     /// Returns a reference to the *ZKP Component* or none.
     pub fn take_zkp_component(&mut self) -> Option<ZKPComponent> {
         self.zkp_component.take()
@@ -665,4 +743,10 @@ impl Client {
     pub fn zkp_component(&self) -> ZKPComponentProxy {
         self.inner.zkp_component.clone()
     }
+
+    /// Returns a reference to the database.
+    #[cfg(feature = "database-storage")]
+    pub fn environment(&self) -> DatabaseProxy {
+        self.inner.environment.clone()
+    }
 }
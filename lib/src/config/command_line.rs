@@ -67,6 +67,16 @@ pub struct CommandLine {
     /// Internally used flag to start a zero-knowledge prover process.
     #[clap(long, action)]
     pub prove: bool,
+
+    /// Start a remote prover service listening on the given address instead of a node.
+    /// Nodes authenticate with the `auth_key` of the `[zkp.remote_prover]` section.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client --prover-server 0.0.0.0:8650`
+    ///
+    #[clap(long, value_name = "ADDRESS")]
+    pub prover_server: Option<String>,
 }
 
 impl CommandLine {
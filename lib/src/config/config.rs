@@ -2,6 +2,8 @@
 use std::net::IpAddr;
 #[cfg(feature = "metrics-server")]
 use std::net::SocketAddr;
+#[cfg(feature = "zkp-prover")]
+use std::time::Duration;
 use std::{
     fmt::Debug,
     num::NonZeroU8,
@@ -12,12 +14,17 @@ use std::{
 use derive_builder::Builder;
 #[cfg(feature = "validator")]
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
+#[cfg(feature = "database-redb")]
+use nimiq_database::redb::RedbDatabase;
 #[cfg(feature = "database-storage")]
 use nimiq_database::{mdbx::MdbxDatabase, volatile::VolatileDatabase, DatabaseProxy};
+use nimiq_keys::Address;
 #[cfg(feature = "validator")]
-use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_keys::{KeyPair, PrivateKey};
 #[cfg(feature = "nimiq-mempool")]
-use nimiq_mempool::{config::MempoolConfig, filter::MempoolRules};
+use nimiq_mempool::{
+    block_building::BlockBuildingConfig, config::MempoolConfig, filter::MempoolRules,
+};
 use nimiq_network_interface::Multiaddr;
 use nimiq_network_libp2p::{Keypair as IdentityKeypair, Libp2pKeyPair};
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
@@ -26,6 +33,8 @@ use nimiq_serde::Deserialize;
 use nimiq_utils::key_rng::SecureGenerate;
 use nimiq_utils::{file_store::FileStore, Sensitive};
 use nimiq_zkp_circuits::DEFAULT_KEYS_PATH;
+#[cfg(feature = "zkp-prover")]
+use nimiq_zkp_component::remote_prover::RemoteProverConfig;
 use strum_macros::Display;
 
 #[cfg(feature = "database-storage")]
@@ -58,6 +67,8 @@ pub enum SyncMode {
     Full,
     /// Light nodes: They use LightMacroSync + BlockLiveSync
     Light,
+    /// Archive nodes: Like history nodes, but they keep and back-fill all blocks of past epochs
+    Archive,
 }
 
 impl Default for SyncMode {
@@ -79,6 +90,40 @@ pub struct ConsensusConfig {
     #[builder(default = "1")]
     /// Maximum number of epochs that are stored in the client
     pub max_epochs_stored: u32,
+    #[builder(default)]
+    /// Number of finalized batches for which micro block bodies are kept. `None` keeps all bodies.
+    pub micro_body_batches_stored: Option<u32>,
+    #[builder(default)]
+    /// Number of finalized batches for which accounts trie diffs are kept. `None` keeps all diffs
+    /// of the stored epochs.
+    pub accounts_diff_batches_stored: Option<u32>,
+    #[builder(default)]
+    /// Number of finalized batches of the current epoch that can be reverted with the admin
+    /// `revertChain` method
+    pub revert_info_batches_stored: u32,
+    #[builder(default)]
+    /// Whether the transaction history of finalized epochs is pruned. Only applies to full nodes.
+    pub prune_history: bool,
+    #[builder(default)]
+    /// Addresses whose transactions are kept when the transaction history is pruned. The history
+    /// tree of a pruned epoch is removed nonetheless, so the kept transactions can't be proven.
+    pub history_watched_addresses: Vec<Address>,
+}
+
+impl ConsensusConfig {
+    /// Returns whether micro block bodies of the current epoch may be pruned, in which case the
+    /// client can't serve the full blocks of the epoch to its peers. Archive nodes never prune.
+    pub fn prunes_epoch_bodies(&self) -> bool {
+        self.sync_mode != SyncMode::Archive
+            && self
+                .micro_body_batches_stored
+                .is_some_and(|batches| batches < Policy::batches_per_epoch() as u32)
+    }
+
+    /// Returns whether the transaction history of finalized epochs is pruned.
+    pub fn prunes_history(&self) -> bool {
+        self.prune_history && self.sync_mode == SyncMode::Full
+    }
 }
 
 impl Default for ConsensusConfig {
@@ -87,6 +132,11 @@ impl Default for ConsensusConfig {
             sync_mode: SyncMode::default(),
             min_peers: 3,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
+            micro_body_batches_stored: None,
+            accounts_diff_batches_stored: None,
+            revert_info_batches_stored: 0,
+            prune_history: false,
+            history_watched_addresses: vec![],
         }
     }
 }
@@ -241,6 +291,16 @@ impl Default for FileStorageConfig {
     }
 }
 
+/// Storage engine of the persistent database.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Display)]
+pub enum DatabaseBackend {
+    /// Memory-mapped MDBX database
+    #[default]
+    Mdbx,
+    /// Pure Rust redb database. Requires the `database-redb` feature.
+    Redb,
+}
+
 /// Configuration options for the database
 #[cfg(feature = "database-storage")]
 #[derive(Debug, Clone, Builder, Eq, PartialEq)]
@@ -260,6 +320,10 @@ pub struct DatabaseConfig {
     /// Recommended: 600
     #[builder(default = "600")]
     max_readers: u32,
+
+    /// Storage engine of the persistent database. Default: MDBX
+    #[builder(default)]
+    backend: DatabaseBackend,
 }
 #[cfg(feature = "database-storage")]
 impl Default for DatabaseConfig {
@@ -267,8 +331,9 @@ impl Default for DatabaseConfig {
         Self {
             // 1 TB
             size: 1024 * 1024 * 1024 * 1024,
-            max_dbs: 20,
+            max_dbs: 32,
             max_readers: 600,
+            backend: DatabaseBackend::default(),
         }
     }
 }
@@ -282,6 +347,10 @@ impl From<Option<DatabaseSettings>> for DatabaseConfig {
                 size: db_settings.size.unwrap_or(default.size),
                 max_dbs: db_settings.max_dbs.unwrap_or(default.max_dbs),
                 max_readers: db_settings.max_readers.unwrap_or(default.max_readers),
+                backend: db_settings
+                    .backend
+                    .map(Into::into)
+                    .unwrap_or(default.backend),
             }
         } else {
             default
@@ -347,12 +416,22 @@ impl StorageConfig {
                         ))
                     })?
                     .to_string();
-                MdbxDatabase::new_with_max_readers(
-                    db_path,
-                    db_config.size,
-                    db_config.max_dbs,
-                    db_config.max_readers,
-                )?
+                match db_config.backend {
+                    DatabaseBackend::Mdbx => MdbxDatabase::new_with_max_readers(
+                        db_path,
+                        db_config.size,
+                        db_config.max_dbs,
+                        db_config.max_readers,
+                    )?,
+                    #[cfg(feature = "database-redb")]
+                    DatabaseBackend::Redb => RedbDatabase::new(db_path)?,
+                    #[cfg(not(feature = "database-redb"))]
+                    DatabaseBackend::Redb => {
+                        return Err(Error::config_error(
+                            "The redb database backend requires the `database-redb` feature",
+                        ))
+                    }
+                }
             }
         })
     }
@@ -495,6 +574,9 @@ pub struct ValidatorConfig {
 
     /// Config if the validator automatically reactivates itself.
     pub automatic_reactivate: bool,
+
+    /// How the validator fills its micro blocks with transactions.
+    pub block_building: BlockBuildingConfig,
 }
 
 /// Credentials for JSON RPC server, metrics server or websocket RPC server
@@ -551,6 +633,14 @@ pub struct RpcServerConfig {
     #[builder(setter(strip_option))]
     pub allowed_methods: Option<Vec<String>>,
 
+    /// Enable the administrative RPC methods, e.g. `backupDatabase` which writes to the node's
+    /// file system and `revertChain`.
+    ///
+    /// Default: `false`
+    ///
+    #[builder(default)]
+    pub admin: bool,
+
     /// If specified, require HTTP basic auth with these credentials
     #[builder(setter(strip_option))]
     pub credentials: Option<Credentials>,
@@ -753,6 +843,16 @@ impl ClientConfigBuilder {
         if let Some(min_peers) = config_file.consensus.min_peers {
             consensus.min_peers = min_peers;
         }
+        consensus.micro_body_batches_stored = config_file.consensus.micro_body_batches_stored;
+        consensus.accounts_diff_batches_stored = config_file.consensus.accounts_diff_batches_stored;
+        consensus.revert_info_batches_stored = config_file.consensus.revert_info_batches_stored;
+        consensus.prune_history = config_file.consensus.prune_history;
+        consensus.history_watched_addresses = config_file
+            .consensus
+            .history_watched_addresses
+            .iter()
+            .map(|address| Address::from_any_str(address))
+            .collect::<Result<_, _>>()?;
         self.consensus(consensus);
 
         // Configure network
@@ -778,6 +878,7 @@ impl ClientConfigBuilder {
             self.validator(ValidatorConfig {
                 validator_address: Address::from_any_str(&validator_config.validator_address)?,
                 automatic_reactivate: validator_config.automatic_reactivate,
+                block_building: validator_config.block_building.clone().try_into()?,
             });
 
             if let Some(key_path) = &validator_config.voting_key_file {
@@ -812,9 +913,31 @@ impl ClientConfigBuilder {
                 prover_keys_path = PathBuf::from(zkp_path);
             }
 
+            #[cfg(feature = "zkp-prover")]
+            let remote_prover = zkp_settings
+                .remote_prover
+                .as_ref()
+                .map(|settings| {
+                    let auth_key = hex::decode(&settings.auth_key).map_err(|e| {
+                        Error::config_error(format!("Invalid remote prover auth key: {e}"))
+                    })?;
+                    let mut config = RemoteProverConfig::new(settings.address.clone(), auth_key);
+                    if let Some(max_retries) = settings.max_retries {
+                        config.max_retries = max_retries;
+                    }
+                    if let Some(retry_delay) = settings.retry_delay {
+                        config.retry_delay = Duration::from_secs(retry_delay);
+                    }
+                    Ok::<_, Error>(config)
+                })
+                .transpose()?;
+
             self.zkp = Some(ZKPConfig {
                 prover_active: zkp_settings.prover_active,
                 prover_keys_path,
+                proof_retention: zkp_settings.proof_retention,
+                #[cfg(feature = "zkp-prover")]
+                remote_prover,
             });
         }
 
@@ -864,6 +987,7 @@ impl ClientConfigBuilder {
                     corsdomain: Some(rpc_config.corsdomain.clone()),
                     allow_ips,
                     allowed_methods: Some(rpc_config.methods.clone()),
+                    admin: rpc_config.admin,
                     credentials,
                 }));
             }
@@ -933,6 +1057,16 @@ pub struct ZKPConfig {
 
     /// Prover keys path for the zkp prover.
     pub prover_keys_path: PathBuf,
+
+    /// Number of most recent election blocks for which the zk proof is kept in storage.
+    /// If `None`, the proofs of all election blocks are kept.
+    #[builder(default)]
+    pub proof_retention: Option<u32>,
+
+    /// Remote prover service generating the proofs instead of a local prover process.
+    #[cfg(feature = "zkp-prover")]
+    #[builder(default)]
+    pub remote_prover: Option<RemoteProverConfig>,
 }
 
 impl Default for ZKPConfig {
@@ -940,6 +1074,9 @@ impl Default for ZKPConfig {
         Self {
             prover_active: false,
             prover_keys_path: PathBuf::from(DEFAULT_KEYS_PATH),
+            proof_retention: None,
+            #[cfg(feature = "zkp-prover")]
+            remote_prover: None,
         }
     }
 }
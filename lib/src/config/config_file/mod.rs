@@ -5,6 +5,7 @@ use std::{
 use log::level_filters::LevelFilter;
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::{
+    block_building::{BlockBuildingConfig, BlockBuildingPolicyConfig},
     config::MempoolConfig,
     filter::{MempoolFilter, MempoolRules},
     mempool::Mempool,
@@ -186,6 +187,20 @@ pub struct ConsensusSettings {
     pub network: Option<NetworkId>,
     /// Minimum number of peers necessary to reach consensus
     pub min_peers: Option<usize>,
+    /// Number of finalized batches for which micro block bodies are kept (all if not set)
+    pub micro_body_batches_stored: Option<u32>,
+    /// Number of finalized batches for which accounts trie diffs are kept (all if not set)
+    pub accounts_diff_batches_stored: Option<u32>,
+    #[serde(default)]
+    /// Number of finalized batches of the current epoch that can be reverted (none if not set)
+    pub revert_info_batches_stored: u32,
+    #[serde(default)]
+    /// Prune the transaction history of finalized epochs (full nodes only)
+    pub prune_history: bool,
+    #[serde(default)]
+    /// Addresses whose transactions are kept when the transaction history is pruned (without
+    /// inclusion proofs)
+    pub history_watched_addresses: Vec<String>,
 }
 
 #[derive(Clone, Copy, Deserialize, Debug, Default, Eq, PartialEq)]
@@ -199,6 +214,8 @@ pub enum SyncMode {
     Full,
     /// Light nodes use LightMacroSync + BlockLiveSync to reach consensus
     Light,
+    /// Archive nodes sync like History nodes and additionally keep all blocks of past epochs
+    Archive,
 }
 
 #[derive(Debug, Error)]
@@ -213,6 +230,7 @@ impl FromStr for SyncMode {
             "history" => Self::History,
             "full" => Self::Full,
             "light" => Self::Light,
+            "archive" => Self::Archive,
             _ => return Err(SyncModeParseError(s.to_string())),
         })
     }
@@ -224,6 +242,7 @@ impl From<SyncMode> for config::SyncMode {
             SyncMode::History => Self::History,
             SyncMode::Full => Self::Full,
             SyncMode::Light => Self::Light,
+            SyncMode::Archive => Self::Archive,
         }
     }
 }
@@ -241,6 +260,8 @@ pub struct RpcServerSettings {
     pub allowip: Vec<String>,
     #[serde(default)]
     pub methods: Vec<String>,
+    #[serde(default)]
+    pub admin: bool,
     pub username: Option<String>,
     pub password: Option<Sensitive<String>>,
 }
@@ -318,6 +339,43 @@ pub struct DatabaseSettings {
     pub size: Option<usize>,
     pub max_dbs: Option<u32>,
     pub max_readers: Option<u32>,
+    pub backend: Option<DatabaseBackend>,
+}
+
+#[derive(Clone, Copy, Deserialize, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+/// Storage engine used for the persistent database
+pub enum DatabaseBackend {
+    #[default]
+    /// Memory-mapped MDBX database
+    Mdbx,
+    /// Pure Rust redb database (requires the `database-redb` feature)
+    Redb,
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid database backend: {0}")]
+pub struct DatabaseBackendParseError(String);
+
+impl FromStr for DatabaseBackend {
+    type Err = DatabaseBackendParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "mdbx" => Self::Mdbx,
+            "redb" => Self::Redb,
+            _ => return Err(DatabaseBackendParseError(s.to_string())),
+        })
+    }
+}
+
+impl From<DatabaseBackend> for config::DatabaseBackend {
+    fn from(backend: DatabaseBackend) -> Self {
+        match backend {
+            DatabaseBackend::Mdbx => Self::Mdbx,
+            DatabaseBackend::Redb => Self::Redb,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -416,6 +474,62 @@ pub struct ValidatorSettings {
     pub fee_key: Option<Sensitive<String>>,
     #[serde(default)]
     pub automatic_reactivate: bool,
+    #[serde(default)]
+    pub block_building: BlockBuildingSettings,
+}
+
+#[derive(Clone, Copy, Deserialize, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+/// Policy used by a validator to select the transactions of its micro blocks
+pub enum BlockBuildingPolicy {
+    #[default]
+    /// Include the transactions with the highest fee per byte first
+    FeePriority,
+    /// Include transactions by arrival within fee tiers
+    ArrivalOrder,
+    /// Include transactions by fee per byte, but limit the number of transactions per sender
+    SenderCap,
+}
+
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BlockBuildingSettings {
+    #[serde(default)]
+    pub policy: BlockBuildingPolicy,
+    #[serde(default)]
+    pub fee_tiers: Vec<u64>,
+    pub max_transactions_per_sender: Option<usize>,
+    pub reserved_control_bytes: Option<usize>,
+    pub reserved_signaling_bytes: Option<usize>,
+}
+
+#[cfg(feature = "nimiq-mempool")]
+impl TryFrom<BlockBuildingSettings> for BlockBuildingConfig {
+    type Error = Error;
+
+    fn try_from(settings: BlockBuildingSettings) -> Result<Self, Self::Error> {
+        let policy = match settings.policy {
+            BlockBuildingPolicy::FeePriority => BlockBuildingPolicyConfig::FeePriority,
+            BlockBuildingPolicy::ArrivalOrder => BlockBuildingPolicyConfig::ArrivalOrder {
+                fee_tiers: settings.fee_tiers,
+            },
+            BlockBuildingPolicy::SenderCap => BlockBuildingPolicyConfig::SenderCap {
+                max_transactions_per_sender: settings.max_transactions_per_sender.ok_or_else(
+                    || {
+                        Error::config_error(
+                            "The sender-cap block building policy requires max_transactions_per_sender",
+                        )
+                    },
+                )?,
+            },
+        };
+
+        Ok(Self {
+            policy,
+            reserved_control_bytes: settings.reserved_control_bytes,
+            reserved_signaling_bytes: settings.reserved_signaling_bytes,
+        })
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Default)]
@@ -425,4 +539,19 @@ pub struct ZKPSettings {
     pub prover_active: bool,
     #[serde(default)]
     pub prover_keys_path: Option<String>,
+    #[serde(default)]
+    pub proof_retention: Option<u32>,
+    #[serde(default)]
+    pub remote_prover: Option<RemoteProverSettings>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteProverSettings {
+    pub address: String,
+    /// Hex encoded key shared with the remote prover service
+    pub auth_key: String,
+    pub max_retries: Option<u32>,
+    /// Delay before retrying a failed proof request, in seconds
+    pub retry_delay: Option<u64>,
 }
@@ -2,6 +2,8 @@ use std::{net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
+#[cfg(feature = "nimiq-handel")]
+use nimiq_handel::tracker::AggregationTracker;
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::mempool::Mempool;
 pub use nimiq_metrics_server::NimiqTaskMonitor;
@@ -11,6 +13,7 @@ pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
     blockchain_proxy: BlockchainProxy,
     #[cfg(feature = "nimiq-mempool")] mempool: Option<Arc<Mempool>>,
+    #[cfg(feature = "nimiq-handel")] aggregation_trackers: Vec<Arc<AggregationTracker>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
     task_monitors: &[NimiqTaskMonitor],
@@ -21,6 +24,8 @@ pub fn start_metrics_server<TNetwork: Network>(
         addr,
         blockchain_proxy,
         mempool,
+        #[cfg(feature = "nimiq-handel")]
+        aggregation_trackers,
         consensus_proxy,
         network,
         task_monitors,
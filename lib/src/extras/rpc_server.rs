@@ -2,7 +2,7 @@ use std::{collections::HashSet, iter::FromIterator, sync::Arc};
 
 use nimiq_jsonrpc_core::Credentials;
 use nimiq_jsonrpc_server::{AllowListDispatcher, Config, ModularDispatcher, Server as _Server};
-use nimiq_rpc_server::dispatchers::*;
+use nimiq_rpc_server::{dispatchers::*, scheduler::TransactionScheduler};
 use nimiq_wallet::WalletStore;
 
 #[cfg(feature = "rpc-server")]
@@ -38,14 +38,33 @@ pub fn initialize_rpc_server(
 
     let mut dispatcher = ModularDispatcher::default();
 
-    let wallet_dispatcher = WalletDispatcher::new(wallet_store);
+    let wallet_dispatcher = WalletDispatcher::new(Arc::clone(&wallet_store), client.blockchain());
     let unlocked_wallets = Arc::clone(&wallet_dispatcher.unlocked_wallets);
 
+    // Submit scheduled transactions in the background.
+    let scheduler = Arc::new(TransactionScheduler::new(
+        wallet_store,
+        client.consensus_proxy(),
+        Arc::clone(&unlocked_wallets),
+    ));
+    tokio::spawn(Arc::clone(&scheduler).run());
+
+    // The administrative methods can write to the file system and revert the chain, so they
+    // are only available if explicitly enabled.
+    if config.admin {
+        dispatcher.add(AdminDispatcher::new(
+            client.environment(),
+            client.blockchain(),
+        ));
+    }
+
     dispatcher.add(BlockchainDispatcher::new(client.blockchain()));
 
     dispatcher.add(ConsensusDispatcher::new(
         client.consensus_proxy(),
         Some(unlocked_wallets),
+        client.mempool(),
+        scheduler,
     ));
     dispatcher.add(NetworkDispatcher::new(client.network()));
     if let Some(mempool) = client.mempool() {
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use keyed_priority_queue::KeyedPriorityQueue;
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_serde::Serialize;
+use nimiq_transaction::{Transaction, TransactionFlags};
+
+use crate::mempool_transactions::{BestTxOrder, MempoolTransactions, TxPriority};
+
+/// A mempool transaction that can be included in a block.
+pub struct TransactionCandidate<'a> {
+    /// The hash of the transaction
+    pub hash: Blake2bHash,
+    /// The transaction itself
+    pub transaction: &'a Transaction,
+    /// The priority the transaction was added to the mempool with
+    pub priority: TxPriority,
+    /// The fee per byte of the transaction
+    pub fee_per_byte: f64,
+    /// The position of the transaction in the order of arrival at the mempool (lower is older)
+    pub insertion_order: u64,
+}
+
+impl<'a> TransactionCandidate<'a> {
+    /// Returns the number of bytes the transaction occupies in a block.
+    /// This accounts for one extra byte per transaction to encode its final execution status.
+    pub fn block_size(&self) -> usize {
+        1 + self.transaction.serialized_size()
+    }
+}
+
+/// The transactions of a mempool container, yielded from best to worst according to the mempool
+/// ordering: by priority, then by fee per byte (highest first), then by arrival (oldest first).
+///
+/// Transactions are taken out of the ordering lazily, so policies that only look at the best
+/// transactions don't pay for the rest. All transactions are put back once the candidates are
+/// dropped, only the selected ones are removed from the mempool afterwards.
+pub struct BlockCandidates<'a> {
+    best_transactions: &'a mut KeyedPriorityQueue<Blake2bHash, BestTxOrder>,
+    transactions: &'a HashMap<Blake2bHash, Transaction>,
+    taken: Vec<(Blake2bHash, BestTxOrder)>,
+    only_signaling: bool,
+}
+
+impl<'a> BlockCandidates<'a> {
+    pub(crate) fn new(transactions: &'a mut MempoolTransactions) -> Self {
+        Self {
+            best_transactions: &mut transactions.best_transactions,
+            transactions: &transactions.transactions,
+            taken: vec![],
+            only_signaling: false,
+        }
+    }
+
+    /// Like [`BlockCandidates::new`], but only yields signaling transactions.
+    pub(crate) fn signaling(transactions: &'a mut MempoolTransactions) -> Self {
+        Self {
+            only_signaling: true,
+            ..Self::new(transactions)
+        }
+    }
+}
+
+impl<'a> Iterator for BlockCandidates<'a> {
+    type Item = TransactionCandidate<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (hash, order) = self.best_transactions.pop()?;
+
+            let transactions: &'a HashMap<Blake2bHash, Transaction> = self.transactions;
+            let transaction = transactions
+                .get(&hash)
+                .expect("Transaction of best_transactions must be in the mempool");
+            let candidate = TransactionCandidate {
+                hash: hash.clone(),
+                transaction,
+                priority: order.priority,
+                fee_per_byte: order.fee_per_byte,
+                insertion_order: order.insertion_order,
+            };
+
+            self.taken.push((hash, order));
+            if self.only_signaling && !transaction.flags.contains(TransactionFlags::SIGNALING) {
+                continue;
+            }
+            return Some(candidate);
+        }
+    }
+}
+
+impl<'a> Drop for BlockCandidates<'a> {
+    fn drop(&mut self) {
+        for (hash, order) in self.taken.drain(..) {
+            self.best_transactions.push(hash, order);
+        }
+    }
+}
+
+/// A policy deciding which mempool transactions a block producer includes in its block.
+pub trait BlockBuildingPolicy: Send + Sync {
+    /// Selects the transactions to include in a block from the given candidates.
+    ///
+    /// Returns the hashes of the selected transactions in the order in which they should appear
+    /// in the block. The sum of the [block sizes](TransactionCandidate::block_size) of the
+    /// selected transactions must not exceed `max_bytes`.
+    fn select(&self, candidates: BlockCandidates<'_>, max_bytes: usize) -> Vec<Blake2bHash>;
+}
+
+/// Strict fee priority: Transactions are included from the highest to the lowest fee per byte
+/// until the next transaction doesn't fit into the block anymore.
+/// This is the default policy.
+#[derive(Clone, Debug, Default)]
+pub struct FeePriority;
+
+impl BlockBuildingPolicy for FeePriority {
+    fn select(&self, candidates: BlockCandidates<'_>, max_bytes: usize) -> Vec<Blake2bHash> {
+        let mut selected = vec![];
+        let mut size = 0_usize;
+
+        for candidate in candidates {
+            // Calculate size. If we can't fit the transaction in the block, then we stop here.
+            // TODO: We can optimize this. There might be a smaller transaction that still fits.
+            let next_size = size + candidate.block_size();
+            if next_size > max_bytes {
+                break;
+            }
+            size = next_size;
+
+            selected.push(candidate.hash);
+        }
+
+        selected
+    }
+}
+
+/// Fair ordering by arrival: Transactions are grouped into fee tiers and included in the order
+/// in which they arrived at the mempool within each tier, higher tiers first.
+///
+/// The tiers are given by their lower bounds in Luna per byte. A transaction belongs to the
+/// highest tier whose bound its fee per byte reaches. Without any bounds, all transactions are
+/// included purely by arrival. Transactions with a higher mempool priority still come first.
+#[derive(Clone, Debug, Default)]
+pub struct ArrivalOrder {
+    fee_tiers: Vec<u64>,
+}
+
+impl ArrivalOrder {
+    /// Creates the policy with the given lower bounds (in Luna per byte) of the fee tiers.
+    pub fn new(fee_tiers: Vec<u64>) -> Self {
+        Self { fee_tiers }
+    }
+
+    /// Returns the tier of the given fee per byte, higher tiers have a greater index.
+    fn fee_tier(&self, fee_per_byte: f64) -> usize {
+        self.fee_tiers
+            .iter()
+            .filter(|&&bound| fee_per_byte >= bound as f64)
+            .count()
+    }
+}
+
+impl BlockBuildingPolicy for ArrivalOrder {
+    fn select(&self, candidates: BlockCandidates<'_>, max_bytes: usize) -> Vec<Blake2bHash> {
+        let mut candidates: Vec<_> = candidates.collect();
+        candidates.sort_by(|a, b| {
+            (b.priority as u8)
+                .cmp(&(a.priority as u8))
+                .then(
+                    self.fee_tier(b.fee_per_byte)
+                        .cmp(&self.fee_tier(a.fee_per_byte)),
+                )
+                .then(a.insertion_order.cmp(&b.insertion_order))
+        });
+
+        // Since the order no longer follows the fee, smaller transactions further back may still
+        // fit into the block, so we skip the ones that don't fit instead of stopping.
+        let mut selected = vec![];
+        let mut size = 0_usize;
+        for candidate in candidates {
+            let next_size = size + candidate.block_size();
+            if next_size > max_bytes {
+                continue;
+            }
+            size = next_size;
+
+            selected.push(candidate.hash);
+        }
+
+        selected
+    }
+}
+
+/// Per-sender caps: Transactions are included by fee priority, but no more than the given number
+/// of transactions per sender address. This prevents a single sender from filling entire blocks.
+#[derive(Clone, Debug)]
+pub struct SenderCap {
+    max_transactions_per_sender: usize,
+}
+
+impl SenderCap {
+    /// Creates the policy with the given maximum number of transactions per sender and block.
+    pub fn new(max_transactions_per_sender: usize) -> Self {
+        Self {
+            max_transactions_per_sender,
+        }
+    }
+}
+
+impl BlockBuildingPolicy for SenderCap {
+    fn select(&self, candidates: BlockCandidates<'_>, max_bytes: usize) -> Vec<Blake2bHash> {
+        let mut selected = vec![];
+        let mut size = 0_usize;
+        let mut transactions_per_sender: HashMap<Address, usize> = HashMap::new();
+
+        for candidate in candidates {
+            let count = transactions_per_sender
+                .entry(candidate.transaction.sender.clone())
+                .or_default();
+            if *count >= self.max_transactions_per_sender {
+                continue;
+            }
+
+            let next_size = size + candidate.block_size();
+            if next_size > max_bytes {
+                break;
+            }
+            size = next_size;
+            *count += 1;
+
+            selected.push(candidate.hash);
+        }
+
+        selected
+    }
+}
+
+/// Selection of one of the block building policies provided by the mempool.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlockBuildingPolicyConfig {
+    /// See [`FeePriority`]
+    #[default]
+    FeePriority,
+    /// See [`ArrivalOrder`]
+    ArrivalOrder {
+        /// Lower bounds of the fee tiers (Luna per byte)
+        fee_tiers: Vec<u64>,
+    },
+    /// See [`SenderCap`]
+    SenderCap {
+        /// Maximum number of transactions per sender and block
+        max_transactions_per_sender: usize,
+    },
+}
+
+impl BlockBuildingPolicyConfig {
+    /// Creates the configured policy.
+    pub fn build(&self) -> Box<dyn BlockBuildingPolicy> {
+        match self {
+            BlockBuildingPolicyConfig::FeePriority => Box::new(FeePriority),
+            BlockBuildingPolicyConfig::ArrivalOrder { fee_tiers } => {
+                Box::new(ArrivalOrder::new(fee_tiers.clone()))
+            }
+            BlockBuildingPolicyConfig::SenderCap {
+                max_transactions_per_sender,
+            } => Box::new(SenderCap::new(*max_transactions_per_sender)),
+        }
+    }
+}
+
+/// Configuration of how a block producer fills its blocks with mempool transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlockBuildingConfig {
+    /// The policy used to select transactions from both the regular and the control mempool
+    pub policy: BlockBuildingPolicyConfig,
+    /// Block space (bytes) reserved for control transactions, which include all signaling
+    /// transactions. Regular transactions are selected first and leave this space free, control
+    /// transactions are then selected into the reserved space and whatever space regular
+    /// transactions left over. Regular transactions may use the part of the reserved space that
+    /// control transactions don't need.
+    /// If neither this nor `reserved_signaling_bytes` is set, control transactions are selected
+    /// first and may fill the entire block.
+    pub reserved_control_bytes: Option<usize>,
+    /// Block space (bytes) reserved for signaling transactions only, in addition to
+    /// `reserved_control_bytes`. Signaling transactions are selected into this space before any
+    /// other control transaction, so that e.g. validator reactivations can't be crowded out by
+    /// other staking transactions.
+    pub reserved_signaling_bytes: Option<usize>,
+}
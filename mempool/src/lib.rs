@@ -10,6 +10,8 @@ extern crate log;
 /// Mempool state module
 mod mempool_state;
 
+/// Block building policies module
+pub mod block_building;
 /// Mempool config module
 pub mod config;
 /// Mempool executor module
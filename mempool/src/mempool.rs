@@ -4,9 +4,9 @@ use std::{
 };
 
 use futures::{
-    future::{AbortHandle, Abortable},
+    future::{self, AbortHandle, Abortable},
     lock::{Mutex, MutexGuard},
-    stream::BoxStream,
+    stream::{BoxStream, StreamExt},
 };
 use nimiq_account::ReservedBalance;
 use nimiq_block::Block;
@@ -15,22 +15,35 @@ use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::Address;
 use nimiq_network_interface::network::{Network, Topic};
+use nimiq_primitives::coin::Coin;
 use nimiq_serde::Serialize;
 use nimiq_transaction::{ControlTransactionTopic, Transaction, TransactionTopic};
 use parking_lot::RwLock;
 use tokio_metrics::TaskMonitor;
+use tokio_stream::wrappers::BroadcastStream;
 
 #[cfg(feature = "metrics")]
 use crate::mempool_metrics::MempoolMetrics;
 use crate::{
+    block_building::{BlockBuildingPolicy, BlockCandidates, FeePriority},
     config::MempoolConfig,
     executor::MempoolExecutor,
     filter::{MempoolFilter, MempoolRules},
     mempool_state::{EvictionReason, MempoolState},
     mempool_transactions::{MempoolTransactions, TxPriority},
-    verify::{verify_tx, VerifyErr},
+    verify::{verify_tx, verify_txs, VerifyErr},
 };
 
+/// Events emitted when the set of transactions in the mempool changes.
+#[derive(Clone, Debug)]
+pub enum MempoolEvent {
+    /// A transaction was added to the mempool.
+    TransactionAdded(Transaction),
+    /// A transaction was removed from the mempool without being included in a block,
+    /// e.g. because it expired, became invalid or the mempool was full.
+    TransactionEvicted(Blake2bHash),
+}
+
 /// Struct defining the Mempool
 pub struct Mempool {
     /// Blockchain reference
@@ -488,10 +501,29 @@ impl Mempool {
         &self,
         blockchain: &Blockchain,
         max_bytes: usize,
+    ) -> (Vec<Transaction>, usize) {
+        self.get_transactions_for_block_with_policy_locked(blockchain, max_bytes, &FeePriority)
+    }
+
+    /// Returns a vector with accepted transactions from the mempool.
+    /// If the caller already holds a blockchain lock, it can be passed to this function to prevent
+    /// double-locking the blockchain.
+    ///
+    /// Returns the transactions selected by the given policy up to max_bytes and removes them from
+    /// the mempool. It also return the sum of the serialized size of the returned transactions.
+    pub fn get_transactions_for_block_with_policy_locked(
+        &self,
+        blockchain: &Blockchain,
+        max_bytes: usize,
+        policy: &dyn BlockBuildingPolicy,
     ) -> (Vec<Transaction>, usize) {
         let mut state = self.state.write();
-        let (txs, size) =
-            Self::get_transactions_for_block_impl(&mut state.regular_transactions, max_bytes);
+        let (txs, size) = Self::get_transactions_for_block_impl(
+            &mut state.regular_transactions,
+            max_bytes,
+            policy,
+            false,
+        );
 
         for tx in &txs {
             state.remove(blockchain, &tx.hash(), EvictionReason::BlockBuilding);
@@ -529,10 +561,34 @@ impl Mempool {
         &self,
         blockchain: &Blockchain,
         max_bytes: usize,
+    ) -> (Vec<Transaction>, usize) {
+        self.get_control_transactions_for_block_with_policy_locked(
+            blockchain,
+            max_bytes,
+            &FeePriority,
+        )
+    }
+
+    /// Returns a vector with accepted control transactions from the mempool.
+    /// If the caller already holds a blockchain lock, it can be passed to this function to prevent
+    /// double-locking the blockchain.
+    ///
+    /// Returns the control transactions selected by the given policy up to max_bytes and removes
+    /// them from the mempool. It also return the sum of the serialized size of the returned
+    /// transactions.
+    pub fn get_control_transactions_for_block_with_policy_locked(
+        &self,
+        blockchain: &Blockchain,
+        max_bytes: usize,
+        policy: &dyn BlockBuildingPolicy,
     ) -> (Vec<Transaction>, usize) {
         let mut state = self.state.write();
-        let (txs, size) =
-            Self::get_transactions_for_block_impl(&mut state.control_transactions, max_bytes);
+        let (txs, size) = Self::get_transactions_for_block_impl(
+            &mut state.control_transactions,
+            max_bytes,
+            policy,
+            false,
+        );
 
         for tx in &txs {
             state.remove(blockchain, &tx.hash(), EvictionReason::BlockBuilding);
@@ -547,38 +603,79 @@ impl Mempool {
         (txs, size)
     }
 
+    /// Returns a vector with accepted signaling transactions from the mempool.
+    /// If the caller already holds a blockchain lock, it can be passed to this function to prevent
+    /// double-locking the blockchain.
+    ///
+    /// Returns the signaling transactions selected by the given policy up to max_bytes and removes
+    /// them from the mempool. It also return the sum of the serialized size of the returned
+    /// transactions.
+    pub fn get_signaling_transactions_for_block_with_policy_locked(
+        &self,
+        blockchain: &Blockchain,
+        max_bytes: usize,
+        policy: &dyn BlockBuildingPolicy,
+    ) -> (Vec<Transaction>, usize) {
+        let mut state = self.state.write();
+        let (txs, size) = Self::get_transactions_for_block_impl(
+            &mut state.control_transactions,
+            max_bytes,
+            policy,
+            true,
+        );
+
+        for tx in &txs {
+            state.remove(blockchain, &tx.hash(), EvictionReason::BlockBuilding);
+        }
+
+        debug!(
+            returned_txs = txs.len(),
+            remaining_txs = state.control_transactions.len(),
+            "Returned signaling transactions from mempool"
+        );
+
+        (txs, size)
+    }
+
     fn get_transactions_for_block_impl(
         transactions: &mut MempoolTransactions,
         max_bytes: usize,
+        policy: &dyn BlockBuildingPolicy,
+        only_signaling: bool,
     ) -> (Vec<Transaction>, usize) {
+        let candidates = if only_signaling {
+            BlockCandidates::signaling(transactions)
+        } else {
+            BlockCandidates::new(transactions)
+        };
+        let selected = policy.select(candidates, max_bytes);
+
         let mut txs = vec![];
         let mut size = 0_usize;
-
-        loop {
-            // Get the hash of the highest paying transactions.
-            let tx_hash = match transactions.best_transactions.peek() {
-                None => break,
-                Some((tx_hash, _)) => tx_hash.clone(),
+        let mut included = HashSet::new();
+
+        for tx_hash in selected {
+            // Don't trust the policy blindly, it must neither include unknown or duplicate
+            // transactions nor exceed the available space.
+            let tx = match transactions.get(&tx_hash) {
+                Some(tx) if !included.contains(&tx_hash) => tx,
+                _ => {
+                    warn!(%tx_hash, "Block building policy selected an invalid transaction");
+                    continue;
+                }
             };
 
-            // Get the transaction.
-            let tx = transactions.get(&tx_hash).unwrap().clone();
-
-            // Calculate size. If we can't fit the transaction in the block, then we stop here.
-            // TODO: We can optimize this. There might be a smaller transaction that still fits.
             // We need to account for one extra byte per transaction to encode its final execution status
             let next_size = size + 1 + tx.serialized_size();
             if next_size > max_bytes {
-                break;
+                warn!(%tx_hash, "Block building policy exceeded the available block space");
+                continue;
             }
             size = next_size;
 
-            // Remove the transaction from best_transactions so that we can advance.
-            // The caller needs to clean up the rest of the data structures.
-            transactions.best_transactions.pop();
-
-            // Push the transaction to our output vector.
-            txs.push(tx);
+            // The caller needs to clean up the data structures.
+            txs.push(tx.clone());
+            included.insert(tx_hash);
         }
 
         (txs, size)
@@ -605,6 +702,26 @@ impl Mempool {
         .await
     }
 
+    /// Adds a batch of transactions to the Mempool, either all or none of them.
+    /// Returns the index of the first rejected transaction and the reason otherwise.
+    pub async fn add_transactions(
+        &self,
+        transactions: &[Transaction],
+        tx_priority: Option<TxPriority>,
+    ) -> Result<(), (usize, VerifyErr)> {
+        let blockchain = Arc::clone(&self.blockchain);
+        let network_id = blockchain.read().network_id;
+        verify_txs(
+            transactions,
+            blockchain,
+            network_id,
+            &self.state,
+            Arc::clone(&self.filter),
+            tx_priority.unwrap_or(TxPriority::Medium),
+        )
+        .await
+    }
+
     /// Checks whether a transaction has been filtered
     pub fn is_filtered(&self, hash: &Blake2bHash) -> bool {
         self.filter.read().blacklisted(hash)
@@ -620,6 +737,25 @@ impl Mempool {
         self.state.read().contains(hash)
     }
 
+    /// Returns the balance of the sender that is reserved by the transactions in the mempool.
+    pub fn get_reserved_balance(&self, sender: &Address) -> Coin {
+        self.state
+            .read()
+            .state_by_sender
+            .get(sender)
+            .map_or(Coin::ZERO, |sender_state| {
+                sender_state.reserved_balance.balance()
+            })
+    }
+
+    /// Returns a stream of events notifying about transactions being added to or evicted from
+    /// the mempool.
+    pub fn notifier_as_stream(&self) -> BoxStream<'static, MempoolEvent> {
+        BroadcastStream::new(self.state.read().notifier.subscribe())
+            .filter_map(|event| future::ready(event.ok()))
+            .boxed()
+    }
+
     /// Gets a transactions by its hash.
     pub fn get_transaction_by_hash(&self, hash: &Blake2bHash) -> Option<Transaction> {
         self.state.read().get(hash).cloned()
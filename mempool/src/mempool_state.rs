@@ -8,14 +8,19 @@ use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::Address;
 use nimiq_primitives::account::AccountType;
 use nimiq_transaction::Transaction;
+use tokio::sync::broadcast::{channel as broadcast, Sender as BroadcastSender};
 
 #[cfg(feature = "metrics")]
 use crate::mempool_metrics::MempoolMetrics;
 use crate::{
+    mempool::MempoolEvent,
     mempool_transactions::{MempoolTransactions, TxPriority},
     verify::VerifyErr,
 };
 
+/// The capacity of the broadcast channel used to notify about mempool events.
+const BROADCAST_MAX_CAPACITY: usize = 256;
+
 pub(crate) struct MempoolState {
     // Container where the regular transactions are stored
     pub(crate) regular_transactions: MempoolTransactions,
@@ -26,16 +31,22 @@ pub(crate) struct MempoolState {
     // The pending balance per sender.
     pub(crate) state_by_sender: HashMap<Address, SenderPendingState>,
 
+    // Notifies about transactions being added to or evicted from the mempool.
+    pub(crate) notifier: BroadcastSender<MempoolEvent>,
+
     #[cfg(feature = "metrics")]
     pub(crate) metrics: Arc<MempoolMetrics>,
 }
 
 impl MempoolState {
     pub fn new(regular_txns_limit: usize, control_txns_limit: usize) -> Self {
+        let (notifier, _rx) = broadcast(BROADCAST_MAX_CAPACITY);
+
         MempoolState {
             regular_transactions: MempoolTransactions::new(regular_txns_limit),
             control_transactions: MempoolTransactions::new(control_txns_limit),
             state_by_sender: HashMap::new(),
+            notifier,
             #[cfg(feature = "metrics")]
             metrics: Default::default(),
         }
@@ -110,6 +121,13 @@ impl MempoolState {
             self.remove(blockchain, &tx_hash, EvictionReason::TooFull);
         }
 
+        // Only announce the new txn once the evictions are done, it might have been the worst one.
+        if self.contains(&tx_hash) {
+            self.notifier
+                .send(MempoolEvent::TransactionAdded(tx.clone()))
+                .ok();
+        }
+
         Ok(())
     }
 
@@ -117,13 +135,24 @@ impl MempoolState {
         &mut self,
         blockchain: &Blockchain,
         tx_hash: &Blake2bHash,
-        #[allow(unused_variables)] reason: EvictionReason,
+        reason: EvictionReason,
     ) -> Option<Transaction> {
         let tx = self
             .regular_transactions
             .delete(tx_hash)
             .or_else(|| self.control_transactions.delete(tx_hash))?;
 
+        // Transactions removed for block building or because they were included in a block
+        // are not evicted, they are reported by the blockchain instead.
+        match reason {
+            EvictionReason::Expired | EvictionReason::Invalid | EvictionReason::TooFull => {
+                self.notifier
+                    .send(MempoolEvent::TransactionEvicted(tx_hash.clone()))
+                    .ok();
+            }
+            EvictionReason::BlockBuilding | EvictionReason::AlreadyIncluded => {}
+        }
+
         let sender_state = match self.state_by_sender.get_mut(&tx.sender) {
             Some(state) => state,
             None => return Some(tx),
@@ -28,9 +28,9 @@ pub enum TxPriority {
 //       we might prefer basic transactions over staking contract transactions, etc, etc.
 #[derive(PartialEq)]
 pub struct BestTxOrder {
-    priority: TxPriority,
-    fee_per_byte: f64,
-    insertion_order: u64,
+    pub(crate) priority: TxPriority,
+    pub(crate) fee_per_byte: f64,
+    pub(crate) insertion_order: u64,
 }
 
 impl Eq for BestTxOrder {}
@@ -8,7 +8,11 @@ use nimiq_transaction::Transaction;
 use parking_lot::RwLock;
 use thiserror::Error;
 
-use crate::{filter::MempoolFilter, mempool_state::MempoolState, mempool_transactions::TxPriority};
+use crate::{
+    filter::MempoolFilter,
+    mempool_state::{EvictionReason, MempoolState},
+    mempool_transactions::TxPriority,
+};
 
 /// Error codes for the transaction verification
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -28,6 +32,8 @@ pub enum VerifyErr {
     Filtered,
     #[error("Can't verify transaction without consensus")]
     NoConsensus,
+    #[error("Transaction was evicted because the mempool is full")]
+    MempoolFull,
 }
 
 /// Verifies a transaction and adds it to the mempool.
@@ -49,7 +55,86 @@ pub(crate) async fn verify_tx(
     // 2. Acquire blockchain read lock
     let blockchain = blockchain.read();
 
-    // 3. Check validity window and already included
+    // 3. Acquire the mempool state write lock
+    let mut mempool_state = mempool_state.write();
+
+    // 4. Check and add the transaction
+    check_and_put_tx(
+        transaction,
+        &blockchain,
+        &mut mempool_state,
+        &filter.read(),
+        priority,
+    )
+}
+
+/// Verifies a batch of transactions and adds either all or none of them to the mempool.
+/// Returns the index of the first rejected transaction otherwise.
+pub(crate) async fn verify_txs(
+    transactions: &[Transaction],
+    blockchain: Arc<RwLock<Blockchain>>,
+    network_id: NetworkId,
+    mempool_state: &Arc<RwLock<MempoolState>>,
+    filter: Arc<RwLock<MempoolFilter>>,
+    priority: TxPriority,
+) -> Result<(), (usize, VerifyErr)> {
+    // 1. Verify the signatures of all transactions
+    let mut txs = transactions.to_vec();
+    tokio::task::spawn_blocking(move || {
+        txs.iter_mut().enumerate().try_for_each(|(index, tx)| {
+            tx.verify_mut(network_id)
+                .map_err(|error| (index, VerifyErr::from(error)))
+        })
+    })
+    .await
+    .unwrap()?;
+
+    // 2. Acquire the blockchain read lock and the mempool state write lock once for the batch,
+    // such that the balance checks can't be interleaved with other transactions.
+    let blockchain = blockchain.read();
+    let mut mempool_state = mempool_state.write();
+    let filter = filter.read();
+
+    // 3. Check and add the transactions, taking back the added ones on the first failure
+    for (index, transaction) in transactions.iter().enumerate() {
+        if let Err(error) = check_and_put_tx(
+            transaction,
+            &blockchain,
+            &mut mempool_state,
+            &filter,
+            priority,
+        ) {
+            for transaction in &transactions[..index] {
+                mempool_state.remove(&blockchain, &transaction.hash(), EvictionReason::Invalid);
+            }
+            return Err((index, error));
+        }
+    }
+
+    // 4. Adding transactions to a full mempool may have evicted earlier ones of the batch
+    if let Some(index) = transactions
+        .iter()
+        .position(|transaction| !mempool_state.contains(&transaction.hash()))
+    {
+        for transaction in transactions {
+            mempool_state.remove(&blockchain, &transaction.hash(), EvictionReason::Invalid);
+        }
+        return Err((index, VerifyErr::MempoolFull));
+    }
+
+    Ok(())
+}
+
+/// Checks a transaction with a verified signature against the blockchain and the filter and
+/// adds it to the mempool.
+fn check_and_put_tx(
+    transaction: &Transaction,
+    blockchain: &Blockchain,
+    mempool_state: &mut MempoolState,
+    filter: &MempoolFilter,
+    priority: TxPriority,
+) -> Result<(), VerifyErr> {
+    // 1. Check validity window and already included
     let block_number = blockchain.block_number() + 1;
     if !transaction.is_valid_at(block_number) {
         debug!(
@@ -65,27 +150,21 @@ pub(crate) async fn verify_tx(
         return Err(VerifyErr::AlreadyIncluded);
     }
 
-    // 4. Acquire the mempool state write lock
-    let mut mempool_state = mempool_state.write();
-
-    // 5. Check if we already know the transaction
+    // 2. Check if we already know the transaction
     if mempool_state.contains(&transaction.hash()) {
         // We already know this transaction, no need to process
         return Err(VerifyErr::Known);
     }
 
-    // 6. Check if the transaction is going to be filtered.
-    {
-        let filter = filter.read();
-        if !filter.accepts_transaction(transaction) || filter.blacklisted(&transaction.hash()) {
-            // FIXME add transaction to blacklist
-            log::debug!("Transaction filtered");
-            return Err(VerifyErr::Filtered);
-        }
+    // 3. Check if the transaction is going to be filtered.
+    if !filter.accepts_transaction(transaction) || filter.blacklisted(&transaction.hash()) {
+        // FIXME add transaction to blacklist
+        log::debug!("Transaction filtered");
+        return Err(VerifyErr::Filtered);
     }
 
-    // 7. Add transaction to the mempool. Balance checks are performed within put().
-    mempool_state.put(&blockchain, transaction, priority)?;
+    // 4. Add transaction to the mempool. Balance checks are performed within put().
+    mempool_state.put(blockchain, transaction, priority)?;
 
     Ok(())
 
@@ -1,5 +1,6 @@
 use std::{env, str::FromStr, sync::Arc};
 
+use futures::StreamExt;
 use nimiq_block::{Block, MicroBlock, MicroBody, MicroHeader};
 use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
 use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
@@ -11,7 +12,13 @@ use nimiq_keys::{
     Address, Ed25519PublicKey as SchnorrPublicKey, KeyPair as SchnorrKeyPair,
     PrivateKey as SchnorrPrivateKey, SecureGenerate,
 };
-use nimiq_mempool::{config::MempoolConfig, mempool::Mempool, mempool_transactions::TxPriority};
+use nimiq_mempool::{
+    block_building::{ArrivalOrder, FeePriority, SenderCap},
+    config::MempoolConfig,
+    mempool::{Mempool, MempoolEvent},
+    mempool_transactions::TxPriority,
+    verify::VerifyErr,
+};
 use nimiq_network_mock::{MockHub, MockId, MockNetwork, MockPeerId};
 use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
 use nimiq_serde::{Deserialize, Serialize};
@@ -729,6 +736,90 @@ async fn push_tx_with_insufficient_balance() {
     assert_eq!(txns.len(), 1);
 }
 
+#[test(tokio::test)]
+async fn reserves_balance_of_pending_transactions() {
+    let mut rng = test_rng(true);
+    let num_txns = 3;
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    // Generate recipient accounts
+    let recipient_accounts =
+        generate_accounts(vec![0; num_txns], &mut genesis_builder, false, &mut rng);
+    // Generate sender account
+    let sender_accounts = generate_accounts(vec![100], &mut genesis_builder, true, &mut rng);
+
+    // Generate transactions
+    let mempool_transactions = recipient_accounts
+        .iter()
+        .map(|recipient| TestTransaction {
+            fee: 1,
+            value: 10,
+            recipient: recipient.clone(),
+            sender: sender_accounts[0].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    let time = Arc::new(OffsetTime::new());
+    let env = VolatileDatabase::new(20).unwrap();
+
+    // Add a validator to genesis
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = match genesis_info.block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            nimiq_block::Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env.clone(),
+            BlockchainConfig::default(),
+            time,
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+    let mut hub = MockHub::new();
+    let mock_id = MockId::new(hub.new_address().into());
+    let mock_network = Arc::new(hub.new_network());
+
+    let sender = &sender_accounts[0].address;
+    assert_eq!(mempool.get_reserved_balance(sender), Coin::ZERO);
+
+    send_txn_to_mempool(&mempool, mock_network, mock_id, txns).await;
+
+    // Value and fee of all transactions are reserved
+    assert_eq!(mempool.num_transactions(), num_txns);
+    assert_eq!(
+        mempool.get_reserved_balance(sender),
+        Coin::from_u64_unchecked(33)
+    );
+    assert_eq!(
+        mempool.get_reserved_balance(&recipient_accounts[0].address),
+        Coin::ZERO
+    );
+}
+
 #[test(tokio::test)]
 async fn multiple_transactions_multiple_senders() {
     let mut rng = test_rng(true);
@@ -2029,3 +2120,327 @@ async fn it_can_reject_invalid_vesting_contract_transaction() {
         "Number of txns in the mempools is not what is expected"
     );
 }
+
+fn blockchain_with_genesis(mut genesis_builder: GenesisBuilder) -> Arc<RwLock<Blockchain>> {
+    let env = VolatileDatabase::new(20).unwrap();
+    let mut rng = test_rng(true);
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = match genesis_info.block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env,
+            BlockchainConfig::default(),
+            Arc::new(OffsetTime::new()),
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ))
+}
+
+#[test(tokio::test)]
+async fn arrival_order_policy_orders_by_arrival_within_fee_tiers() {
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    // Generate transactions with increasing fees from a single sender.
+    let value = 10;
+    let num_txns = 4;
+    let mut rng = test_rng(false);
+    let recipient_accounts = generate_accounts(
+        vec![0; num_txns as usize],
+        &mut genesis_builder,
+        false,
+        &mut rng,
+    );
+    let sender_accounts = generate_accounts(vec![100_000; 1], &mut genesis_builder, true, &mut rng);
+
+    let mut mempool_transactions = vec![];
+    for i in 0..num_txns {
+        mempool_transactions.push(TestTransaction {
+            fee: (i + 1) * 1000,
+            value,
+            recipient: recipient_accounts[i as usize].clone(),
+            sender: sender_accounts[0].clone(),
+        });
+    }
+    let (txns, txns_len) = generate_transactions(mempool_transactions, true);
+
+    let blockchain = blockchain_with_genesis(genesis_builder);
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+
+    // Add the transactions one by one so that their arrival order is deterministic.
+    for tx in txns.clone() {
+        mempool.add_transaction(tx, None).await.unwrap();
+    }
+
+    // Put the two cheaper transactions into a lower fee tier than the two more expensive ones.
+    // Within each tier, the older transaction comes first.
+    let fee_tier = txns[2].fee_per_byte().floor() as u64;
+    assert!(txns[1].fee_per_byte() < fee_tier as f64);
+    let policy = ArrivalOrder::new(vec![0, fee_tier]);
+    let (block_txns, _) = mempool.get_transactions_for_block_with_policy_locked(
+        &blockchain.read(),
+        txns_len,
+        &policy,
+    );
+
+    assert_eq!(
+        block_txns,
+        vec![
+            txns[2].clone(),
+            txns[3].clone(),
+            txns[0].clone(),
+            txns[1].clone()
+        ]
+    );
+    assert_eq!(mempool.num_transactions(), 0);
+}
+
+#[test(tokio::test)]
+async fn sender_cap_policy_limits_transactions_per_sender() {
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let value = 10;
+    let num_txns = 4;
+    let mut rng = test_rng(false);
+    let recipient_accounts = generate_accounts(
+        vec![0; num_txns as usize],
+        &mut genesis_builder,
+        false,
+        &mut rng,
+    );
+    let sender_accounts = generate_accounts(vec![100_000; 2], &mut genesis_builder, true, &mut rng);
+
+    // The first sender sends three well paying transactions, the second one a cheap one.
+    let mut mempool_transactions = vec![];
+    for i in 0..num_txns {
+        mempool_transactions.push(TestTransaction {
+            fee: if i < 3 { (i + 2) * 1000 } else { 1000 },
+            value,
+            recipient: recipient_accounts[i as usize].clone(),
+            sender: sender_accounts[if i < 3 { 0 } else { 1 }].clone(),
+        });
+    }
+    let (txns, txns_len) = generate_transactions(mempool_transactions, true);
+
+    let blockchain = blockchain_with_genesis(genesis_builder);
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+
+    for tx in txns.clone() {
+        mempool.add_transaction(tx, None).await.unwrap();
+    }
+
+    let policy = SenderCap::new(2);
+    let (block_txns, _) = mempool.get_transactions_for_block_with_policy_locked(
+        &blockchain.read(),
+        txns_len,
+        &policy,
+    );
+
+    // Only the two best transactions of the first sender are included, but the cheap transaction
+    // of the second sender still makes it into the block.
+    assert_eq!(
+        block_txns,
+        vec![txns[2].clone(), txns[1].clone(), txns[3].clone()]
+    );
+
+    // The remaining transaction stays in the mempool.
+    assert_eq!(mempool.num_transactions(), 1);
+    assert!(mempool.contains_transaction_by_hash(&txns[0].hash()));
+}
+
+#[test(tokio::test)]
+async fn selects_signaling_transactions_separately() {
+    let time = Arc::new(OffsetTime::new());
+    let env = VolatileDatabase::new(20).unwrap();
+
+    let key_pair = ed25519_key_pair(ACCOUNT_SECRET_KEY);
+    let validator_signing_key = ed25519_key_pair(VALIDATOR_SECRET_KEY);
+    let address = Address::from_any_str(STAKER_ADDRESS).unwrap();
+    let validator_address = Address::from_any_str(VALIDATOR_ADDRESS).unwrap();
+
+    // A signaling transaction with a lower fee than the other control transaction.
+    let reactivate = TransactionBuilder::new_reactivate_validator(
+        &key_pair,
+        validator_address,
+        &validator_signing_key,
+        1.try_into().unwrap(),
+        1 + Policy::genesis_block_number(),
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+
+    let create_staker = TransactionBuilder::new_create_staker(
+        &key_pair,
+        &key_pair,
+        Some(address),
+        100_000_000.try_into().unwrap(),
+        100.try_into().unwrap(),
+        1 + Policy::genesis_block_number(),
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            env,
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            time,
+        )
+        .unwrap(),
+    ));
+
+    let mempool = Mempool::new(blockchain.clone(), MempoolConfig::default());
+    let mut hub = MockHub::new();
+    let mock_id = MockId::new(hub.new_address().into());
+    let mock_network = Arc::new(hub.new_network());
+
+    send_control_txn_to_mempool(&mempool, mock_network, mock_id, vec![create_staker.clone()]).await;
+    mempool
+        .add_transaction(reactivate.clone(), None)
+        .await
+        .unwrap();
+    assert_eq!(mempool.num_transactions(), 2);
+
+    // Only the signaling transaction is selected, even though the other one pays more.
+    let (signaling_txns, _) = mempool.get_signaling_transactions_for_block_with_policy_locked(
+        &blockchain.read(),
+        10_000,
+        &FeePriority,
+    );
+    assert_eq!(signaling_txns, vec![reactivate]);
+
+    // The other control transaction stays in the mempool.
+    assert_eq!(mempool.num_transactions(), 1);
+    assert!(mempool.contains_transaction_by_hash(&create_staker.hash()));
+}
+
+#[test(tokio::test)]
+async fn adds_batches_of_transactions_atomically() {
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let mut rng = test_rng(false);
+    let recipient_accounts = generate_accounts(vec![0; 3], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![100], &mut genesis_builder, true, &mut rng);
+
+    // The second transaction exceeds the balance left by the first one.
+    let mempool_transactions = [10, 90, 10]
+        .into_iter()
+        .zip(&recipient_accounts)
+        .map(|(value, recipient)| TestTransaction {
+            fee: 1,
+            value,
+            recipient: recipient.clone(),
+            sender: sender_accounts[0].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    let blockchain = blockchain_with_genesis(genesis_builder);
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+    let sender = &sender_accounts[0].address;
+
+    // The failure in the middle of the batch takes back the first transaction.
+    assert_eq!(
+        mempool.add_transactions(&txns, None).await,
+        Err((1, VerifyErr::InsufficientFunds))
+    );
+    assert_eq!(mempool.num_transactions(), 0);
+    assert_eq!(mempool.get_reserved_balance(sender), Coin::ZERO);
+
+    // A funded batch is added as a whole.
+    let batch = [txns[0].clone(), txns[2].clone()];
+    assert_eq!(mempool.add_transactions(&batch, None).await, Ok(()));
+    assert_eq!(mempool.num_transactions(), 2);
+    assert_eq!(
+        mempool.get_reserved_balance(sender),
+        Coin::from_u64_unchecked(22)
+    );
+
+    // Transactions that are already pending are not taken back by a failing batch.
+    assert_eq!(
+        mempool.add_transactions(&txns[1..], None).await,
+        Err((0, VerifyErr::InsufficientFunds))
+    );
+    assert_eq!(mempool.num_transactions(), 2);
+}
+
+#[test(tokio::test)]
+async fn notifies_about_added_and_evicted_transactions() {
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    // Generate transactions with increasing fees from different senders.
+    let num_txns = 3;
+    let mut rng = test_rng(false);
+    let recipient_accounts =
+        generate_accounts(vec![0; num_txns], &mut genesis_builder, false, &mut rng);
+    let sender_accounts =
+        generate_accounts(vec![100; num_txns], &mut genesis_builder, true, &mut rng);
+
+    let mempool_transactions = (0..num_txns)
+        .map(|i| TestTransaction {
+            fee: 2 * i as u64,
+            value: 10,
+            recipient: recipient_accounts[i].clone(),
+            sender: sender_accounts[i].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    // Only two of the transactions fit into the mempool.
+    // Need to account for the executed txn size
+    let blockchain = blockchain_with_genesis(genesis_builder);
+    let mempool_config = MempoolConfig {
+        size_limit: 2 + txns[0].serialized_size() + txns[1].serialized_size(),
+        ..Default::default()
+    };
+    let mempool = Mempool::new(blockchain, mempool_config);
+    let mut events = mempool.notifier_as_stream();
+
+    for tx in txns.clone() {
+        mempool.add_transaction(tx, None).await.unwrap();
+    }
+
+    // The last transaction evicts the one with the lowest fee and is announced afterwards.
+    let expected = [
+        (true, txns[0].hash::<Blake2bHash>()),
+        (true, txns[1].hash()),
+        (false, txns[0].hash()),
+        (true, txns[2].hash()),
+    ];
+    for (added, hash) in expected {
+        match events.next().await {
+            Some(MempoolEvent::TransactionAdded(tx)) if added => {
+                assert_eq!(tx.hash::<Blake2bHash>(), hash)
+            }
+            Some(MempoolEvent::TransactionEvicted(tx_hash)) if !added => assert_eq!(tx_hash, hash),
+            event => panic!("Unexpected mempool event: {:?}", event),
+        }
+    }
+    assert_eq!(mempool.num_transactions(), 2);
+}
@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use nimiq_handel::tracker::AggregationTracker;
+use prometheus_client::registry::Registry;
+
+pub struct AggregationMetrics {}
+
+impl AggregationMetrics {
+    pub fn register(registry: &mut Registry, trackers: &[Arc<AggregationTracker>]) {
+        let sub_registry = registry.sub_registry_with_prefix("handel");
+
+        for tracker in trackers {
+            let tracker_registry =
+                sub_registry.sub_registry_with_label(("aggregation".into(), tracker.name().into()));
+            tracker.metrics().register(tracker_registry);
+        }
+    }
+}
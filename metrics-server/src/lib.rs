@@ -2,6 +2,8 @@ use std::{fmt::Debug, net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
+#[cfg(feature = "nimiq-handel")]
+use nimiq_handel::tracker::AggregationTracker;
 use nimiq_mempool::mempool::Mempool;
 use nimiq_network_interface::network::Network;
 use parking_lot::RwLock;
@@ -14,6 +16,8 @@ use prometheus_client::{
 use tokio_metrics::RuntimeMonitor;
 use tokio_metrics::TaskMonitor;
 
+#[cfg(feature = "nimiq-handel")]
+use crate::handel::AggregationMetrics;
 #[cfg(tokio_unstable)]
 use crate::tokio_runtime::TokioRuntimeMetrics;
 use crate::{
@@ -23,6 +27,8 @@ use crate::{
 
 mod chain;
 mod consensus;
+#[cfg(feature = "nimiq-handel")]
+mod handel;
 mod mempool;
 mod network;
 mod server;
@@ -82,6 +88,7 @@ pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
     blockchain_proxy: BlockchainProxy,
     mempool: Option<Arc<Mempool>>,
+    #[cfg(feature = "nimiq-handel")] aggregation_trackers: Vec<Arc<AggregationTracker>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
     task_monitors: &[NimiqTaskMonitor],
@@ -97,6 +104,9 @@ pub fn start_metrics_server<TNetwork: Network>(
         MempoolMetrics::register(nimiq_registry, mempool);
     }
 
+    #[cfg(feature = "nimiq-handel")]
+    AggregationMetrics::register(nimiq_registry, &aggregation_trackers);
+
     // Setup the task metrics
     let task_metrics = Arc::new(RwLock::new(TokioTaskMetrics::new()));
     task_metrics.write().register(
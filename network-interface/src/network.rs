@@ -111,6 +111,10 @@ pub trait Network: Send + Sync + Unpin + 'static {
     /// Returns true when the given peer provides the services flags that are required by us
     fn peer_provides_services(&self, peer_id: Self::PeerId, services: Services) -> bool;
 
+    /// Starts providing the given services in addition to the ones provided so far. Other peers
+    /// learn about them through the discovery of our updated contact.
+    async fn add_provided_services(&self, services: Services);
+
     /// Disconnects a peer with a close reason
     async fn disconnect_peer(&self, peer_id: Self::PeerId, close_reason: CloseReason);
 
@@ -41,6 +41,10 @@ bitflags! {
 
         /// This node is configured as a validator, so it is interested for other validator nodes.
         const VALIDATOR = 1 << 7;
+
+        /// The node stores all blocks of all past epochs, including micro blocks and their bodies,
+        /// and provides them batch by batch.
+        const ARCHIVE = 1 << 8;
     }
 }
 
@@ -52,6 +56,8 @@ pub enum NodeType {
     Light,
     /// Full node type
     Full,
+    /// Archive node type
+    Archive,
 }
 
 impl Services {
@@ -69,6 +75,7 @@ impl Services {
             NodeType::Full => {
                 Services::ACCOUNTS_PROOF | Services::FULL_BLOCKS | Services::ACCOUNTS_CHUNKS
             }
+            NodeType::Archive => Services::provided(NodeType::History) | Services::ARCHIVE,
         }
     }
 
@@ -78,6 +85,7 @@ impl Services {
             NodeType::History => Services::HISTORY | Services::FULL_BLOCKS,
             NodeType::Light => Services::ACCOUNTS_PROOF,
             NodeType::Full => Services::FULL_BLOCKS | Services::ACCOUNTS_CHUNKS,
+            NodeType::Archive => Services::required(NodeType::History),
         }
     }
 }
@@ -150,6 +150,13 @@ impl Behaviour {
             .add_own_addresses(addresses, &self.keypair)
     }
 
+    /// Adds services to the ones provided in our own contact within the peer contact book
+    pub fn add_own_services(&self, services: Services) {
+        self.peer_contact_book
+            .write()
+            .add_own_services(services, &self.keypair)
+    }
+
     /// Returns whether an address in `Multiaddr` format is a dialable websocket address
     pub fn is_address_dialable(&self, address: &Multiaddr) -> bool {
         self.peer_contact_book.read().is_address_dialable(address)
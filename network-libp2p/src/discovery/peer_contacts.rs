@@ -449,6 +449,14 @@ impl PeerContactBook {
         self.own_peer_contact = PeerContactInfo::from(contact.sign(keypair));
     }
 
+    /// Adds a set of services to the services provided by our own.
+    pub fn add_own_services(&mut self, services: Services, keypair: &Keypair) {
+        let mut contact = self.own_peer_contact.contact.inner.clone();
+        contact.services |= services;
+        contact.set_current_time();
+        self.own_peer_contact = PeerContactInfo::from(contact.sign(keypair));
+    }
+
     /// Updates the timestamp of our own contact
     pub fn update_own_contact(&mut self, keypair: &Keypair) {
         // Not really optimal to clone here, but *shrugs*
@@ -137,6 +137,9 @@ pub(crate) enum NetworkAction {
     ListenOn {
         listen_addresses: Vec<Multiaddr>,
     },
+    AddOwnServices {
+        services: Services,
+    },
     ConnectPeersByServices {
         services: Services,
         num_peers: usize,
@@ -1598,6 +1601,9 @@ impl Network {
             NetworkAction::StartConnecting => {
                 swarm.behaviour_mut().pool.start_connecting();
             }
+            NetworkAction::AddOwnServices { services } => {
+                swarm.behaviour_mut().discovery.add_own_services(services);
+            }
             NetworkAction::ConnectPeersByServices {
                 services,
                 num_peers,
@@ -2233,6 +2239,16 @@ impl NetworkInterface for Network {
         }
     }
 
+    async fn add_provided_services(&self, services: Services) {
+        if let Err(error) = self
+            .action_tx
+            .send(NetworkAction::AddOwnServices { services })
+            .await
+        {
+            error!(%error, "Failed to send NetworkAction::AddOwnServices");
+        }
+    }
+
     async fn disconnect_peer(&self, peer_id: PeerId, close_reason: CloseReason) {
         if let Err(error) = self
             .action_tx
@@ -0,0 +1,55 @@
+use std::{fmt, time::Duration};
+
+use nimiq_network_interface::request::RequestType;
+
+use crate::MockPeerId;
+
+/// A message that is about to be delivered from one mock network to another.
+#[derive(Debug)]
+pub struct MockMessage<'a> {
+    /// The peer that sent the message.
+    pub sender: MockPeerId,
+    /// The peer that is about to receive the message.
+    pub recipient: MockPeerId,
+    /// The kind of the message, including its serialized payload.
+    pub kind: MockMessageKind<'a>,
+}
+
+#[derive(Debug)]
+pub enum MockMessageKind<'a> {
+    /// A message published on a gossipsub topic.
+    Gossipsub { topic: &'a str, data: &'a [u8] },
+    /// A direct message or request.
+    Request {
+        request_type: RequestType,
+        data: &'a [u8],
+    },
+}
+
+/// Decision taken by a [`MessageFilter`] for a single message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Delivery {
+    /// Deliver the message immediately.
+    Deliver,
+    /// Deliver the message after the given delay.
+    Delay(Duration),
+    /// Silently drop the message.
+    Drop,
+}
+
+/// A filter that decides for every message passing through a [`MockHub`](crate::MockHub) whether
+/// and when it is delivered. This allows tests to simulate latency, message loss and network
+/// partitions.
+///
+/// Gossipsub messages are filtered per recipient. Since every subscriber consumes its messages in
+/// order, a delayed gossipsub message also delays all later messages of the same topic for that
+/// recipient. Delayed direct messages and requests are delivered independently from each other.
+pub trait MessageFilter: Send + Sync {
+    fn filter(&self, message: &MockMessage<'_>) -> Delivery;
+}
+
+impl fmt::Debug for dyn MessageFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MessageFilter")
+    }
+}
@@ -9,6 +9,7 @@ use parking_lot::{Mutex, RwLock};
 use tokio::sync::{broadcast, mpsc, oneshot};
 
 use crate::{
+    filter::{Delivery, MessageFilter, MockMessage},
     network::{MockNetwork, MockRequestId},
     MockAddress, MockPeerId, ObservableHashMap,
 };
@@ -68,9 +69,20 @@ pub(crate) struct MockHubInner {
 
     /// Arcs to `AtomicBool`s for each network if they're connected.
     pub is_connected: HashMap<MockAddress, Arc<AtomicBool>>,
+
+    /// Filter that decides whether and when messages are delivered.
+    pub message_filter: Option<Arc<dyn MessageFilter>>,
 }
 
 impl MockHubInner {
+    /// Returns the delivery decision of the installed message filter for the given message.
+    /// Messages are always delivered immediately if no filter is installed.
+    pub fn filter_message(&self, message: &MockMessage<'_>) -> Delivery {
+        self.message_filter
+            .as_ref()
+            .map_or(Delivery::Deliver, |filter| filter.filter(message))
+    }
+
     /// Returns the requested MockTopic.
     pub fn get_topic(&mut self, topic_name: &String) -> Option<&MockTopic> {
         self.gossipsub_topics.get(topic_name)
@@ -148,4 +160,15 @@ impl MockHub {
         log::debug!("New mock network with address={}", address);
         MockNetwork::new(address, Arc::clone(&self.inner))
     }
+
+    /// Installs a filter that is consulted for every message sent through this hub, replacing any
+    /// previously installed filter.
+    pub fn set_message_filter<F: MessageFilter + 'static>(&self, filter: F) {
+        self.inner.lock().message_filter = Some(Arc::new(filter));
+    }
+
+    /// Removes the installed message filter, if any.
+    pub fn clear_message_filter(&self) {
+        self.inner.lock().message_filter = None;
+    }
 }
@@ -1,8 +1,10 @@
+mod filter;
 mod hub;
 mod network;
 mod observable_hash_map;
 
 use derive_more::{Display, From, Into};
+pub use filter::{Delivery, MessageFilter, MockMessage, MockMessageKind};
 pub use hub::MockHub;
 pub use network::{MockId, MockNetwork};
 use nimiq_network_interface::{multiaddr, Multiaddr};
@@ -72,13 +74,17 @@ pub async fn create_mock_validator_network(n: usize, dial: bool) -> Vec<MockNetw
 pub mod tests {
     use futures::{Stream, StreamExt};
     use nimiq_keys::{KeyPair, SecureGenerate};
-    use nimiq_network_interface::network::{Network, NetworkEvent, SubscribeEvents, Topic};
+    use nimiq_network_interface::network::{
+        Network, NetworkEvent, PubsubId, SubscribeEvents, Topic,
+    };
     use nimiq_test_log::test;
     use nimiq_test_utils::test_rng::test_rng;
     use nimiq_utils::tagged_signing::TaggedSignable;
     use serde::{Deserialize, Serialize};
 
-    use super::{network::MockNetworkError, MockHub, MockPeerId};
+    use super::{
+        network::MockNetworkError, Delivery, MessageFilter, MockHub, MockMessage, MockPeerId,
+    };
 
     pub async fn assert_peer_joined(
         events: &mut SubscribeEvents<MockPeerId>,
@@ -218,4 +224,55 @@ pub mod tests {
             net1.unsubscribe::<TestTopic>().await
         );
     }
+
+    /// Drops all messages sent by the given peer.
+    struct DropFrom(MockPeerId);
+
+    impl MessageFilter for DropFrom {
+        fn filter(&self, message: &MockMessage<'_>) -> Delivery {
+            if message.sender == self.0 {
+                Delivery::Drop
+            } else {
+                Delivery::Deliver
+            }
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_gossipsub_message_filter() {
+        let mut hub = MockHub::new();
+        let net1 = hub.new_network();
+        let net2 = hub.new_network();
+        let net3 = hub.new_network();
+        net1.dial_mock(&net2);
+        net1.dial_mock(&net3);
+
+        hub.set_message_filter(DropFrom(net2.peer_id()));
+
+        let mut messages = net1.subscribe::<TestTopic>().await.unwrap();
+        consume_stream(net2.subscribe::<TestTopic>().await.unwrap());
+        consume_stream(net3.subscribe::<TestTopic>().await.unwrap());
+
+        // The message of net2 is dropped, only the one of net3 arrives.
+        net2.publish::<TestTopic>(TestRecord { x: 1 })
+            .await
+            .unwrap();
+        net3.publish::<TestTopic>(TestRecord { x: 2 })
+            .await
+            .unwrap();
+
+        let (received_message, peer) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 2 });
+        assert_eq!(peer.propagation_source(), net3.peer_id());
+
+        // Once the filter is removed, messages of net2 are delivered again.
+        hub.clear_message_filter();
+        net2.publish::<TestTopic>(TestRecord { x: 3 })
+            .await
+            .unwrap();
+
+        let (received_message, peer) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 3 });
+        assert_eq!(peer.propagation_source(), net2.peer_id());
+    }
 }
@@ -26,6 +26,7 @@ use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
 
 use crate::{
+    filter::{Delivery, MockMessage, MockMessageKind},
     hub::{MockHubInner, RequestKey, ResponseSender},
     observable_hash_map, MockAddress, MockPeerId, ObservableHashMap,
 };
@@ -225,7 +226,47 @@ impl MockNetwork {
 
         let data = request.serialize_request();
 
+        let delivery = self.hub.lock().filter_message(&MockMessage {
+            sender: sender_id,
+            recipient: peer_id,
+            kind: MockMessageKind::Request {
+                request_type: RequestType::from_request::<Req>(),
+                data: &data,
+            },
+        });
+
         let request = (data, request_id, sender_id);
+        match delivery {
+            Delivery::Deliver => {}
+            Delivery::Delay(delay) => {
+                // Deliver the request in the background such that the sender isn't blocked.
+                // Responses are still subject to the request timeout.
+                let address = self.address;
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    if let Err(e) = sender.send(request).await {
+                        log::warn!(
+                            "Cannot send delayed request {} from {} to {} - {:?}",
+                            std::any::type_name::<Req>(),
+                            address,
+                            peer_id,
+                            e
+                        );
+                    }
+                });
+                return self.await_response::<Req>(request_id, rx).await;
+            }
+            Delivery::Drop => {
+                log::trace!(
+                    "Dropping request {} from {} to {}",
+                    std::any::type_name::<Req>(),
+                    self.address,
+                    peer_id,
+                );
+                return self.await_response::<Req>(request_id, rx).await;
+            }
+        }
+
         if let Err(e) = sender.send(request).await {
             log::warn!(
                 "Cannot send request {} from {} to {} - {:?}",
@@ -240,6 +281,14 @@ impl MockNetwork {
             ));
         }
 
+        self.await_response::<Req>(request_id, rx).await
+    }
+
+    async fn await_response<Req: RequestCommon>(
+        &self,
+        request_id: MockRequestId,
+        rx: oneshot::Receiver<Vec<u8>>,
+    ) -> Result<Req::Response, RequestError> {
         let result = tokio::time::timeout(MockNetwork::REQUEST_TIMEOUT, rx).await;
         match result {
             Ok(Ok(data)) => match Req::Response::deserialize_from_vec(&data[..]) {
@@ -323,8 +372,10 @@ impl MockNetwork {
     where
         T: Topic + Sync,
     {
+        let hub_inner = Arc::clone(&self.hub);
         let mut hub = self.hub.lock();
         let is_connected = Arc::clone(&self.is_connected);
+        let own_peer_id = self.peer_id();
 
         log::debug!(
             "Peer {} subscribing to topic '{}'",
@@ -342,16 +393,47 @@ impl MockNetwork {
 
         let stream = BroadcastStream::new(sender.subscribe()).filter_map(move |r| {
             let is_connected = Arc::clone(&is_connected);
+            let hub = Arc::clone(&hub_inner);
+            let topic_name = topic_name.clone();
 
             async move {
                 if is_connected.load(Ordering::SeqCst) {
                     match r {
-                        Ok((data, peer_id)) => match T::Item::deserialize_from_vec(&data) {
-                            Ok(item) => return Some((item, peer_id)),
-                            Err(e) => {
-                                log::warn!("Dropped item because deserialization failed: {}", e)
+                        Ok((data, peer_id)) => {
+                            // Our own messages are never filtered.
+                            let delivery = if peer_id == own_peer_id {
+                                Delivery::Deliver
+                            } else {
+                                hub.lock().filter_message(&MockMessage {
+                                    sender: peer_id,
+                                    recipient: own_peer_id,
+                                    kind: MockMessageKind::Gossipsub {
+                                        topic: &topic_name,
+                                        data: data.as_slice(),
+                                    },
+                                })
+                            };
+                            match delivery {
+                                Delivery::Deliver => {}
+                                Delivery::Delay(delay) => tokio::time::sleep(delay).await,
+                                Delivery::Drop => {
+                                    log::trace!(
+                                        "Dropping gossipsub message on topic '{}' from {} to {}",
+                                        topic_name,
+                                        peer_id,
+                                        own_peer_id,
+                                    );
+                                    return None;
+                                }
                             }
-                        },
+
+                            match T::Item::deserialize_from_vec(&data) {
+                                Ok(item) => return Some((item, peer_id)),
+                                Err(e) => {
+                                    log::warn!("Dropped item because deserialization failed: {}", e)
+                                }
+                            }
+                        }
                         Err(BroadcastStreamRecvError::Lagged(_)) => {
                             log::warn!("Mock gossipsub channel is lagging")
                         }
@@ -640,6 +722,10 @@ impl Network for MockNetwork {
         true
     }
 
+    async fn add_provided_services(&self, _services: Services) {
+        // Mock peers provide all services anyway.
+    }
+
     fn get_peer_info(&self, peer_id: Self::PeerId) -> Option<PeerInfo> {
         self.peers.read().get(&peer_id).cloned()
     }
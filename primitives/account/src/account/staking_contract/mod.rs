@@ -114,6 +114,17 @@ impl StakingContract {
         vec![]
     }
 
+    /// Get a list containing all stakers
+    /// IMPORTANT: This is a very expensive operation, iterating over all existing stakers in the contract.
+    pub fn get_stakers<T: DataStoreReadOps + DataStoreIterOps>(
+        &self,
+        data_store: &T,
+    ) -> Vec<Staker> {
+        StakingContractStoreRead::new(data_store)
+            .iter_stakers()
+            .collect()
+    }
+
     /// Get a list containing all validators
     /// IMPORTANT: This is a very expensive operation, iterating over all existing validators in the contract.
     pub fn get_validators<T: DataStoreReadOps + DataStoreIterOps>(
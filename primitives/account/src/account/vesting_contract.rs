@@ -67,7 +67,8 @@ impl VestingContract {
         Ok(())
     }
 
-    fn min_cap(&self, time: u64) -> Coin {
+    /// Returns the balance that is still locked in the contract at the given time.
+    pub fn min_cap(&self, time: u64) -> Coin {
         if self.time_step > 0 && self.step_amount > Coin::ZERO {
             let steps = (time as i128 - self.start_time as i128) / self.time_step as i128;
             let min_cap =
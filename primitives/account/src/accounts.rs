@@ -29,6 +29,17 @@ use crate::{
 /// An alias for the accounts tree.
 pub type AccountsTrie = MerkleRadixTrie;
 
+/// The outcome of executing a single transaction without persisting its changes.
+#[derive(Debug)]
+pub struct TransactionSimulation {
+    /// The receipt of the transaction. If the transaction failed, only its fee was deducted.
+    pub receipt: TransactionOperationReceipt,
+    /// The logs the transaction produced.
+    pub log: TransactionLog,
+    /// The error that made the transaction fail, if it did.
+    pub error: Option<AccountError>,
+}
+
 /// The Accounts struct is simply an wrapper containing a database environment and, more importantly,
 /// a MerkleRadixTrie with accounts as leaf values. This struct basically holds all the accounts in
 /// the blockchain. It also has methods to commit and revert transactions, so we can use it to
@@ -289,6 +300,46 @@ impl Accounts {
         Ok((state_hash, diff_hash, executed_txns))
     }
 
+    /// Executes a single transaction on top of the current state and returns its receipt and logs.
+    /// As in `exercise_transactions`, the changes are never persisted.
+    /// Returns an error if the transaction could not even be included as a failed transaction,
+    /// e.g. because the sender can't pay the fee.
+    pub fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+        block_state: &BlockState,
+    ) -> Result<TransactionSimulation, AccountError> {
+        let mut raw_txn = self.env.write_transaction();
+        let mut txn: WriteTransactionProxy = (&mut raw_txn).into();
+        assert!(self.is_complete(Some(&txn)), "Tree must be complete");
+
+        let mut log = TransactionLog::new(transaction.hash(), vec![]);
+        let result = match self.try_commit_transaction(&mut txn, transaction, block_state, &mut log)
+        {
+            Ok(receipt) => Ok(TransactionSimulation {
+                receipt: OperationReceipt::Ok(receipt),
+                log,
+                error: None,
+            }),
+            Err(error) => {
+                let fail_reason = FailReason::from(&error);
+                log.clear();
+                log.push_failed_log(transaction, fail_reason);
+
+                self.commit_failed_transaction(&mut txn, transaction, block_state, &mut log)
+                    .map(|receipt| TransactionSimulation {
+                        receipt: OperationReceipt::Err(receipt, fail_reason),
+                        log,
+                        error: Some(error),
+                    })
+            }
+        };
+
+        raw_txn.abort();
+
+        result
+    }
+
     pub fn commit(
         &self,
         txn: &mut WriteTransactionProxy,
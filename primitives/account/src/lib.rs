@@ -3,7 +3,7 @@
 extern crate log;
 
 #[cfg(feature = "accounts")]
-pub use crate::accounts::{Accounts, AccountsTrie};
+pub use crate::accounts::{Accounts, AccountsTrie, TransactionSimulation};
 #[cfg(feature = "interaction-traits")]
 pub use crate::data_store::{DataStore, DataStoreRead, DataStoreWrite};
 #[cfg(feature = "interaction-traits")]
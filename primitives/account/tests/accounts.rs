@@ -12,9 +12,10 @@ use nimiq_database::{
     volatile::VolatileDatabase,
 };
 use nimiq_genesis_builder::GenesisBuilder;
+use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::{Address, Ed25519PublicKey, KeyPair, PrivateKey, SecureGenerate};
 use nimiq_primitives::{
-    account::{AccountType, FailReason},
+    account::{AccountError, AccountType, FailReason},
     coin::Coin,
     networks::NetworkId,
     policy::Policy,
@@ -894,6 +895,89 @@ fn it_commits_valid_and_failing_txns() {
     );
 }
 
+#[test]
+fn it_simulates_transactions_without_changing_state() {
+    let key_pair = KeyPair::generate(&mut test_rng(false));
+    let sender = Address::from(&key_pair.public);
+    let recipient = Address::from([2u8; 20]);
+
+    let accounts = TestCommitRevert::with_initial_state(&[(
+        sender.clone(),
+        Account::Basic(BasicAccount {
+            balance: Coin::from_u64_unchecked(1000),
+        }),
+    )]);
+    let state_root = accounts.get_root_hash_assert(None);
+
+    let create_tx = |value: u64, fee: u64| {
+        let mut tx = Transaction::new_basic(
+            sender.clone(),
+            recipient.clone(),
+            Coin::from_u64_unchecked(value),
+            Coin::from_u64_unchecked(fee),
+            1,
+            NetworkId::Dummy,
+        );
+        let signature = key_pair.sign(&tx.serialize_content()[..]);
+        tx.proof = SignatureProof::from_ed25519(key_pair.public, signature).serialize_to_vec();
+        tx
+    };
+    let block_state = BlockState::new(1, 200);
+
+    // A successful transaction.
+    let tx = create_tx(500, 100);
+    let simulation = accounts.simulate_transaction(&tx, &block_state).unwrap();
+    assert!(matches!(simulation.receipt, OperationReceipt::Ok(_)));
+    assert!(simulation.error.is_none());
+    assert!(!simulation.log.failed);
+    assert_eq!(simulation.log.tx_hash, tx.hash::<Blake2bHash>());
+    assert_eq!(
+        simulation.log.logs,
+        vec![
+            Log::PayFee {
+                from: sender.clone(),
+                fee: tx.fee
+            },
+            Log::Transfer {
+                from: sender.clone(),
+                to: recipient.clone(),
+                amount: tx.value,
+                data: None
+            }
+        ]
+    );
+
+    // A transaction that fails, but whose fee can be paid.
+    let tx = create_tx(2000, 100);
+    let simulation = accounts.simulate_transaction(&tx, &block_state).unwrap();
+    assert_eq!(
+        simulation.receipt,
+        TransactionOperationReceipt::Err(
+            TransactionReceipt::default(),
+            FailReason::InsufficientFunds
+        )
+    );
+    assert_eq!(
+        simulation.error,
+        Some(AccountError::InsufficientFunds {
+            needed: Coin::from_u64_unchecked(2100),
+            balance: Coin::from_u64_unchecked(1000)
+        })
+    );
+    assert!(simulation.log.failed);
+
+    // A transaction whose fee can't be paid.
+    let tx = create_tx(0, 2000);
+    assert!(accounts.simulate_transaction(&tx, &block_state).is_err());
+
+    // None of the simulations changed the state.
+    assert_eq!(accounts.get_root_hash_assert(None), state_root);
+    assert_eq!(
+        accounts.get_complete(&sender, None).balance(),
+        Coin::from_u64_unchecked(1000)
+    );
+}
+
 #[test]
 fn can_revert_transactions() {
     let accounts = TestCommitRevert::new();
@@ -153,6 +153,12 @@ pub enum FailReason {
 
 impl From<AccountError> for FailReason {
     fn from(err: AccountError) -> Self {
+        FailReason::from(&err)
+    }
+}
+
+impl From<&AccountError> for FailReason {
+    fn from(err: &AccountError) -> Self {
         match err {
             AccountError::InsufficientFunds { .. } => FailReason::InsufficientFunds,
             AccountError::TypeMismatch { .. } => FailReason::TypeMismatch,
@@ -242,6 +242,26 @@ pub struct WebauthnExtraFields {
 }
 
 impl WebauthnExtraFields {
+    /// The `origin` field of the `clientDataJSON`, exactly as it appears in the JSON.
+    pub fn origin_json_str(&self) -> &str {
+        &self.origin_json_str
+    }
+
+    /// Whether the `clientDataJSON` has a `"crossOrigin":false` field.
+    pub fn has_cross_origin_field(&self) -> bool {
+        self.has_cross_origin_field
+    }
+
+    /// Extra, unknown fields of the `clientDataJSON`, exactly as they appear in the JSON.
+    pub fn client_data_extra_json(&self) -> &str {
+        &self.client_data_extra_json
+    }
+
+    /// Extra data included in the signed data after the RP ID.
+    pub fn authenticator_data_suffix(&self) -> &[u8] {
+        &self.authenticator_data_suffix
+    }
+
     fn from_client_data_json(
         client_data_json: &str,
         authenticator_data_suffix: Vec<u8>,
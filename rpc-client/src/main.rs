@@ -3,9 +3,9 @@ use clap::Parser;
 use nimiq_jsonrpc_client::{websocket::WebsocketClient, ArcClient, Client as RPCclient};
 use nimiq_jsonrpc_core::Credentials;
 use nimiq_rpc_interface::{
-    blockchain::BlockchainProxy, consensus::ConsensusProxy, mempool::MempoolProxy,
-    network::NetworkProxy, policy::PolicyProxy, validator::ValidatorProxy, wallet::WalletProxy,
-    zkp_component::ZKPComponentProxy,
+    admin::AdminProxy, blockchain::BlockchainProxy, consensus::ConsensusProxy,
+    mempool::MempoolProxy, network::NetworkProxy, policy::PolicyProxy, validator::ValidatorProxy,
+    wallet::WalletProxy, zkp_component::ZKPComponentProxy,
 };
 use url::Url;
 pub mod subcommands;
@@ -45,6 +45,18 @@ enum Command {
     #[clap(name = "tx", flatten)]
     Transaction(TransactionCommand),
 
+    /// Manage multi-signature accounts and sign their transactions.
+    #[clap(flatten)]
+    Multisig(MultisigCommand),
+
+    /// Create, sign and finalize partially signed transactions.
+    #[clap(flatten)]
+    Pst(PstCommand),
+
+    /// Schedule transactions to be sent once a block height, time or contract state is reached.
+    #[clap(flatten)]
+    Schedule(ScheduleCommand),
+
     /// Shows local mempool information and push transactions to the mempool.
     #[clap(flatten)]
     Mempool(MempoolCommand),
@@ -61,6 +73,10 @@ enum Command {
     /// Shows the zkp information.
     #[clap(flatten)]
     Zkp(ZKPComponentCommand),
+
+    /// Inspects and backs up the node's database.
+    #[clap(flatten)]
+    Admin(AdminCommand),
 }
 
 impl Command {
@@ -70,10 +86,14 @@ impl Command {
             Command::Blockchain(command) => command.handle_subcommand(client).await,
             Command::Account(command) => command.handle_subcommand(client).await,
             Command::Transaction(command) => command.handle_subcommand(client).await,
+            Command::Multisig(command) => command.handle_subcommand(client).await,
+            Command::Pst(command) => command.handle_subcommand(client).await,
+            Command::Schedule(command) => command.handle_subcommand(client).await,
             Command::Network(command) => command.handle_subcommand(client).await,
             Command::Mempool(command) => command.handle_subcommand(client).await,
             Command::Validator(command) => command.handle_subcommand(client).await,
             Command::Zkp(command) => command.handle_subcommand(client).await,
+            Command::Admin(command) => command.handle_subcommand(client).await,
         }
     }
 }
@@ -88,6 +108,7 @@ pub struct Client {
     pub validator: ValidatorProxy<ArcClient<WebsocketClient>>,
     pub network: NetworkProxy<ArcClient<WebsocketClient>>,
     pub zkp_component: ZKPComponentProxy<ArcClient<WebsocketClient>>,
+    pub admin: AdminProxy<ArcClient<WebsocketClient>>,
 }
 
 impl Client {
@@ -103,6 +124,7 @@ impl Client {
             validator: ValidatorProxy::new(client.clone()),
             network: NetworkProxy::new(client.clone()),
             zkp_component: ZKPComponentProxy::new(client.clone()),
+            admin: AdminProxy::new(client.clone()),
             ws_client: client,
         })
     }
@@ -1,3 +1,5 @@
+use std::{fs, path::PathBuf};
+
 use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
@@ -92,6 +94,115 @@ pub enum AccountCommand {
         is_hex: bool,
     },
 
+    /// Imports a 24 words BIP39 mnemonic and stores its seed encrypted. Accounts can then be
+    /// derived from the seed.
+    ImportMnemonic {
+        /// Encryption password.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// The optional BIP39 password protecting the mnemonic.
+        #[clap(long)]
+        mnemonic_password: Option<String>,
+
+        /// The words of the mnemonic.
+        #[clap(num_args = 1.., required = true)]
+        mnemonic: Vec<String>,
+    },
+
+    /// Lists the identifiers of all imported seeds.
+    ListSeeds {},
+
+    /// Derives an account from an imported seed and imports it, locked with the seed's password.
+    Derive {
+        /// Encryption password of the seed.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// The derivation path. If absent, the next unused account below m/44'/242'/0' is derived.
+        #[clap(long)]
+        path: Option<String>,
+
+        /// The identifier of the seed.
+        seed_id: Address,
+    },
+
+    /// Lists the accounts derived from imported seeds.
+    ListDerived {
+        /// Only lists the accounts derived from this seed.
+        seed_id: Option<Address>,
+    },
+
+    /// Imports all accounts of a seed that have been used in the transaction history.
+    Discover {
+        /// Encryption password of the seed.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// The number of consecutive unused accounts after which the discovery stops (at most 100).
+        #[clap(long)]
+        gap_limit: Option<u32>,
+
+        /// The identifier of the seed.
+        seed_id: Address,
+    },
+
+    /// Exports accounts as an encrypted backup file.
+    ExportBackup {
+        /// Password of the accounts, if they are not unlocked.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// Encryption password of the backup.
+        #[clap(long)]
+        backup_password: String,
+
+        /// The file to write the backup to.
+        #[clap(short, long)]
+        output: PathBuf,
+
+        /// The addresses of the accounts to export. If absent, all accounts are exported.
+        addresses: Vec<Address>,
+    },
+
+    /// Imports the accounts of an encrypted backup file. The accounts remain locked after this
+    /// operation.
+    ImportBackup {
+        /// Encryption password for the imported accounts.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// Encryption password of the backup.
+        #[clap(long)]
+        backup_password: String,
+
+        /// The backup file.
+        file: PathBuf,
+    },
+
+    /// Re-encrypts stored accounts with a new password and/or key derivation parameters.
+    Reencrypt {
+        /// Current password of the accounts.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// New password of the accounts. If absent, the current password is kept.
+        #[clap(long)]
+        new_password: Option<String>,
+
+        /// The number of key derivation iterations.
+        #[clap(long)]
+        iterations: Option<u32>,
+
+        /// The memory cost of the key derivation in KiB.
+        #[clap(long)]
+        memory_cost: Option<u32>,
+
+        /// The addresses of the accounts, seeds or multisig accounts to re-encrypt. If absent, all
+        /// accounts and seeds are re-encrypted.
+        addresses: Vec<Address>,
+    },
+
     /// Queries all accounts in the accounts tree
     GetAll {},
 
@@ -114,7 +225,7 @@ impl HandleSubcommand for AccountCommand {
                     } else {
                         let account = client
                             .blockchain
-                            .get_account_by_address(address.clone())
+                            .get_account_by_address(address.clone(), None)
                             .await?;
                         println!("{}: {:#?}", address.to_user_friendly_address(), account);
                     }
@@ -172,15 +283,112 @@ impl HandleSubcommand for AccountCommand {
                         .await?
                 );
             }
+            AccountCommand::ImportMnemonic {
+                password,
+                mnemonic_password,
+                mnemonic,
+            } => {
+                let seed_id = client
+                    .wallet
+                    .import_mnemonic(mnemonic.join(" "), mnemonic_password, password)
+                    .await?;
+                println!("{seed_id:#?}");
+            }
+            AccountCommand::ListSeeds {} => {
+                println!("{:#?}", client.wallet.list_seeds().await?);
+            }
+            AccountCommand::Derive {
+                password,
+                path,
+                seed_id,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .derive_account(seed_id, path, password)
+                        .await?
+                );
+            }
+            AccountCommand::ListDerived { seed_id } => {
+                println!("{:#?}", client.wallet.list_derived_accounts(seed_id).await?);
+            }
+            AccountCommand::Discover {
+                password,
+                gap_limit,
+                seed_id,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .discover_accounts(seed_id, password, gap_limit)
+                        .await?
+                );
+            }
+            AccountCommand::ExportBackup {
+                password,
+                backup_password,
+                output,
+                addresses,
+            } => {
+                let addresses = (!addresses.is_empty()).then_some(addresses);
+                let backup = client
+                    .wallet
+                    .export_backup(addresses, password, backup_password)
+                    .await?
+                    .data;
+                fs::write(&output, hex::decode(backup)?)?;
+                println!("Backup written to {}", output.display());
+            }
+            AccountCommand::ImportBackup {
+                password,
+                backup_password,
+                file,
+            } => {
+                let backup = hex::encode(fs::read(file)?);
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .import_backup(backup, backup_password, password)
+                        .await?
+                );
+            }
+            AccountCommand::Reencrypt {
+                password,
+                new_password,
+                iterations,
+                memory_cost,
+                addresses,
+            } => {
+                let addresses = (!addresses.is_empty()).then_some(addresses);
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .reencrypt_accounts(
+                            addresses,
+                            password,
+                            new_password,
+                            iterations,
+                            memory_cost,
+                        )
+                        .await?
+                );
+            }
             AccountCommand::Get { address } => {
                 println!(
                     "{:#?}",
-                    client.blockchain.get_account_by_address(address).await?
+                    client
+                        .blockchain
+                        .get_account_by_address(address, None)
+                        .await?
                 );
             }
 
             AccountCommand::GetAll {} => {
-                println!("{:#?}", client.blockchain.get_accounts().await?);
+                println!("{:#?}", client.blockchain.get_accounts(None).await?);
             }
         }
 
@@ -0,0 +1,55 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use nimiq_rpc_interface::admin::AdminInterface;
+
+use super::accounts_subcommands::HandleSubcommand;
+use crate::Client;
+
+#[derive(Debug, Parser)]
+pub enum AdminCommand {
+    /// Shows the page usage and fragmentation of the node's database and the sizes of its tables.
+    DatabaseStats {},
+
+    /// Writes a compacted copy of the node's database to a directory on the node's machine,
+    /// without stopping the node.
+    BackupDatabase {
+        /// The directory to write the backup to. It must not exist or be empty.
+        path: String,
+    },
+
+    /// Reverts the chain of the node to the given block. The node syncs the reverted blocks again
+    /// afterwards.
+    RevertChain {
+        /// The block number to revert to.
+        block_number: u32,
+
+        /// Allows reverting below the last macro block, within the revert window of the node.
+        #[clap(long)]
+        force: bool,
+    },
+}
+
+#[async_trait]
+impl HandleSubcommand for AdminCommand {
+    async fn handle_subcommand(self, mut client: Client) -> Result<Client, Error> {
+        match self {
+            AdminCommand::DatabaseStats {} => {
+                println!("{:#?}", client.admin.get_database_stats().await?);
+            }
+            AdminCommand::BackupDatabase { path } => {
+                println!("{:#?}", client.admin.backup_database(path).await?);
+            }
+            AdminCommand::RevertChain {
+                block_number,
+                force,
+            } => {
+                println!(
+                    "{:#?}",
+                    client.admin.revert_chain(block_number, force).await?
+                );
+            }
+        }
+        Ok(client)
+    }
+}
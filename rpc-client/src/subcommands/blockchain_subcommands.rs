@@ -4,7 +4,9 @@ use clap::{ArgGroup, Parser};
 use futures::StreamExt;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
-use nimiq_rpc_interface::{blockchain::BlockchainInterface, types::LogType};
+use nimiq_rpc_interface::{
+    blockchain::BlockchainInterface, consensus::ConsensusInterface, types::LogType,
+};
 
 use super::accounts_subcommands::HandleSubcommand;
 use crate::Client;
@@ -97,6 +99,45 @@ pub enum BlockchainCommand {
         just_hash: bool,
     },
 
+    /// Lists the latest HTLCs that the given address created or is the sender or recipient of,
+    /// together with their current status. Only available on history nodes.
+    HtlcsByAddress {
+        /// The address to query by.
+        address: Address,
+
+        /// Max number of HTLCs to fetch. If absent it defaults to 500.
+        #[clap(long)]
+        max: Option<u16>,
+    },
+
+    /// Lists the latest vesting contracts owned by the given address, together with their current
+    /// status. Only available on history nodes.
+    VestingContractsByOwner {
+        /// The owner address to query by.
+        address: Address,
+
+        /// Max number of vesting contracts to fetch. If absent it defaults to 500.
+        #[clap(long)]
+        max: Option<u16>,
+    },
+
+    /// Decodes a serialized transaction, including its data and proof.
+    DecodeTransaction {
+        /// The serialized transaction.
+        raw_tx: String,
+    },
+
+    /// Executes a serialized transaction on top of the current state without broadcasting it and
+    /// shows whether it would succeed, the fee it would pay and the logs it would produce.
+    SimulateTransaction {
+        /// The serialized transaction.
+        raw_tx: String,
+
+        /// The block number to simulate the transaction in. If absent, the next block is used.
+        #[clap(short, long)]
+        block_number: Option<u32>,
+    },
+
     /// Returns the information for the slot owner at the given block height and offset. The
     /// offset is optional, it will default to the block number for micro blocks and to the round number for macro blocks.
     /// We only have this information available for the last 2 batches at most.
@@ -143,6 +184,61 @@ pub enum BlockchainCommand {
     /// Lists the current stakes from the staking contract.
     Stakes {},
 
+    /// Attributes the rewards a validator received in the given epoch to its stakers.
+    /// Only available on history nodes.
+    StakerRewards {
+        /// The validator address to query by.
+        address: Address,
+
+        /// The epoch for which the rewards are attributed.
+        epoch_number: u32,
+
+        /// The commission deducted from the stakers' rewards, in basis points.
+        #[clap(short, long)]
+        commission: Option<u16>,
+    },
+
+    /// Lists the history of a validator from most recent to least recent.
+    /// Only available on history nodes.
+    ValidatorHistory {
+        /// The validator address to query by.
+        address: Address,
+
+        /// Max number of events to fetch. If absent it defaults to 500.
+        #[clap(long)]
+        max: Option<u16>,
+
+        /// Only fetch the events preceding the event with this transaction hash.
+        #[clap(long)]
+        start_at: Option<Blake2bHash>,
+    },
+
+    /// Lists the history of a staker from most recent to least recent.
+    /// Only available on history nodes.
+    StakerHistory {
+        /// The staker address to query by.
+        address: Address,
+
+        /// Max number of events to fetch. If absent it defaults to 500.
+        #[clap(long)]
+        max: Option<u16>,
+
+        /// Only fetch the events preceding the event with this transaction hash.
+        #[clap(long)]
+        start_at: Option<Blake2bHash>,
+    },
+
+    /// Lists the number of stakers and the total stake of a validator for the most recent epochs.
+    /// Only available on history nodes.
+    ValidatorStakersHistory {
+        /// The validator address to query by.
+        address: Address,
+
+        /// Max number of epochs to fetch. If absent it defaults to 30.
+        #[clap(long)]
+        max_epochs: Option<u16>,
+    },
+
     /// Follow the head of the blockchain.
     FollowHead {
         /// Show the full block instead of only the hash.
@@ -167,6 +263,19 @@ pub enum BlockchainCommand {
         #[clap(short = 'l', long, value_enum)]
         log_types: Vec<LogType>,
     },
+
+    /// Follow the transactions of the specified addresses from the mempool until they are
+    /// finalized, including retractions caused by rebranches.
+    FollowTransactionsOfAddresses {
+        /// List of all addresses to follow.
+        #[clap(short = 'a', long, required = true)]
+        addresses: Vec<Address>,
+
+        /// The number of confirmations after which a transaction is reported as confirmed.
+        /// If absent it defaults to 10.
+        #[clap(short, long)]
+        confirmations: Option<u32>,
+    },
 }
 
 #[async_trait]
@@ -202,6 +311,24 @@ impl HandleSubcommand for BlockchainCommand {
             BlockchainCommand::EpochNumber {} => {
                 println!("{:#?}", client.blockchain.get_epoch_number().await?)
             }
+            BlockchainCommand::DecodeTransaction { raw_tx } => {
+                println!(
+                    "{:#?}",
+                    client.consensus.get_raw_transaction_info(raw_tx).await?
+                )
+            }
+            BlockchainCommand::SimulateTransaction {
+                raw_tx,
+                block_number,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .blockchain
+                        .simulate_transaction(raw_tx, block_number)
+                        .await?
+                )
+            }
             BlockchainCommand::SlotAt {
                 block_number,
                 offset,
@@ -285,43 +412,106 @@ impl HandleSubcommand for BlockchainCommand {
                     )
                 }
             }
+            BlockchainCommand::HtlcsByAddress { address, max } => println!(
+                "{:#?}",
+                client.blockchain.get_htlcs_by_address(address, max).await?
+            ),
+            BlockchainCommand::VestingContractsByOwner { address, max } => println!(
+                "{:#?}",
+                client
+                    .blockchain
+                    .get_vesting_contracts_by_owner(address, max)
+                    .await?
+            ),
             BlockchainCommand::PenalizedSlots { previous_penalized } => {
                 if previous_penalized {
                     println!(
                         "{:#?}",
-                        client.blockchain.get_current_penalized_slots().await?
+                        client.blockchain.get_current_penalized_slots(None).await?
                     )
                 } else {
                     println!(
                         "{:#?}",
-                        client.blockchain.get_previous_penalized_slots().await?
+                        client.blockchain.get_previous_penalized_slots(None).await?
                     )
                 }
             }
             BlockchainCommand::ValidatorByAddress { address } => println!(
                 "{:#?}",
-                client.blockchain.get_validator_by_address(address).await?
+                client
+                    .blockchain
+                    .get_validator_by_address(address, None)
+                    .await?
             ),
 
             BlockchainCommand::Validators {} => {
-                println!("{:#?}", client.blockchain.get_validators().await?)
+                println!("{:#?}", client.blockchain.get_validators(None).await?)
             }
 
             BlockchainCommand::StakersByValidator { address } => println!(
                 "{:#?}",
                 client
                     .blockchain
-                    .get_stakers_by_validator_address(address)
+                    .get_stakers_by_validator_address(address, None)
                     .await?
             ),
             BlockchainCommand::Staker { address } => {
                 println!(
                     "{:#?}",
-                    client.blockchain.get_staker_by_address(address).await?
+                    client
+                        .blockchain
+                        .get_staker_by_address(address, None)
+                        .await?
                 )
             }
+            BlockchainCommand::StakerRewards {
+                address,
+                epoch_number,
+                commission,
+            } => println!(
+                "{:#?}",
+                client
+                    .blockchain
+                    .get_staker_rewards(address, epoch_number, commission)
+                    .await?
+            ),
+            BlockchainCommand::ValidatorHistory {
+                address,
+                max,
+                start_at,
+            } => println!(
+                "{:#?}",
+                client
+                    .blockchain
+                    .get_validator_history(address, max, start_at)
+                    .await?
+            ),
+            BlockchainCommand::StakerHistory {
+                address,
+                max,
+                start_at,
+            } => println!(
+                "{:#?}",
+                client
+                    .blockchain
+                    .get_staker_history(address, max, start_at)
+                    .await?
+            ),
+            BlockchainCommand::ValidatorStakersHistory {
+                address,
+                max_epochs,
+            } => println!(
+                "{:#?}",
+                client
+                    .blockchain
+                    .get_validator_stakers_history(address, max_epochs)
+                    .await?
+            ),
             BlockchainCommand::Stakes {} => {
-                println!("{:#?}", client.blockchain.get_active_validators().await?);
+                println!(
+                    "{:#?}",
+                    client.blockchain.get_active_validators(None).await?
+                );
             }
 
             BlockchainCommand::FollowHead { block: show_block } => {
@@ -364,6 +554,19 @@ impl HandleSubcommand for BlockchainCommand {
                     println!("{blocklog:#?}");
                 }
             }
+            BlockchainCommand::FollowTransactionsOfAddresses {
+                addresses,
+                confirmations,
+            } => {
+                let mut stream = client
+                    .consensus
+                    .subscribe_for_transactions_by_addresses(addresses, confirmations)
+                    .await?;
+
+                while let Some(event) = stream.next().await {
+                    println!("{event:#?}");
+                }
+            }
         }
         Ok(client)
     }
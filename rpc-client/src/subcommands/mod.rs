@@ -1,17 +1,25 @@
 pub use accounts_subcommands::{AccountCommand, HandleSubcommand};
+pub use admin_subcommands::AdminCommand;
 pub use blockchain_subcommands::BlockchainCommand;
 pub use mempool_subcommands::MempoolCommand;
+pub use multisig_subcommands::MultisigCommand;
 pub use network_subcommands::NetworkCommand;
 pub use policy_subcommands::PolicyCommand;
+pub use pst_subcommands::PstCommand;
+pub use schedule_subcommands::ScheduleCommand;
 pub use transactions_subcommands::TransactionCommand;
 pub use validator_subcommands::ValidatorCommand;
 pub use zkp_component_subcommands::ZKPComponentCommand;
 
 mod accounts_subcommands;
+mod admin_subcommands;
 mod blockchain_subcommands;
 mod mempool_subcommands;
+mod multisig_subcommands;
 mod network_subcommands;
 mod policy_subcommands;
+mod pst_subcommands;
+mod schedule_subcommands;
 mod transactions_subcommands;
 mod validator_subcommands;
 mod zkp_component_subcommands;
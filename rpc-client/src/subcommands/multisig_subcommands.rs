@@ -0,0 +1,155 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use nimiq_keys::{Address, Ed25519PublicKey};
+use nimiq_primitives::coin::Coin;
+use nimiq_rpc_interface::wallet::WalletInterface;
+
+use super::accounts_subcommands::HandleSubcommand;
+use crate::Client;
+
+#[derive(Debug, Parser)]
+pub enum MultisigCommand {
+    /// Registers a k-of-n multi-signature account in the wallet. The account of the local
+    /// participant must have been imported before.
+    ImportMultisig {
+        /// The public key of the local participant.
+        owner_public_key: Ed25519PublicKey,
+
+        /// The minimum number of signatures required to send a transaction.
+        #[clap(long)]
+        min_signatures: u8,
+
+        /// The public keys of all participants.
+        #[clap(long, num_args = 1.., required = true)]
+        public_keys: Vec<Ed25519PublicKey>,
+    },
+
+    /// Lists all registered multi-signature accounts.
+    ListMultisig {},
+
+    /// Removes a registered multi-signature account from the wallet.
+    RemoveMultisig {
+        /// The address of the multi-signature account.
+        address: Address,
+    },
+
+    /// Creates a partially signed basic transaction sent from a registered multi-signature account.
+    CreateMultisigTx {
+        /// The address of the multi-signature account.
+        multisig_address: Address,
+
+        /// Recipient for this transaction. This must be a basic account.
+        recipient: Address,
+
+        /// The amount of NIM to be sent.
+        value: Coin,
+
+        /// The associated transaction fee to be paid. If absent it defaults to 0 NIM.
+        #[clap(short, long, default_value = "0")]
+        fee: Coin,
+
+        /// The block height from which on the transaction could be applied.
+        #[clap(short, long)]
+        validity_start_height: u32,
+
+        /// The public keys of the participants that will sign the transaction.
+        #[clap(long, num_args = 1.., required = true)]
+        signers: Vec<Ed25519PublicKey>,
+    },
+
+    /// Adds the commitments of the local participant to a partially signed transaction sent from
+    /// a registered multi-signature account.
+    CommitMultisigTx {
+        /// The partially signed transaction in hexadecimal.
+        pst: String,
+
+        /// The password of the local participant's account, if it is not unlocked.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+    },
+
+    /// Adds the partial signature of the local participant to a partially signed transaction sent
+    /// from a registered multi-signature account.
+    SignMultisigTx {
+        /// The partially signed transaction in hexadecimal.
+        pst: String,
+
+        /// The password of the local participant's account, if it is not unlocked.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+    },
+}
+
+#[async_trait]
+impl HandleSubcommand for MultisigCommand {
+    async fn handle_subcommand(self, mut client: Client) -> Result<Client, Error> {
+        match self {
+            MultisigCommand::ImportMultisig {
+                owner_public_key,
+                min_signatures,
+                public_keys,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .import_multisig_account(public_keys, min_signatures, owner_public_key)
+                        .await?
+                );
+            }
+            MultisigCommand::ListMultisig {} => {
+                println!("{:#?}", client.wallet.list_multisig_accounts().await?);
+            }
+            MultisigCommand::RemoveMultisig { address } => {
+                println!(
+                    "{:#?}",
+                    client.wallet.remove_multisig_account(address).await?
+                );
+            }
+            MultisigCommand::CreateMultisigTx {
+                multisig_address,
+                recipient,
+                value,
+                fee,
+                validity_start_height,
+                signers,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .create_multisig_transaction(
+                            multisig_address,
+                            recipient,
+                            value,
+                            fee,
+                            validity_start_height,
+                            signers
+                        )
+                        .await?
+                );
+            }
+            MultisigCommand::CommitMultisigTx { pst, password } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .commit_multisig_transaction(pst, password)
+                        .await?
+                );
+            }
+            MultisigCommand::SignMultisigTx { pst, password } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .sign_multisig_transaction(pst, password)
+                        .await?
+                );
+            }
+        }
+
+        Ok(client)
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use nimiq_keys::{Address, Ed25519PublicKey};
+use nimiq_rpc_interface::wallet::WalletInterface;
+
+use super::accounts_subcommands::HandleSubcommand;
+use crate::Client;
+
+#[derive(Debug, Parser)]
+pub enum PstCommand {
+    /// Creates a partially signed transaction for a transaction sent from a single-signature account.
+    CreatePst {
+        /// The serialized transaction in hexadecimal.
+        raw_tx: String,
+
+        /// The public key that has to sign the transaction.
+        public_key: Ed25519PublicKey,
+    },
+
+    /// Creates a partially signed transaction for a transaction sent from a multi-signature account.
+    CreateMultisigPst {
+        /// The serialized transaction in hexadecimal.
+        raw_tx: String,
+
+        /// The public keys of all owners of the multi-signature account.
+        #[clap(long, num_args = 1.., required = true)]
+        public_keys: Vec<Ed25519PublicKey>,
+
+        /// The minimum number of signatures required by the multi-signature account.
+        #[clap(long)]
+        min_signatures: u8,
+
+        /// The public keys of the owners that will sign the transaction.
+        #[clap(long, num_args = 1.., required = true)]
+        signers: Vec<Ed25519PublicKey>,
+    },
+
+    /// Adds the commitments of the given account to a multi-signature partially signed transaction.
+    /// The secret nonces are kept by the node until the account signs the transaction.
+    CommitPst {
+        /// The partially signed transaction in hexadecimal.
+        pst: String,
+
+        /// The address of the signing account.
+        address: Address,
+
+        /// The password of the account, if it is not unlocked.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+    },
+
+    /// Adds the (partial) signature of the given account to a partially signed transaction.
+    SignPst {
+        /// The partially signed transaction in hexadecimal.
+        pst: String,
+
+        /// The address of the signing account.
+        address: Address,
+
+        /// The password of the account, if it is not unlocked.
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+    },
+
+    /// Combines the commitments and signatures of multiple copies of a partially signed transaction.
+    CombinePsts {
+        /// The partially signed transactions in hexadecimal.
+        #[clap(num_args = 1.., required = true)]
+        psts: Vec<String>,
+    },
+
+    /// Turns a complete partially signed transaction into a serialized transaction.
+    FinalizePst {
+        /// The partially signed transaction in hexadecimal.
+        pst: String,
+    },
+}
+
+#[async_trait]
+impl HandleSubcommand for PstCommand {
+    async fn handle_subcommand(self, mut client: Client) -> Result<Client, Error> {
+        match self {
+            PstCommand::CreatePst { raw_tx, public_key } => {
+                println!("{:#?}", client.wallet.create_pst(raw_tx, public_key).await?);
+            }
+            PstCommand::CreateMultisigPst {
+                raw_tx,
+                public_keys,
+                min_signatures,
+                signers,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .create_multisig_pst(raw_tx, public_keys, min_signatures, signers)
+                        .await?
+                );
+            }
+            PstCommand::CommitPst {
+                pst,
+                address,
+                password,
+            } => {
+                println!(
+                    "{:#?}",
+                    client.wallet.commit_pst(pst, address, password).await?
+                );
+            }
+            PstCommand::SignPst {
+                pst,
+                address,
+                password,
+            } => {
+                println!(
+                    "{:#?}",
+                    client.wallet.sign_pst(pst, address, password).await?
+                );
+            }
+            PstCommand::CombinePsts { psts } => {
+                println!("{:#?}", client.wallet.combine_psts(psts).await?);
+            }
+            PstCommand::FinalizePst { pst } => {
+                println!("{:#?}", client.wallet.finalize_pst(pst).await?);
+            }
+        }
+
+        Ok(client)
+    }
+}
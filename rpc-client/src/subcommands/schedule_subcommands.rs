@@ -0,0 +1,203 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
+use nimiq_rpc_interface::{consensus::ConsensusInterface, types::ScheduleTrigger};
+
+use super::accounts_subcommands::HandleSubcommand;
+use crate::Client;
+
+#[derive(Debug, Parser)]
+pub enum ScheduleCommand {
+    /// Schedules a signed transaction to be sent to the network once the trigger is reached.
+    ScheduleRawTx {
+        /// The serialized transaction in hexadecimal.
+        raw_tx: String,
+
+        /// The trigger: `block:<number>`, `time:<milliseconds>`, `htlc:<contract address>` or
+        /// `vesting:<contract address>:<value in luna>`.
+        #[clap(short, long)]
+        trigger: ScheduleTrigger,
+    },
+
+    /// Schedules a basic transaction to be sent to the network once the trigger is reached.
+    /// The sender wallet must be unlocked when the trigger is reached.
+    ScheduleBasicTx {
+        /// The wallet used to sign the transaction.
+        wallet: Address,
+
+        /// Recipient for this transaction. This must be a basic account.
+        recipient: Address,
+
+        /// The amount of NIM to be sent.
+        value: Coin,
+
+        /// The associated transaction fee to be paid. If absent it defaults to 0 NIM.
+        #[clap(short, long, default_value = "0")]
+        fee: Coin,
+
+        /// The trigger: `block:<number>`, `time:<milliseconds>`, `htlc:<contract address>` or
+        /// `vesting:<contract address>:<value in luna>`.
+        #[clap(short, long)]
+        trigger: ScheduleTrigger,
+    },
+
+    /// Schedules a transaction redeeming an HTLC contract once its timeout is reached.
+    /// The sender wallet must be unlocked when the timeout is reached.
+    ScheduleRedeemHTLCTimeout {
+        /// The wallet used to sign the transaction. This should be the sender of the HTLC.
+        wallet: Address,
+
+        /// The HTLC contract address.
+        contract_address: Address,
+
+        /// The address of the basic account that will receive the funds.
+        recipient: Address,
+
+        /// The amount of NIM to be redeemed.
+        value: Coin,
+
+        /// The associated transaction fee to be paid. If absent it defaults to 0 NIM.
+        #[clap(short, long, default_value = "0")]
+        fee: Coin,
+    },
+
+    /// Schedules a transaction redeeming a vesting contract once the given value is unlocked.
+    /// The sender wallet must be unlocked when the value is unlocked.
+    ScheduleVestingRedeem {
+        /// The wallet used to sign the transaction. This should be the owner of the contract.
+        wallet: Address,
+
+        /// The vesting contract address.
+        contract_address: Address,
+
+        /// The address of the basic account that will receive the funds.
+        recipient: Address,
+
+        /// The amount of NIM to be redeemed.
+        value: Coin,
+
+        /// The associated transaction fee to be paid. If absent it defaults to 0 NIM.
+        #[clap(short, long, default_value = "0")]
+        fee: Coin,
+    },
+
+    /// Lists all scheduled transactions.
+    ListScheduled {},
+
+    /// Shows the scheduled transaction with the given identifier.
+    GetScheduled {
+        /// The identifier of the scheduled transaction.
+        id: u32,
+    },
+
+    /// Cancels a scheduled transaction that has not been sent yet.
+    CancelScheduled {
+        /// The identifier of the scheduled transaction.
+        id: u32,
+    },
+}
+
+#[async_trait]
+impl HandleSubcommand for ScheduleCommand {
+    async fn handle_subcommand(self, mut client: Client) -> Result<Client, Error> {
+        match self {
+            ScheduleCommand::ScheduleRawTx { raw_tx, trigger } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .consensus
+                        .schedule_raw_transaction(raw_tx, trigger)
+                        .await?
+                );
+            }
+            ScheduleCommand::ScheduleBasicTx {
+                wallet,
+                recipient,
+                value,
+                fee,
+                trigger,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .consensus
+                        .schedule_basic_transaction(wallet, recipient, value, fee, trigger)
+                        .await?
+                );
+            }
+            ScheduleCommand::ScheduleRedeemHTLCTimeout {
+                wallet,
+                contract_address,
+                recipient,
+                value,
+                fee,
+            } => {
+                let trigger = ScheduleTrigger::HtlcTimeout {
+                    contract_address: contract_address.clone(),
+                };
+                println!(
+                    "{:#?}",
+                    client
+                        .consensus
+                        .schedule_redeem_timeout_htlc_transaction(
+                            wallet,
+                            contract_address,
+                            recipient,
+                            value,
+                            fee,
+                            trigger
+                        )
+                        .await?
+                );
+            }
+            ScheduleCommand::ScheduleVestingRedeem {
+                wallet,
+                contract_address,
+                recipient,
+                value,
+                fee,
+            } => {
+                let trigger = ScheduleTrigger::VestingUnlocked {
+                    contract_address: contract_address.clone(),
+                    value: value + fee,
+                };
+                println!(
+                    "{:#?}",
+                    client
+                        .consensus
+                        .schedule_redeem_vesting_transaction(
+                            wallet,
+                            contract_address,
+                            recipient,
+                            value,
+                            fee,
+                            trigger
+                        )
+                        .await?
+                );
+            }
+            ScheduleCommand::ListScheduled {} => {
+                println!(
+                    "{:#?}",
+                    client.consensus.get_scheduled_transactions().await?
+                );
+            }
+            ScheduleCommand::GetScheduled { id } => {
+                println!(
+                    "{:#?}",
+                    client.consensus.get_scheduled_transaction(id).await?
+                );
+            }
+            ScheduleCommand::CancelScheduled { id } => {
+                println!(
+                    "{:#?}",
+                    client.consensus.cancel_scheduled_transaction(id).await?
+                );
+            }
+        }
+
+        Ok(client)
+    }
+}
@@ -7,7 +7,7 @@ use nimiq_keys::Address;
 use nimiq_primitives::coin::Coin;
 use nimiq_rpc_interface::{
     consensus::ConsensusInterface,
-    types::{HashAlgorithm, ValidityStartHeight},
+    types::{BatchPayment, HashAlgorithm, ValidityStartHeight},
 };
 use nimiq_transaction::account::htlc_contract::{AnyHash, AnyHash32, AnyHash64, PreImage};
 
@@ -54,6 +54,26 @@ pub enum TransactionCommand {
         tx_commons: TxCommonWithValue,
     },
 
+    /// Sends a batch of basic transactions from the wallet `wallet`, all sharing the same fee and
+    /// validity start height. Either all transactions are sent or none of them.
+    Batch {
+        /// Transactions will be sent from this address. The sender wallet must be unlocked prior to this action.
+        sender_wallet: Address,
+
+        /// The payments in the form `<recipient>=<value>`.
+        #[clap(num_args = 1.., required = true, value_parser = parse_batch_payment)]
+        payments: Vec<BatchPayment>,
+
+        /// The associated fee to be paid per transaction. If absent it defaults to 0 NIM.
+        #[clap(short, long, default_value = "0")]
+        fee: Coin,
+
+        /// The block height from which on the transactions could be applied.
+        /// If absent it defaults to the current block height at time of processing.
+        #[clap(short, long, default_value_t)]
+        validity_start_height: ValidityStartHeight,
+    },
+
     /* Staker transactions */
     /// Sends a `new_staker` transaction to the network. You need to provide the address of a basic
     /// account (the sender wallet) to pay the transaction fee.
@@ -330,6 +350,16 @@ pub enum TransactionCommand {
     },
 }
 
+fn parse_batch_payment(payment: &str) -> Result<BatchPayment, Error> {
+    let (recipient, value) = payment
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("Expected `<recipient>=<value>`, got `{payment}`"))?;
+    Ok(BatchPayment {
+        recipient: Address::from_str(recipient)?,
+        value: Coin::from_str(value)?,
+    })
+}
+
 impl TransactionCommand {
     fn parse_hash(hash_algorithm: &HashAlgorithm, hash_str: String) -> Result<AnyHash, Error> {
         match hash_algorithm {
@@ -807,6 +837,18 @@ impl HandleSubcommand for TransactionCommand {
                     println!("{txid:#?}");
                 }
             }
+            TransactionCommand::Batch {
+                sender_wallet,
+                payments,
+                fee,
+                validity_start_height,
+            } => {
+                let result = client
+                    .consensus
+                    .send_batch(sender_wallet, payments, fee, validity_start_height)
+                    .await?;
+                println!("{result:#?}");
+            }
             TransactionCommand::SignRedeemHTLCEarly {
                 sender_wallet,
                 contract_address,
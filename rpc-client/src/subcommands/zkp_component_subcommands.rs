@@ -1,6 +1,7 @@
 use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
+use futures::StreamExt;
 use nimiq_rpc_interface::zkp_component::ZKPComponentInterface;
 
 use super::accounts_subcommands::HandleSubcommand;
@@ -10,6 +11,15 @@ use crate::Client;
 pub enum ZKPComponentCommand {
     /// Returns the current zkp state.
     ZkpState {},
+
+    /// Returns the zk proof for the election block at the given block number.
+    ZkpByBlockNumber {
+        /// The block number of the election block.
+        block_number: u32,
+    },
+
+    /// Follow the new zk proofs.
+    FollowZkps {},
 }
 
 #[async_trait]
@@ -19,6 +29,22 @@ impl HandleSubcommand for ZKPComponentCommand {
             ZKPComponentCommand::ZkpState {} => {
                 println!("{:?}", client.zkp_component.get_zkp_state().await?);
             }
+            ZKPComponentCommand::ZkpByBlockNumber { block_number } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .zkp_component
+                        .get_zkp_by_block_number(block_number)
+                        .await?
+                );
+            }
+            ZKPComponentCommand::FollowZkps {} => {
+                let mut stream = client.zkp_component.subscribe_for_zkps().await?;
+
+                while let Some(zk_proof) = stream.next().await {
+                    println!("{zk_proof:#?}");
+                }
+            }
         }
         Ok(client)
     }
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+
+use crate::types::{DatabaseBackup, DatabaseStats, RPCResult, RevertedChain};
+
+/// The administrative methods of a node. They are only available if enabled with `admin = true`
+/// in the `[rpc-server]` section of the node's configuration.
+#[nimiq_jsonrpc_derive::proxy(name = "AdminProxy", rename_all = "camelCase")]
+#[async_trait]
+pub trait AdminInterface {
+    type Error;
+
+    /// Returns the page usage and fragmentation statistics of the database, including the
+    /// entry counts and sizes of all tables.
+    async fn get_database_stats(&mut self) -> RPCResult<DatabaseStats, (), Self::Error>;
+
+    /// Writes a compacted, consistent copy of the database to the given directory on the node's
+    /// file system while the node keeps running. The directory must not exist or be empty.
+    async fn backup_database(&mut self, path: String)
+        -> RPCResult<DatabaseBackup, (), Self::Error>;
+
+    /// Reverts the chain to the given block number, e.g. to recover from a bug. Consensus syncs
+    /// the reverted blocks again afterwards. Reverting below the last macro block requires
+    /// `force` and is only possible if the node stores revert info for past batches, i.e.
+    /// `revert_info_batches_stored` in the `[consensus]` section is greater than 0. It defaults
+    /// to 0, in which case only blocks after the last macro block can be reverted.
+    async fn revert_chain(
+        &mut self,
+        block_number: u32,
+        force: bool,
+    ) -> RPCResult<RevertedChain, (), Self::Error>;
+}
@@ -4,8 +4,9 @@ use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 
 use crate::types::{
-    Account, Block, BlockLog, BlockchainState, ExecutedTransaction, Inherent, LogType,
-    PenalizedSlots, RPCData, RPCResult, Slot, Staker, Validator,
+    Account, Block, BlockLog, BlockchainState, ExecutedTransaction, Htlc, Inherent, LogType,
+    PenalizedSlots, RPCData, RPCResult, Slot, Staker, StakerRewards, StakingEvent,
+    TransactionSimulation, Validator, ValidatorStakers, VestingContract,
 };
 
 #[nimiq_jsonrpc_derive::proxy(name = "BlockchainProxy", rename_all = "camelCase")]
@@ -111,59 +112,153 @@ pub trait BlockchainInterface {
         max: Option<u16>,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error>;
 
+    /// Returns the latest HTLCs that the given address created or is the sender or recipient of,
+    /// together with their current status. It has an option to specify the maximum number of HTLCs
+    /// to fetch, it defaults to 500. Only available on history nodes. Nodes synced before the
+    /// contract indexes existed must rebuild them with `nimiq-db rebuild-history-indexes`.
+    async fn get_htlcs_by_address(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<Htlc>, (), Self::Error>;
+
+    /// Returns the latest vesting contracts owned by the given address, together with their
+    /// current status. It has an option to specify the maximum number of contracts to fetch, it
+    /// defaults to 500. Only available on history nodes. Nodes synced before the contract indexes
+    /// existed must rebuild them with `nimiq-db rebuild-history-indexes`.
+    async fn get_vesting_contracts_by_owner(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<VestingContract>, (), Self::Error>;
+
     /// Tries to fetch the account at the given address.
+    ///
+    /// The state of the account and the returned blockchain state always refer to the same block.
+    /// If a block hash is given, the request fails unless that block is still the head, which
+    /// allows combining the results of several requests.
     async fn get_account_by_address(
         &mut self,
         address: Address,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Account, BlockchainState, Self::Error>;
 
-    /// Fetches all accounts in the accounts tree.
+    /// Fetches all accounts in the accounts tree, optionally pinned to a block hash.
     /// IMPORTANT: This operation iterates over all accounts in the accounts tree
     /// and thus is extremely computationally expensive.
-    async fn get_accounts(&mut self) -> RPCResult<Vec<Account>, BlockchainState, Self::Error>;
+    async fn get_accounts(
+        &mut self,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Account>, BlockchainState, Self::Error>;
+
+    /// Executes a serialized transaction on top of the current state without broadcasting it or
+    /// persisting any changes. The transaction is simulated as if it was included in the block
+    /// with the given number, which defaults to the next block.
+    async fn simulate_transaction(
+        &mut self,
+        raw_tx: String,
+        block_number: Option<u32>,
+    ) -> RPCResult<TransactionSimulation, BlockchainState, Self::Error>;
 
-    /// Returns a collection of the currently active validator's addresses and balances.
+    /// Returns a collection of the currently active validator's addresses and balances,
+    /// optionally pinned to a block hash.
     async fn get_active_validators(
         &mut self,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error>;
 
     /// Returns information about the currently penalized slots. This includes slots that lost rewards
-    /// and that were disabled.
+    /// and that were disabled. Optionally pinned to a block hash.
     async fn get_current_penalized_slots(
         &mut self,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<PenalizedSlots, BlockchainState, Self::Error>;
 
     /// Returns information about the penalized slots of the previous batch. This includes slots that
-    /// lost rewards and that were disabled.
+    /// lost rewards and that were disabled. Optionally pinned to a block hash.
     async fn get_previous_penalized_slots(
         &mut self,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<PenalizedSlots, BlockchainState, Self::Error>;
 
-    /// Tries to fetch a validator information given its address.
+    /// Tries to fetch a validator information given its address, optionally pinned to a block hash.
     async fn get_validator_by_address(
         &mut self,
         address: Address,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Validator, BlockchainState, Self::Error>;
 
-    /// Fetches all validators in the staking contract.
+    /// Fetches all validators in the staking contract, optionally pinned to a block hash.
     /// IMPORTANT: This operation iterates over all validators in the staking contract
     /// and thus is extremely computationally expensive.
-    async fn get_validators(&mut self) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error>;
+    async fn get_validators(
+        &mut self,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error>;
 
-    /// Fetches all stakers for a given validator.
+    /// Fetches all stakers for a given validator, optionally pinned to a block hash.
     /// IMPORTANT: This operation iterates over all stakers of the staking contract
     /// and thus is extremely computationally expensive.
     async fn get_stakers_by_validator_address(
         &mut self,
         address: Address,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error>;
 
-    /// Tries to fetch a staker information given its address.
+    /// Tries to fetch a staker information given its address, optionally pinned to a block hash.
     async fn get_staker_by_address(
         &mut self,
         address: Address,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Staker, BlockchainState, Self::Error>;
 
+    /// Attributes the rewards a validator received for the given epoch to its stakers, according
+    /// to their active balance at the election block preceding the epoch. The optional commission
+    /// is given in basis points (defaults to 0) and deducted from the stakers' rewards.
+    /// Only available on history nodes.
+    async fn get_staker_rewards(
+        &mut self,
+        validator_address: Address,
+        epoch_number: u32,
+        commission: Option<u16>,
+    ) -> RPCResult<StakerRewards, (), Self::Error>;
+
+    /// Returns the history of the validator with the given address from most recent to least
+    /// recent: its creation, updates, deactivations, jail and penalty events, retirement and
+    /// deletion, as well as the stake changes and (re)delegations of the stakers delegating to it.
+    /// It has an option to specify the maximum number of events to fetch, it defaults to 500. To
+    /// fetch the next page, pass the hash of the last event received as `start_at`. Only available
+    /// on history nodes. Nodes synced before the staking index existed must rebuild it with
+    /// `nimiq-db rebuild-history-indexes`.
+    async fn get_validator_history(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<StakingEvent>, (), Self::Error>;
+
+    /// Returns the history of the staker with the given address from most recent to least recent:
+    /// stake changes, (re)delegations and fee deductions. It has an option to specify the maximum
+    /// number of events to fetch, it defaults to 500. To fetch the next page, pass the hash of the
+    /// last event received as `start_at`. Only available on history nodes. Nodes synced before
+    /// the staking index existed must rebuild it with `nimiq-db rebuild-history-indexes`.
+    async fn get_staker_history(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<StakingEvent>, (), Self::Error>;
+
+    /// Returns the number of stakers delegating active stake to the validator with the given
+    /// address and its total stake for each of the last `max_epochs` epochs, from most recent to
+    /// least recent. It defaults to 30 epochs. Epochs in which the validator wasn't active are
+    /// skipped. Only available on history nodes.
+    async fn get_validator_stakers_history(
+        &mut self,
+        address: Address,
+        max_epochs: Option<u16>,
+    ) -> RPCResult<Vec<ValidatorStakers>, (), Self::Error>;
+
     /// Subscribes to new block events (retrieves the full block).
     #[stream]
     async fn subscribe_for_head_block(
@@ -1,10 +1,14 @@
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_primitives::coin::Coin;
 use nimiq_transaction::account::htlc_contract::{AnyHash, PreImage};
 
-use crate::types::{RPCResult, Transaction, ValidityStartHeight};
+use crate::types::{
+    BatchPayment, BatchResult, BlockchainState, RPCData, RPCResult, ScheduleTrigger,
+    ScheduledTransaction, Transaction, ValidityStartHeight, WatchedTransactionEvent,
+};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ConsensusProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -28,6 +32,20 @@ pub trait ConsensusInterface {
         raw_tx: String,
     ) -> RPCResult<Blake2bHash, (), Self::Error>;
 
+    /// Creates, signs and sends a basic transaction from `wallet` for each payment, all sharing
+    /// the same fee and validity start height. The batch is only sent if all transactions are
+    /// valid and their aggregate value and fees are covered by the sender's balance minus the
+    /// balance reserved by its transactions pending in the mempool. The batch is added to the
+    /// mempool as a whole before it is broadcast, so this method is not available on nodes
+    /// without a mempool.
+    async fn send_batch(
+        &mut self,
+        wallet: Address,
+        payments: Vec<BatchPayment>,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<BatchResult, (), Self::Error>;
+
     /// Returns a serialized basic transaction.
     async fn create_basic_transaction(
         &mut self,
@@ -541,4 +559,82 @@ pub trait ConsensusInterface {
         value: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
+    /// Schedules the given serialized transaction to be sent to the network once the trigger is
+    /// reached. The transaction must still be valid at that point.
+    async fn schedule_raw_transaction(
+        &mut self,
+        raw_tx: String,
+        trigger: ScheduleTrigger,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error>;
+
+    /// Schedules a basic transaction to be sent to the network once the trigger is reached.
+    /// The transaction is signed at that point, so the wallet needs to be unlocked by then.
+    /// Wallets are locked again after a restart of the node. Until the wallet is unlocked, the
+    /// submission is postponed with every block and the schedule reports the locked wallet as
+    /// its error.
+    async fn schedule_basic_transaction(
+        &mut self,
+        wallet: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        trigger: ScheduleTrigger,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error>;
+
+    /// Schedules a transaction redeeming the funds of an HTLC contract after its timeout. The
+    /// transaction is signed once the trigger is reached, so the wallet needs to be unlocked by
+    /// then.
+    /// See `schedule_basic_transaction` for wallets that are locked at that point.
+    async fn schedule_redeem_timeout_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        trigger: ScheduleTrigger,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error>;
+
+    /// Schedules a transaction redeeming funds from a vesting contract. The transaction is signed
+    /// once the trigger is reached, so the wallet needs to be unlocked by then.
+    /// See `schedule_basic_transaction` for wallets that are locked at that point.
+    async fn schedule_redeem_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        trigger: ScheduleTrigger,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error>;
+
+    /// Returns all scheduled transactions, including the ones that were already submitted,
+    /// failed or cancelled.
+    async fn get_scheduled_transactions(
+        &mut self,
+    ) -> RPCResult<Vec<ScheduledTransaction>, (), Self::Error>;
+
+    /// Returns the scheduled transaction with the given identifier.
+    async fn get_scheduled_transaction(
+        &mut self,
+        id: u32,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error>;
+
+    /// Cancels the scheduled transaction with the given identifier if it was not submitted yet.
+    async fn cancel_scheduled_transaction(
+        &mut self,
+        id: u32,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error>;
+
+    /// Subscribes to the transactions that involve any of the given addresses as sender or
+    /// recipient. Emits an event whenever such a transaction is added to or evicted from the
+    /// mempool, included in a block, reaches the given number of confirmations (defaults to 10),
+    /// is finalized by a macro block or retracted by a rebranch.
+    #[stream]
+    async fn subscribe_for_transactions_by_addresses(
+        &mut self,
+        addresses: Vec<Address>,
+        confirmations: Option<u32>,
+    ) -> Result<BoxStream<'static, RPCData<WatchedTransactionEvent, BlockchainState>>, Self::Error>;
 }
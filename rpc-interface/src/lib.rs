@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod blockchain;
 pub mod consensus;
 pub mod error;
@@ -9,21 +9,33 @@ use std::{
 use clap::ValueEnum;
 use nimiq_account::{BlockLog as BBlockLog, Log, TransactionLog};
 use nimiq_block::{MicroJustification, MultiSignature};
+use nimiq_blockchain::BlockchainSnapshot;
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
 use nimiq_blockchain_proxy::BlockchainReadProxy;
-use nimiq_bls::CompressedPublicKey;
+use nimiq_bls::{CompressedPublicKey, CompressedSignature};
 use nimiq_collections::BitSet;
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
-use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature, PrivateKey};
+use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature, PrivateKey, PublicKey, Signature};
 use nimiq_primitives::{
-    coin::Coin, networks::NetworkId, policy::Policy, slots_allocation::Validators,
+    account::AccountType, coin::Coin, networks::NetworkId, policy::Policy,
+    slots_allocation::Validators,
 };
 use nimiq_serde::Serialize as NimiqSerialize;
 use nimiq_transaction::{
-    account::htlc_contract::AnyHash,
+    account::{
+        htlc_contract::{
+            AnyHash, CreationTransactionData as HtlcCreationData, OutgoingHTLCTransactionProof,
+            PreImage,
+        },
+        staking_contract::{
+            full_parse, IncomingStakingTransactionData, OutgoingStakingTransactionData,
+        },
+        vesting_contract::CreationTransactionData as VestingCreationData,
+    },
     historic_transaction::{
         HistoricTransaction, HistoricTransactionData, JailEvent, PenalizeEvent, RewardEvent,
     },
+    TransactionFlags,
 };
 use nimiq_vrf::VrfSeed;
 use serde::{Deserialize, Serialize};
@@ -571,6 +583,16 @@ pub struct Transaction {
     #[serde(with = "crate::serde_helpers::hex")]
     pub proof: Vec<u8>,
     pub network_id: u8,
+
+    /// The decoded sender data, if it has a known format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded_sender_data: Option<TransactionData>,
+    /// The decoded recipient data, if it has a known format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded_recipient_data: Option<TransactionData>,
+    /// The decoded proof, if it has a known format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded_proof: Option<TransactionProof>,
 }
 
 impl Transaction {
@@ -598,6 +620,10 @@ impl Transaction {
         timestamp: Option<u64>,
         head_height: Option<u32>,
     ) -> Self {
+        let decoded_sender_data = TransactionData::from_sender_data(&transaction);
+        let decoded_recipient_data = TransactionData::from_recipient_data(&transaction);
+        let decoded_proof = TransactionProof::from_transaction(&transaction);
+
         Transaction {
             hash: transaction.hash(),
             block_number,
@@ -619,10 +645,485 @@ impl Transaction {
             validity_start_height: transaction.validity_start_height,
             proof: transaction.proof,
             network_id: transaction.network_id as u8,
+            decoded_sender_data,
+            decoded_recipient_data,
+            decoded_proof,
+        }
+    }
+}
+
+/// The decoded sender or recipient data of a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum TransactionData {
+    /// Creates a new HTLC contract.
+    #[serde(rename_all = "camelCase")]
+    HtlcCreation {
+        sender: Address,
+        recipient: Address,
+        /// Hash algorithm and Hex-encoded hash root.
+        hash_root: AnyHash,
+        hash_count: u8,
+        timeout: u64,
+    },
+    /// Creates a new vesting contract.
+    #[serde(rename_all = "camelCase")]
+    VestingCreation {
+        owner: Address,
+        start_time: u64,
+        time_step: u64,
+        step_amount: Coin,
+        total_amount: Coin,
+    },
+    #[serde(rename_all = "camelCase")]
+    CreateValidator {
+        signing_key: Ed25519PublicKey,
+        voting_key: CompressedPublicKey,
+        reward_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signal_data: Option<Blake2bHash>,
+        proof_of_knowledge: CompressedSignature,
+        proof: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    UpdateValidator {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_signing_key: Option<Ed25519PublicKey>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_voting_key: Option<CompressedPublicKey>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_reward_address: Option<Address>,
+        /// Set if the signal data is updated. An inner `None` removes the signal data.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_signal_data: Option<Option<Blake2bHash>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_proof_of_knowledge: Option<CompressedSignature>,
+        proof: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    DeactivateValidator {
+        validator_address: Address,
+        proof: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    ReactivateValidator {
+        validator_address: Address,
+        proof: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    RetireValidator { proof: SignatureProof },
+    #[serde(rename_all = "camelCase")]
+    CreateStaker {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delegation: Option<Address>,
+        proof: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    AddStake { staker_address: Address },
+    #[serde(rename_all = "camelCase")]
+    UpdateStaker {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_delegation: Option<Address>,
+        reactivate_all_stake: bool,
+        proof: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    SetActiveStake {
+        new_active_balance: Coin,
+        proof: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    RetireStake {
+        retire_stake: Coin,
+        proof: SignatureProof,
+    },
+    /// Deletes a retired validator. Only used as sender data.
+    DeleteValidator,
+    /// Removes retired stake. Only used as sender data.
+    RemoveStake,
+}
+
+impl TransactionData {
+    /// Decodes the sender data of a transaction. Only outgoing staking transactions carry sender
+    /// data with a known format.
+    pub fn from_sender_data(transaction: &nimiq_transaction::Transaction) -> Option<Self> {
+        if transaction.sender_type != AccountType::Staking {
+            return None;
+        }
+
+        let data = OutgoingStakingTransactionData::parse(transaction).ok()?;
+        Some(match data {
+            OutgoingStakingTransactionData::DeleteValidator => TransactionData::DeleteValidator,
+            OutgoingStakingTransactionData::RemoveStake => TransactionData::RemoveStake,
+        })
+    }
+
+    /// Decodes the recipient data of contract creation and incoming staking transactions.
+    pub fn from_recipient_data(transaction: &nimiq_transaction::Transaction) -> Option<Self> {
+        let is_creation = transaction
+            .flags
+            .contains(TransactionFlags::CONTRACT_CREATION);
+
+        match transaction.recipient_type {
+            AccountType::Basic => None,
+            AccountType::HTLC if is_creation => {
+                let data = HtlcCreationData::parse(transaction).ok()?;
+                Some(TransactionData::HtlcCreation {
+                    sender: data.sender,
+                    recipient: data.recipient,
+                    hash_root: data.hash_root,
+                    hash_count: data.hash_count,
+                    timeout: data.timeout,
+                })
+            }
+            AccountType::Vesting if is_creation => {
+                let data = VestingCreationData::parse(transaction).ok()?;
+                Some(TransactionData::VestingCreation {
+                    owner: data.owner,
+                    start_time: data.start_time,
+                    time_step: data.time_step,
+                    step_amount: data.step_amount,
+                    total_amount: data.total_amount,
+                })
+            }
+            AccountType::HTLC | AccountType::Vesting => None,
+            AccountType::Staking => {
+                let data: IncomingStakingTransactionData =
+                    full_parse(&transaction.recipient_data).ok()?;
+                Some(TransactionData::from_incoming_staking_data(data))
+            }
+        }
+    }
+
+    fn from_incoming_staking_data(data: IncomingStakingTransactionData) -> Self {
+        match data {
+            IncomingStakingTransactionData::CreateValidator {
+                signing_key,
+                voting_key,
+                reward_address,
+                signal_data,
+                proof_of_knowledge,
+                proof,
+            } => TransactionData::CreateValidator {
+                signing_key,
+                voting_key,
+                reward_address,
+                signal_data,
+                proof_of_knowledge,
+                proof: SignatureProof::from_signature_proof(&proof),
+            },
+            IncomingStakingTransactionData::UpdateValidator {
+                new_signing_key,
+                new_voting_key,
+                new_reward_address,
+                new_signal_data,
+                new_proof_of_knowledge,
+                proof,
+            } => TransactionData::UpdateValidator {
+                new_signing_key,
+                new_voting_key,
+                new_reward_address,
+                new_signal_data,
+                new_proof_of_knowledge,
+                proof: SignatureProof::from_signature_proof(&proof),
+            },
+            IncomingStakingTransactionData::DeactivateValidator {
+                validator_address,
+                proof,
+            } => TransactionData::DeactivateValidator {
+                validator_address,
+                proof: SignatureProof::from_signature_proof(&proof),
+            },
+            IncomingStakingTransactionData::ReactivateValidator {
+                validator_address,
+                proof,
+            } => TransactionData::ReactivateValidator {
+                validator_address,
+                proof: SignatureProof::from_signature_proof(&proof),
+            },
+            IncomingStakingTransactionData::RetireValidator { proof } => {
+                TransactionData::RetireValidator {
+                    proof: SignatureProof::from_signature_proof(&proof),
+                }
+            }
+            IncomingStakingTransactionData::CreateStaker { delegation, proof } => {
+                TransactionData::CreateStaker {
+                    delegation,
+                    proof: SignatureProof::from_signature_proof(&proof),
+                }
+            }
+            IncomingStakingTransactionData::AddStake { staker_address } => {
+                TransactionData::AddStake { staker_address }
+            }
+            IncomingStakingTransactionData::UpdateStaker {
+                new_delegation,
+                reactivate_all_stake,
+                proof,
+            } => TransactionData::UpdateStaker {
+                new_delegation,
+                reactivate_all_stake,
+                proof: SignatureProof::from_signature_proof(&proof),
+            },
+            IncomingStakingTransactionData::SetActiveStake {
+                new_active_balance,
+                proof,
+            } => TransactionData::SetActiveStake {
+                new_active_balance,
+                proof: SignatureProof::from_signature_proof(&proof),
+            },
+            IncomingStakingTransactionData::RetireStake {
+                retire_stake,
+                proof,
+            } => TransactionData::RetireStake {
+                retire_stake,
+                proof: SignatureProof::from_signature_proof(&proof),
+            },
         }
     }
 }
 
+/// The decoded proof of a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum TransactionProof {
+    /// The proof of transactions sent from basic accounts, vesting contracts and the staking
+    /// contract.
+    Signature(SignatureProof),
+    #[serde(rename_all = "camelCase")]
+    HtlcRegularTransfer {
+        hash_depth: u8,
+        /// Hash algorithm and Hex-encoded hash root.
+        hash_root: AnyHash,
+        pre_image: PreImage,
+        signature_proof: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    HtlcEarlyResolve {
+        signature_proof_recipient: SignatureProof,
+        signature_proof_sender: SignatureProof,
+    },
+    #[serde(rename_all = "camelCase")]
+    HtlcTimeoutResolve {
+        signature_proof_sender: SignatureProof,
+    },
+}
+
+impl TransactionProof {
+    /// Decodes the proof of a transaction according to its sender type.
+    pub fn from_transaction(transaction: &nimiq_transaction::Transaction) -> Option<Self> {
+        match transaction.sender_type {
+            AccountType::Basic | AccountType::Vesting | AccountType::Staking => {
+                let proof: nimiq_transaction::SignatureProof =
+                    full_parse(&transaction.proof).ok()?;
+                Some(TransactionProof::Signature(
+                    SignatureProof::from_signature_proof(&proof),
+                ))
+            }
+            AccountType::HTLC => {
+                let proof = OutgoingHTLCTransactionProof::parse(transaction).ok()?;
+                Some(match proof {
+                    OutgoingHTLCTransactionProof::RegularTransfer {
+                        hash_depth,
+                        hash_root,
+                        pre_image,
+                        signature_proof,
+                    } => TransactionProof::HtlcRegularTransfer {
+                        hash_depth,
+                        hash_root,
+                        pre_image,
+                        signature_proof: SignatureProof::from_signature_proof(&signature_proof),
+                    },
+                    OutgoingHTLCTransactionProof::EarlyResolve {
+                        signature_proof_recipient,
+                        signature_proof_sender,
+                    } => TransactionProof::HtlcEarlyResolve {
+                        signature_proof_recipient: SignatureProof::from_signature_proof(
+                            &signature_proof_recipient,
+                        ),
+                        signature_proof_sender: SignatureProof::from_signature_proof(
+                            &signature_proof_sender,
+                        ),
+                    },
+                    OutgoingHTLCTransactionProof::TimeoutResolve {
+                        signature_proof_sender,
+                    } => TransactionProof::HtlcTimeoutResolve {
+                        signature_proof_sender: SignatureProof::from_signature_proof(
+                            &signature_proof_sender,
+                        ),
+                    },
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignatureAlgorithm {
+    Ed25519,
+    Es256,
+}
+
+/// A decoded signature proof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureProof {
+    /// The address of the signer, computed from the public key and the merkle path.
+    pub signer: Address,
+    pub algorithm: SignatureAlgorithm,
+    /// Hex-encoded public key.
+    pub public_key: String,
+    /// The number of nodes of the merkle path, which is non-empty for multi-signature accounts.
+    pub merkle_path_length: usize,
+    /// Hex-encoded signature.
+    pub signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webauthn_fields: Option<WebauthnFields>,
+}
+
+impl SignatureProof {
+    pub fn from_signature_proof(proof: &nimiq_transaction::SignatureProof) -> Self {
+        let (algorithm, public_key) = match proof.public_key {
+            PublicKey::Ed25519(ref public_key) => {
+                (SignatureAlgorithm::Ed25519, public_key.to_hex())
+            }
+            PublicKey::ES256(ref public_key) => (SignatureAlgorithm::Es256, public_key.to_hex()),
+        };
+        let signature = match proof.signature {
+            Signature::Ed25519(ref signature) => signature.to_hex(),
+            Signature::ES256(ref signature) => signature.to_hex(),
+        };
+
+        SignatureProof {
+            signer: proof.compute_signer(),
+            algorithm,
+            public_key,
+            merkle_path_length: proof.merkle_path.len(),
+            signature,
+            webauthn_fields: proof.webauthn_fields.as_ref().map(|fields| WebauthnFields {
+                origin_json_str: fields.origin_json_str().to_string(),
+                has_cross_origin_field: fields.has_cross_origin_field(),
+                client_data_extra_json: fields.client_data_extra_json().to_string(),
+                authenticator_data_suffix: fields.authenticator_data_suffix().to_vec(),
+            }),
+        }
+    }
+}
+
+/// The extra fields of a signature proof created with Webauthn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebauthnFields {
+    /// The `origin` field of the `clientDataJSON`, exactly as it appears in the JSON.
+    pub origin_json_str: String,
+    pub has_cross_origin_field: bool,
+    /// Extra fields of the `clientDataJSON`, exactly as they appear in the JSON.
+    pub client_data_extra_json: String,
+    #[serde(with = "crate::serde_helpers::hex")]
+    pub authenticator_data_suffix: Vec<u8>,
+}
+
+/// A single payment of a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchPayment {
+    pub recipient: Address,
+    pub value: Coin,
+}
+
+/// The result of a single payment of a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchPaymentResult {
+    pub recipient: Address,
+    pub value: Coin,
+    /// The hash of the transaction, if it could be created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<Blake2bHash>,
+    /// The reason why the payment failed, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The result of a batch of payments. Either all transactions of the batch are sent or none.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResult {
+    /// Whether the transactions have been sent.
+    pub sent: bool,
+    /// The validity start height shared by all transactions.
+    pub validity_start_height: u32,
+    /// The total value and fees of all payments.
+    pub total: Coin,
+    /// The balance of the sender that is not reserved by pending transactions.
+    pub available_balance: Coin,
+    /// The reason why the batch as a whole was rejected, if it was.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub payments: Vec<BatchPaymentResult>,
+}
+
+/// The state of a transaction that involves a watched address.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchedTransactionState {
+    /// The transaction was added to the mempool.
+    Pending,
+    /// The transaction was removed from the mempool without being included in a block.
+    Evicted,
+    /// The transaction was included in a block on the main chain.
+    Included,
+    /// The block including the transaction reached the requested number of confirmations.
+    Confirmed,
+    /// The block including the transaction was finalized by a macro block.
+    Finalized,
+    /// The block including the transaction was reverted by a rebranch.
+    Retracted,
+}
+
+/// A state transition of a transaction that involves a watched address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedTransactionEvent {
+    pub state: WatchedTransactionState,
+    pub hash: Blake2bHash,
+    /// The block that includes (or included) the transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u32>,
+    /// The number of confirmations of the block that includes the transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u32>,
+    /// Whether the transaction was executed successfully. Only set once it is included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_result: Option<bool>,
+    /// The transaction itself. Only set for pending and included transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<Transaction>,
+}
+
+/// The outcome of executing a transaction against the current state without broadcasting it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSimulation {
+    pub hash: Blake2bHash,
+    /// The height of the block the transaction was simulated in.
+    pub block_number: u32,
+    /// Whether the transaction could be included in a block at all. If not, no fee is paid.
+    pub includable: bool,
+    /// Whether the transaction would succeed. Failed transactions still pay the fee.
+    pub success: bool,
+    /// The fee that would be paid.
+    pub fee: Coin,
+    /// The logs the transaction would produce.
+    pub logs: Vec<Log>,
+    /// The serialized operation receipt of the transaction, if it could be included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<String>,
+    /// The reason why the transaction failed or could not be included, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum Inherent {
@@ -725,6 +1226,136 @@ pub struct ReturnAccount {
     pub private_key: PrivateKey,
 }
 
+/// An account derived from a seed stored in the wallet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HdAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The identifier of the seed, i.e. the address of its master key.
+    pub seed_id: Address,
+    /// The derivation path of the account.
+    pub path: String,
+}
+
+/// A multi-signature account registered in the wallet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigAccount {
+    /// The address of the multi-signature account.
+    pub address: Address,
+    /// The address of the wallet account holding the key of the local participant.
+    pub owner: Address,
+    /// The public key of the local participant.
+    pub owner_public_key: Ed25519PublicKey,
+    /// The minimum number of signatures required to send a transaction.
+    pub min_signatures: u8,
+    /// The public keys of all participants.
+    pub public_keys: Vec<Ed25519PublicKey>,
+}
+
+/// The condition under which a scheduled transaction is submitted.
+///
+/// On the command line, triggers are written as `block:<number>`, `time:<milliseconds>`,
+/// `htlc:<contract address>` or `vesting:<contract address>:<value in luna>`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum ScheduleTrigger {
+    /// Due once the head of the chain reached the given block number.
+    #[serde(rename_all = "camelCase")]
+    BlockNumber { block_number: u32 },
+    /// Due once the timestamp of the head block reached the given time (in milliseconds).
+    Timestamp { timestamp: u64 },
+    /// Due once the timeout of the HTLC contract has been reached.
+    #[serde(rename_all = "camelCase")]
+    HtlcTimeout { contract_address: Address },
+    /// Due once the given value can be withdrawn from the vesting contract.
+    #[serde(rename_all = "camelCase")]
+    VestingUnlocked {
+        contract_address: Address,
+        value: Coin,
+    },
+}
+
+impl FromStr for ScheduleTrigger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, argument) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Missing trigger argument: {s}"))?;
+        let invalid = |e: &dyn Display| format!("Invalid trigger argument {argument}: {e}");
+
+        match kind {
+            "block" => Ok(Self::BlockNumber {
+                block_number: argument.parse().map_err(|e| invalid(&e))?,
+            }),
+            "time" => Ok(Self::Timestamp {
+                timestamp: argument.parse().map_err(|e| invalid(&e))?,
+            }),
+            "htlc" => Ok(Self::HtlcTimeout {
+                contract_address: argument.parse().map_err(|e| invalid(&e))?,
+            }),
+            "vesting" => {
+                let (address, value) = argument
+                    .rsplit_once(':')
+                    .ok_or_else(|| format!("Missing vesting value: {argument}"))?;
+                Ok(Self::VestingUnlocked {
+                    contract_address: address.parse().map_err(|e| invalid(&e))?,
+                    value: Coin::try_from(value.parse::<u64>().map_err(|e| invalid(&e))?)
+                        .map_err(|e| invalid(&e))?,
+                })
+            }
+            _ => Err(format!("Unknown trigger type: {kind}")),
+        }
+    }
+}
+
+/// The kind of transaction that is submitted once a schedule is due.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScheduledTransactionKind {
+    /// A transaction that was signed in advance.
+    Raw,
+    Basic,
+    RedeemHtlcTimeout,
+    RedeemVesting,
+}
+
+/// The lifecycle state of a scheduled transaction.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScheduledTransactionState {
+    Scheduled,
+    Submitted,
+    Failed,
+    Cancelled,
+}
+
+/// A transaction that is submitted by the node once its trigger is reached.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTransaction {
+    /// The identifier of the schedule.
+    pub id: u32,
+    pub trigger: ScheduleTrigger,
+    pub kind: ScheduledTransactionKind,
+    pub state: ScheduledTransactionState,
+    /// The wallet that signs the transaction at submission time, if it was not signed in
+    /// advance.
+    pub wallet: Option<Address>,
+    pub sender: Address,
+    pub recipient: Address,
+    pub value: Coin,
+    pub fee: Coin,
+    /// The hash of the submitted transaction, or of the transaction that was signed in advance.
+    pub transaction_hash: Option<Blake2bHash>,
+    /// The block number at which the transaction was submitted.
+    pub submitted_at: Option<u32>,
+    /// The reason why the transaction failed, or why its last submission was postponed.
+    pub error: Option<String>,
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
@@ -855,6 +1486,481 @@ impl Staker {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakerReward {
+    pub address: Address,
+    pub balance: Coin,
+    pub reward: Coin,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakerRewards {
+    pub validator_address: Address,
+    pub epoch_number: u32,
+    pub total_stake: Coin,
+    pub total_reward: Coin,
+    pub commission: Coin,
+    pub validator_reward: Coin,
+    pub stakers: Vec<StakerReward>,
+}
+
+impl StakerRewards {
+    pub fn from_staker_rewards(
+        staker_rewards: nimiq_blockchain::reward_attribution::StakerRewards,
+    ) -> Self {
+        StakerRewards {
+            validator_address: staker_rewards.validator_address,
+            epoch_number: staker_rewards.epoch_number,
+            total_stake: staker_rewards.total_stake,
+            total_reward: staker_rewards.total_reward,
+            commission: staker_rewards.commission,
+            validator_reward: staker_rewards.validator_reward,
+            stakers: staker_rewards
+                .stakers
+                .into_iter()
+                .map(|staker| StakerReward {
+                    address: staker.staker_address,
+                    balance: staker.active_balance,
+                    reward: staker.reward,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The state of an HTLC, given by the last transaction sent from the contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HtlcStatus {
+    /// No funds were sent from the contract yet.
+    Funded,
+    /// The recipient redeemed (part of) the funds by revealing a pre-image.
+    Redeemed,
+    /// The sender recovered the funds after the timeout.
+    TimedOut,
+    /// The funds were sent with the consent of both the sender and the recipient.
+    EarlyResolved,
+}
+
+impl From<nimiq_blockchain::contract_lifecycle::HtlcStatus> for HtlcStatus {
+    fn from(status: nimiq_blockchain::contract_lifecycle::HtlcStatus) -> Self {
+        match status {
+            nimiq_blockchain::contract_lifecycle::HtlcStatus::Funded => HtlcStatus::Funded,
+            nimiq_blockchain::contract_lifecycle::HtlcStatus::Redeemed => HtlcStatus::Redeemed,
+            nimiq_blockchain::contract_lifecycle::HtlcStatus::TimedOut => HtlcStatus::TimedOut,
+            nimiq_blockchain::contract_lifecycle::HtlcStatus::EarlyResolved => {
+                HtlcStatus::EarlyResolved
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Htlc {
+    pub address: Address,
+    pub creation_transaction_hash: Blake2bHash,
+    pub creation_block_number: u32,
+    pub sender: Address,
+    pub recipient: Address,
+    /// Hash algorithm and Hex-encoded hash root.
+    pub hash_root: AnyHash,
+    pub hash_count: u8,
+    pub timeout: u64,
+    pub total_amount: Coin,
+    /// The balance left in the contract.
+    pub balance: Coin,
+    pub status: HtlcStatus,
+    /// The pre-image revealed by the last regular transfer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_image: Option<PreImage>,
+    /// The hash of the last transaction sent from the contract.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_transaction_hash: Option<Blake2bHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_block_number: Option<u32>,
+}
+
+impl Htlc {
+    pub fn from_htlc_lifecycle(htlc: nimiq_blockchain::contract_lifecycle::HtlcLifecycle) -> Self {
+        Htlc {
+            address: htlc.contract_address,
+            creation_transaction_hash: htlc.creation_tx_hash,
+            creation_block_number: htlc.creation_block_number,
+            sender: htlc.sender,
+            recipient: htlc.recipient,
+            hash_root: htlc.hash_root,
+            hash_count: htlc.hash_count,
+            timeout: htlc.timeout,
+            total_amount: htlc.total_amount,
+            balance: htlc.balance,
+            status: htlc.status.into(),
+            pre_image: htlc.pre_image,
+            resolution_transaction_hash: htlc.resolution_tx_hash,
+            resolution_block_number: htlc.resolution_block_number,
+        }
+    }
+}
+
+/// The state of a vesting contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VestingStatus {
+    /// The contract still holds funds.
+    Active,
+    /// All funds were withdrawn from the contract.
+    Withdrawn,
+}
+
+impl From<nimiq_blockchain::contract_lifecycle::VestingStatus> for VestingStatus {
+    fn from(status: nimiq_blockchain::contract_lifecycle::VestingStatus) -> Self {
+        match status {
+            nimiq_blockchain::contract_lifecycle::VestingStatus::Active => VestingStatus::Active,
+            nimiq_blockchain::contract_lifecycle::VestingStatus::Withdrawn => {
+                VestingStatus::Withdrawn
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingContract {
+    pub address: Address,
+    pub creation_transaction_hash: Blake2bHash,
+    pub creation_block_number: u32,
+    pub owner: Address,
+    /// The block that the vesting contracted commenced.
+    pub vesting_start: u64,
+    /// The number of blocks after which some part of the vested funds is released.
+    pub vesting_step_blocks: u64,
+    /// The amount (in Luna) released every vestingStepBlocks blocks.
+    pub vesting_step_amount: Coin,
+    /// The total amount (in smallest unit) that was provided at the contract creation.
+    pub vesting_total_amount: Coin,
+    /// The balance left in the contract.
+    pub balance: Coin,
+    pub status: VestingStatus,
+    pub num_withdrawals: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_withdrawal_block_number: Option<u32>,
+}
+
+impl VestingContract {
+    pub fn from_vesting_lifecycle(
+        vesting: nimiq_blockchain::contract_lifecycle::VestingLifecycle,
+    ) -> Self {
+        VestingContract {
+            address: vesting.contract_address,
+            creation_transaction_hash: vesting.creation_tx_hash,
+            creation_block_number: vesting.creation_block_number,
+            owner: vesting.owner,
+            vesting_start: vesting.start_time,
+            vesting_step_blocks: vesting.time_step,
+            vesting_step_amount: vesting.step_amount,
+            vesting_total_amount: vesting.total_amount,
+            balance: vesting.balance,
+            status: vesting.status.into(),
+            num_withdrawals: vesting.num_withdrawals,
+            last_withdrawal_block_number: vesting.last_withdrawal_block_number,
+        }
+    }
+}
+
+/// A change to a validator or a staker in the staking contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum StakingEventData {
+    #[serde(rename_all = "camelCase")]
+    CreateValidator {
+        validator_address: Address,
+        signing_key: Ed25519PublicKey,
+        voting_key: CompressedPublicKey,
+        reward_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signal_data: Option<Blake2bHash>,
+        deposit: Coin,
+    },
+    #[serde(rename_all = "camelCase")]
+    UpdateValidator {
+        validator_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_signing_key: Option<Ed25519PublicKey>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_voting_key: Option<CompressedPublicKey>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_reward_address: Option<Address>,
+        /// Set if the signal data is updated. An inner `None` removes the signal data.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_signal_data: Option<Option<Blake2bHash>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    DeactivateValidator { validator_address: Address },
+    #[serde(rename_all = "camelCase")]
+    ReactivateValidator { validator_address: Address },
+    #[serde(rename_all = "camelCase")]
+    RetireValidator { validator_address: Address },
+    #[serde(rename_all = "camelCase")]
+    DeleteValidator {
+        validator_address: Address,
+        recipient: Address,
+        value: Coin,
+    },
+    /// The fee of a failed transaction sent from the deposit of a validator.
+    #[serde(rename_all = "camelCase")]
+    ValidatorFeeDeduction {
+        validator_address: Address,
+        fee: Coin,
+    },
+    #[serde(rename_all = "camelCase")]
+    JailValidator {
+        validator_address: Address,
+        offense_event_block: u32,
+    },
+    #[serde(rename_all = "camelCase")]
+    PenalizeValidator {
+        validator_address: Address,
+        slot: u16,
+        offense_event_block: u32,
+    },
+    #[serde(rename_all = "camelCase")]
+    CreateStaker {
+        staker_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delegation: Option<Address>,
+        value: Coin,
+    },
+    #[serde(rename_all = "camelCase")]
+    AddStake {
+        staker_address: Address,
+        value: Coin,
+    },
+    #[serde(rename_all = "camelCase")]
+    UpdateStaker {
+        staker_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        old_delegation: Option<Address>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new_delegation: Option<Address>,
+        reactivate_all_stake: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    SetActiveStake {
+        staker_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delegation: Option<Address>,
+        new_active_balance: Coin,
+    },
+    #[serde(rename_all = "camelCase")]
+    RetireStake {
+        staker_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delegation: Option<Address>,
+        retire_stake: Coin,
+    },
+    #[serde(rename_all = "camelCase")]
+    RemoveStake {
+        staker_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delegation: Option<Address>,
+        recipient: Address,
+        value: Coin,
+    },
+    /// The fee of a failed transaction sent from the stake of a staker.
+    #[serde(rename_all = "camelCase")]
+    StakerFeeDeduction { staker_address: Address, fee: Coin },
+}
+
+impl From<nimiq_blockchain::staking_history::StakingEventData> for StakingEventData {
+    fn from(data: nimiq_blockchain::staking_history::StakingEventData) -> Self {
+        use nimiq_blockchain::staking_history::StakingEventData as Data;
+
+        match data {
+            Data::CreateValidator {
+                validator_address,
+                signing_key,
+                voting_key,
+                reward_address,
+                signal_data,
+                deposit,
+            } => StakingEventData::CreateValidator {
+                validator_address,
+                signing_key,
+                voting_key,
+                reward_address,
+                signal_data,
+                deposit,
+            },
+            Data::UpdateValidator {
+                validator_address,
+                new_signing_key,
+                new_voting_key,
+                new_reward_address,
+                new_signal_data,
+            } => StakingEventData::UpdateValidator {
+                validator_address,
+                new_signing_key,
+                new_voting_key,
+                new_reward_address,
+                new_signal_data,
+            },
+            Data::DeactivateValidator { validator_address } => {
+                StakingEventData::DeactivateValidator { validator_address }
+            }
+            Data::ReactivateValidator { validator_address } => {
+                StakingEventData::ReactivateValidator { validator_address }
+            }
+            Data::RetireValidator { validator_address } => {
+                StakingEventData::RetireValidator { validator_address }
+            }
+            Data::DeleteValidator {
+                validator_address,
+                recipient,
+                value,
+            } => StakingEventData::DeleteValidator {
+                validator_address,
+                recipient,
+                value,
+            },
+            Data::ValidatorFeeDeduction {
+                validator_address,
+                fee,
+            } => StakingEventData::ValidatorFeeDeduction {
+                validator_address,
+                fee,
+            },
+            Data::JailValidator {
+                validator_address,
+                offense_event_block,
+            } => StakingEventData::JailValidator {
+                validator_address,
+                offense_event_block,
+            },
+            Data::PenalizeValidator {
+                validator_address,
+                slot,
+                offense_event_block,
+            } => StakingEventData::PenalizeValidator {
+                validator_address,
+                slot,
+                offense_event_block,
+            },
+            Data::CreateStaker {
+                staker_address,
+                delegation,
+                value,
+            } => StakingEventData::CreateStaker {
+                staker_address,
+                delegation,
+                value,
+            },
+            Data::AddStake {
+                staker_address,
+                value,
+            } => StakingEventData::AddStake {
+                staker_address,
+                value,
+            },
+            Data::UpdateStaker {
+                staker_address,
+                old_delegation,
+                new_delegation,
+                reactivate_all_stake,
+            } => StakingEventData::UpdateStaker {
+                staker_address,
+                old_delegation,
+                new_delegation,
+                reactivate_all_stake,
+            },
+            Data::SetActiveStake {
+                staker_address,
+                delegation,
+                new_active_balance,
+            } => StakingEventData::SetActiveStake {
+                staker_address,
+                delegation,
+                new_active_balance,
+            },
+            Data::RetireStake {
+                staker_address,
+                delegation,
+                retire_stake,
+            } => StakingEventData::RetireStake {
+                staker_address,
+                delegation,
+                retire_stake,
+            },
+            Data::RemoveStake {
+                staker_address,
+                delegation,
+                recipient,
+                value,
+            } => StakingEventData::RemoveStake {
+                staker_address,
+                delegation,
+                recipient,
+                value,
+            },
+            Data::StakerFeeDeduction {
+                staker_address,
+                fee,
+            } => StakingEventData::StakerFeeDeduction {
+                staker_address,
+                fee,
+            },
+        }
+    }
+}
+
+/// An event of the validator or staker history, together with the transaction or inherent it
+/// originates from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingEvent {
+    /// The raw hash of the transaction, or the hash of the jail or penalty inherent. It can be
+    /// passed as `startAt` to fetch the events preceding this one.
+    pub transaction_hash: Blake2bHash,
+    pub block_number: u32,
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub data: StakingEventData,
+}
+
+impl StakingEvent {
+    pub fn from_staking_event(event: nimiq_blockchain::staking_history::StakingEvent) -> Self {
+        StakingEvent {
+            transaction_hash: event.tx_hash,
+            block_number: event.block_number,
+            timestamp: event.block_time,
+            data: event.data.into(),
+        }
+    }
+}
+
+/// The stakers delegating active stake to a validator during an epoch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStakers {
+    pub epoch_number: u32,
+    pub num_stakers: usize,
+    /// The deposit of the validator and the active balance of its stakers.
+    pub total_stake: Coin,
+}
+
+impl ValidatorStakers {
+    pub fn from_validator_stakers(
+        stakers: nimiq_blockchain::staking_history::ValidatorStakers,
+    ) -> Self {
+        ValidatorStakers {
+            epoch_number: stakers.epoch_number,
+            num_stakers: stakers.num_stakers,
+            total_stake: stakers.total_stake,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Validator {
@@ -915,6 +2021,13 @@ impl<T> RPCData<T, BlockchainState> {
             metadata: BlockchainState::with_blockchain(blockchain),
         }
     }
+
+    pub fn with_snapshot(data: T, snapshot: &BlockchainSnapshot) -> RPCData<T, BlockchainState> {
+        RPCData {
+            data,
+            metadata: BlockchainState::with_snapshot(snapshot),
+        }
+    }
 }
 
 impl<T> From<T> for RPCData<T, ()> {
@@ -983,6 +2096,10 @@ impl BlockchainState {
         let block = blockchain.head();
         BlockchainState::new(block.block_number(), block.hash())
     }
+
+    pub fn with_snapshot(snapshot: &BlockchainSnapshot) -> Self {
+        BlockchainState::new(snapshot.block_number(), snapshot.block_hash().clone())
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, ValueEnum)]
@@ -1114,6 +2231,24 @@ impl ZKPState {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZKProof {
+    /// The block number of the election block the proof is for.
+    pub block_number: u32,
+    /// The hex encoded proof in its network and storage serialization.
+    pub proof: String,
+}
+
+impl ZKProof {
+    pub fn from_zk_proof(zk_proof: &nimiq_zkp_component::types::ZKProof) -> Self {
+        Self {
+            block_number: zk_proof.block_number,
+            proof: hex::encode(zk_proof.serialize_to_vec()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MempoolInfo {
@@ -1292,3 +2427,57 @@ impl MempoolInfo {
         info
     }
 }
+
+/// Page usage and fragmentation statistics of the node's database.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStats {
+    pub page_size: usize,
+    /// The maximum size of the database in bytes.
+    pub map_size: usize,
+    /// The number of pages allocated in the database file.
+    pub used_pages: usize,
+    pub used_size: usize,
+    /// The number of allocated pages that are free for reuse.
+    pub free_pages: usize,
+    pub free_size: usize,
+    /// The share of allocated pages that are free, between 0 and 1.
+    pub fragmentation: f64,
+    pub tables: Vec<DatabaseTableStats>,
+}
+
+/// Storage statistics of a single database table.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseTableStats {
+    pub name: String,
+    pub entries: usize,
+    pub depth: u32,
+    pub branch_pages: usize,
+    pub leaf_pages: usize,
+    pub overflow_pages: usize,
+    /// The size of all pages of the table in bytes.
+    pub size: usize,
+}
+
+/// A completed backup of the node's database.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseBackup {
+    /// The directory the backup was written to.
+    pub path: String,
+    /// The time it took to create the backup in milliseconds.
+    pub duration: u64,
+}
+
+/// The result of reverting the chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertedChain {
+    /// The block number of the new head.
+    pub block_number: u32,
+    /// The hash of the new head.
+    pub hash: Blake2bHash,
+    /// The number of blocks that were reverted.
+    pub num_reverted_blocks: u32,
+}
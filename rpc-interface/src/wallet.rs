@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature};
+use nimiq_primitives::coin::Coin;
 
-use crate::types::{RPCResult, ReturnAccount, ReturnSignature};
+use crate::types::{HdAccount, MultisigAccount, RPCResult, ReturnAccount, ReturnSignature};
 
 #[nimiq_jsonrpc_derive::proxy(name = "WalletProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -61,4 +62,165 @@ pub trait WalletInterface {
         signature: Ed25519Signature,
         is_hex: bool,
     ) -> RPCResult<bool, (), Self::Error>;
+
+    /// Creates a partially signed transaction, in hexadecimal format, for the given raw
+    /// transaction to be signed by a single key.
+    async fn create_pst(
+        &mut self,
+        raw_tx: String,
+        public_key: Ed25519PublicKey,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Creates a partially signed transaction, in hexadecimal format, for the given raw
+    /// transaction to be signed by the `signers` of a multi-signature account.
+    async fn create_multisig_pst(
+        &mut self,
+        raw_tx: String,
+        public_keys: Vec<Ed25519PublicKey>,
+        min_signatures: u8,
+        signers: Vec<Ed25519PublicKey>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Adds the MuSig2 commitments of the account to a partially signed transaction of a
+    /// multi-signature account. The secret nonces are kept in memory until the account signs.
+    async fn commit_pst(
+        &mut self,
+        pst: String,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Adds the (partial) signature of the account to a partially signed transaction.
+    /// For multi-signature accounts, the account must have committed before and all commitments
+    /// must have been collected. The secret nonces of the commitment are consumed by the first
+    /// signing attempt, even if it fails, so the account has to commit again to sign again.
+    async fn sign_pst(
+        &mut self,
+        pst: String,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Combines the contributions of several copies of a partially signed transaction.
+    async fn combine_psts(&mut self, psts: Vec<String>) -> RPCResult<String, (), Self::Error>;
+
+    /// Creates the signed raw transaction, in hexadecimal format, from a partially signed
+    /// transaction once all signatures have been collected.
+    async fn finalize_pst(&mut self, pst: String) -> RPCResult<String, (), Self::Error>;
+
+    /// Registers the k-of-n multi-signature account formed by the `public_keys`. The
+    /// `owner_public_key` is the key of the local participant and its account must have been
+    /// imported before.
+    async fn import_multisig_account(
+        &mut self,
+        public_keys: Vec<Ed25519PublicKey>,
+        min_signatures: u8,
+        owner_public_key: Ed25519PublicKey,
+    ) -> RPCResult<MultisigAccount, (), Self::Error>;
+
+    /// Returns the multi-signature accounts that have been registered.
+    async fn list_multisig_accounts(&mut self) -> RPCResult<Vec<MultisigAccount>, (), Self::Error>;
+
+    /// Removes a registered multi-signature account. Returns if the account was registered.
+    async fn remove_multisig_account(
+        &mut self,
+        address: Address,
+    ) -> RPCResult<bool, (), Self::Error>;
+
+    /// Creates a partially signed basic transaction, in hexadecimal format, sending funds from a
+    /// registered multi-signature account. The transaction is going to be signed by `signers`.
+    async fn create_multisig_transaction(
+        &mut self,
+        multisig_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        signers: Vec<Ed25519PublicKey>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Adds the commitments of the local participant to a partially signed transaction sent from
+    /// a registered multi-signature account.
+    async fn commit_multisig_transaction(
+        &mut self,
+        pst: String,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Adds the partial signature of the local participant to a partially signed transaction
+    /// sent from a registered multi-signature account.
+    async fn sign_multisig_transaction(
+        &mut self,
+        pst: String,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Imports a 24 words BIP39 mnemonic, optionally protected by `mnemonic_password`, and stores
+    /// its seed locked with the passphrase. Returns the identifier of the seed.
+    async fn import_mnemonic(
+        &mut self,
+        mnemonic: String,
+        mnemonic_password: Option<String>,
+        passphrase: Option<String>,
+    ) -> RPCResult<Address, (), Self::Error>;
+
+    /// Returns the identifiers of the seeds that have been imported.
+    async fn list_seeds(&mut self) -> RPCResult<Vec<Address>, (), Self::Error>;
+
+    /// Derives the account at `path` from a seed and imports it, locked with the passphrase of
+    /// the seed. If no path is given, the next unused index below `m/44'/242'/0'` is used.
+    async fn derive_account(
+        &mut self,
+        seed_id: Address,
+        path: Option<String>,
+        passphrase: Option<String>,
+    ) -> RPCResult<HdAccount, (), Self::Error>;
+
+    /// Returns the accounts that have been derived from the given seed, or from any seed.
+    async fn list_derived_accounts(
+        &mut self,
+        seed_id: Option<Address>,
+    ) -> RPCResult<Vec<HdAccount>, (), Self::Error>;
+
+    /// Derives the accounts `m/44'/242'/0'/i'` of a seed and imports the ones having
+    /// transactions in the history, until `gap_limit` (default 20, at most 100) consecutive unused
+    /// accounts are found. Returns the used accounts.
+    async fn discover_accounts(
+        &mut self,
+        seed_id: Address,
+        passphrase: Option<String>,
+        gap_limit: Option<u32>,
+    ) -> RPCResult<Vec<HdAccount>, (), Self::Error>;
+
+    /// Exports the given accounts, or all accounts, as an encrypted backup in hexadecimal format.
+    /// The accounts are unlocked with `passphrase` and the backup is encrypted with
+    /// `backup_passphrase`, using Argon2id with the parameters recorded in the backup.
+    async fn export_backup(
+        &mut self,
+        addresses: Option<Vec<Address>>,
+        passphrase: Option<String>,
+        backup_passphrase: String,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Imports the accounts of an encrypted backup and locks them with `passphrase`. Returns the
+    /// addresses of the imported accounts.
+    async fn import_backup(
+        &mut self,
+        backup: String,
+        backup_passphrase: String,
+        passphrase: Option<String>,
+    ) -> RPCResult<Vec<Address>, (), Self::Error>;
+
+    /// Re-encrypts the given accounts and seeds, or all of them, with a new passphrase (if given)
+    /// and the current key derivation algorithm with `iterations` and `memory_cost` in KiB (if
+    /// given, the defaults otherwise). Multisig addresses re-encrypt the account owning them.
+    /// Returns the given addresses, or all re-encrypted addresses.
+    async fn reencrypt_accounts(
+        &mut self,
+        addresses: Option<Vec<Address>>,
+        passphrase: Option<String>,
+        new_passphrase: Option<String>,
+        iterations: Option<u32>,
+        memory_cost: Option<u32>,
+    ) -> RPCResult<Vec<Address>, (), Self::Error>;
 }
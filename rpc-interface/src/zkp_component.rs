@@ -1,6 +1,7 @@
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 
-use crate::types::{RPCResult, ZKPState};
+use crate::types::{RPCData, RPCResult, ZKPState, ZKProof};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ZKPComponentProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -9,4 +10,16 @@ pub trait ZKPComponentInterface {
 
     /// Returns the current ZKP state (proof with its related block hash and block number).
     async fn get_zkp_state(&mut self) -> RPCResult<ZKPState, (), Self::Error>;
+
+    /// Returns the ZK proof for the election block at the given block number, if it is still kept.
+    async fn get_zkp_by_block_number(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<ZKProof, (), Self::Error>;
+
+    /// Subscribes to new ZK proofs, both received from peers and generated by us.
+    #[stream]
+    async fn subscribe_for_zkps(
+        &mut self,
+    ) -> Result<BoxStream<'static, RPCData<ZKProof, ()>>, Self::Error>;
 }
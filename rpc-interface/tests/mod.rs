@@ -1,5 +1,14 @@
+use hex::FromHex;
 use nimiq_hash::Blake2bHash;
-use nimiq_rpc_interface::types::Block;
+use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_primitives::{account::AccountType, coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_rpc_interface::types::{
+    Block, ScheduleTrigger, SignatureAlgorithm, Transaction, TransactionData, TransactionProof,
+};
+use nimiq_serde::Serialize;
+use nimiq_transaction::{
+    account::staking_contract::IncomingStakingTransactionData, SignatureProof,
+};
 
 #[test]
 fn it_can_deserialize_result_blocks() {
@@ -7,3 +16,101 @@ fn it_can_deserialize_result_blocks() {
     let value = serde_json::from_str(data).unwrap();
     let _result: Result<Block, Blake2bHash> = serde_json::from_value(value).unwrap();
 }
+
+#[test]
+fn it_decodes_staking_transactions() {
+    let key_pair = KeyPair::from(
+        PrivateKey::from_hex("9d5bd02379e7e45cf515c788048f5cf3c454ffabd3e83bd1d7667716c325c3c0")
+            .unwrap(),
+    );
+    let sender = Address::from(&key_pair.public);
+    let validator = Address::from([1u8; 20]);
+
+    let data = IncomingStakingTransactionData::CreateStaker {
+        delegation: Some(validator.clone()),
+        proof: SignatureProof::from_ed25519(key_pair.public, key_pair.sign(b"data")),
+    };
+    let mut tx = nimiq_transaction::Transaction::new_extended(
+        sender.clone(),
+        AccountType::Basic,
+        vec![],
+        Policy::STAKING_CONTRACT_ADDRESS,
+        AccountType::Staking,
+        data.serialize_to_vec(),
+        Coin::from_u64_unchecked(100),
+        Coin::ZERO,
+        1,
+        NetworkId::Dummy,
+    );
+    let signature = key_pair.sign(&tx.serialize_content());
+    tx.proof = SignatureProof::from_ed25519(key_pair.public, signature).serialize_to_vec();
+
+    let tx = Transaction::from_transaction(tx);
+    assert!(tx.decoded_sender_data.is_none());
+    match tx.decoded_recipient_data {
+        Some(TransactionData::CreateStaker {
+            ref delegation,
+            ref proof,
+        }) => {
+            assert_eq!(delegation, &Some(validator));
+            assert_eq!(proof.signer, sender);
+        }
+        ref data => panic!("Unexpected recipient data: {data:?}"),
+    }
+    match tx.decoded_proof {
+        Some(TransactionProof::Signature(ref proof)) => {
+            assert_eq!(proof.signer, sender);
+            assert_eq!(proof.algorithm, SignatureAlgorithm::Ed25519);
+            assert_eq!(proof.public_key, key_pair.public.to_hex());
+            assert!(proof.webauthn_fields.is_none());
+        }
+        ref proof => panic!("Unexpected proof: {proof:?}"),
+    }
+
+    // The decoded fields survive a round trip through JSON.
+    let json = serde_json::to_string(&tx).unwrap();
+    let tx: Transaction = serde_json::from_str(&json).unwrap();
+    assert!(matches!(
+        tx.decoded_recipient_data,
+        Some(TransactionData::CreateStaker { .. })
+    ));
+}
+
+#[test]
+fn it_parses_schedule_triggers() {
+    let address = Address::from_any_str("NQ20 TSB0 DFSM UH9C 15GQ GAGJ TTE4 D3MA 859E").unwrap();
+
+    assert!(matches!(
+        "block:1000".parse::<ScheduleTrigger>(),
+        Ok(ScheduleTrigger::BlockNumber { block_number: 1000 })
+    ));
+    assert!(matches!(
+        "time:1654872311288".parse::<ScheduleTrigger>(),
+        Ok(ScheduleTrigger::Timestamp {
+            timestamp: 1654872311288
+        })
+    ));
+    match format!("htlc:{}", address.to_user_friendly_address()).parse::<ScheduleTrigger>() {
+        Ok(ScheduleTrigger::HtlcTimeout { contract_address }) => {
+            assert_eq!(contract_address, address)
+        }
+        trigger => panic!("Unexpected trigger: {trigger:?}"),
+    }
+    match format!("vesting:{}:500", address.to_user_friendly_address()).parse::<ScheduleTrigger>() {
+        Ok(ScheduleTrigger::VestingUnlocked {
+            contract_address,
+            value,
+        }) => {
+            assert_eq!(contract_address, address);
+            assert_eq!(value, Coin::from_u64_unchecked(500));
+        }
+        trigger => panic!("Unexpected trigger: {trigger:?}"),
+    }
+
+    assert!("block".parse::<ScheduleTrigger>().is_err());
+    assert!("block:soon".parse::<ScheduleTrigger>().is_err());
+    assert!("epoch:1".parse::<ScheduleTrigger>().is_err());
+
+    let json = serde_json::to_string(&ScheduleTrigger::BlockNumber { block_number: 7 }).unwrap();
+    assert_eq!(json, r#"{"type":"blockNumber","blockNumber":7}"#);
+}
@@ -0,0 +1,109 @@
+use std::{sync::Arc, time::Instant};
+
+use async_trait::async_trait;
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_database::DatabaseProxy;
+use nimiq_rpc_interface::{
+    admin::AdminInterface,
+    types::{DatabaseBackup, DatabaseStats, DatabaseTableStats, RPCResult, RevertedChain},
+};
+
+use crate::error::Error;
+
+pub struct AdminDispatcher {
+    env: DatabaseProxy,
+    blockchain: BlockchainProxy,
+}
+
+impl AdminDispatcher {
+    pub fn new(env: DatabaseProxy, blockchain: BlockchainProxy) -> Self {
+        AdminDispatcher { env, blockchain }
+    }
+}
+
+#[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
+#[async_trait]
+impl AdminInterface for AdminDispatcher {
+    type Error = Error;
+
+    async fn get_database_stats(&mut self) -> RPCResult<DatabaseStats, (), Self::Error> {
+        let stats = self.env.stats()?;
+        let tables = self
+            .env
+            .table_stats()?
+            .into_iter()
+            .map(|table| DatabaseTableStats {
+                name: table.name,
+                entries: table.entries,
+                depth: table.depth,
+                branch_pages: table.branch_pages,
+                leaf_pages: table.leaf_pages,
+                overflow_pages: table.overflow_pages,
+                size: table.size,
+            })
+            .collect();
+
+        Ok(DatabaseStats {
+            page_size: stats.page_size,
+            map_size: stats.map_size,
+            used_pages: stats.used_pages,
+            used_size: stats.used_size,
+            free_pages: stats.free_pages,
+            free_size: stats.free_size,
+            fragmentation: stats.fragmentation(),
+            tables,
+        }
+        .into())
+    }
+
+    async fn backup_database(
+        &mut self,
+        path: String,
+    ) -> RPCResult<DatabaseBackup, (), Self::Error> {
+        let env = self.env.clone();
+        let start = Instant::now();
+
+        // Copying the whole database takes a while, so don't block the executor.
+        let backup_path = path.clone();
+        tokio::task::spawn_blocking(move || env.backup_to(backup_path))
+            .await
+            .map_err(|error| Error::Io(error.into()))??;
+
+        log::info!(%path, duration = ?start.elapsed(), "Database backup created");
+        Ok(DatabaseBackup {
+            path,
+            duration: start.elapsed().as_millis() as u64,
+        }
+        .into())
+    }
+
+    async fn revert_chain(
+        &mut self,
+        block_number: u32,
+        force: bool,
+    ) -> RPCResult<RevertedChain, (), Self::Error> {
+        let BlockchainProxy::Full(blockchain) = &self.blockchain else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        let blockchain = Arc::clone(blockchain);
+
+        // Reverting many blocks takes a while, so don't block the executor.
+        let reverting_blockchain = Arc::clone(&blockchain);
+        let reverted_blocks = tokio::task::spawn_blocking(move || {
+            Blockchain::revert_to(reverting_blockchain.upgradable_read(), block_number, force)
+        })
+        .await
+        .map_err(|error| Error::Io(error.into()))??;
+
+        let blockchain = blockchain.read();
+        Ok(RevertedChain {
+            block_number: blockchain.block_number(),
+            hash: blockchain.head_hash(),
+            num_reverted_blocks: reverted_blocks.len() as u32,
+        }
+        .into())
+    }
+}
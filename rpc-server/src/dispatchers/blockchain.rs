@@ -1,19 +1,23 @@
 use async_trait::async_trait;
 use futures::{future, stream::BoxStream, StreamExt};
 use nimiq_account::{BlockLog as BBlockLog, TransactionLog};
+use nimiq_blockchain::{reward_attribution::MAX_COMMISSION, Blockchain, BlockchainSnapshot};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
-use nimiq_hash::Blake2bHash;
+use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::Address;
-use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
+use nimiq_primitives::{coin::Coin, key_nibbles::KeyNibbles, policy::Policy};
 use nimiq_rpc_interface::{
     blockchain::BlockchainInterface,
     types::{
         is_of_log_type_and_related_to_addresses, Account, Block, BlockLog, BlockchainState,
-        ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, Slot, Staker,
-        Validator,
+        ExecutedTransaction, Htlc, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, Slot,
+        Staker, StakerRewards, StakingEvent, TransactionSimulation, Validator, ValidatorStakers,
+        VestingContract,
     },
 };
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::Transaction;
 use tokio_stream::wrappers::BroadcastStream;
 
 use crate::error::Error;
@@ -45,29 +49,36 @@ fn get_block_by_hash(
         .map(|block| block.into())
 }
 
-/// Tries to fetch a validator information given its address.
+/// Takes a snapshot of the blockchain state at the given block, or at the head if no block is
+/// given. All parts of a response must be read from the same snapshot to refer to the same block.
 /// This function requires the read lock acquisition prior to its execution.
+fn snapshot<'a>(
+    blockchain: &'a Blockchain,
+    block_hash: Option<&Blake2bHash>,
+) -> Result<BlockchainSnapshot<'a>, Error> {
+    match block_hash {
+        Some(block_hash) => blockchain
+            .snapshot_at(block_hash)
+            .ok_or_else(|| Error::StateNotAvailable(block_hash.clone())),
+        None => Ok(blockchain.snapshot()),
+    }
+}
+
+/// Tries to fetch a validator information given its address.
 fn get_validator_by_address(
-    blockchain_proxy: &BlockchainReadProxy,
+    snapshot: &BlockchainSnapshot,
     address: &Address,
 ) -> RPCResult<Validator, BlockchainState, Error> {
-    if let BlockchainReadProxy::Full(blockchain) = blockchain_proxy {
-        let staking_contract = blockchain
-            .get_staking_contract_if_complete(None)
-            .ok_or(Error::NoConsensus)?;
-        let data_store = blockchain.get_staking_contract_store();
-        let db_txn = blockchain.read_transaction();
-        let validator = staking_contract
-            .get_validator(&data_store.read(&db_txn), address)
-            .ok_or_else(|| Error::ValidatorNotFound(address.clone()))?;
-
-        Ok(RPCData::with_blockchain(
-            Validator::from_validator(&validator),
-            blockchain_proxy,
-        ))
-    } else {
-        Err(Error::NotSupportedForLightBlockchain)
-    }
+    let staking_contract = snapshot.get_staking_contract().ok_or(Error::NoConsensus)?;
+    let data_store = snapshot.get_staking_contract_store();
+    let validator = staking_contract
+        .get_validator(&data_store.read(snapshot.txn()), address)
+        .ok_or_else(|| Error::ValidatorNotFound(address.clone()))?;
+
+    Ok(RPCData::with_snapshot(
+        Validator::from_validator(&validator),
+        snapshot,
+    ))
 }
 
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
@@ -155,8 +166,12 @@ impl BlockchainInterface for BlockchainDispatcher {
         hash: Blake2bHash,
     ) -> RPCResult<ExecutedTransaction, (), Self::Error> {
         if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = blockchain.snapshot();
+
             // Get all the historic transactions that correspond to this hash.
-            let mut historic_tx_vec = blockchain.history_store.get_hist_tx_by_hash(&hash, None);
+            let mut historic_tx_vec = blockchain
+                .history_store
+                .get_hist_tx_by_hash(&hash, Some(snapshot.txn()));
 
             // Unpack the transaction or raise an error.
             let historic_tx = match historic_tx_vec.len() {
@@ -179,7 +194,7 @@ impl BlockchainInterface for BlockchainDispatcher {
                     tx,
                     block_number,
                     timestamp,
-                    blockchain.block_number(),
+                    snapshot.block_number(),
                 )
                 .into()),
                 Err(_) => Err(Error::TransactionNotFound(hash)),
@@ -194,10 +209,12 @@ impl BlockchainInterface for BlockchainDispatcher {
         block_number: u32,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error> {
         if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = blockchain.snapshot();
+
             // Get all the historic transactions that correspond to this block.
             let historic_tx_vec = blockchain
                 .history_store
-                .get_block_transactions(block_number, None);
+                .get_block_transactions(block_number, Some(snapshot.txn()));
 
             // Get the timestamp of the block from one of the historic transactions. This complicated
             // setup is because we might not have any transactions.
@@ -213,7 +230,7 @@ impl BlockchainInterface for BlockchainDispatcher {
                         tx,
                         block_number,
                         timestamp,
-                        blockchain.block_number(),
+                        snapshot.block_number(),
                     ));
                 }
             }
@@ -229,10 +246,12 @@ impl BlockchainInterface for BlockchainDispatcher {
         block_number: u32,
     ) -> RPCResult<Vec<Inherent>, (), Self::Error> {
         if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = blockchain.snapshot();
+
             // Get all the historic transactions that correspond to this block.
             let historic_tx_vec = blockchain
                 .history_store
-                .get_block_transactions(block_number, None);
+                .get_block_transactions(block_number, Some(snapshot.txn()));
 
             // Get only the inherents. This includes reward inherents.
             let mut inherents = vec![];
@@ -263,10 +282,13 @@ impl BlockchainInterface for BlockchainDispatcher {
             ))?;
 
             // Search all micro blocks of the batch to find the transactions.
+            let snapshot = blockchain.snapshot();
             let mut transactions = vec![];
 
             for i in first_block..=last_block {
-                let hist_txs = blockchain.history_store.get_block_transactions(i, None);
+                let hist_txs = blockchain
+                    .history_store
+                    .get_block_transactions(i, Some(snapshot.txn()));
 
                 // Get the timestamp of the block from one of the historic transactions. This complicated
                 // setup is because we might not have any transactions.
@@ -280,7 +302,7 @@ impl BlockchainInterface for BlockchainDispatcher {
                             tx,
                             i,
                             timestamp,
-                            blockchain.block_number(),
+                            snapshot.block_number(),
                         ));
                     }
                 }
@@ -301,6 +323,7 @@ impl BlockchainInterface for BlockchainDispatcher {
                 Error::InvalidArgument("Batch number out of bounds".to_string()),
             )?;
 
+            let snapshot = blockchain.snapshot();
             let mut inherent_tx_vec = vec![];
 
             // Search all micro blocks of the batch to find the punishment inherents.
@@ -310,7 +333,9 @@ impl BlockchainInterface for BlockchainDispatcher {
             let last_micro_block = macro_block_number - 1;
 
             for i in first_micro_block..=last_micro_block {
-                let micro_hist_tx_vec = blockchain.history_store.get_block_transactions(i, None);
+                let micro_hist_tx_vec = blockchain
+                    .history_store
+                    .get_block_transactions(i, Some(snapshot.txn()));
 
                 for hist_tx in micro_hist_tx_vec {
                     if let Some(inherent) = Inherent::try_from(hist_tx) {
@@ -323,7 +348,7 @@ impl BlockchainInterface for BlockchainDispatcher {
             inherent_tx_vec.extend(
                 blockchain
                     .history_store
-                    .get_block_transactions(macro_block_number, None)
+                    .get_block_transactions(macro_block_number, Some(snapshot.txn()))
                     .into_iter()
                     .filter_map(Inherent::try_from),
             );
@@ -339,11 +364,11 @@ impl BlockchainInterface for BlockchainDispatcher {
         address: Address,
         max: Option<u16>,
     ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error> {
-        if let BlockchainProxy::Full(blockchain) = &self.blockchain {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = blockchain.snapshot();
             Ok(blockchain
-                .read()
                 .history_store
-                .get_tx_hashes_by_address(&address, max.unwrap_or(500), None)
+                .get_tx_hashes_by_address(&address, max.unwrap_or(500), Some(snapshot.txn()))
                 .into())
         } else {
             Err(Error::NotSupportedForLightBlockchain)
@@ -356,18 +381,24 @@ impl BlockchainInterface for BlockchainDispatcher {
         max: Option<u16>,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error> {
         if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            // Read the hashes and the transactions from the same snapshot, such that all
+            // transactions are found and their confirmations refer to the same head.
+            let snapshot = blockchain.snapshot();
+
             // Get the transaction hashes for this address.
             let tx_hashes = blockchain.history_store.get_tx_hashes_by_address(
                 &address,
                 max.unwrap_or(500),
-                None,
+                Some(snapshot.txn()),
             );
 
             let mut txs = vec![];
 
             for hash in tx_hashes {
                 // Get all the historic transactions that correspond to this hash.
-                let mut historic_tx_vec = blockchain.history_store.get_hist_tx_by_hash(&hash, None);
+                let mut historic_tx_vec = blockchain
+                    .history_store
+                    .get_hist_tx_by_hash(&hash, Some(snapshot.txn()));
 
                 // Unpack the transaction or raise an error.
                 let historic_tx = match historic_tx_vec.len() {
@@ -390,7 +421,7 @@ impl BlockchainInterface for BlockchainDispatcher {
                         tx,
                         block_number,
                         timestamp,
-                        blockchain.block_number(),
+                        snapshot.block_number(),
                     ));
                 }
             }
@@ -401,65 +432,152 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_htlcs_by_address(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<Htlc>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            Ok(blockchain
+                .get_htlcs_by_address(&address, max.unwrap_or(500))
+                .into_iter()
+                .map(Htlc::from_htlc_lifecycle)
+                .collect::<Vec<_>>()
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_vesting_contracts_by_owner(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<VestingContract>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            Ok(blockchain
+                .get_vesting_contracts_by_owner(&address, max.unwrap_or(500))
+                .into_iter()
+                .map(VestingContract::from_vesting_lifecycle)
+                .collect::<Vec<_>>()
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_account_by_address(
         &mut self,
         address: Address,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Account, BlockchainState, Self::Error> {
-        let blockchain_proxy = self.blockchain.read();
-        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
-            let account = blockchain
-                .get_account_if_complete(&address)
-                .ok_or(Error::NoConsensus)?;
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
+            let account = snapshot.get_account(&address).ok_or(Error::NoConsensus)?;
             Ok(Account::from_account_with_state(
                 address,
                 account,
-                BlockchainState::new(blockchain.block_number(), blockchain.head_hash()),
+                BlockchainState::with_snapshot(&snapshot),
             ))
         } else {
             Err(Error::NotSupportedForLightBlockchain)
         }
     }
 
-    async fn get_accounts(&mut self) -> RPCResult<Vec<Account>, BlockchainState, Self::Error> {
-        let blockchain_proxy = self.blockchain.read();
-        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
-            let db_txn = blockchain.read_transaction();
+    async fn get_accounts(
+        &mut self,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Account>, BlockchainState, Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
             let mut start = Some(KeyNibbles::default());
             let mut accounts = vec![];
             while start.is_some() {
-                let chunk = blockchain.get_accounts_chunk(Some(&db_txn), start.unwrap(), 1000);
+                let chunk = snapshot.get_accounts_chunk(start.unwrap(), 1000);
                 start = chunk.end_key;
                 for account in chunk.accounts {
                     accounts.push(Account::from_account(account.0, account.1));
                 }
             }
-            Ok(RPCData::with_blockchain(accounts, &blockchain_proxy))
+            Ok(RPCData::with_snapshot(accounts, &snapshot))
         } else {
             Err(Error::NotSupportedForLightBlockchain)
         }
     }
 
-    async fn get_active_validators(
+    async fn simulate_transaction(
         &mut self,
-    ) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error> {
+        raw_tx: String,
+        block_number: Option<u32>,
+    ) -> RPCResult<TransactionSimulation, BlockchainState, Self::Error> {
+        let tx: Transaction = Deserialize::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
+        let hash = tx.hash::<Blake2bHash>();
+
         let blockchain_proxy = self.blockchain.read();
         if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
-            let staking_contract = blockchain
-                .get_staking_contract_if_complete(None)
-                .ok_or(Error::NoConsensus)?;
+            if !blockchain.accounts_complete() {
+                return Err(Error::NoConsensus);
+            }
 
-            let mut active_validators = vec![];
+            let block_number = block_number.unwrap_or_else(|| blockchain.block_number() + 1);
+            let not_includable = |error: String| TransactionSimulation {
+                hash: hash.clone(),
+                block_number,
+                includable: false,
+                success: false,
+                fee: Coin::ZERO,
+                logs: vec![],
+                receipt: None,
+                error: Some(error),
+            };
 
-            for (address, _) in staking_contract.active_validators {
-                if let Ok(rpc_result) = get_validator_by_address(&blockchain_proxy, &address) {
-                    active_validators.push(rpc_result.data);
+            // Check the same conditions a block producer would check before executing it.
+            let simulation = if let Err(e) = tx.verify(blockchain.network_id()) {
+                not_includable(format!("Invalid transaction: {e}"))
+            } else if !tx.is_valid_at(block_number) {
+                not_includable(format!("Transaction is not valid at block {block_number}"))
+            } else if blockchain.contains_tx_in_validity_window(&hash, None) {
+                not_includable("Transaction is already included in the chain".to_string())
+            } else {
+                match blockchain.simulate_transaction(&tx, Some(block_number)) {
+                    Ok(simulation) => TransactionSimulation {
+                        hash: hash.clone(),
+                        block_number,
+                        includable: true,
+                        success: simulation.error.is_none(),
+                        fee: tx.fee,
+                        logs: simulation.log.logs,
+                        receipt: Some(hex::encode(simulation.receipt.serialize_to_vec())),
+                        error: simulation.error.map(|e| e.to_string()),
+                    },
+                    Err(e) => not_includable(e.to_string()),
                 }
-            }
+            };
 
-            Ok(RPCData::with_blockchain(
-                active_validators,
-                &blockchain_proxy,
-            ))
+            Ok(RPCData::with_blockchain(simulation, &blockchain_proxy))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_active_validators(
+        &mut self,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
+            let staking_contract = snapshot.get_staking_contract().ok_or(Error::NoConsensus)?;
+            let data_store = snapshot.get_staking_contract_store();
+            let data_store_read = data_store.read(snapshot.txn());
+
+            let active_validators = staking_contract
+                .active_validators
+                .keys()
+                .filter_map(|address| staking_contract.get_validator(&data_store_read, address))
+                .map(|validator| Validator::from_validator(&validator))
+                .collect();
+
+            Ok(RPCData::with_snapshot(active_validators, &snapshot))
         } else {
             Err(Error::NotSupportedForLightBlockchain)
         }
@@ -467,22 +585,20 @@ impl BlockchainInterface for BlockchainDispatcher {
 
     async fn get_current_penalized_slots(
         &mut self,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<PenalizedSlots, BlockchainState, Self::Error> {
-        let blockchain_proxy = self.blockchain.read();
-        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
-            let block_number = blockchain.block_number();
-            let staking_contract = blockchain
-                .get_staking_contract_if_complete(None)
-                .ok_or(Error::NoConsensus)?;
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
+            let staking_contract = snapshot.get_staking_contract().ok_or(Error::NoConsensus)?;
 
-            Ok(RPCData::with_blockchain(
+            Ok(RPCData::with_snapshot(
                 PenalizedSlots {
-                    block_number,
+                    block_number: snapshot.block_number(),
                     disabled: staking_contract
                         .punished_slots
                         .current_batch_punished_slots(),
                 },
-                &blockchain_proxy,
+                &snapshot,
             ))
         } else {
             Err(Error::NotSupportedForLightBlockchain)
@@ -491,22 +607,20 @@ impl BlockchainInterface for BlockchainDispatcher {
 
     async fn get_previous_penalized_slots(
         &mut self,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<PenalizedSlots, BlockchainState, Self::Error> {
-        let blockchain_proxy = self.blockchain.read();
-        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
-            let block_number = blockchain.block_number();
-            let staking_contract = blockchain
-                .get_staking_contract_if_complete(None)
-                .ok_or(Error::NoConsensus)?;
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
+            let staking_contract = snapshot.get_staking_contract().ok_or(Error::NoConsensus)?;
 
-            Ok(RPCData::with_blockchain(
+            Ok(RPCData::with_snapshot(
                 PenalizedSlots {
-                    block_number,
+                    block_number: snapshot.block_number(),
                     disabled: staking_contract
                         .punished_slots
                         .current_batch_punished_slots(),
                 },
-                &blockchain_proxy,
+                &snapshot,
             ))
         } else {
             Err(Error::NotSupportedForLightBlockchain)
@@ -516,24 +630,29 @@ impl BlockchainInterface for BlockchainDispatcher {
     async fn get_validator_by_address(
         &mut self,
         address: Address,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Validator, BlockchainState, Self::Error> {
-        get_validator_by_address(&self.blockchain.read(), &address)
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
+            get_validator_by_address(&snapshot, &address)
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
     }
 
-    async fn get_validators(&mut self) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error> {
-        let blockchain_proxy = self.blockchain.read();
+    async fn get_validators(
+        &mut self,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
+            let staking_contract = snapshot.get_staking_contract().ok_or(Error::NoConsensus)?;
+            let data_store = snapshot.get_staking_contract_store();
+            let validators = staking_contract.get_validators(&data_store.read(snapshot.txn()));
 
-        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
-            let staking_contract = blockchain
-                .get_staking_contract_if_complete(None)
-                .ok_or(Error::NoConsensus)?;
-            let data_store = blockchain.get_staking_contract_store();
-            let db_txn = blockchain.read_transaction();
-            let validators = staking_contract.get_validators(&data_store.read(&db_txn));
-
-            Ok(RPCData::with_blockchain(
+            Ok(RPCData::with_snapshot(
                 validators.iter().map(Validator::from_validator).collect(),
-                &blockchain_proxy,
+                &snapshot,
             ))
         } else {
             Err(Error::NotSupportedForLightBlockchain)
@@ -543,21 +662,18 @@ impl BlockchainInterface for BlockchainDispatcher {
     async fn get_stakers_by_validator_address(
         &mut self,
         address: Address,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error> {
-        let blockchain_proxy = self.blockchain.read();
-
-        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
-            let staking_contract = blockchain
-                .get_staking_contract_if_complete(None)
-                .ok_or(Error::NoConsensus)?;
-            let data_store = blockchain.get_staking_contract_store();
-            let db_txn = blockchain.read_transaction();
-            let staker =
-                staking_contract.get_stakers_for_validator(&data_store.read(&db_txn), &address);
-
-            Ok(RPCData::with_blockchain(
-                staker.iter().map(Staker::from_staker).collect(),
-                &blockchain_proxy,
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
+            let staking_contract = snapshot.get_staking_contract().ok_or(Error::NoConsensus)?;
+            let data_store = snapshot.get_staking_contract_store();
+            let stakers = staking_contract
+                .get_stakers_for_validator(&data_store.read(snapshot.txn()), &address);
+
+            Ok(RPCData::with_snapshot(
+                stakers.iter().map(Staker::from_staker).collect(),
+                &snapshot,
             ))
         } else {
             Err(Error::NotSupportedForLightBlockchain)
@@ -567,27 +683,101 @@ impl BlockchainInterface for BlockchainDispatcher {
     async fn get_staker_by_address(
         &mut self,
         address: Address,
+        block_hash: Option<Blake2bHash>,
     ) -> RPCResult<Staker, BlockchainState, Self::Error> {
-        let blockchain_proxy = self.blockchain.read();
-        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
-            let staking_contract = blockchain
-                .get_staking_contract_if_complete(None)
-                .ok_or(Error::NoConsensus)?;
-            let data_store = blockchain.get_staking_contract_store();
-            let db_txn = blockchain.read_transaction();
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let snapshot = snapshot(&blockchain, block_hash.as_ref())?;
+            let staking_contract = snapshot.get_staking_contract().ok_or(Error::NoConsensus)?;
+            let data_store = snapshot.get_staking_contract_store();
             let staker = staking_contract
-                .get_staker(&data_store.read(&db_txn), &address)
+                .get_staker(&data_store.read(snapshot.txn()), &address)
                 .ok_or(Error::StakerNotFound(address))?;
 
-            Ok(RPCData::with_blockchain(
+            Ok(RPCData::with_snapshot(
                 Staker::from_staker(&staker),
-                &blockchain_proxy,
+                &snapshot,
             ))
         } else {
             Err(Error::NotSupportedForLightBlockchain)
         }
     }
 
+    async fn get_staker_rewards(
+        &mut self,
+        validator_address: Address,
+        epoch_number: u32,
+        commission: Option<u16>,
+    ) -> RPCResult<StakerRewards, (), Self::Error> {
+        let commission = commission.unwrap_or_default();
+        if commission > MAX_COMMISSION {
+            return Err(Error::InvalidArgument(format!(
+                "Commission must not exceed {MAX_COMMISSION} basis points"
+            )));
+        }
+
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let staker_rewards =
+                blockchain.get_staker_rewards(&validator_address, epoch_number, commission)?;
+
+            Ok(StakerRewards::from_staker_rewards(staker_rewards).into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_validator_history(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<StakingEvent>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            Ok(blockchain
+                .get_validator_history(&address, max.unwrap_or(500), start_at.as_ref())
+                .into_iter()
+                .map(StakingEvent::from_staking_event)
+                .collect::<Vec<_>>()
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_staker_history(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<StakingEvent>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            Ok(blockchain
+                .get_staker_history(&address, max.unwrap_or(500), start_at.as_ref())
+                .into_iter()
+                .map(StakingEvent::from_staking_event)
+                .collect::<Vec<_>>()
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_validator_stakers_history(
+        &mut self,
+        address: Address,
+        max_epochs: Option<u16>,
+    ) -> RPCResult<Vec<ValidatorStakers>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            Ok(blockchain
+                .get_validator_stakers_history(&address, max_epochs.unwrap_or(30))
+                .into_iter()
+                .map(ValidatorStakers::from_validator_stakers)
+                .collect::<Vec<_>>()
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     #[stream]
     async fn subscribe_for_head_block(
         &mut self,
@@ -644,10 +834,13 @@ impl BlockchainInterface for BlockchainDispatcher {
         Ok(stream
             .filter_map(move |event| {
                 let result = match event {
-                    BlockchainEvent::EpochFinalized(..) => {
-                        let blockchain_rg = blockchain.read();
-                        get_validator_by_address(&blockchain_rg, &address).ok()
-                    }
+                    BlockchainEvent::EpochFinalized(..) => match blockchain.read() {
+                        BlockchainReadProxy::Full(blockchain) => {
+                            let snapshot = blockchain.snapshot();
+                            get_validator_by_address(&snapshot, &address).ok()
+                        }
+                        _ => None,
+                    },
                     _ => None,
                 };
                 future::ready(result)
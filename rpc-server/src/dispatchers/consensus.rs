@@ -1,17 +1,27 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use async_trait::async_trait;
-use nimiq_blockchain_interface::AbstractBlockchain;
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_blockchain_proxy::BlockchainReadProxy;
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
 use nimiq_consensus::ConsensusProxy;
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::{Address, Ed25519PublicKey, KeyPair, PrivateKey};
+use nimiq_mempool::mempool::{Mempool, MempoolEvent};
 use nimiq_network_libp2p::Network;
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_rpc_interface::{
     consensus::ConsensusInterface,
-    types::{RPCResult, Transaction as RPCTransaction, ValidityStartHeight},
+    types::{
+        BatchPayment, BatchPaymentResult, BatchResult, BlockchainState, RPCData, RPCResult,
+        ScheduleTrigger, ScheduledTransaction, Transaction as RPCTransaction, ValidityStartHeight,
+        WatchedTransactionEvent,
+    },
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{
@@ -19,23 +29,35 @@ use nimiq_transaction::{
     SignatureProof, Transaction,
 };
 use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_wallet::TransactionIntent;
 use parking_lot::RwLock;
 
-use crate::{error::Error, wallets::UnlockedWallets};
+use crate::{
+    error::Error,
+    scheduler::TransactionScheduler,
+    wallets::UnlockedWallets,
+    watch_list::{WatchList, DEFAULT_CONFIRMATIONS},
+};
 
 pub struct ConsensusDispatcher {
     consensus: ConsensusProxy<Network>,
     unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+    mempool: Option<Arc<Mempool>>,
+    scheduler: Arc<TransactionScheduler>,
 }
 
 impl ConsensusDispatcher {
     pub fn new(
         consensus: ConsensusProxy<Network>,
         unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+        mempool: Option<Arc<Mempool>>,
+        scheduler: Arc<TransactionScheduler>,
     ) -> Self {
         Self {
             consensus,
             unlocked_wallets,
+            mempool,
+            scheduler,
         }
     }
 
@@ -57,6 +79,27 @@ impl ConsensusDispatcher {
         self.consensus.blockchain.read().network_id()
     }
 
+    /// Returns the balance of the account that is not reserved by its transactions in the
+    /// mempool.
+    fn get_available_balance(&self, address: &Address) -> Result<Coin, Error> {
+        let balance =
+            if let BlockchainReadProxy::Full(blockchain) = self.consensus.blockchain.read() {
+                blockchain
+                    .get_account_if_complete(address)
+                    .ok_or(Error::NoConsensus)?
+                    .balance()
+            } else {
+                return Err(Error::NotSupportedForLightBlockchain);
+            };
+
+        let reserved = self
+            .mempool
+            .as_ref()
+            .map_or(Coin::ZERO, |mempool| mempool.get_reserved_balance(address));
+
+        Ok(balance.saturating_sub(reserved))
+    }
+
     /// Calculates the actual block number for the validity start height given the ValidityStartHeight
     /// struct.
     fn validity_start_height(&self, validity_start_height: ValidityStartHeight) -> u32 {
@@ -133,6 +176,112 @@ impl ConsensusInterface for ConsensusDispatcher {
         self.send_raw_transaction(raw_tx).await
     }
 
+    async fn send_batch(
+        &mut self,
+        wallet: Address,
+        payments: Vec<BatchPayment>,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<BatchResult, (), Self::Error> {
+        // Without a mempool, the batch can't be checked as a whole before it is broadcast.
+        let Some(mempool) = self.mempool.clone() else {
+            return Err(Error::MempoolNotAvailable);
+        };
+
+        let key_pair = self.get_wallet_keypair(&wallet)?;
+        let validity_start_height = self.validity_start_height(validity_start_height);
+        let network_id = self.get_network_id();
+        let available_balance = self.get_available_balance(&wallet)?;
+
+        // Build and verify all transactions before sending any of them.
+        let mut transactions = Vec::with_capacity(payments.len());
+        let mut results = Vec::with_capacity(payments.len());
+        let mut hashes = HashSet::with_capacity(payments.len());
+        let mut total = Some(Coin::ZERO);
+        for payment in payments {
+            total = total
+                .and_then(|total| total.checked_add(payment.value))
+                .and_then(|total| total.checked_add(fee));
+
+            let transaction = TransactionBuilder::new_basic(
+                &key_pair,
+                payment.recipient.clone(),
+                payment.value,
+                fee,
+                validity_start_height,
+                network_id,
+            )
+            .map_err(|error| error.to_string())
+            .and_then(|transaction| {
+                transaction
+                    .verify(network_id)
+                    .map(|_| transaction)
+                    .map_err(|error| error.to_string())
+            });
+
+            let (hash, error) = match transaction {
+                Ok(transaction) => {
+                    let hash = transaction.hash::<Blake2bHash>();
+                    // Identical payments would result in identical transactions.
+                    let error =
+                        (!hashes.insert(hash.clone())).then(|| "Duplicate payment".to_string());
+                    transactions.push(transaction);
+                    (Some(hash), error)
+                }
+                Err(error) => (None, Some(error)),
+            };
+
+            results.push(BatchPaymentResult {
+                recipient: payment.recipient,
+                value: payment.value,
+                hash,
+                error,
+            });
+        }
+
+        let mut error = match total {
+            _ if results.iter().any(|result| result.error.is_some()) => {
+                Some("The batch contains invalid payments".to_string())
+            }
+            Some(total) if total > available_balance => Some(format!(
+                "Insufficient funds: {total} needed, {available_balance} available"
+            )),
+            Some(_) => None,
+            None => Some("The total value of the batch overflows".to_string()),
+        };
+
+        if error.is_none() {
+            // Add the whole batch to the mempool at once, which checks the balance again under the
+            // mempool lock and takes back the already added transactions if one of them fails.
+            if let Err((index, verify_error)) = mempool.add_transactions(&transactions, None).await
+            {
+                results[index].error = Some(verify_error.to_string());
+                error = Some("The batch was rejected by the mempool".to_string());
+            }
+        }
+
+        let sent = error.is_none();
+        if sent {
+            // The batch was accepted as a whole, so the transactions don't depend on each other
+            // anymore and can be broadcast one by one.
+            for (transaction, result) in transactions.into_iter().zip(results.iter_mut()) {
+                if let Err(error) = self.consensus.send_transaction(transaction).await {
+                    result.error = Some(Error::NetworkError(error).to_string());
+                }
+            }
+        }
+
+        Ok(BatchResult {
+            sent,
+            validity_start_height,
+            total: total.unwrap_or(Coin::ZERO),
+            available_balance,
+            error,
+            payments: results,
+        }
+        .into())
+    }
+
     async fn create_basic_transaction_with_data(
         &mut self,
         wallet: Address,
@@ -1166,4 +1315,188 @@ impl ConsensusInterface for ConsensusDispatcher {
             .data;
         self.send_raw_transaction(raw_tx).await
     }
+
+    async fn schedule_raw_transaction(
+        &mut self,
+        raw_tx: String,
+        trigger: ScheduleTrigger,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error> {
+        let tx: Transaction = Deserialize::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
+        tx.verify(self.get_network_id())
+            .map_err(|e| Error::InvalidArgument(format!("Invalid transaction: {e}")))?;
+
+        Ok(self
+            .scheduler
+            .schedule(trigger, TransactionIntent::Signed(tx))?
+            .into())
+    }
+
+    async fn schedule_basic_transaction(
+        &mut self,
+        wallet: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        trigger: ScheduleTrigger,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error> {
+        let intent = TransactionIntent::Basic {
+            wallet,
+            recipient,
+            value,
+            fee,
+        };
+        Ok(self.scheduler.schedule(trigger, intent)?.into())
+    }
+
+    async fn schedule_redeem_timeout_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        trigger: ScheduleTrigger,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error> {
+        let intent = TransactionIntent::RedeemHtlcTimeout {
+            wallet,
+            contract: contract_address,
+            recipient,
+            value,
+            fee,
+        };
+        Ok(self.scheduler.schedule(trigger, intent)?.into())
+    }
+
+    async fn schedule_redeem_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        trigger: ScheduleTrigger,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error> {
+        let intent = TransactionIntent::RedeemVesting {
+            wallet,
+            contract: contract_address,
+            recipient,
+            value,
+            fee,
+        };
+        Ok(self.scheduler.schedule(trigger, intent)?.into())
+    }
+
+    async fn get_scheduled_transactions(
+        &mut self,
+    ) -> RPCResult<Vec<ScheduledTransaction>, (), Self::Error> {
+        Ok(self.scheduler.list().into())
+    }
+
+    async fn get_scheduled_transaction(
+        &mut self,
+        id: u32,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error> {
+        Ok(self.scheduler.get(id)?.into())
+    }
+
+    async fn cancel_scheduled_transaction(
+        &mut self,
+        id: u32,
+    ) -> RPCResult<ScheduledTransaction, (), Self::Error> {
+        Ok(self.scheduler.cancel(id)?.into())
+    }
+
+    #[stream]
+    async fn subscribe_for_transactions_by_addresses(
+        &mut self,
+        addresses: Vec<Address>,
+        confirmations: Option<u32>,
+    ) -> Result<BoxStream<'static, RPCData<WatchedTransactionEvent, BlockchainState>>, Self::Error>
+    {
+        let blockchain = self.consensus.blockchain.clone();
+        let blockchain_events = if let BlockchainReadProxy::Full(blockchain) = blockchain.read() {
+            blockchain.notifier_as_stream()
+        } else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+        let mempool_events = match self.mempool {
+            Some(ref mempool) => mempool.notifier_as_stream(),
+            None => stream::empty().boxed(),
+        };
+        let events = stream::select(
+            blockchain_events.map(WatchListEvent::Blockchain),
+            mempool_events.map(WatchListEvent::Mempool),
+        );
+
+        let mut watch_list =
+            WatchList::new(addresses, confirmations.unwrap_or(DEFAULT_CONFIRMATIONS));
+        Ok(events
+            .flat_map(move |event| {
+                let blockchain_proxy = blockchain.read();
+                let events = match blockchain_proxy {
+                    BlockchainReadProxy::Full(ref blockchain) => {
+                        handle_watch_list_event(&mut watch_list, blockchain, event)
+                    }
+                    _ => vec![],
+                };
+                let state = BlockchainState::with_blockchain(&blockchain_proxy);
+
+                stream::iter(
+                    events
+                        .into_iter()
+                        .map(move |event| RPCData::new(event, state.clone())),
+                )
+            })
+            .boxed())
+    }
+}
+
+/// The events that change the state of watched transactions.
+enum WatchListEvent {
+    Blockchain(BlockchainEvent),
+    Mempool(MempoolEvent),
+}
+
+/// Updates the watch list with the given event and returns the resulting state transitions.
+fn handle_watch_list_event(
+    watch_list: &mut WatchList,
+    blockchain: &Blockchain,
+    event: WatchListEvent,
+) -> Vec<WatchedTransactionEvent> {
+    let head_block_number = blockchain.block_number();
+
+    match event {
+        WatchListEvent::Mempool(MempoolEvent::TransactionAdded(tx)) => {
+            watch_list.add_pending(&tx).into_iter().collect()
+        }
+        WatchListEvent::Mempool(MempoolEvent::TransactionEvicted(hash)) => {
+            watch_list.evict_pending(&hash).into_iter().collect()
+        }
+        WatchListEvent::Blockchain(
+            BlockchainEvent::Extended(hash) | BlockchainEvent::HistoryAdopted(hash),
+        ) => match blockchain.get_block(&hash, true, None) {
+            Ok(block) => watch_list.apply_block(&block, head_block_number),
+            Err(_) => vec![],
+        },
+        WatchListEvent::Blockchain(BlockchainEvent::Rebranched(
+            reverted_blocks,
+            adopted_blocks,
+        )) => {
+            let mut events = vec![];
+            for (_, block) in &reverted_blocks {
+                events.append(&mut watch_list.revert_block(block));
+            }
+            for (_, block) in &adopted_blocks {
+                events.append(&mut watch_list.apply_block(block, head_block_number));
+            }
+            events
+        }
+        WatchListEvent::Blockchain(
+            BlockchainEvent::Finalized(hash) | BlockchainEvent::EpochFinalized(hash),
+        ) => match blockchain.get_block(&hash, false, None) {
+            Ok(block) => watch_list.finalize(block.block_number(), head_block_number),
+            Err(_) => vec![],
+        },
+        WatchListEvent::Blockchain(BlockchainEvent::Stored(_)) => vec![],
+    }
 }
@@ -1,3 +1,4 @@
+pub use admin::AdminDispatcher;
 pub use blockchain::BlockchainDispatcher;
 pub use consensus::ConsensusDispatcher;
 pub use mempool::MempoolDispatcher;
@@ -7,6 +8,7 @@ pub use validator::ValidatorDispatcher;
 pub use wallet::WalletDispatcher;
 pub use zkp_component::ZKPComponentDispatcher;
 
+mod admin;
 mod blockchain;
 mod consensus;
 mod mempool;
@@ -1,16 +1,31 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, VecDeque},
+    num::NonZeroU8,
+    sync::Arc,
+};
 
 use async_trait::async_trait;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_database::traits::WriteTransaction;
-use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature, KeyPair, PrivateKey};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::{
+    multisig::{commitment::CommitmentPair, MUSIG2_PARAMETER_V},
+    Address, Ed25519PublicKey, Ed25519Signature, KeyPair, PrivateKey, PublicKey,
+};
+use nimiq_primitives::{coin::Coin, policy::Policy};
 use nimiq_rpc_interface::{
-    types::{RPCResult, ReturnAccount, ReturnSignature},
+    types::{HdAccount, MultisigAccount, RPCResult, ReturnAccount, ReturnSignature},
     wallet::WalletInterface,
 };
-use nimiq_serde::Deserialize;
-use nimiq_utils::otp::Locked;
-use nimiq_wallet::{WalletAccount, WalletStore};
-use parking_lot::RwLock;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::Transaction;
+use nimiq_utils::otp::{Algorithm, Locked, OtpLock, Unlocked};
+use nimiq_wallet::{
+    account_path, DerivedAccount, HdSeed, MultiSigAccountInfo, PartiallySignedTransaction,
+    PstSigner, WalletAccount, WalletBackup, WalletStore,
+};
+use parking_lot::{Mutex, RwLock};
 
 use crate::{error::Error, wallets::UnlockedWallets};
 
@@ -22,18 +37,239 @@ fn message_from_maybe_hex(s: String, is_hex: bool) -> Result<Vec<u8>, Error> {
     }
 }
 
+fn pst_from_hex(pst: String) -> Result<PartiallySignedTransaction, Error> {
+    Ok(PartiallySignedTransaction::from_bytes(&hex::decode(pst)?)?)
+}
+
+fn pst_to_hex(pst: &PartiallySignedTransaction) -> String {
+    hex::encode(pst.to_bytes())
+}
+
+/// The default number of consecutive unused accounts after which account discovery stops.
+const DEFAULT_GAP_LIMIT: u32 = 20;
+/// The maximum gap limit of account discovery, since every account checked is a history lookup.
+const MAX_GAP_LIMIT: u32 = 100;
+
+fn hd_account(account: DerivedAccount) -> HdAccount {
+    HdAccount {
+        address: account.address,
+        seed_id: account.seed_id,
+        path: account.path,
+    }
+}
+
+fn multisig_account(info: MultiSigAccountInfo) -> MultisigAccount {
+    MultisigAccount {
+        address: info.address.clone(),
+        owner: info.owner_address(),
+        owner_public_key: info.owner,
+        min_signatures: info.min_signatures.get(),
+        public_keys: info.public_keys,
+    }
+}
+
+/// The maximum number of partially signed transactions for which secret nonces are kept.
+const MAX_PST_NONCES: usize = 1000;
+
+type PstNonceKey = (Blake2bHash, Ed25519PublicKey);
+
+/// The secret MuSig2 nonces of the commitments created for partially signed transactions,
+/// indexed by the hash of the transaction and the public key of the signer.
+///
+/// The nonces are only kept in memory, so signers have to commit again after a restart. Nonces
+/// of transactions that can no longer be included in a block are dropped, and at most
+/// [`MAX_PST_NONCES`] are kept, dropping the oldest ones first.
+#[derive(Default)]
+struct PstNonces {
+    nonces: HashMap<PstNonceKey, ([CommitmentPair; MUSIG2_PARAMETER_V], u32)>,
+    /// The keys of the nonces in the order they were created.
+    order: VecDeque<PstNonceKey>,
+}
+
+impl PstNonces {
+    /// Stores the nonces for the transaction with the given validity start height.
+    fn insert(
+        &mut self,
+        key: PstNonceKey,
+        pairs: [CommitmentPair; MUSIG2_PARAMETER_V],
+        validity_start_height: u32,
+        block_number: u32,
+    ) {
+        // Drop the nonces of expired transactions, they can't be signed anymore anyway.
+        self.nonces.retain(|_, (_, validity_start_height)| {
+            block_number
+                < validity_start_height.saturating_add(Policy::transaction_validity_window_blocks())
+        });
+        self.order.retain(|key| self.nonces.contains_key(key));
+
+        if self
+            .nonces
+            .insert(key.clone(), (pairs, validity_start_height))
+            .is_some()
+        {
+            self.order.retain(|other| other != &key);
+        }
+        self.order.push_back(key);
+
+        while self.order.len() > MAX_PST_NONCES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.nonces.remove(&oldest);
+            }
+        }
+    }
+
+    /// Removes and returns the nonces for the given key. Nonces must only ever be used for a
+    /// single signature, so they are taken out before signing and never put back.
+    fn take(&mut self, key: &PstNonceKey) -> Option<[CommitmentPair; MUSIG2_PARAMETER_V]> {
+        let (pairs, _) = self.nonces.remove(key)?;
+        self.order.retain(|other| other != key);
+        Some(pairs)
+    }
+}
+
 pub struct WalletDispatcher {
     wallet_store: Arc<WalletStore>,
+    blockchain: BlockchainProxy,
     pub unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+    pst_nonces: Arc<Mutex<PstNonces>>,
 }
 
 impl WalletDispatcher {
-    pub fn new(wallet_store: Arc<WalletStore>) -> Self {
+    pub fn new(wallet_store: Arc<WalletStore>, blockchain: BlockchainProxy) -> Self {
         Self {
             wallet_store,
+            blockchain,
             unlocked_wallets: Arc::new(RwLock::new(UnlockedWallets::default())),
+            pst_nonces: Arc::new(Mutex::new(PstNonces::default())),
         }
     }
+
+    /// Returns the unlocked account or unlocks it with the given passphrase.
+    fn get_wallet_account(
+        &self,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> Result<WalletAccount, Error> {
+        if let Some(wallet) = self.unlocked_wallets.read().get(&address) {
+            return Ok(wallet.clone());
+        }
+
+        let passphrase = passphrase.unwrap_or_default();
+        Ok(self
+            .wallet_store
+            .get(&address, None)
+            .ok_or(Error::AccountNotFound(address))?
+            .unlock(passphrase.as_bytes())
+            .map_err(|_locked| Error::WrongPassphrase)?
+            .key_pair
+            .clone()
+            .into())
+    }
+
+    /// Unlocks the seed with the given identifier.
+    fn unlock_seed(&self, seed_id: Address, passphrase: &str) -> Result<HdSeed, Error> {
+        Ok(self
+            .wallet_store
+            .get_seed(&seed_id, None)
+            .ok_or(Error::SeedNotFound(seed_id))?
+            .unlock(passphrase.as_bytes())
+            .map_err(|_locked| Error::WrongPassphrase)?
+            .clone())
+    }
+
+    /// Derives the account at `path` and imports it, locked with the passphrase.
+    fn import_derived_account(
+        &self,
+        seed: &HdSeed,
+        path: String,
+        passphrase: &str,
+    ) -> Result<DerivedAccount, Error> {
+        let account = seed.derive(&path)?;
+        let derived = DerivedAccount {
+            address: account.address.clone(),
+            seed_id: seed.id().clone(),
+            path,
+        };
+        let locked_account = Locked::with_defaults(account, passphrase.as_bytes())?;
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store
+            .put(&derived.address, &locked_account, &mut txn);
+        self.wallet_store.put_derived(&derived, &mut txn);
+        txn.commit();
+
+        Ok(derived)
+    }
+
+    /// Returns whether the address appears in the transaction history.
+    fn is_address_used(&self, address: &Address) -> Result<bool, Error> {
+        if let BlockchainProxy::Full(blockchain) = &self.blockchain {
+            Ok(!blockchain
+                .read()
+                .history_store
+                .get_tx_hashes_by_address(address, 1, None)
+                .is_empty())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    /// Returns the registered multi-signature account sending the partially signed transaction.
+    fn get_multisig_account(
+        &self,
+        pst: &PartiallySignedTransaction,
+    ) -> Result<MultiSigAccountInfo, Error> {
+        let address = &pst.transaction.sender;
+        self.wallet_store
+            .get_multisig(address, None)
+            .ok_or_else(|| Error::MultisigAccountNotFound(address.clone()))
+    }
+
+    fn commit(
+        &self,
+        mut pst: PartiallySignedTransaction,
+        wallet: WalletAccount,
+    ) -> Result<String, Error> {
+        let pairs = pst.create_commitments(wallet.key_pair.public)?;
+        let block_number = self.blockchain.read().block_number();
+        self.pst_nonces.lock().insert(
+            (
+                pst.transaction.hash::<Blake2bHash>(),
+                wallet.key_pair.public,
+            ),
+            pairs,
+            pst.transaction.validity_start_height,
+            block_number,
+        );
+
+        Ok(pst_to_hex(&pst))
+    }
+
+    fn sign_pst_with(
+        &self,
+        mut pst: PartiallySignedTransaction,
+        wallet: WalletAccount,
+    ) -> Result<String, Error> {
+        match pst.signer {
+            PstSigner::Single { .. } => pst.sign(&wallet.key_pair)?,
+            PstSigner::MultiSig(_) => {
+                let key = (
+                    pst.transaction.hash::<Blake2bHash>(),
+                    wallet.key_pair.public,
+                );
+                // Take the nonces out under a single lock, so that concurrent calls can never
+                // sign with the same nonces. They are dropped even if signing fails.
+                let pairs = self
+                    .pst_nonces
+                    .lock()
+                    .take(&key)
+                    .ok_or(Error::PstCommitmentsNotFound(wallet.key_pair.public))?;
+                pst.partially_sign(&wallet.key_pair, pairs)?;
+            }
+        }
+
+        Ok(pst_to_hex(&pst))
+    }
 }
 
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
@@ -137,25 +373,7 @@ impl WalletInterface for WalletDispatcher {
     ) -> RPCResult<ReturnSignature, (), Self::Error> {
         let message = message_from_maybe_hex(message, is_hex)?;
 
-        let passphrase = passphrase.unwrap_or_default();
-
-        let wallet_account: WalletAccount;
-        let unlocked_wallets = self.unlocked_wallets.read();
-
-        let wallet = if let Some(wallet) = unlocked_wallets.get(&address) {
-            wallet
-        } else {
-            wallet_account = self
-                .wallet_store
-                .get(&address, None)
-                .ok_or(Error::AccountNotFound(address))?
-                .unlock(passphrase.as_bytes())
-                .map_err(|_locked| Error::WrongPassphrase)?
-                .key_pair
-                .clone()
-                .into();
-            &wallet_account
-        };
+        let wallet = self.get_wallet_account(address, passphrase)?;
 
         let (public_key, signature) = wallet.sign_message(&message);
 
@@ -176,4 +394,402 @@ impl WalletInterface for WalletDispatcher {
         let message = message_from_maybe_hex(message, is_hex)?;
         Ok(WalletAccount::verify_message(&public_key, &message, &signature).into())
     }
+
+    async fn create_pst(
+        &mut self,
+        raw_tx: String,
+        public_key: Ed25519PublicKey,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction = Transaction::deserialize_from_vec(&hex::decode(raw_tx)?)?;
+        let pst = PartiallySignedTransaction::new(transaction, PublicKey::Ed25519(public_key));
+
+        Ok(pst_to_hex(&pst).into())
+    }
+
+    async fn create_multisig_pst(
+        &mut self,
+        raw_tx: String,
+        public_keys: Vec<Ed25519PublicKey>,
+        min_signatures: u8,
+        signers: Vec<Ed25519PublicKey>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction = Transaction::deserialize_from_vec(&hex::decode(raw_tx)?)?;
+        let min_signatures = NonZeroU8::new(min_signatures).ok_or_else(|| {
+            Error::InvalidArgument("Minimum number of signatures must not be zero".to_string())
+        })?;
+        let pst = PartiallySignedTransaction::new_multisig(
+            transaction,
+            min_signatures,
+            &public_keys,
+            &signers,
+        )?;
+
+        Ok(pst_to_hex(&pst).into())
+    }
+
+    async fn commit_pst(
+        &mut self,
+        pst: String,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let pst = pst_from_hex(pst)?;
+        let wallet = self.get_wallet_account(address, passphrase)?;
+
+        Ok(self.commit(pst, wallet)?.into())
+    }
+
+    async fn sign_pst(
+        &mut self,
+        pst: String,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let pst = pst_from_hex(pst)?;
+        let wallet = self.get_wallet_account(address, passphrase)?;
+
+        Ok(self.sign_pst_with(pst, wallet)?.into())
+    }
+
+    async fn combine_psts(&mut self, psts: Vec<String>) -> RPCResult<String, (), Self::Error> {
+        let mut psts = psts.into_iter();
+        let mut pst = pst_from_hex(psts.next().ok_or_else(|| {
+            Error::InvalidArgument("No partially signed transactions given".to_string())
+        })?)?;
+        for other in psts {
+            pst.combine(&pst_from_hex(other)?)?;
+        }
+
+        Ok(pst_to_hex(&pst).into())
+    }
+
+    async fn finalize_pst(&mut self, pst: String) -> RPCResult<String, (), Self::Error> {
+        let transaction = pst_from_hex(pst)?.finalize()?;
+
+        Ok(hex::encode(transaction.serialize_to_vec()).into())
+    }
+
+    async fn import_multisig_account(
+        &mut self,
+        public_keys: Vec<Ed25519PublicKey>,
+        min_signatures: u8,
+        owner_public_key: Ed25519PublicKey,
+    ) -> RPCResult<MultisigAccount, (), Self::Error> {
+        let owner = Address::from(&owner_public_key);
+        if self.wallet_store.get(&owner, None).is_none() {
+            return Err(Error::AccountNotFound(owner));
+        }
+
+        let min_signatures = NonZeroU8::new(min_signatures).ok_or_else(|| {
+            Error::InvalidArgument("Minimum number of signatures must not be zero".to_string())
+        })?;
+        let info = MultiSigAccountInfo::new(owner_public_key, min_signatures, &public_keys)?;
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store.put_multisig(&info, &mut txn);
+        txn.commit();
+
+        Ok(multisig_account(info).into())
+    }
+
+    async fn list_multisig_accounts(&mut self) -> RPCResult<Vec<MultisigAccount>, (), Self::Error> {
+        let txn = self.wallet_store.create_read_transaction();
+        Ok(self
+            .wallet_store
+            .list_multisig(Some(&txn))
+            .iter()
+            .filter_map(|address| self.wallet_store.get_multisig(address, Some(&txn)))
+            .map(multisig_account)
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    async fn remove_multisig_account(
+        &mut self,
+        address: Address,
+    ) -> RPCResult<bool, (), Self::Error> {
+        if self.wallet_store.get_multisig(&address, None).is_none() {
+            return Ok(false.into());
+        }
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store.remove_multisig(&address, &mut txn);
+        txn.commit();
+
+        Ok(true.into())
+    }
+
+    async fn create_multisig_transaction(
+        &mut self,
+        multisig_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        signers: Vec<Ed25519PublicKey>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let info = self
+            .wallet_store
+            .get_multisig(&multisig_address, None)
+            .ok_or(Error::MultisigAccountNotFound(multisig_address))?;
+
+        let transaction = Transaction::new_basic(
+            info.address.clone(),
+            recipient,
+            value,
+            fee,
+            validity_start_height,
+            self.blockchain.read().network_id(),
+        );
+        let pst = info.create_pst(transaction, &signers)?;
+
+        Ok(pst_to_hex(&pst).into())
+    }
+
+    async fn commit_multisig_transaction(
+        &mut self,
+        pst: String,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let pst = pst_from_hex(pst)?;
+        let info = self.get_multisig_account(&pst)?;
+        let wallet = self.get_wallet_account(info.owner_address(), passphrase)?;
+
+        Ok(self.commit(pst, wallet)?.into())
+    }
+
+    async fn sign_multisig_transaction(
+        &mut self,
+        pst: String,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let pst = pst_from_hex(pst)?;
+        let info = self.get_multisig_account(&pst)?;
+        let wallet = self.get_wallet_account(info.owner_address(), passphrase)?;
+
+        Ok(self.sign_pst_with(pst, wallet)?.into())
+    }
+
+    async fn import_mnemonic(
+        &mut self,
+        mnemonic: String,
+        mnemonic_password: Option<String>,
+        passphrase: Option<String>,
+    ) -> RPCResult<Address, (), Self::Error> {
+        let passphrase = passphrase.unwrap_or_default();
+
+        let seed = HdSeed::from_mnemonic(&mnemonic, mnemonic_password.as_deref())?;
+        let seed_id = seed.id().clone();
+        let locked_seed = Locked::with_defaults(seed, passphrase.as_bytes())?;
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store.put_seed(&seed_id, &locked_seed, &mut txn);
+        txn.commit();
+
+        Ok(seed_id.into())
+    }
+
+    async fn list_seeds(&mut self) -> RPCResult<Vec<Address>, (), Self::Error> {
+        Ok(self.wallet_store.list_seeds(None).into())
+    }
+
+    async fn derive_account(
+        &mut self,
+        seed_id: Address,
+        path: Option<String>,
+        passphrase: Option<String>,
+    ) -> RPCResult<HdAccount, (), Self::Error> {
+        let passphrase = passphrase.unwrap_or_default();
+        let seed = self.unlock_seed(seed_id, &passphrase)?;
+
+        let path = path.unwrap_or_else(|| {
+            let derived = self.wallet_store.list_derived(Some(seed.id()), None);
+            (0..)
+                .map(account_path)
+                .find(|path| !derived.iter().any(|account| &account.path == path))
+                .unwrap()
+        });
+
+        Ok(hd_account(self.import_derived_account(&seed, path, &passphrase)?).into())
+    }
+
+    async fn list_derived_accounts(
+        &mut self,
+        seed_id: Option<Address>,
+    ) -> RPCResult<Vec<HdAccount>, (), Self::Error> {
+        Ok(self
+            .wallet_store
+            .list_derived(seed_id.as_ref(), None)
+            .into_iter()
+            .map(hd_account)
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    async fn discover_accounts(
+        &mut self,
+        seed_id: Address,
+        passphrase: Option<String>,
+        gap_limit: Option<u32>,
+    ) -> RPCResult<Vec<HdAccount>, (), Self::Error> {
+        let passphrase = passphrase.unwrap_or_default();
+        let gap_limit = gap_limit.unwrap_or(DEFAULT_GAP_LIMIT);
+        if gap_limit > MAX_GAP_LIMIT {
+            return Err(Error::InvalidArgument(format!(
+                "Gap limit must not exceed {MAX_GAP_LIMIT}"
+            )));
+        }
+        let seed = self.unlock_seed(seed_id, &passphrase)?;
+
+        let mut used_accounts = vec![];
+        let mut unused = 0;
+        let mut index = 0;
+        while unused < gap_limit {
+            let path = account_path(index);
+            let address = seed.derive(&path)?.address;
+
+            if self.is_address_used(&address)? {
+                unused = 0;
+                let account = match self.wallet_store.get_derived(&address, None) {
+                    Some(account) => account,
+                    None => self.import_derived_account(&seed, path, &passphrase)?,
+                };
+                used_accounts.push(hd_account(account));
+            } else {
+                unused += 1;
+            }
+
+            index = index
+                .checked_add(1)
+                .ok_or_else(|| Error::InvalidArgument("No more accounts to derive".to_string()))?;
+        }
+
+        Ok(used_accounts.into())
+    }
+
+    async fn export_backup(
+        &mut self,
+        addresses: Option<Vec<Address>>,
+        passphrase: Option<String>,
+        backup_passphrase: String,
+    ) -> RPCResult<String, (), Self::Error> {
+        let addresses = addresses.unwrap_or_else(|| self.wallet_store.list(None));
+        let accounts = addresses
+            .into_iter()
+            .map(|address| self.get_wallet_account(address, passphrase.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let backup = WalletBackup::create(&accounts, backup_passphrase.as_bytes())?;
+
+        Ok(hex::encode(backup.to_bytes()).into())
+    }
+
+    async fn import_backup(
+        &mut self,
+        backup: String,
+        backup_passphrase: String,
+        passphrase: Option<String>,
+    ) -> RPCResult<Vec<Address>, (), Self::Error> {
+        let passphrase = passphrase.unwrap_or_default();
+
+        let backup = WalletBackup::from_bytes(&hex::decode(backup)?)?;
+        let accounts = backup.open(backup_passphrase.as_bytes())?;
+
+        let mut addresses = Vec::with_capacity(accounts.len());
+        let mut txn = self.wallet_store.create_write_transaction();
+        for account in accounts {
+            let address = account.address.clone();
+            let locked_account = Locked::with_defaults(account, passphrase.as_bytes())?;
+            self.wallet_store.put(&address, &locked_account, &mut txn);
+            addresses.push(address);
+        }
+        txn.commit();
+
+        Ok(addresses.into())
+    }
+
+    async fn reencrypt_accounts(
+        &mut self,
+        addresses: Option<Vec<Address>>,
+        passphrase: Option<String>,
+        new_passphrase: Option<String>,
+        iterations: Option<u32>,
+        memory_cost: Option<u32>,
+    ) -> RPCResult<Vec<Address>, (), Self::Error> {
+        let passphrase = passphrase.unwrap_or_default();
+        let new_passphrase = new_passphrase.unwrap_or_else(|| passphrase.clone());
+        let iterations = iterations.unwrap_or(OtpLock::<WalletAccount>::DEFAULT_ITERATIONS);
+        let memory_cost = memory_cost.unwrap_or(OtpLock::<WalletAccount>::DEFAULT_MEMORY_COST);
+        let addresses = addresses.unwrap_or_else(|| {
+            let mut addresses = self.wallet_store.list(None);
+            addresses.extend(self.wallet_store.list_seeds(None));
+            addresses
+        });
+
+        // Unlock everything first, such that either all or none of the entries are re-encrypted.
+        let mut locked_accounts: Vec<(Address, Locked<WalletAccount>)> = vec![];
+        let mut locked_seeds = vec![];
+        for address in &addresses {
+            if let Some(locked_seed) = self.wallet_store.get_seed(address, None) {
+                let unlocked_seed = locked_seed
+                    .unlock(passphrase.as_bytes())
+                    .map_err(|_locked| Error::WrongPassphrase)?;
+
+                locked_seeds.push((
+                    address.clone(),
+                    Locked::new_with_memory_cost(
+                        Unlocked::into_unlocked_data(unlocked_seed),
+                        new_passphrase.as_bytes(),
+                        iterations,
+                        memory_cost,
+                        OtpLock::<HdSeed>::DEFAULT_SALT_LENGTH,
+                        Algorithm::Argon2id,
+                    )?,
+                ));
+                continue;
+            }
+
+            // Multisig accounts are secured by the key of their owner account.
+            let account_address = self
+                .wallet_store
+                .get_multisig(address, None)
+                .map_or_else(|| address.clone(), |info| info.owner_address());
+            if locked_accounts
+                .iter()
+                .any(|(locked_address, _)| locked_address == &account_address)
+            {
+                continue;
+            }
+
+            let unlocked_account = self
+                .wallet_store
+                .get(&account_address, None)
+                .ok_or_else(|| Error::AccountNotFound(account_address.clone()))?
+                .unlock(passphrase.as_bytes())
+                .map_err(|_locked| Error::WrongPassphrase)?;
+
+            locked_accounts.push((
+                account_address,
+                Locked::new_with_memory_cost(
+                    Unlocked::into_unlocked_data(unlocked_account),
+                    new_passphrase.as_bytes(),
+                    iterations,
+                    memory_cost,
+                    OtpLock::<WalletAccount>::DEFAULT_SALT_LENGTH,
+                    Algorithm::Argon2id,
+                )?,
+            ));
+        }
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        for (address, locked_account) in &locked_accounts {
+            self.wallet_store.put(address, locked_account, &mut txn);
+        }
+        for (id, locked_seed) in &locked_seeds {
+            self.wallet_store.put_seed(id, locked_seed, &mut txn);
+        }
+        txn.commit();
+
+        Ok(addresses.into())
+    }
 }
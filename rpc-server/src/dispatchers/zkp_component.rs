@@ -1,7 +1,8 @@
 use async_trait::async_trait;
+use futures::{future, stream::BoxStream, StreamExt};
 use nimiq_network_libp2p::Network;
 use nimiq_rpc_interface::{
-    types::{RPCResult, ZKPState},
+    types::{RPCData, RPCResult, ZKPState, ZKProof},
     zkp_component::ZKPComponentInterface,
 };
 use nimiq_zkp_component::zkp_component::ZKPComponentProxy;
@@ -26,4 +27,32 @@ impl ZKPComponentInterface for ZKPComponentDispatcher {
     async fn get_zkp_state(&mut self) -> RPCResult<ZKPState, (), Self::Error> {
         Ok(ZKPState::with_zkp_state(&self.zkp_component.get_zkp_state()).into())
     }
+
+    async fn get_zkp_by_block_number(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<ZKProof, (), Self::Error> {
+        self.zkp_component
+            .get_zkp_by_block_number(block_number)
+            .map(|zk_proof| ZKProof::from_zk_proof(&zk_proof).into())
+            .ok_or(Error::ZKProofNotFound(block_number))
+    }
+
+    #[stream]
+    async fn subscribe_for_zkps(
+        &mut self,
+    ) -> Result<BoxStream<'static, RPCData<ZKProof, ()>>, Self::Error> {
+        // Events missed because the subscriber lagged behind are skipped.
+        Ok(self
+            .zkp_component
+            .subscribe_zkps()
+            .filter_map(|event| {
+                future::ready(
+                    event
+                        .ok()
+                        .map(|event| ZKProof::from_zk_proof(&event.proof).into()),
+                )
+            })
+            .boxed())
+    }
 }
@@ -21,6 +21,9 @@ pub enum Error {
     #[error("Block not found: {0}")]
     BlockNotFoundByHash(Blake2bHash),
 
+    #[error("The state at block {0} is not available, it is not the head of the chain")]
+    StateNotAvailable(Blake2bHash),
+
     #[error("Block number cannot be smaller than genesis block")]
     BlockNumberBeforeGenesis,
 
@@ -84,8 +87,50 @@ pub enum Error {
     #[error("No consensus")]
     NoConsensus,
 
+    #[error("ZK proof not found for block: {0}")]
+    ZKProofNotFound(u32),
+
+    #[error("{0}")]
+    Pst(#[from] nimiq_wallet::PstError),
+
+    #[error("No commitments created for signer: {0}")]
+    PstCommitmentsNotFound(nimiq_keys::Ed25519PublicKey),
+
+    #[error("Multi-signature account not found: {0}")]
+    MultisigAccountNotFound(Address),
+
+    #[error("{0}")]
+    MultiSigAccount(#[from] nimiq_wallet::MultiSigAccountError),
+
+    #[error("Seed not found: {0}")]
+    SeedNotFound(Address),
+
+    #[error("{0}")]
+    HdWallet(#[from] nimiq_wallet::HdWalletError),
+
+    #[error("{0}")]
+    Backup(#[from] nimiq_wallet::BackupError),
+
+    #[error("{0}")]
+    RewardAttribution(#[from] nimiq_blockchain::reward_attribution::RewardAttributionError),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Scheduled transaction not found: {0}")]
+    ScheduledTransactionNotFound(u32),
+
+    #[error("Scheduled transaction {0} was already submitted or cancelled")]
+    ScheduledTransactionNotPending(u32),
+
+    #[error("Database error: {0}")]
+    Database(#[from] nimiq_database::Error),
+
+    #[error("Failed to revert the chain: {0}")]
+    Revert(#[from] nimiq_blockchain_interface::RevertError),
+
+    #[error("Method requires a mempool")]
+    MempoolNotAvailable,
 }
 
 impl From<Error> for nimiq_jsonrpc_core::RpcError {
@@ -3,4 +3,6 @@ pub use nimiq_jsonrpc_server::{Config, Server};
 
 pub mod dispatchers;
 pub mod error;
+pub mod scheduler;
 pub mod wallets;
+pub mod watch_list;
@@ -0,0 +1,601 @@
+use std::{collections::HashSet, sync::Arc};
+
+use futures::StreamExt;
+use nimiq_account::Account;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
+use nimiq_blockchain_proxy::BlockchainReadProxy;
+use nimiq_consensus::ConsensusProxy;
+use nimiq_database::traits::WriteTransaction;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::{Address, KeyPair};
+use nimiq_network_libp2p::Network;
+use nimiq_primitives::networks::NetworkId;
+use nimiq_rpc_interface::types::{
+    ScheduleTrigger as RPCScheduleTrigger, ScheduledTransaction as RPCScheduledTransaction,
+    ScheduledTransactionKind, ScheduledTransactionState,
+};
+use nimiq_transaction::Transaction;
+use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_wallet::{
+    ScheduleState, ScheduleTrigger, ScheduledTransaction, TransactionIntent, WalletStore,
+};
+use parking_lot::{Mutex, RwLock};
+
+use crate::{error::Error, wallets::UnlockedWallets};
+
+/// Persists transaction intents together with a trigger and sends them to the network once
+/// they are due. Schedules are stored in the wallet store and thus survive restarts.
+pub struct TransactionScheduler {
+    wallet_store: Arc<WalletStore>,
+    consensus: ConsensusProxy<Network>,
+    unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+    /// Serializes all read-modify-write cycles on the stored schedules and holds the identifiers
+    /// of the schedules that are currently being submitted.
+    lock: Mutex<HashSet<u32>>,
+}
+
+/// The outcome of trying to build the transaction of a due schedule.
+#[derive(Debug, PartialEq, Eq)]
+enum BuildError {
+    /// The transaction cannot be built right now, the submission is retried with the next block.
+    Postponed(String),
+    /// The transaction can never be built.
+    Failed(String),
+}
+
+impl TransactionScheduler {
+    pub fn new(
+        wallet_store: Arc<WalletStore>,
+        consensus: ConsensusProxy<Network>,
+        unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+    ) -> Self {
+        Self {
+            wallet_store,
+            consensus,
+            unlocked_wallets,
+            lock: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Stores a new schedule and returns it.
+    pub fn schedule(
+        &self,
+        trigger: RPCScheduleTrigger,
+        intent: TransactionIntent,
+    ) -> Result<RPCScheduledTransaction, Error> {
+        let trigger = schedule_trigger(trigger);
+        if matches!(
+            trigger,
+            ScheduleTrigger::HtlcTimeout(_) | ScheduleTrigger::VestingUnlocked { .. }
+        ) && !matches!(
+            self.consensus.blockchain.read(),
+            BlockchainReadProxy::Full(_)
+        ) {
+            return Err(Error::NotSupportedForLightBlockchain);
+        }
+
+        let _guard = self.lock.lock();
+        let mut txn = self.wallet_store.create_write_transaction();
+        let scheduled =
+            ScheduledTransaction::new(self.wallet_store.next_scheduled_id(&txn), trigger, intent);
+        self.wallet_store.put_scheduled(&scheduled, &mut txn);
+        txn.commit();
+
+        log::info!(id = scheduled.id, trigger = ?scheduled.trigger, "Scheduled transaction");
+        Ok(rpc_scheduled_transaction(scheduled))
+    }
+
+    /// Returns all schedules, including the ones that are no longer pending.
+    pub fn list(&self) -> Vec<RPCScheduledTransaction> {
+        self.wallet_store
+            .list_scheduled(None)
+            .into_iter()
+            .map(rpc_scheduled_transaction)
+            .collect()
+    }
+
+    pub fn get(&self, id: u32) -> Result<RPCScheduledTransaction, Error> {
+        self.wallet_store
+            .get_scheduled(id, None)
+            .map(rpc_scheduled_transaction)
+            .ok_or(Error::ScheduledTransactionNotFound(id))
+    }
+
+    /// Cancels a schedule that has not been submitted yet.
+    pub fn cancel(&self, id: u32) -> Result<RPCScheduledTransaction, Error> {
+        let submitting = self.lock.lock();
+        let mut scheduled = self
+            .wallet_store
+            .get_scheduled(id, None)
+            .ok_or(Error::ScheduledTransactionNotFound(id))?;
+        if !scheduled.is_pending() || submitting.contains(&id) {
+            return Err(Error::ScheduledTransactionNotPending(id));
+        }
+
+        scheduled.state = ScheduleState::Cancelled;
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store.put_scheduled(&scheduled, &mut txn);
+        txn.commit();
+
+        log::info!(id, "Cancelled scheduled transaction");
+        Ok(rpc_scheduled_transaction(scheduled))
+    }
+
+    /// Submits the due schedules whenever the head of the chain changes. This runs until the
+    /// blockchain stops emitting events.
+    pub async fn run(self: Arc<Self>) {
+        let mut events = self.consensus.blockchain.read().notifier_as_stream();
+
+        // Schedules might have become due while the node was offline.
+        self.submit_due().await;
+
+        while let Some(event) = events.next().await {
+            if !matches!(event, BlockchainEvent::Stored(_)) {
+                self.submit_due().await;
+            }
+        }
+    }
+
+    async fn submit_due(&self) {
+        if !self.consensus.is_established() {
+            return;
+        }
+
+        let (due, block_number, network_id) = {
+            let blockchain = self.consensus.blockchain.read();
+            let due: Vec<_> = self
+                .wallet_store
+                .list_scheduled(None)
+                .into_iter()
+                .filter(|scheduled| {
+                    scheduled.is_pending()
+                        && is_due(
+                            &scheduled.trigger,
+                            blockchain.block_number(),
+                            blockchain.timestamp(),
+                            |address| get_account(&blockchain, address),
+                        )
+                })
+                .collect();
+            (due, blockchain.block_number(), blockchain.network_id())
+        };
+
+        for scheduled in due {
+            self.submit(scheduled, block_number, network_id).await;
+        }
+    }
+
+    async fn submit(
+        &self,
+        mut scheduled: ScheduledTransaction,
+        block_number: u32,
+        network_id: NetworkId,
+    ) {
+        // Mark the schedule as being submitted, such that it can't be cancelled while the
+        // transaction is on its way to the network.
+        {
+            let mut submitting = self.lock.lock();
+            let is_pending = self
+                .wallet_store
+                .get_scheduled(scheduled.id, None)
+                .map_or(false, |stored| stored.is_pending());
+            if !is_pending || !submitting.insert(scheduled.id) {
+                return;
+            }
+        }
+
+        // Wallets are locked after a restart, so unsigned intents are postponed until their
+        // wallet is unlocked again.
+        let key_pair = scheduled.intent.wallet().and_then(|wallet| {
+            self.unlocked_wallets
+                .read()
+                .get(wallet)
+                .map(|account| account.key_pair.clone())
+        });
+
+        match build_transaction(
+            &scheduled.intent,
+            key_pair.as_ref(),
+            block_number,
+            network_id,
+        ) {
+            Ok(transaction) => {
+                let hash: Blake2bHash = transaction.hash();
+                match self.consensus.send_transaction(transaction).await {
+                    Ok(_) => {
+                        log::info!(id = scheduled.id, %hash, "Submitted scheduled transaction");
+                        scheduled.state = ScheduleState::Submitted { hash, block_number };
+                        scheduled.last_error = None;
+                    }
+                    Err(error) => {
+                        log::warn!(id = scheduled.id, %error, "Failed to send scheduled transaction");
+                        scheduled.last_error = Some(error.to_string());
+                    }
+                }
+            }
+            Err(BuildError::Postponed(reason)) => {
+                log::debug!(id = scheduled.id, %reason, "Postponed scheduled transaction");
+                scheduled.last_error = Some(reason);
+            }
+            Err(BuildError::Failed(reason)) => {
+                log::warn!(id = scheduled.id, %reason, "Scheduled transaction failed");
+                scheduled.state = ScheduleState::Failed(reason);
+            }
+        }
+
+        // The schedule can't have been cancelled in the meantime, so the outcome is always
+        // recorded.
+        let mut submitting = self.lock.lock();
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store.put_scheduled(&scheduled, &mut txn);
+        txn.commit();
+        submitting.remove(&scheduled.id);
+    }
+}
+
+/// Builds the transaction of the given intent, to be included in the block following the given
+/// block number. Unsigned intents are signed with the given key pair and postponed without one.
+fn build_transaction(
+    intent: &TransactionIntent,
+    key_pair: Option<&KeyPair>,
+    block_number: u32,
+    network_id: NetworkId,
+) -> Result<Transaction, BuildError> {
+    if let TransactionIntent::Signed(transaction) = intent {
+        return if transaction.is_valid_at(block_number + 1) {
+            Ok(transaction.clone())
+        } else {
+            Err(BuildError::Failed(
+                "Transaction is not valid at the scheduled block".to_string(),
+            ))
+        };
+    }
+
+    let wallet = intent.wallet().expect("Unsigned intents have a wallet");
+    let key_pair = key_pair.ok_or_else(|| {
+        BuildError::Postponed(Error::UnlockedWalletNotFound(wallet.clone()).to_string())
+    })?;
+
+    let transaction = match intent {
+        TransactionIntent::Signed(_) => unreachable!(),
+        TransactionIntent::Basic {
+            recipient,
+            value,
+            fee,
+            ..
+        } => TransactionBuilder::new_basic(
+            key_pair,
+            recipient.clone(),
+            *value,
+            *fee,
+            block_number,
+            network_id,
+        ),
+        TransactionIntent::RedeemHtlcTimeout {
+            contract,
+            recipient,
+            value,
+            fee,
+            ..
+        } => TransactionBuilder::new_redeem_htlc_timeout(
+            key_pair,
+            contract.clone(),
+            recipient.clone(),
+            *value,
+            *fee,
+            block_number,
+            network_id,
+        ),
+        TransactionIntent::RedeemVesting {
+            contract,
+            recipient,
+            value,
+            fee,
+            ..
+        } => TransactionBuilder::new_redeem_vesting(
+            key_pair,
+            contract.clone(),
+            recipient.clone(),
+            *value,
+            *fee,
+            block_number,
+            network_id,
+        ),
+    };
+
+    transaction.map_err(|error| BuildError::Failed(error.to_string()))
+}
+
+/// Checks whether the trigger has been reached at a head of the chain with the given block
+/// number and timestamp.
+fn is_due(
+    trigger: &ScheduleTrigger,
+    head_block_number: u32,
+    head_timestamp: u64,
+    get_account: impl Fn(&Address) -> Option<Account>,
+) -> bool {
+    match trigger {
+        ScheduleTrigger::BlockNumber(block_number) => head_block_number >= *block_number,
+        ScheduleTrigger::Timestamp(timestamp) => head_timestamp >= *timestamp,
+        ScheduleTrigger::HtlcTimeout(address) => match get_account(address) {
+            // The timeout can be resolved in any block with a timestamp after the timeout.
+            Some(Account::HTLC(htlc)) => htlc.timeout < head_timestamp,
+            _ => false,
+        },
+        ScheduleTrigger::VestingUnlocked { contract, value } => match get_account(contract) {
+            Some(Account::Vesting(vesting)) => {
+                vesting
+                    .balance
+                    .saturating_sub(vesting.min_cap(head_timestamp))
+                    >= *value
+            }
+            _ => false,
+        },
+    }
+}
+
+fn get_account(blockchain: &BlockchainReadProxy, address: &Address) -> Option<Account> {
+    match blockchain {
+        BlockchainReadProxy::Full(blockchain) => blockchain.get_account_if_complete(address),
+        _ => None,
+    }
+}
+
+fn schedule_trigger(trigger: RPCScheduleTrigger) -> ScheduleTrigger {
+    match trigger {
+        RPCScheduleTrigger::BlockNumber { block_number } => {
+            ScheduleTrigger::BlockNumber(block_number)
+        }
+        RPCScheduleTrigger::Timestamp { timestamp } => ScheduleTrigger::Timestamp(timestamp),
+        RPCScheduleTrigger::HtlcTimeout { contract_address } => {
+            ScheduleTrigger::HtlcTimeout(contract_address)
+        }
+        RPCScheduleTrigger::VestingUnlocked {
+            contract_address,
+            value,
+        } => ScheduleTrigger::VestingUnlocked {
+            contract: contract_address,
+            value,
+        },
+    }
+}
+
+fn rpc_scheduled_transaction(scheduled: ScheduledTransaction) -> RPCScheduledTransaction {
+    let trigger = match scheduled.trigger {
+        ScheduleTrigger::BlockNumber(block_number) => {
+            RPCScheduleTrigger::BlockNumber { block_number }
+        }
+        ScheduleTrigger::Timestamp(timestamp) => RPCScheduleTrigger::Timestamp { timestamp },
+        ScheduleTrigger::HtlcTimeout(contract_address) => {
+            RPCScheduleTrigger::HtlcTimeout { contract_address }
+        }
+        ScheduleTrigger::VestingUnlocked { contract, value } => {
+            RPCScheduleTrigger::VestingUnlocked {
+                contract_address: contract,
+                value,
+            }
+        }
+    };
+
+    let wallet = scheduled.intent.wallet().cloned();
+    let (kind, sender, recipient, value, fee, mut transaction_hash) = match scheduled.intent {
+        TransactionIntent::Signed(transaction) => (
+            ScheduledTransactionKind::Raw,
+            transaction.sender.clone(),
+            transaction.recipient.clone(),
+            transaction.value,
+            transaction.fee,
+            Some(transaction.hash::<Blake2bHash>()),
+        ),
+        TransactionIntent::Basic {
+            wallet: sender,
+            recipient,
+            value,
+            fee,
+        } => (
+            ScheduledTransactionKind::Basic,
+            sender,
+            recipient,
+            value,
+            fee,
+            None,
+        ),
+        TransactionIntent::RedeemHtlcTimeout {
+            contract,
+            recipient,
+            value,
+            fee,
+            ..
+        } => (
+            ScheduledTransactionKind::RedeemHtlcTimeout,
+            contract,
+            recipient,
+            value,
+            fee,
+            None,
+        ),
+        TransactionIntent::RedeemVesting {
+            contract,
+            recipient,
+            value,
+            fee,
+            ..
+        } => (
+            ScheduledTransactionKind::RedeemVesting,
+            contract,
+            recipient,
+            value,
+            fee,
+            None,
+        ),
+    };
+
+    let (state, submitted_at, error) = match scheduled.state {
+        ScheduleState::Scheduled => (
+            ScheduledTransactionState::Scheduled,
+            None,
+            scheduled.last_error,
+        ),
+        ScheduleState::Submitted { hash, block_number } => {
+            transaction_hash = Some(hash);
+            (
+                ScheduledTransactionState::Submitted,
+                Some(block_number),
+                None,
+            )
+        }
+        ScheduleState::Failed(reason) => (ScheduledTransactionState::Failed, None, Some(reason)),
+        ScheduleState::Cancelled => (ScheduledTransactionState::Cancelled, None, None),
+    };
+
+    RPCScheduledTransaction {
+        id: scheduled.id,
+        trigger,
+        kind,
+        state,
+        wallet,
+        sender,
+        recipient,
+        value,
+        fee,
+        transaction_hash,
+        submitted_at,
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_account::{Account, BasicAccount, HashedTimeLockedContract, VestingContract};
+    use nimiq_keys::{Address, KeyPair, PrivateKey};
+    use nimiq_primitives::{coin::Coin, networks::NetworkId};
+    use nimiq_serde::Deserialize;
+    use nimiq_test_log::test;
+    use nimiq_transaction::{account::htlc_contract::AnyHash, Transaction};
+    use nimiq_wallet::{ScheduleTrigger, TransactionIntent};
+
+    use super::{build_transaction, is_due, BuildError};
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; Address::SIZE])
+    }
+
+    fn key_pair() -> KeyPair {
+        PrivateKey::deserialize_from_vec(&[1; 32]).unwrap().into()
+    }
+
+    fn htlc(timeout: u64) -> Account {
+        Account::HTLC(HashedTimeLockedContract {
+            balance: Coin::from_u64_unchecked(100),
+            sender: address(1),
+            recipient: address(2),
+            hash_root: AnyHash::default(),
+            hash_count: 1,
+            timeout,
+            total_amount: Coin::from_u64_unchecked(100),
+        })
+    }
+
+    fn vesting() -> Account {
+        // Releases 10 every 100ms, starting at 0.
+        Account::Vesting(VestingContract {
+            balance: Coin::from_u64_unchecked(100),
+            owner: address(1),
+            start_time: 0,
+            time_step: 100,
+            step_amount: Coin::from_u64_unchecked(10),
+            total_amount: Coin::from_u64_unchecked(100),
+        })
+    }
+
+    #[test]
+    fn block_number_and_timestamp_triggers_are_due_once_reached() {
+        let no_account = |_: &Address| None;
+
+        let trigger = ScheduleTrigger::BlockNumber(10);
+        assert!(!is_due(&trigger, 9, 0, no_account));
+        assert!(is_due(&trigger, 10, 0, no_account));
+        assert!(is_due(&trigger, 11, 0, no_account));
+
+        let trigger = ScheduleTrigger::Timestamp(1000);
+        assert!(!is_due(&trigger, 0, 999, no_account));
+        assert!(is_due(&trigger, 0, 1000, no_account));
+    }
+
+    #[test]
+    fn htlc_timeout_triggers_are_due_after_the_timeout() {
+        let trigger = ScheduleTrigger::HtlcTimeout(address(3));
+
+        assert!(!is_due(&trigger, 0, 1000, |_| Some(htlc(1000))));
+        assert!(is_due(&trigger, 0, 1001, |_| Some(htlc(1000))));
+
+        // Other or missing accounts never trigger.
+        assert!(!is_due(&trigger, 0, 1001, |_| None));
+        assert!(!is_due(&trigger, 0, 1001, |_| Some(vesting())));
+    }
+
+    #[test]
+    fn vesting_triggers_are_due_once_the_value_is_released() {
+        let trigger = ScheduleTrigger::VestingUnlocked {
+            contract: address(3),
+            value: Coin::from_u64_unchecked(20),
+        };
+
+        assert!(!is_due(&trigger, 0, 199, |_| Some(vesting())));
+        assert!(is_due(&trigger, 0, 200, |_| Some(vesting())));
+
+        // Other or missing accounts never trigger.
+        assert!(!is_due(&trigger, 0, 200, |_| None));
+        assert!(!is_due(&trigger, 0, 200, |_| Some(Account::Basic(
+            BasicAccount {
+                balance: Coin::from_u64_unchecked(100)
+            }
+        ))));
+    }
+
+    #[test]
+    fn signed_intents_are_submitted_while_they_are_valid() {
+        let transaction = Transaction::new_basic(
+            address(1),
+            address(2),
+            Coin::from_u64_unchecked(10),
+            Coin::ZERO,
+            100,
+            NetworkId::UnitAlbatross,
+        );
+        let intent = TransactionIntent::Signed(transaction.clone());
+
+        assert_eq!(
+            build_transaction(&intent, None, 100, NetworkId::UnitAlbatross),
+            Ok(transaction)
+        );
+        assert!(matches!(
+            build_transaction(&intent, None, 10, NetworkId::UnitAlbatross),
+            Err(BuildError::Failed(_))
+        ));
+    }
+
+    #[test]
+    fn unsigned_intents_are_postponed_without_a_key() {
+        let key_pair = key_pair();
+        let intent = TransactionIntent::Basic {
+            wallet: Address::from(&key_pair),
+            recipient: address(2),
+            value: Coin::from_u64_unchecked(10),
+            fee: Coin::from_u64_unchecked(1),
+        };
+
+        assert!(matches!(
+            build_transaction(&intent, None, 100, NetworkId::UnitAlbatross),
+            Err(BuildError::Postponed(_))
+        ));
+
+        let transaction =
+            build_transaction(&intent, Some(&key_pair), 100, NetworkId::UnitAlbatross).unwrap();
+        assert_eq!(transaction.sender, Address::from(&key_pair));
+        assert_eq!(transaction.recipient, address(2));
+        assert_eq!(transaction.value, Coin::from_u64_unchecked(10));
+        assert_eq!(transaction.validity_start_height, 100);
+        assert!(transaction.verify(NetworkId::UnitAlbatross).is_ok());
+    }
+}
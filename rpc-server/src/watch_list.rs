@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+
+use nimiq_block::Block;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+use nimiq_rpc_interface::types::{
+    Transaction as RPCTransaction, WatchedTransactionEvent, WatchedTransactionState,
+};
+use nimiq_transaction::Transaction;
+
+/// The number of confirmations after which a transaction is reported as confirmed, if not
+/// specified otherwise.
+pub const DEFAULT_CONFIRMATIONS: u32 = 10;
+
+/// Tracks the transactions involving a set of addresses from the mempool until they are
+/// finalized and produces an event for every state transition.
+pub struct WatchList {
+    addresses: HashSet<Address>,
+    confirmations: u32,
+    /// The hashes of the watched transactions that are currently in the mempool.
+    pending: HashSet<Blake2bHash>,
+    /// The watched transactions that are included in the main chain but not yet finalized.
+    included: HashMap<Blake2bHash, IncludedTransaction>,
+}
+
+struct IncludedTransaction {
+    block_number: u32,
+    confirmed: bool,
+}
+
+impl WatchList {
+    pub fn new(addresses: Vec<Address>, confirmations: u32) -> Self {
+        Self {
+            addresses: addresses.into_iter().collect(),
+            confirmations: confirmations.max(1),
+            pending: HashSet::new(),
+            included: HashMap::new(),
+        }
+    }
+
+    fn is_watched(&self, transaction: &Transaction) -> bool {
+        self.addresses.contains(&transaction.sender)
+            || self.addresses.contains(&transaction.recipient)
+    }
+
+    /// Handles a transaction that was added to the mempool.
+    pub fn add_pending(&mut self, transaction: &Transaction) -> Option<WatchedTransactionEvent> {
+        if !self.is_watched(transaction) {
+            return None;
+        }
+
+        let hash: Blake2bHash = transaction.hash();
+        self.pending.insert(hash.clone());
+
+        Some(WatchedTransactionEvent {
+            state: WatchedTransactionState::Pending,
+            hash,
+            block_number: None,
+            confirmations: None,
+            execution_result: None,
+            transaction: Some(RPCTransaction::from_transaction(transaction.clone())),
+        })
+    }
+
+    /// Handles a transaction that was evicted from the mempool.
+    pub fn evict_pending(&mut self, hash: &Blake2bHash) -> Option<WatchedTransactionEvent> {
+        if !self.pending.remove(hash) {
+            return None;
+        }
+
+        Some(WatchedTransactionEvent {
+            state: WatchedTransactionState::Evicted,
+            hash: hash.clone(),
+            block_number: None,
+            confirmations: None,
+            execution_result: None,
+            transaction: None,
+        })
+    }
+
+    /// Handles a block that was added to the main chain. The block must include its body.
+    pub fn apply_block(
+        &mut self,
+        block: &Block,
+        head_block_number: u32,
+    ) -> Vec<WatchedTransactionEvent> {
+        let block_number = block.block_number();
+        let mut events = vec![];
+
+        for executed_tx in block.transactions().unwrap_or_default() {
+            let transaction = executed_tx.get_raw_transaction();
+            if !self.is_watched(transaction) {
+                continue;
+            }
+
+            let hash: Blake2bHash = transaction.hash();
+            self.pending.remove(&hash);
+            self.included.insert(
+                hash.clone(),
+                IncludedTransaction {
+                    block_number,
+                    confirmed: false,
+                },
+            );
+
+            events.push(WatchedTransactionEvent {
+                state: WatchedTransactionState::Included,
+                hash,
+                block_number: Some(block_number),
+                confirmations: Some(confirmations(block_number, head_block_number)),
+                execution_result: Some(executed_tx.succeeded()),
+                transaction: Some(RPCTransaction::from_blockchain(
+                    transaction.clone(),
+                    block_number,
+                    block.timestamp(),
+                    head_block_number,
+                )),
+            });
+        }
+
+        events.append(&mut self.confirm(head_block_number));
+        events
+    }
+
+    /// Handles a block that was reverted by a rebranch. The block must include its body.
+    pub fn revert_block(&mut self, block: &Block) -> Vec<WatchedTransactionEvent> {
+        let mut events = vec![];
+
+        for executed_tx in block.transactions().unwrap_or_default() {
+            let hash: Blake2bHash = executed_tx.get_raw_transaction().hash();
+            if let Some(included) = self.included.remove(&hash) {
+                events.push(WatchedTransactionEvent {
+                    state: WatchedTransactionState::Retracted,
+                    hash,
+                    block_number: Some(included.block_number),
+                    confirmations: None,
+                    execution_result: None,
+                    transaction: None,
+                });
+            }
+        }
+
+        events
+    }
+
+    /// Handles a macro block that finalized all blocks up to the given block number.
+    pub fn finalize(
+        &mut self,
+        macro_block_number: u32,
+        head_block_number: u32,
+    ) -> Vec<WatchedTransactionEvent> {
+        let mut finalized: Vec<_> = self
+            .included
+            .iter()
+            .filter(|(_, included)| included.block_number <= macro_block_number)
+            .map(|(hash, included)| (included.block_number, hash.clone()))
+            .collect();
+        finalized.sort();
+
+        let mut events = vec![];
+        for (block_number, hash) in finalized {
+            let included = self.included.remove(&hash).unwrap();
+            let confirmations = Some(confirmations(block_number, head_block_number));
+
+            // Finality implies confirmation, so make sure that it is reported first.
+            if !included.confirmed {
+                events.push(WatchedTransactionEvent {
+                    state: WatchedTransactionState::Confirmed,
+                    hash: hash.clone(),
+                    block_number: Some(block_number),
+                    confirmations,
+                    execution_result: None,
+                    transaction: None,
+                });
+            }
+
+            events.push(WatchedTransactionEvent {
+                state: WatchedTransactionState::Finalized,
+                hash,
+                block_number: Some(block_number),
+                confirmations,
+                execution_result: None,
+                transaction: None,
+            });
+        }
+
+        events
+    }
+
+    /// Reports the included transactions that reached the requested number of confirmations.
+    fn confirm(&mut self, head_block_number: u32) -> Vec<WatchedTransactionEvent> {
+        let required_confirmations = self.confirmations;
+        let mut events: Vec<_> = self
+            .included
+            .iter_mut()
+            .filter(|(_, included)| {
+                !included.confirmed
+                    && confirmations(included.block_number, head_block_number)
+                        >= required_confirmations
+            })
+            .map(|(hash, included)| {
+                included.confirmed = true;
+                WatchedTransactionEvent {
+                    state: WatchedTransactionState::Confirmed,
+                    hash: hash.clone(),
+                    block_number: Some(included.block_number),
+                    confirmations: Some(confirmations(included.block_number, head_block_number)),
+                    execution_result: None,
+                    transaction: None,
+                }
+            })
+            .collect();
+        events.sort_by_key(|event| event.block_number);
+        events
+    }
+}
+
+fn confirmations(block_number: u32, head_block_number: u32) -> u32 {
+    head_block_number.saturating_sub(block_number) + 1
+}
@@ -0,0 +1,177 @@
+use nimiq_block::{Block, MicroBlock, MicroBody, MicroHeader};
+use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_rpc_interface::types::{WatchedTransactionEvent, WatchedTransactionState};
+use nimiq_rpc_server::watch_list::WatchList;
+use nimiq_test_log::test;
+use nimiq_transaction::{ExecutedTransaction, Transaction};
+use nimiq_vrf::VrfSeed;
+
+fn address(byte: u8) -> Address {
+    Address::from([byte; Address::SIZE])
+}
+
+fn transaction(sender: u8, recipient: u8, value: u64) -> Transaction {
+    Transaction::new_basic(
+        address(sender),
+        address(recipient),
+        Coin::from_u64_unchecked(value),
+        Coin::ZERO,
+        1,
+        NetworkId::UnitAlbatross,
+    )
+}
+
+fn micro_block(block_number: u32, transactions: &[Transaction]) -> Block {
+    Block::Micro(MicroBlock {
+        header: MicroHeader {
+            network: NetworkId::UnitAlbatross,
+            version: 0,
+            block_number,
+            timestamp: 0,
+            parent_hash: Blake2bHash::default(),
+            seed: VrfSeed::default(),
+            extra_data: vec![],
+            state_root: Blake2bHash::default(),
+            body_root: Blake2sHash::default(),
+            diff_root: Blake2bHash::default(),
+            history_root: Blake2bHash::default(),
+        },
+        body: Some(MicroBody {
+            equivocation_proofs: vec![],
+            transactions: transactions
+                .iter()
+                .map(|tx| ExecutedTransaction::Ok(tx.clone()))
+                .collect(),
+        }),
+        justification: None,
+    })
+}
+
+fn states(events: &[WatchedTransactionEvent]) -> Vec<(WatchedTransactionState, Blake2bHash)> {
+    events
+        .iter()
+        .map(|event| (event.state, event.hash.clone()))
+        .collect()
+}
+
+#[test]
+fn it_reports_pending_and_evicted_transactions() {
+    let mut watch_list = WatchList::new(vec![address(1)], 2);
+    let watched = transaction(1, 2, 10);
+    let unwatched = transaction(3, 4, 10);
+
+    assert!(watch_list.add_pending(&unwatched).is_none());
+    assert!(watch_list.evict_pending(&unwatched.hash()).is_none());
+
+    let event = watch_list.add_pending(&watched).unwrap();
+    assert_eq!(event.state, WatchedTransactionState::Pending);
+    assert_eq!(event.hash, watched.hash::<Blake2bHash>());
+    assert!(event.transaction.is_some());
+
+    let event = watch_list.evict_pending(&watched.hash()).unwrap();
+    assert_eq!(event.state, WatchedTransactionState::Evicted);
+
+    // A transaction is only evicted once.
+    assert!(watch_list.evict_pending(&watched.hash()).is_none());
+}
+
+#[test]
+fn it_follows_transactions_until_finality() {
+    let mut watch_list = WatchList::new(vec![address(2)], 2);
+    let tx = transaction(1, 2, 10);
+    let hash: Blake2bHash = tx.hash();
+
+    watch_list.add_pending(&tx).unwrap();
+
+    let events = watch_list.apply_block(&micro_block(10, &[tx.clone()]), 10);
+    assert_eq!(
+        states(&events),
+        vec![(WatchedTransactionState::Included, hash.clone())]
+    );
+    assert_eq!(events[0].block_number, Some(10));
+    assert_eq!(events[0].confirmations, Some(1));
+    assert_eq!(events[0].execution_result, Some(true));
+
+    // An included transaction is not pending anymore.
+    assert!(watch_list.evict_pending(&hash).is_none());
+
+    let events = watch_list.apply_block(&micro_block(11, &[]), 11);
+    assert_eq!(
+        states(&events),
+        vec![(WatchedTransactionState::Confirmed, hash.clone())]
+    );
+    assert_eq!(events[0].confirmations, Some(2));
+
+    // Confirmations are only reported once.
+    assert!(watch_list.apply_block(&micro_block(12, &[]), 12).is_empty());
+
+    assert!(watch_list.finalize(9, 12).is_empty());
+    let events = watch_list.finalize(12, 12);
+    assert_eq!(
+        states(&events),
+        vec![(WatchedTransactionState::Finalized, hash)]
+    );
+    assert_eq!(events[0].confirmations, Some(3));
+
+    // Finalized transactions are not tracked anymore.
+    assert!(watch_list.finalize(12, 12).is_empty());
+}
+
+#[test]
+fn it_confirms_transactions_before_finalizing_them() {
+    let mut watch_list = WatchList::new(vec![address(1)], 10);
+    let tx = transaction(1, 2, 10);
+    let hash: Blake2bHash = tx.hash();
+
+    watch_list.apply_block(&micro_block(10, &[tx]), 10);
+
+    assert_eq!(
+        states(&watch_list.finalize(10, 10)),
+        vec![
+            (WatchedTransactionState::Confirmed, hash.clone()),
+            (WatchedTransactionState::Finalized, hash)
+        ]
+    );
+}
+
+#[test]
+fn it_retracts_transactions_on_rebranch() {
+    let mut watch_list = WatchList::new(vec![address(1)], 2);
+    let tx_1 = transaction(1, 2, 10);
+    let tx_2 = transaction(1, 2, 20);
+    let hash_1: Blake2bHash = tx_1.hash();
+    let hash_2: Blake2bHash = tx_2.hash();
+
+    let block = micro_block(10, &[tx_1.clone(), tx_2.clone()]);
+    watch_list.apply_block(&block, 10);
+
+    let events = watch_list.revert_block(&block);
+    assert_eq!(
+        states(&events),
+        vec![
+            (WatchedTransactionState::Retracted, hash_1.clone()),
+            (WatchedTransactionState::Retracted, hash_2)
+        ]
+    );
+    assert_eq!(events[0].block_number, Some(10));
+
+    // Retracted transactions are neither confirmed nor finalized.
+    assert!(watch_list.apply_block(&micro_block(11, &[]), 11).is_empty());
+    assert!(watch_list.finalize(11, 11).is_empty());
+
+    // The new branch includes one of the transactions again.
+    let events = watch_list.apply_block(&micro_block(11, &[tx_1]), 11);
+    assert_eq!(
+        states(&events),
+        vec![(WatchedTransactionState::Included, hash_1.clone())]
+    );
+    assert_eq!(
+        states(&watch_list.finalize(12, 12)),
+        vec![
+            (WatchedTransactionState::Confirmed, hash_1.clone()),
+            (WatchedTransactionState::Finalized, hash_1)
+        ]
+    );
+}
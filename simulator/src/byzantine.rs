@@ -0,0 +1,119 @@
+use std::{collections::HashSet, sync::Arc};
+
+use futures::StreamExt;
+use nimiq_block::{Block, BlockTopic, MicroJustification};
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::KeyPair as SchnorrKeyPair;
+use nimiq_network_interface::network::Network;
+use nimiq_network_mock::MockNetwork;
+use parking_lot::{Mutex, RwLock};
+
+/// Extra data that marks the conflicting twin of a micro block produced by a byzantine validator.
+pub const EQUIVOCATION_EXTRA_DATA: &[u8] = b"equivocation";
+
+/// Misbehaviour of a simulated validator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ByzantineBehaviour {
+    /// The validator never sends any of its votes, i.e. it withholds all of its Tendermint and
+    /// skip block contributions.
+    WithholdVotes,
+    /// The validator publishes a second, conflicting micro block for every micro block it
+    /// produces. Both blocks are delivered to all validators.
+    DoubleMicroBlocks,
+    /// The validator produces two conflicting micro blocks for each of its slots and sends one of
+    /// them to each of the given groups of validators. Validators in neither group are sent
+    /// neither block.
+    EquivocatingProposer { groups: (Vec<usize>, Vec<usize>) },
+}
+
+/// Keeps track of the conflicting blocks produced by byzantine validators.
+#[derive(Debug, Default)]
+pub struct EquivocationRegistry {
+    conflicting_blocks: Mutex<HashSet<Blake2bHash>>,
+}
+
+impl EquivocationRegistry {
+    pub fn insert(&self, hash: Blake2bHash) {
+        self.conflicting_blocks.lock().insert(hash);
+    }
+
+    /// Returns whether the block with the given hash is the conflicting twin of an honestly
+    /// produced block.
+    pub fn is_conflicting(&self, hash: &Blake2bHash) -> bool {
+        self.conflicting_blocks.lock().contains(hash)
+    }
+
+    /// Returns the number of conflicting blocks produced so far.
+    pub fn len(&self) -> usize {
+        self.conflicting_blocks.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Creates a micro block that conflicts with the given one. The conflicting block only differs in
+/// its extra data and is signed with the same signing key, which makes it an equivocation.
+pub fn conflicting_micro_block(block: &Block, signing_key: &SchnorrKeyPair) -> Option<Block> {
+    let block = match block {
+        Block::Micro(block) if !block.is_skip_block() => block,
+        _ => return None,
+    };
+
+    let mut conflicting = block.clone();
+    conflicting.header.extra_data = EQUIVOCATION_EXTRA_DATA.to_vec();
+    let hash = conflicting.header.hash::<Blake2bHash>();
+    conflicting.justification = Some(MicroJustification::Micro(signing_key.sign(hash.as_slice())));
+
+    Some(Block::Micro(conflicting))
+}
+
+/// Watches the chain of a byzantine validator and publishes a conflicting micro block for each
+/// micro block the validator produces itself.
+pub(crate) async fn publish_conflicting_blocks(
+    blockchain: Arc<RwLock<Blockchain>>,
+    network: Arc<MockNetwork>,
+    signing_key: SchnorrKeyPair,
+    registry: Arc<EquivocationRegistry>,
+) {
+    let mut events = blockchain.read().notifier_as_stream();
+
+    while let Some(event) = events.next().await {
+        let hash = match event {
+            BlockchainEvent::Extended(hash) => hash,
+            _ => continue,
+        };
+
+        let conflicting = {
+            let blockchain = blockchain.read();
+            let block = match blockchain.get_block(&hash, true) {
+                Ok(block) => block,
+                Err(_) => continue,
+            };
+
+            // Only equivocate on our own blocks.
+            match blockchain.get_proposer_of(&hash) {
+                Ok(slot) if slot.validator.signing_key == signing_key.public => {}
+                _ => continue,
+            }
+
+            match conflicting_micro_block(&block, &signing_key) {
+                Some(conflicting) => conflicting,
+                None => continue,
+            }
+        };
+
+        log::info!(
+            block = %conflicting,
+            "Byzantine validator publishing conflicting micro block"
+        );
+        registry.insert(conflicting.hash());
+
+        if let Err(error) = network.publish::<BlockTopic>(conflicting).await {
+            log::warn!(%error, "Failed to publish conflicting micro block");
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::{sync::Arc, time::Duration};
+
+use nimiq_utils::time::OffsetTime;
+use tokio::time::Instant;
+
+/// The Unix timestamp in milliseconds at which every simulation starts, 2024-01-01T00:00:00Z.
+pub const EPOCH: u64 = 1_704_067_200_000;
+
+/// A clock that follows Tokio's (possibly paused) time instead of the wall clock.
+///
+/// The time source handed to the simulated nodes is anchored at [`EPOCH`] and advances with the
+/// Tokio time elapsed since the clock was created. When running on a runtime with paused time, the
+/// simulation thus runs as fast as the CPU allows, and the nodes observe the same timestamps in
+/// every run with the same seed.
+#[derive(Clone, Debug)]
+pub struct VirtualClock {
+    time: Arc<OffsetTime>,
+    start: Instant,
+}
+
+impl VirtualClock {
+    pub fn new() -> Self {
+        let start = Instant::now();
+        Self {
+            time: Arc::new(OffsetTime::with_source(move || {
+                EPOCH + start.elapsed().as_millis() as u64
+            })),
+            start,
+        }
+    }
+
+    /// The time source to hand to the simulated nodes.
+    pub fn time(&self) -> Arc<OffsetTime> {
+        Arc::clone(&self.time)
+    }
+
+    /// The simulated time that elapsed since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
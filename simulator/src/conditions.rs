@@ -0,0 +1,147 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use nimiq_block::{Block, BlockHeaderTopic, BlockTopic};
+use nimiq_network_interface::network::Topic;
+use nimiq_network_mock::{Delivery, MessageFilter, MockMessage, MockMessageKind, MockPeerId};
+use nimiq_serde::Deserialize;
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    byzantine::{ByzantineBehaviour, EquivocationRegistry},
+    clock::VirtualClock,
+    faults::{FaultSchedule, NetworkFault},
+};
+
+/// The type IDs of all messages sent through the validator network are offset by this value.
+/// See `ValidatorMessage` in `nimiq-validator-network`.
+const VALIDATOR_MESSAGE_TYPE_ID_OFFSET: u16 = 10_000;
+
+/// The network conditions of a simulation. Decides for every message sent between the simulated
+/// validators whether and when it is delivered, based on the fault schedule and the byzantine
+/// behaviours of the validators.
+pub(crate) struct NetworkConditions {
+    clock: VirtualClock,
+    schedule: FaultSchedule,
+    validator_indices: HashMap<MockPeerId, usize>,
+    withholding: HashSet<usize>,
+    equivocating: HashMap<usize, (Vec<usize>, Vec<usize>)>,
+    registry: Arc<EquivocationRegistry>,
+    rng: Mutex<StdRng>,
+}
+
+impl NetworkConditions {
+    pub(crate) fn new(
+        clock: VirtualClock,
+        schedule: FaultSchedule,
+        validator_indices: HashMap<MockPeerId, usize>,
+        byzantine: &HashMap<usize, ByzantineBehaviour>,
+        registry: Arc<EquivocationRegistry>,
+        seed: u64,
+    ) -> Self {
+        let mut withholding = HashSet::new();
+        let mut equivocating = HashMap::new();
+        for (&index, behaviour) in byzantine {
+            match behaviour {
+                ByzantineBehaviour::WithholdVotes => {
+                    withholding.insert(index);
+                }
+                ByzantineBehaviour::EquivocatingProposer { groups } => {
+                    equivocating.insert(index, groups.clone());
+                }
+                ByzantineBehaviour::DoubleMicroBlocks => {}
+            }
+        }
+
+        Self {
+            clock,
+            schedule,
+            validator_indices,
+            withholding,
+            equivocating,
+            registry,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Applies the byzantine behaviour of the sender to the given message. Returns `false` if the
+    /// message must not reach the recipient.
+    fn apply_byzantine(&self, sender: usize, recipient: usize, kind: &MockMessageKind<'_>) -> bool {
+        match kind {
+            MockMessageKind::Request { request_type, .. } => {
+                !(self.withholding.contains(&sender)
+                    && request_type.type_id() >= VALIDATOR_MESSAGE_TYPE_ID_OFFSET)
+            }
+            MockMessageKind::Gossipsub { topic, data } => {
+                let groups = match self.equivocating.get(&sender) {
+                    Some(groups) => groups,
+                    None => return true,
+                };
+                if *topic != BlockTopic::NAME && *topic != BlockHeaderTopic::NAME {
+                    return true;
+                }
+                let block = match Block::deserialize_from_vec(data) {
+                    Ok(block) => block,
+                    Err(_) => return true,
+                };
+
+                // The honest block only reaches the first group, the conflicting one only the
+                // second group. Validators in neither group see neither block and have to
+                // request it from their peers.
+                if self.registry.is_conflicting(&block.hash()) {
+                    groups.1.contains(&recipient)
+                } else {
+                    groups.0.contains(&recipient)
+                }
+            }
+        }
+    }
+}
+
+impl MessageFilter for NetworkConditions {
+    fn filter(&self, message: &MockMessage<'_>) -> Delivery {
+        let (sender, recipient) = match (
+            self.validator_indices.get(&message.sender),
+            self.validator_indices.get(&message.recipient),
+        ) {
+            (Some(&sender), Some(&recipient)) => (sender, recipient),
+            _ => return Delivery::Deliver,
+        };
+
+        if !self.apply_byzantine(sender, recipient, &message.kind) {
+            return Delivery::Drop;
+        }
+
+        let elapsed = self.clock.elapsed();
+        let mut rng = self.rng.lock();
+        let mut delay = Duration::ZERO;
+        for fault in self.schedule.active_faults(elapsed) {
+            if !fault.is_link_up(sender, recipient) {
+                return Delivery::Drop;
+            }
+            match fault {
+                NetworkFault::Drop(probability) => {
+                    if rng.gen_bool(*probability) {
+                        return Delivery::Drop;
+                    }
+                }
+                NetworkFault::Delay(range) => {
+                    if !range.is_empty() {
+                        delay += rng.gen_range(range.clone());
+                    }
+                }
+                NetworkFault::Partition(_) | NetworkFault::Isolate(_) => {}
+            }
+        }
+
+        if delay.is_zero() {
+            Delivery::Deliver
+        } else {
+            Delivery::Delay(delay)
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{ops::Range, time::Duration};
+
+/// A fault that affects the delivery of messages between validators.
+///
+/// Validators are referred to by their index in the simulation.
+#[derive(Clone, Debug)]
+pub enum NetworkFault {
+    /// Delays every message by a random duration in the given range.
+    Delay(Range<Duration>),
+    /// Drops every message with the given probability (between `0.0` and `1.0`).
+    Drop(f64),
+    /// Splits the validators into groups which can only communicate within themselves.
+    /// Validators that are not part of any group can't communicate with anyone.
+    Partition(Vec<Vec<usize>>),
+    /// Cuts off the given validators from all other validators. The isolated validators can still
+    /// communicate with each other.
+    Isolate(Vec<usize>),
+}
+
+impl NetworkFault {
+    /// Returns whether messages from `sender` to `recipient` can pass this fault at all.
+    pub(crate) fn is_link_up(&self, sender: usize, recipient: usize) -> bool {
+        match self {
+            NetworkFault::Partition(groups) => groups
+                .iter()
+                .any(|group| group.contains(&sender) && group.contains(&recipient)),
+            NetworkFault::Isolate(validators) => {
+                validators.contains(&sender) == validators.contains(&recipient)
+            }
+            NetworkFault::Delay(_) | NetworkFault::Drop(_) => true,
+        }
+    }
+}
+
+/// A fault that is active during a window of simulated time.
+#[derive(Clone, Debug)]
+pub struct ScheduledFault {
+    /// The time, relative to the start of the simulation, from which on the fault is active.
+    pub start: Duration,
+    /// The time, relative to the start of the simulation, at which the fault is lifted.
+    /// The fault stays active until the end of the simulation if this is `None`.
+    pub end: Option<Duration>,
+    pub fault: NetworkFault,
+}
+
+impl ScheduledFault {
+    pub fn is_active(&self, elapsed: Duration) -> bool {
+        elapsed >= self.start && self.end.map_or(true, |end| elapsed < end)
+    }
+}
+
+/// A list of network faults that are applied over the course of a simulation.
+#[derive(Clone, Debug, Default)]
+pub struct FaultSchedule {
+    faults: Vec<ScheduledFault>,
+}
+
+impl FaultSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a fault that is active for the whole simulation.
+    pub fn with_permanent_fault(&mut self, fault: NetworkFault) -> &mut Self {
+        self.with_fault(Duration::ZERO, None, fault)
+    }
+
+    /// Adds a fault that is active from `start` until `end` (or the end of the simulation).
+    pub fn with_fault(
+        &mut self,
+        start: Duration,
+        end: Option<Duration>,
+        fault: NetworkFault,
+    ) -> &mut Self {
+        self.faults.push(ScheduledFault { start, end, fault });
+        self
+    }
+
+    /// Returns an iterator over all faults active at the given simulation time.
+    pub fn active_faults(&self, elapsed: Duration) -> impl Iterator<Item = &NetworkFault> {
+        self.faults
+            .iter()
+            .filter(move |scheduled| scheduled.is_active(elapsed))
+            .map(|scheduled| &scheduled.fault)
+    }
+
+    /// Returns the time at which the last fault of this schedule is lifted, or `None` if any of the
+    /// faults is permanent.
+    pub fn healed_at(&self) -> Option<Duration> {
+        self.faults
+            .iter()
+            .map(|scheduled| scheduled.end)
+            .try_fold(Duration::ZERO, |latest, end| end.map(|end| latest.max(end)))
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+use parking_lot::RwLock;
+use thiserror::Error;
+
+/// A violated safety or liveness property of the simulated network.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum InvariantViolation {
+    #[error("Validators {validator_a} and {validator_b} finalized different macro blocks at #{block_number}: {hash_a} vs {hash_b}")]
+    ConflictingMacroBlocks {
+        block_number: u32,
+        validator_a: usize,
+        hash_a: Blake2bHash,
+        validator_b: usize,
+        hash_b: Blake2bHash,
+    },
+    #[error("Validator {validator} is missing the macro block at #{block_number}")]
+    MissingMacroBlock { validator: usize, block_number: u32 },
+    #[error("Validator {validator} only reached block #{block_number}, expected #{expected}")]
+    NoProgress {
+        validator: usize,
+        block_number: u32,
+        expected: u32,
+    },
+}
+
+impl InvariantViolation {
+    /// Returns whether this violation breaks safety, i.e. the agreement on finalized blocks.
+    pub fn is_safety_violation(&self) -> bool {
+        !matches!(self, InvariantViolation::NoProgress { .. })
+    }
+}
+
+/// Checks that all given validators agree on every macro block that all of them have finalized.
+///
+/// Macro blocks commit to their complete history, so agreement on macro blocks implies agreement on
+/// the finalized chain.
+pub fn check_safety(validators: &[(usize, Arc<RwLock<Blockchain>>)]) -> Vec<InvariantViolation> {
+    let mut violations = vec![];
+
+    let finalized = validators
+        .iter()
+        .map(|(_, blockchain)| blockchain.read().macro_head().block_number())
+        .min();
+    let finalized = match finalized {
+        Some(block_number) => block_number,
+        None => return violations,
+    };
+
+    let mut block_number = Policy::genesis_block_number();
+    while block_number <= finalized {
+        let mut reference: Option<(usize, Blake2bHash)> = None;
+        for (index, blockchain) in validators {
+            let hash = match blockchain.read().get_block_at(block_number, false) {
+                Ok(block) => block.hash(),
+                Err(_) => {
+                    violations.push(InvariantViolation::MissingMacroBlock {
+                        validator: *index,
+                        block_number,
+                    });
+                    continue;
+                }
+            };
+
+            match &reference {
+                None => reference = Some((*index, hash)),
+                Some((reference_index, reference_hash)) if *reference_hash != hash => {
+                    violations.push(InvariantViolation::ConflictingMacroBlocks {
+                        block_number,
+                        validator_a: *reference_index,
+                        hash_a: reference_hash.clone(),
+                        validator_b: *index,
+                        hash_b: hash,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        block_number += Policy::blocks_per_batch();
+    }
+
+    violations
+}
+
+/// Checks that all given validators reached at least the `expected` block number.
+pub fn check_liveness(
+    validators: &[(usize, Arc<RwLock<Blockchain>>)],
+    expected: u32,
+) -> Vec<InvariantViolation> {
+    validators
+        .iter()
+        .filter_map(|(index, blockchain)| {
+            let block_number = blockchain.read().block_number();
+            (block_number < expected).then_some(InvariantViolation::NoProgress {
+                validator: *index,
+                block_number,
+                expected,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,19 @@
+//! Deterministic simulation of a network of Albatross validators.
+//!
+//! The simulator runs a configurable number of full [`Validator`](nimiq_validator::validator::Validator)
+//! instances on a [`MockHub`](nimiq_network_mock::MockHub). Message delivery between the
+//! validators is subject to a [`FaultSchedule`] of delays, message loss and partitions, and
+//! individual validators can be made to misbehave (see [`ByzantineBehaviour`]). At the end of a
+//! run, the safety and liveness of the honest validators are checked and reported in a
+//! [`SimulationReport`].
+pub mod byzantine;
+pub mod clock;
+mod conditions;
+pub mod faults;
+pub mod invariants;
+pub mod simulation;
+
+pub use byzantine::ByzantineBehaviour;
+pub use faults::{FaultSchedule, NetworkFault};
+pub use invariants::InvariantViolation;
+pub use simulation::{Simulation, SimulationConfig, SimulationReport};
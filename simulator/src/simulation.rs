@@ -0,0 +1,389 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use ::time::OffsetDateTime;
+use futures::{future, StreamExt};
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_bls::{cache::PublicKeyCache, KeyPair as BlsKeyPair};
+use nimiq_consensus::{sync::syncer_proxy::SyncerProxy, Consensus, ConsensusEvent};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis_builder::{GenesisBuilder, GenesisInfo};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::{Address, KeyPair as SchnorrKeyPair, SecureGenerate};
+use nimiq_mempool::{block_building::BlockBuildingConfig, config::MempoolConfig};
+use nimiq_network_interface::network::Network;
+use nimiq_network_mock::{MockHub, MockNetwork, MockPeerId};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_validator::validator::Validator;
+use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
+use nimiq_zkp_component::ZKPComponent;
+use parking_lot::{Mutex, RwLock};
+use rand::{rngs::StdRng, SeedableRng};
+use tokio::{task::JoinHandle, time};
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::{
+    byzantine::{publish_conflicting_blocks, ByzantineBehaviour, EquivocationRegistry},
+    clock::{VirtualClock, EPOCH},
+    conditions::NetworkConditions,
+    faults::FaultSchedule,
+    invariants::{check_liveness, check_safety, InvariantViolation},
+};
+
+const BLS_CACHE_MAX_CAPACITY: usize = 100;
+
+/// The configuration of a simulation run.
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    /// The number of validators. All validators have the same stake.
+    pub num_validators: usize,
+    /// The seed from which all keys and random network conditions are derived.
+    pub seed: u64,
+    /// The simulation ends as soon as all honest validators reached this block number.
+    pub target_block_number: u32,
+    /// The maximum simulated time the simulation may take.
+    pub timeout: Duration,
+    /// The network faults to inject.
+    pub faults: FaultSchedule,
+    /// The misbehaving validators, by index.
+    pub byzantine: HashMap<usize, ByzantineBehaviour>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            num_validators: 4,
+            seed: 0,
+            target_block_number: Policy::genesis_block_number() + Policy::blocks_per_batch(),
+            timeout: Duration::from_secs(10 * 60),
+            faults: FaultSchedule::default(),
+            byzantine: HashMap::default(),
+        }
+    }
+}
+
+/// The outcome of a simulation run.
+#[derive(Clone, Debug)]
+pub struct SimulationReport {
+    /// The simulated time the run took.
+    pub elapsed: Duration,
+    /// The head block number of each validator at the end of the run.
+    pub block_numbers: Vec<u32>,
+    /// The head block hash of each validator at the end of the run.
+    pub head_hashes: Vec<Blake2bHash>,
+    /// The indices of the honest validators.
+    pub honest: Vec<usize>,
+    /// The number of skip blocks on the main chain of the first honest validator.
+    pub skip_blocks: usize,
+    /// The number of conflicting micro blocks published by byzantine validators.
+    pub conflicting_blocks: usize,
+    /// All invariant violations among the honest validators.
+    pub violations: Vec<InvariantViolation>,
+}
+
+impl SimulationReport {
+    pub fn safety_violations(&self) -> impl Iterator<Item = &InvariantViolation> {
+        self.violations
+            .iter()
+            .filter(|violation| violation.is_safety_violation())
+    }
+
+    pub fn liveness_violations(&self) -> impl Iterator<Item = &InvariantViolation> {
+        self.violations
+            .iter()
+            .filter(|violation| !violation.is_safety_violation())
+    }
+
+    /// Panics if the honest validators disagree on any finalized block.
+    pub fn assert_safety(&self) {
+        let violations: Vec<_> = self.safety_violations().collect();
+        assert!(violations.is_empty(), "Safety violated: {:?}", violations);
+    }
+
+    /// Panics if any honest validator didn't reach the target block number.
+    pub fn assert_liveness(&self) {
+        let violations: Vec<_> = self.liveness_violations().collect();
+        assert!(violations.is_empty(), "Liveness violated: {:?}", violations);
+    }
+
+    /// Panics if any invariant is violated.
+    pub fn assert_invariants(&self) {
+        self.assert_safety();
+        self.assert_liveness();
+    }
+}
+
+/// A simulated validator node.
+struct SimulatedValidator {
+    blockchain: Arc<RwLock<Blockchain>>,
+    network: Arc<MockNetwork>,
+    signing_key: SchnorrKeyPair,
+}
+
+/// Runs a set of full validators on a [`MockHub`] with a virtual clock and injected faults.
+///
+/// For deterministic results the simulation must be run on a current-thread runtime with paused
+/// time, e.g. using `#[tokio::test(start_paused = true)]`.
+pub struct Simulation {
+    config: SimulationConfig,
+}
+
+impl Simulation {
+    pub fn new(config: SimulationConfig) -> Self {
+        for (&index, behaviour) in &config.byzantine {
+            assert!(
+                index < config.num_validators,
+                "Byzantine validator {index} doesn't exist"
+            );
+            if let ByzantineBehaviour::EquivocatingProposer { groups } = behaviour {
+                assert!(
+                    groups
+                        .0
+                        .iter()
+                        .chain(groups.1.iter())
+                        .all(|&index| index < config.num_validators),
+                    "Equivocation groups refer to validators that don't exist"
+                );
+            }
+        }
+
+        Self { config }
+    }
+
+    /// Runs the simulation until all honest validators reach the target block number or the
+    /// timeout expires, and checks the invariants.
+    pub async fn run(self) -> SimulationReport {
+        let config = self.config;
+        let clock = VirtualClock::new();
+        let mut tasks: Vec<JoinHandle<()>> = vec![];
+
+        // Generate validator keys.
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let keys: Vec<_> = (0..config.num_validators)
+            .map(|_| {
+                (
+                    SchnorrKeyPair::generate(&mut rng),
+                    SchnorrKeyPair::generate(&mut rng),
+                    BlsKeyPair::generate(&mut rng),
+                    SchnorrKeyPair::generate(&mut rng),
+                )
+            })
+            .collect();
+
+        // Generate genesis block.
+        let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+        let mut genesis_builder = GenesisBuilder::default();
+        genesis_builder
+            .with_network(NetworkId::UnitAlbatross)
+            .with_genesis_block_number(Policy::genesis_block_number())
+            .with_timestamp(
+                OffsetDateTime::from_unix_timestamp((EPOCH / 1000) as i64)
+                    .expect("Invalid simulation epoch"),
+            );
+        for (validator_key, signing_key, voting_key, _) in &keys {
+            genesis_builder.with_genesis_validator(
+                Address::from(validator_key),
+                signing_key.public,
+                voting_key.public_key,
+                Address::default(),
+                None,
+                None,
+                false,
+            );
+        }
+        let genesis = genesis_builder.generate(env).unwrap();
+
+        // Install the network conditions. Validator `i` uses the peer ID `i + 1`.
+        let mut hub = MockHub::default();
+        let registry = Arc::new(EquivocationRegistry::default());
+        let validator_indices = (0..config.num_validators)
+            .map(|index| (MockPeerId::from(index as u64 + 1), index))
+            .collect();
+        hub.set_message_filter(NetworkConditions::new(
+            clock.clone(),
+            config.faults.clone(),
+            validator_indices,
+            &config.byzantine,
+            Arc::clone(&registry),
+            config.seed,
+        ));
+
+        // Instantiate the validators.
+        let mut validators = vec![];
+        let mut simulated = vec![];
+        let mut consensus = vec![];
+        for (index, (validator_key, signing_key, voting_key, fee_key)) in
+            keys.into_iter().enumerate()
+        {
+            let network = Arc::new(hub.new_network_with_address(index as u64 + 1));
+            let (blockchain, node_consensus, env) =
+                Self::build_node(&clock, &genesis, Arc::clone(&network)).await;
+
+            let validator = Validator::<ValidatorNetworkImpl<MockNetwork>>::new(
+                env,
+                &node_consensus,
+                Arc::clone(&blockchain),
+                Arc::new(ValidatorNetworkImpl::new(Arc::clone(&network))),
+                Address::from(&validator_key),
+                false,
+                signing_key.clone(),
+                voting_key,
+                fee_key,
+                MempoolConfig::default(),
+                BlockBuildingConfig::default(),
+            );
+
+            validators.push(validator);
+            simulated.push(SimulatedValidator {
+                blockchain,
+                network,
+                signing_key,
+            });
+            consensus.push(node_consensus);
+        }
+
+        // Connect all validators with each other.
+        for (index, validator) in simulated.iter().enumerate() {
+            for other in &simulated[index + 1..] {
+                validator.network.dial_mock(&other.network);
+            }
+        }
+
+        // Wait until consensus is established everywhere.
+        let mut events: Vec<BroadcastStream<ConsensusEvent>> = consensus
+            .iter()
+            .map(|consensus| consensus.subscribe_events())
+            .collect();
+        for consensus in consensus {
+            tasks.push(tokio::spawn(consensus));
+        }
+        future::join_all(events.iter_mut().map(|events| events.next())).await;
+
+        // Start the byzantine behaviours that actively produce messages.
+        for (&index, behaviour) in &config.byzantine {
+            match behaviour {
+                ByzantineBehaviour::DoubleMicroBlocks
+                | ByzantineBehaviour::EquivocatingProposer { .. } => {
+                    let validator = &simulated[index];
+                    tasks.push(tokio::spawn(publish_conflicting_blocks(
+                        Arc::clone(&validator.blockchain),
+                        Arc::clone(&validator.network),
+                        validator.signing_key.clone(),
+                        Arc::clone(&registry),
+                    )));
+                }
+                ByzantineBehaviour::WithholdVotes => {}
+            }
+        }
+
+        for validator in validators {
+            tasks.push(tokio::spawn(validator));
+        }
+
+        let honest: Vec<(usize, Arc<RwLock<Blockchain>>)> = simulated
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !config.byzantine.contains_key(index))
+            .map(|(index, validator)| (index, Arc::clone(&validator.blockchain)))
+            .collect();
+
+        // Run until all honest validators reached the target or the timeout expired.
+        let target_reached = || {
+            honest.iter().all(|(_, blockchain)| {
+                blockchain.read().block_number() >= config.target_block_number
+            })
+        };
+        while !target_reached() && clock.elapsed() < config.timeout {
+            time::sleep(Duration::from_millis(100)).await;
+        }
+
+        for task in &tasks {
+            task.abort();
+        }
+
+        let mut violations = check_safety(&honest);
+        violations.extend(check_liveness(&honest, config.target_block_number));
+
+        let report = SimulationReport {
+            elapsed: clock.elapsed(),
+            block_numbers: simulated
+                .iter()
+                .map(|validator| validator.blockchain.read().block_number())
+                .collect(),
+            head_hashes: simulated
+                .iter()
+                .map(|validator| validator.blockchain.read().head_hash())
+                .collect(),
+            honest: honest.iter().map(|(index, _)| *index).collect(),
+            skip_blocks: honest.first().map_or(0, |(_, blockchain)| {
+                Self::count_skip_blocks(&blockchain.read())
+            }),
+            conflicting_blocks: registry.len(),
+            violations,
+        };
+
+        log::info!(?report, "Simulation finished");
+        report
+    }
+
+    async fn build_node(
+        clock: &VirtualClock,
+        genesis: &GenesisInfo,
+        network: Arc<MockNetwork>,
+    ) -> (
+        Arc<RwLock<Blockchain>>,
+        Consensus<MockNetwork>,
+        nimiq_database::DatabaseProxy,
+    ) {
+        let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+        let blockchain = Arc::new(RwLock::new(
+            Blockchain::with_genesis(
+                env.clone(),
+                BlockchainConfig::default(),
+                clock.time(),
+                NetworkId::UnitAlbatross,
+                genesis.block.clone(),
+                genesis.accounts.clone(),
+            )
+            .unwrap(),
+        ));
+
+        let blockchain_proxy = BlockchainProxy::Full(Arc::clone(&blockchain));
+        let zkp_component = ZKPComponent::new(
+            blockchain_proxy.clone(),
+            Arc::clone(&network),
+            Box::new(|fut| {
+                tokio::spawn(fut);
+            }),
+            None,
+        )
+        .await;
+        let syncer = SyncerProxy::new_history(
+            blockchain_proxy.clone(),
+            Arc::clone(&network),
+            Arc::new(Mutex::new(PublicKeyCache::new(BLS_CACHE_MAX_CAPACITY))),
+            network.subscribe_events(),
+        )
+        .await;
+        let consensus = Consensus::<MockNetwork>::new(
+            blockchain_proxy,
+            network,
+            syncer,
+            1,
+            zkp_component.proxy(),
+            Box::new(|fut| {
+                tokio::spawn(fut);
+            }),
+        );
+
+        (blockchain, consensus, env)
+    }
+
+    fn count_skip_blocks(blockchain: &Blockchain) -> usize {
+        (Policy::genesis_block_number() + 1..=blockchain.block_number())
+            .filter_map(|block_number| blockchain.get_block_at(block_number, false).ok())
+            .filter(|block| block.is_skip())
+            .count()
+    }
+}
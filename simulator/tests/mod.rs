@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use nimiq_primitives::policy::Policy;
+use nimiq_simulator::{
+    ByzantineBehaviour, FaultSchedule, NetworkFault, Simulation, SimulationConfig,
+};
+use nimiq_test_log::test;
+
+#[test(tokio::test(start_paused = true))]
+async fn honest_validators_finalize_batch() {
+    let report = Simulation::new(SimulationConfig::default()).run().await;
+
+    report.assert_invariants();
+    assert_eq!(report.skip_blocks, 0);
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn network_delays_and_message_loss() {
+    let mut faults = FaultSchedule::new();
+    faults
+        .with_permanent_fault(NetworkFault::Delay(
+            Duration::from_millis(10)..Duration::from_millis(200),
+        ))
+        .with_permanent_fault(NetworkFault::Drop(0.05));
+
+    let report = Simulation::new(SimulationConfig {
+        seed: 1,
+        faults,
+        ..Default::default()
+    })
+    .run()
+    .await;
+
+    report.assert_invariants();
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn partition_heals() {
+    // No group has a two-thirds majority while the partition lasts.
+    let mut faults = FaultSchedule::new();
+    faults.with_fault(
+        Duration::ZERO,
+        Some(Duration::from_secs(30)),
+        NetworkFault::Partition(vec![vec![0, 1], vec![2, 3]]),
+    );
+
+    let report = Simulation::new(SimulationConfig {
+        seed: 2,
+        faults,
+        ..Default::default()
+    })
+    .run()
+    .await;
+
+    report.assert_invariants();
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn one_validator_withholds_votes() {
+    let report = Simulation::new(SimulationConfig {
+        seed: 3,
+        byzantine: [(0, ByzantineBehaviour::WithholdVotes)].into(),
+        ..Default::default()
+    })
+    .run()
+    .await;
+
+    report.assert_invariants();
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn double_micro_blocks_do_not_break_safety() {
+    let report = Simulation::new(SimulationConfig {
+        seed: 4,
+        target_block_number: Policy::genesis_block_number() + 2 * Policy::blocks_per_batch(),
+        byzantine: [(1, ByzantineBehaviour::DoubleMicroBlocks)].into(),
+        ..Default::default()
+    })
+    .run()
+    .await;
+
+    report.assert_invariants();
+    assert!(report.conflicting_blocks > 0);
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn equivocating_proposer_does_not_break_safety() {
+    let report = Simulation::new(SimulationConfig {
+        seed: 5,
+        byzantine: [(
+            3,
+            ByzantineBehaviour::EquivocatingProposer {
+                groups: (vec![0], vec![1, 2]),
+            },
+        )]
+        .into(),
+        ..Default::default()
+    })
+    .run()
+    .await;
+
+    report.assert_invariants();
+    assert!(report.conflicting_blocks > 0);
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn equivocating_proposer_with_validator_in_neither_group() {
+    // Validator 2 is sent neither of the conflicting blocks.
+    let report = Simulation::new(SimulationConfig {
+        seed: 7,
+        byzantine: [(
+            3,
+            ByzantineBehaviour::EquivocatingProposer {
+                groups: (vec![0], vec![1]),
+            },
+        )]
+        .into(),
+        ..Default::default()
+    })
+    .run()
+    .await;
+
+    report.assert_invariants();
+    assert!(report.conflicting_blocks > 0);
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn same_seed_is_deterministic() {
+    let config = SimulationConfig {
+        seed: 6,
+        faults: {
+            let mut faults = FaultSchedule::new();
+            faults.with_permanent_fault(NetworkFault::Delay(
+                Duration::from_millis(10)..Duration::from_millis(200),
+            ));
+            faults
+        },
+        ..Default::default()
+    };
+
+    let report_a = Simulation::new(config.clone()).run().await;
+    let report_b = Simulation::new(config).run().await;
+
+    report_a.assert_invariants();
+    assert_eq!(report_a.block_numbers, report_b.block_numbers);
+    assert_eq!(report_a.head_hashes, report_b.head_hashes);
+}
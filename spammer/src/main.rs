@@ -140,6 +140,7 @@ async fn main_inner() -> Result<(), Error> {
         sync_mode: None,
         network: None,
         prove: false,
+        prover_server: None,
     };
 
     // Parse config file - this will obey the `--config` command line option.
@@ -234,6 +235,7 @@ async fn main_inner() -> Result<(), Error> {
             metrics_config.addr,
             client.blockchain(),
             client.mempool(),
+            client.aggregation_trackers(),
             client.consensus_proxy(),
             client.network(),
             &[],
@@ -7,7 +7,7 @@ use nimiq_consensus::{Consensus, ConsensusEvent};
 use nimiq_database::DatabaseProxy;
 use nimiq_genesis_builder::{GenesisBuilder, GenesisInfo};
 use nimiq_keys::{Address, KeyPair as SchnorrKeyPair, SecureGenerate};
-use nimiq_mempool::config::MempoolConfig;
+use nimiq_mempool::{block_building::BlockBuildingConfig, config::MempoolConfig};
 use nimiq_network_interface::network::Network as NetworkInterface;
 use nimiq_network_mock::MockHub;
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
@@ -55,6 +55,7 @@ where
             voting_key,
             fee_key,
             MempoolConfig::default(),
+            BlockBuildingConfig::default(),
         ),
         consensus,
     )
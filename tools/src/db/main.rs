@@ -0,0 +1,322 @@
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+    process::exit,
+    str::FromStr,
+    sync::Arc,
+};
+
+use anyhow::Error;
+use clap::{crate_authors, crate_version, value_parser, Arg, ArgAction, ArgMatches, Command};
+use nimiq_blockchain::{
+    block_file::BlockExporter,
+    integrity::{CheckStatus, IntegrityChecker},
+    Blockchain, BlockchainConfig, HistoryStore,
+};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_database::{
+    mdbx::MdbxDatabase,
+    traits::{Database, WriteTransaction},
+    DatabaseProxy,
+};
+use nimiq_genesis::{NetworkId, NetworkInfo};
+use nimiq_primitives::policy::Policy;
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+/// The memory map size used when opening the database for writing, matching the client default.
+const DB_SIZE: usize = 1024 * 1024 * 1024 * 1024;
+
+/// The number of blocks after which the import progress is reported.
+const PROGRESS_INTERVAL: u32 = 1000;
+
+fn open_database(matches: &ArgMatches, writable: bool) -> Result<DatabaseProxy, Error> {
+    let path = matches.get_one::<PathBuf>("path").unwrap();
+    let max_dbs = *matches.get_one::<u32>("max_dbs").unwrap();
+
+    if !path.exists() {
+        return Err(anyhow::anyhow!("Database not found: {}", path.display()));
+    }
+    if writable {
+        Ok(MdbxDatabase::new(path, DB_SIZE, max_dbs)?)
+    } else {
+        Ok(MdbxDatabase::new_read_only(path, max_dbs)?)
+    }
+}
+
+fn stats(env: &DatabaseProxy) -> Result<(), Error> {
+    let stats = env.table_stats()?;
+
+    println!(
+        "{:<32} {:>12} {:>6} {:>10} {:>10} {:>10} {:>14}",
+        "Table", "Entries", "Depth", "Branch", "Leaf", "Overflow", "Size (bytes)"
+    );
+    for table in &stats {
+        println!(
+            "{:<32} {:>12} {:>6} {:>10} {:>10} {:>10} {:>14}",
+            table.name,
+            table.entries,
+            table.depth,
+            table.branch_pages,
+            table.leaf_pages,
+            table.overflow_pages,
+            table.size
+        );
+    }
+    println!(
+        "{:<32} {:>12} {:>6} {:>10} {:>10} {:>10} {:>14}",
+        "Total",
+        stats.iter().map(|table| table.entries).sum::<usize>(),
+        "",
+        stats.iter().map(|table| table.branch_pages).sum::<usize>(),
+        stats.iter().map(|table| table.leaf_pages).sum::<usize>(),
+        stats
+            .iter()
+            .map(|table| table.overflow_pages)
+            .sum::<usize>(),
+        stats.iter().map(|table| table.size).sum::<usize>()
+    );
+
+    Ok(())
+}
+
+/// Prints the integrity report and returns whether the database is consistent.
+fn check(env: DatabaseProxy) -> bool {
+    let report = IntegrityChecker::new(env).check();
+    for check in &report.checks {
+        println!("[{:>7}] {}: {}", check.status, check.name, check.message);
+    }
+
+    let num_failed = report
+        .checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Failed)
+        .count();
+    if num_failed > 0 {
+        println!("{num_failed} checks failed, the database is inconsistent");
+    } else {
+        println!("The database is consistent");
+    }
+    report.is_consistent()
+}
+
+fn repair(env: DatabaseProxy) -> Result<(), Error> {
+    match IntegrityChecker::new(env.clone()).repair()? {
+        Some(head) => println!(
+            "Rolled back the chain to block #{} {}",
+            head.block_number(),
+            head.hash()
+        ),
+        None => println!("The head block matches the accounts tree, nothing to roll back"),
+    }
+
+    if check(env) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("The database is still inconsistent"))
+    }
+}
+
+fn rebuild_history_indexes(env: DatabaseProxy) -> Result<(), Error> {
+    let history_store = HistoryStore::new(env.clone());
+    let mut txn = env.write_transaction();
+    let num_hist_txs = history_store.rebuild_indexes(&mut txn);
+    txn.commit();
+
+    println!("Rebuilt the history indexes from {num_hist_txs} historic transactions");
+    Ok(())
+}
+
+fn load_blockchain(
+    env: DatabaseProxy,
+    network_id: NetworkId,
+    config: BlockchainConfig,
+) -> Result<Blockchain, Error> {
+    Ok(Blockchain::new(
+        env,
+        config,
+        network_id,
+        Arc::new(OffsetTime::new()),
+    )?)
+}
+
+fn export_blocks(
+    env: DatabaseProxy,
+    network_id: NetworkId,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    let exporter = BlockExporter::new(env, network_id);
+    let from = *sub_matches.get_one::<u32>("from").unwrap();
+    let to = match sub_matches.get_one::<u32>("to") {
+        Some(to) => *to,
+        None => exporter
+            .head_block_number()
+            .ok_or_else(|| anyhow::anyhow!("No head block stored"))?,
+    };
+    let output = sub_matches.get_one::<PathBuf>("output").unwrap();
+
+    let file = BufWriter::new(File::create(output)?);
+    let num_blocks = exporter.export_blocks(from, to, file)?;
+    println!(
+        "Exported {num_blocks} blocks (#{from} to #{to}) to {}",
+        output.display()
+    );
+
+    Ok(())
+}
+
+fn import_blocks(
+    env: DatabaseProxy,
+    network_id: NetworkId,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    let config = BlockchainConfig {
+        archive: sub_matches.get_flag("archive"),
+        ..Default::default()
+    };
+    let blockchain = RwLock::new(load_blockchain(env, network_id, config)?);
+    let input = sub_matches.get_one::<PathBuf>("input").unwrap();
+
+    let file = BufReader::new(File::open(input)?);
+    let progress = Blockchain::import_blocks(&blockchain, file, |progress| {
+        if progress.blocks_read % PROGRESS_INTERVAL == 0 {
+            println!(
+                "Read {} blocks, now at block #{}",
+                progress.blocks_read, progress.block_number
+            );
+        }
+    })?;
+    println!(
+        "Imported {} blocks, {} were already known. The head is block #{}",
+        progress.blocks_imported,
+        progress.blocks_known,
+        blockchain.read().block_number()
+    );
+
+    Ok(())
+}
+
+fn run_app() -> Result<bool, Error> {
+    let matches = Command::new("nimiq-db")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Inspects, checks, exports and imports the database of a stopped node")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("path")
+                .value_name("PATH")
+                .value_parser(value_parser!(PathBuf))
+                .required(true)
+                .help("The database directory of the node"),
+        )
+        .arg(
+            Arg::new("network_id")
+                .short('N')
+                .long("network")
+                .value_name("NETWORK")
+                .default_value("main-albatross")
+                .help("The network the database belongs to"),
+        )
+        .arg(
+            Arg::new("max_dbs")
+                .long("max-dbs")
+                .value_name("NUM")
+                .value_parser(value_parser!(u32))
+                .default_value("32")
+                .help("The maximum number of tables, as configured for the node"),
+        )
+        .subcommand(Command::new("stats").about("Show entry counts and sizes of all tables"))
+        .subcommand(
+            Command::new("check").about(
+                "Verify that the chain store, the history store and the accounts tree agree",
+            ),
+        )
+        .subcommand(Command::new("repair").about(
+            "Roll the chain back to the latest block since the last macro block that matches the accounts tree",
+        ))
+        .subcommand(Command::new("rebuild-history-indexes").about(
+            "Rebuild the contract and staking indexes of the history store, needed once for history nodes synced before they existed",
+        ))
+        .subcommand(
+            Command::new("export-blocks")
+                .about("Write a range of main chain blocks to a block file")
+                .arg(
+                    Arg::new("output")
+                        .value_name("FILE")
+                        .value_parser(value_parser!(PathBuf))
+                        .required(true)
+                        .help("The block file to write"),
+                )
+                .arg(
+                    Arg::new("from")
+                        .long("from")
+                        .value_name("BLOCK_NUMBER")
+                        .value_parser(value_parser!(u32))
+                        .required(true)
+                        .help("The first block to export"),
+                )
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .value_name("BLOCK_NUMBER")
+                        .value_parser(value_parser!(u32))
+                        .help("The last block to export, defaults to the head"),
+                ),
+        )
+        .subcommand(
+            Command::new("import-blocks")
+                .about("Verify and push the blocks of a block file, creating the database if needed")
+                .arg(
+                    Arg::new("input")
+                        .value_name("FILE")
+                        .value_parser(value_parser!(PathBuf))
+                        .required(true)
+                        .help("The block file to read"),
+                )
+                .arg(
+                    Arg::new("archive")
+                        .long("archive")
+                        .action(ArgAction::SetTrue)
+                        .help("Keep all blocks instead of pruning past epochs"),
+                ),
+        )
+        .get_matches();
+
+    let network_id = NetworkId::from_str(matches.get_one::<String>("network_id").unwrap())?;
+    let _ = Policy::get_or_init(Policy {
+        genesis_block_number: NetworkInfo::from_network_id(network_id)
+            .genesis_block()
+            .block_number(),
+        ..Default::default()
+    });
+
+    match matches.subcommand() {
+        Some(("stats", _)) => stats(&open_database(&matches, false)?).map(|_| true),
+        Some(("check", _)) => Ok(check(open_database(&matches, false)?)),
+        Some(("repair", _)) => repair(open_database(&matches, true)?).map(|_| true),
+        Some(("rebuild-history-indexes", _)) => {
+            rebuild_history_indexes(open_database(&matches, true)?).map(|_| true)
+        }
+        Some(("export-blocks", sub_matches)) => {
+            export_blocks(open_database(&matches, false)?, network_id, sub_matches).map(|_| true)
+        }
+        Some(("import-blocks", sub_matches)) => {
+            // Importing into a fresh node starts from an empty database.
+            fs::create_dir_all(matches.get_one::<PathBuf>("path").unwrap())?;
+            import_blocks(open_database(&matches, true)?, network_id, sub_matches).map(|_| true)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn main() {
+    exit(match run_app() {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    });
+}
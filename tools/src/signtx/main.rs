@@ -1,20 +1,244 @@
-use std::{io::stdin, process::exit, str::FromStr};
+use std::{fs, io::stdin, num::NonZeroU8, process::exit, str::FromStr};
 
 use anyhow::Error;
 use clap::{
-    crate_authors, crate_description, crate_version, value_parser, Arg, ArgAction, Command,
+    crate_authors, crate_description, crate_version, value_parser, Arg, ArgAction, ArgMatches,
+    Command,
+};
+use nimiq_keys::{
+    multisig::{commitment::CommitmentPair, MUSIG2_PARAMETER_V},
+    Address, Ed25519PublicKey, KeyPair, PrivateKey, PublicKey,
 };
-use nimiq_keys::{Address, KeyPair, PrivateKey};
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::Transaction;
+use nimiq_wallet::{PartiallySignedTransaction, PstSigner};
 use thiserror::Error;
 
+fn pst_arg() -> Arg {
+    Arg::new("pst")
+        .long("pst")
+        .value_name("PST")
+        .help("The partially signed transaction as hex. Read from STDIN if omitted.")
+}
+
+fn secret_key_arg() -> Arg {
+    Arg::new("secret_key")
+        .short('k')
+        .long("secret-key")
+        .value_name("SECRET_KEY")
+        .required(true)
+        .help("Specify the secret key of the signer.")
+}
+
+fn nonces_file_arg() -> Arg {
+    Arg::new("nonces_file")
+        .long("nonces-file")
+        .value_name("FILE")
+        .help("File to keep the secret MuSig2 nonces in between the commit and sign steps.")
+}
+
+fn pst_command() -> Command {
+    Command::new("pst")
+        .about("Create, sign, combine and finalize partially signed transactions")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("create")
+                .about("Create a partially signed transaction from a transaction given as hex")
+                .arg(
+                    Arg::new("tx")
+                        .long("tx")
+                        .value_name("TX")
+                        .help("The transaction as hex. Read from STDIN if omitted."),
+                )
+                .arg(
+                    Arg::new("public_key")
+                        .long("public-key")
+                        .value_name("PUBLIC_KEY")
+                        .conflicts_with("public_keys")
+                        .help("The public key of the single signer."),
+                )
+                .arg(
+                    Arg::new("public_keys")
+                        .long("public-keys")
+                        .value_name("PUBLIC_KEYS")
+                        .value_delimiter(',')
+                        .requires_all(["min_signatures", "signers"])
+                        .help("The public keys of all owners of the multi-signature account."),
+                )
+                .arg(
+                    Arg::new("min_signatures")
+                        .long("min-signatures")
+                        .value_name("NUM")
+                        .value_parser(value_parser!(NonZeroU8))
+                        .help("The number of signatures required by the multi-signature account."),
+                )
+                .arg(
+                    Arg::new("signers")
+                        .long("signers")
+                        .value_name("PUBLIC_KEYS")
+                        .value_delimiter(',')
+                        .help("The public keys of the owners signing the transaction."),
+                ),
+        )
+        .subcommand(
+            Command::new("commit")
+                .about("Add the MuSig2 commitments of a signer of a multi-signature account")
+                .arg(pst_arg())
+                .arg(secret_key_arg())
+                .arg(nonces_file_arg().required(true)),
+        )
+        .subcommand(
+            Command::new("sign")
+                .about("Add the (partial) signature of a signer")
+                .arg(pst_arg())
+                .arg(secret_key_arg())
+                .arg(nonces_file_arg()),
+        )
+        .subcommand(
+            Command::new("combine")
+                .about(
+                    "Combine the contributions of several copies of a partially signed transaction",
+                )
+                .arg(
+                    Arg::new("psts")
+                        .value_name("PST")
+                        .num_args(2..)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("finalize")
+                .about("Print the signed transaction once all signatures are collected")
+                .arg(pst_arg()),
+        )
+}
+
+fn read_stdin_line() -> Result<String, Error> {
+    let mut line = String::new();
+    stdin().read_line(&mut line)?;
+    Ok(line.trim_end().to_owned())
+}
+
+fn read_pst(matches: &ArgMatches) -> Result<PartiallySignedTransaction, Error> {
+    let pst = match matches.get_one::<String>("pst") {
+        Some(pst) => pst.clone(),
+        None => read_stdin_line()?,
+    };
+    Ok(PartiallySignedTransaction::from_bytes(&hex::decode(pst)?)?)
+}
+
+fn read_key_pair(matches: &ArgMatches) -> Result<KeyPair, Error> {
+    let hex_secret_key = matches
+        .get_one::<String>("secret_key")
+        .ok_or(AppError::SecretKey)?;
+    Ok(PrivateKey::deserialize_from_vec(&hex::decode(hex_secret_key)?)?.into())
+}
+
+fn parse_public_key(hex_public_key: &str) -> Result<Ed25519PublicKey, Error> {
+    Ok(Ed25519PublicKey::deserialize_from_vec(&hex::decode(
+        hex_public_key,
+    )?)?)
+}
+
+fn run_pst(matches: &ArgMatches) -> Result<(), Error> {
+    let pst = match matches.subcommand() {
+        Some(("create", matches)) => {
+            let tx = match matches.get_one::<String>("tx") {
+                Some(tx) => tx.clone(),
+                None => read_stdin_line()?,
+            };
+            let tx = Transaction::deserialize_from_vec(&hex::decode(tx)?)?;
+
+            if let Some(public_key) = matches.get_one::<String>("public_key") {
+                PartiallySignedTransaction::new(
+                    tx,
+                    PublicKey::Ed25519(parse_public_key(public_key)?),
+                )
+            } else if let Some(public_keys) = matches.get_many::<String>("public_keys") {
+                let public_keys = public_keys
+                    .map(|key| parse_public_key(key))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let signers = matches
+                    .get_many::<String>("signers")
+                    .ok_or(AppError::Signers)?
+                    .map(|key| parse_public_key(key))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let min_signatures = *matches
+                    .get_one::<NonZeroU8>("min_signatures")
+                    .ok_or(AppError::MinSignatures)?;
+                PartiallySignedTransaction::new_multisig(
+                    tx,
+                    min_signatures,
+                    &public_keys,
+                    &signers,
+                )?
+            } else {
+                return Err(AppError::PublicKey.into());
+            }
+        }
+        Some(("commit", matches)) => {
+            let mut pst = read_pst(matches)?;
+            let key_pair = read_key_pair(matches)?;
+            let pairs = pst.create_commitments(key_pair.public)?;
+            let nonces_file = matches
+                .get_one::<String>("nonces_file")
+                .ok_or(AppError::NoncesFile)?;
+            fs::write(nonces_file, hex::encode(pairs.serialize_to_vec()))?;
+            pst
+        }
+        Some(("sign", matches)) => {
+            let mut pst = read_pst(matches)?;
+            let key_pair = read_key_pair(matches)?;
+            match pst.signer {
+                PstSigner::Single { .. } => pst.sign(&key_pair)?,
+                PstSigner::MultiSig(_) => {
+                    let nonces_file = matches
+                        .get_one::<String>("nonces_file")
+                        .ok_or(AppError::NoncesFile)?;
+                    let pairs: [CommitmentPair; MUSIG2_PARAMETER_V] =
+                        Deserialize::deserialize_from_vec(&hex::decode(
+                            fs::read_to_string(nonces_file)?.trim_end(),
+                        )?)?;
+                    pst.partially_sign(&key_pair, pairs)?;
+                    // Nonces must never be reused for another signature.
+                    fs::remove_file(nonces_file)?;
+                }
+            }
+            pst
+        }
+        Some(("combine", matches)) => {
+            let mut psts = matches
+                .get_many::<String>("psts")
+                .ok_or(AppError::Pst)?
+                .map(|pst| Ok(PartiallySignedTransaction::from_bytes(&hex::decode(pst)?)?))
+                .collect::<Result<Vec<_>, Error>>()?
+                .into_iter();
+            let mut pst = psts.next().ok_or(AppError::Pst)?;
+            for other in psts {
+                pst.combine(&other)?;
+            }
+            pst
+        }
+        Some(("finalize", matches)) => {
+            let tx = read_pst(matches)?.finalize()?;
+            println!("{}", hex::encode(tx.serialize_to_vec()));
+            return Ok(());
+        }
+        _ => unreachable!(),
+    };
+
+    println!("{}", hex::encode(pst.to_bytes()));
+    Ok(())
+}
+
 fn run_app() -> Result<(), Error> {
     let matches = Command::new("Sign transaction")
         .version(crate_version!())
         .author(crate_authors!())
         .about(crate_description!())
+        .args_conflicts_with_subcommands(true)
+        .subcommand(pst_command())
         .arg(
             Arg::new("secret_key")
                 .short('k')
@@ -73,6 +297,10 @@ fn run_app() -> Result<(), Error> {
         )
         .get_matches();
 
+    if let Some(("pst", matches)) = matches.subcommand() {
+        return run_pst(matches);
+    }
+
     // read transaction either from arguments or stdin
     let tx = if matches.get_flag("tx_from_stdin") {
         let mut line = String::new();
@@ -145,4 +373,14 @@ enum AppError {
     Fee,
     #[error("Validity start height is missing")]
     ValidityStartHeight,
+    #[error("Public key of the signer is missing")]
+    PublicKey,
+    #[error("Signers of the multi-signature account are missing")]
+    Signers,
+    #[error("Minimum number of signatures is missing")]
+    MinSignatures,
+    #[error("Nonces file is missing")]
+    NoncesFile,
+    #[error("Partially signed transaction is missing")]
+    Pst,
 }
@@ -6,7 +6,9 @@ use std::{
 
 use clear_on_drop::clear::Clear;
 use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
-use nimiq_hash::argon2kdf::{compute_argon2_kdf, Argon2Error, Argon2Variant};
+use nimiq_hash::argon2kdf::{
+    compute_argon2_kdf_with_memory_cost, Argon2Error, Argon2Variant, MEMORY_COST,
+};
 use nimiq_serde::{Deserialize, Serialize};
 use rand::{rngs::OsRng, RngCore};
 
@@ -83,7 +85,14 @@ impl<T: Clear + Deserialize + Serialize> Unlocked<T> {
         salt_length: usize,
         algorithm: Algorithm,
     ) -> Result<Self, Argon2Error> {
-        let locked = Locked::create(&secret, password, iterations, salt_length, algorithm)?;
+        let locked = Locked::create(
+            &secret,
+            password,
+            iterations,
+            OtpLock::<T>::DEFAULT_MEMORY_COST,
+            salt_length,
+            algorithm,
+        )?;
         Ok(Unlocked {
             data: ClearOnDrop::new(secret),
             lock: locked,
@@ -169,19 +178,57 @@ pub struct Locked<T: Clear + Deserialize + Serialize> {
     iterations: u32,
     #[serde(default = "Algorithm::backwards_compatible_default")]
     algorithm: Algorithm,
+    /// The memory cost of the key derivation in KiB.
+    memory_cost: u32,
     phantom: PhantomData<T>,
 }
 
+/// The format of locked data before the memory cost was configurable.
+#[derive(Deserialize)]
+struct LegacyLocked {
+    lock: Vec<u8>,
+    salt: Vec<u8>,
+    iterations: u32,
+    algorithm: Algorithm,
+}
+
 impl<T: Clear + Deserialize + Serialize> Locked<T> {
     /// Calling code should make sure to clear the password from memory after use.
     pub fn new(
+        secret: T,
+        password: &[u8],
+        iterations: u32,
+        salt_length: usize,
+        algorithm: Algorithm,
+    ) -> Result<Self, Argon2Error> {
+        Self::new_with_memory_cost(
+            secret,
+            password,
+            iterations,
+            OtpLock::<T>::DEFAULT_MEMORY_COST,
+            salt_length,
+            algorithm,
+        )
+    }
+
+    /// Calling code should make sure to clear the password from memory after use.
+    /// The memory cost is given in KiB.
+    pub fn new_with_memory_cost(
         mut secret: T,
         password: &[u8],
         iterations: u32,
+        memory_cost: u32,
         salt_length: usize,
         algorithm: Algorithm,
     ) -> Result<Self, Argon2Error> {
-        let result = Locked::create(&secret, password, iterations, salt_length, algorithm)?;
+        let result = Locked::create(
+            &secret,
+            password,
+            iterations,
+            memory_cost,
+            salt_length,
+            algorithm,
+        )?;
 
         // Remove secret from memory.
         secret.clear();
@@ -207,6 +254,7 @@ impl<T: Clear + Deserialize + Serialize> Locked<T> {
             &self.lock,
             password,
             self.iterations,
+            self.memory_cost,
             &self.salt,
             self.algorithm,
         )
@@ -238,11 +286,18 @@ impl<T: Clear + Deserialize + Serialize> Locked<T> {
         secret: &[u8],
         password: &[u8],
         iterations: u32,
+        memory_cost: u32,
         salt: &[u8],
         algorithm: Algorithm,
     ) -> Result<Vec<u8>, Argon2Error> {
-        let mut key =
-            compute_argon2_kdf(password, salt, iterations, secret.len(), algorithm.into())?;
+        let mut key = compute_argon2_kdf_with_memory_cost(
+            password,
+            salt,
+            iterations,
+            memory_cost,
+            secret.len(),
+            algorithm.into(),
+        )?;
         assert_eq!(key.len(), secret.len());
 
         for (key_byte, secret_byte) in key.iter_mut().zip(secret.iter()) {
@@ -256,11 +311,12 @@ impl<T: Clear + Deserialize + Serialize> Locked<T> {
         secret: &T,
         password: &[u8],
         iterations: u32,
+        memory_cost: u32,
         salt: Vec<u8>,
         algorithm: Algorithm,
     ) -> Result<Self, Argon2Error> {
         let mut data = secret.serialize_to_vec();
-        let lock = Self::otp(&data, password, iterations, &salt, algorithm)?;
+        let lock = Self::otp(&data, password, iterations, memory_cost, &salt, algorithm)?;
 
         // Always overwrite unencrypted vector.
         for byte in data.iter_mut() {
@@ -272,6 +328,7 @@ impl<T: Clear + Deserialize + Serialize> Locked<T> {
             salt,
             iterations,
             algorithm,
+            memory_cost,
             phantom: PhantomData,
         })
     }
@@ -280,12 +337,18 @@ impl<T: Clear + Deserialize + Serialize> Locked<T> {
         secret: &T,
         password: &[u8],
         iterations: u32,
+        memory_cost: u32,
         salt_length: usize,
         algorithm: Algorithm,
     ) -> Result<Self, Argon2Error> {
         let mut salt = vec![0; salt_length];
         OsRng.fill_bytes(salt.as_mut_slice());
-        Self::lock(secret, password, iterations, salt, algorithm)
+        Self::lock(secret, password, iterations, memory_cost, salt, algorithm)
+    }
+
+    /// The memory cost of the key derivation in KiB.
+    pub fn memory_cost(&self) -> u32 {
+        self.memory_cost
     }
 
     pub fn into_otp_lock(self) -> OtpLock<T> {
@@ -310,7 +373,7 @@ impl<T: Clear + Deserialize + Serialize + Verify> Locked<T> {
     }
 }
 
-impl<T: Default + Deserialize + Serialize> IntoDatabaseValue for Locked<T> {
+impl<T: Clear + Deserialize + Serialize> IntoDatabaseValue for Locked<T> {
     fn database_byte_size(&self) -> usize {
         self.serialized_size()
     }
@@ -320,12 +383,23 @@ impl<T: Default + Deserialize + Serialize> IntoDatabaseValue for Locked<T> {
     }
 }
 
-impl<T: Default + Deserialize + Serialize> FromDatabaseValue for Locked<T> {
+impl<T: Clear + Deserialize + Serialize> FromDatabaseValue for Locked<T> {
     fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
     where
         Self: Sized,
     {
+        // Fall back to the format without memory cost, which always used the default.
         Deserialize::deserialize_from_vec(bytes)
+            .or_else(|_| {
+                LegacyLocked::deserialize_from_vec(bytes).map(|legacy| Locked {
+                    lock: legacy.lock,
+                    salt: legacy.salt,
+                    iterations: legacy.iterations,
+                    algorithm: legacy.algorithm,
+                    memory_cost: MEMORY_COST,
+                    phantom: PhantomData,
+                })
+            })
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
     }
 }
@@ -341,6 +415,8 @@ impl<T: Clear + Deserialize + Serialize> OtpLock<T> {
     // TODO: Adjust.
     pub const DEFAULT_ITERATIONS: u32 = 256;
     pub const DEFAULT_SALT_LENGTH: usize = 32;
+    /// The memory cost of the key derivation in KiB.
+    pub const DEFAULT_MEMORY_COST: u32 = MEMORY_COST;
 
     /// Calling code should make sure to clear the password from memory after use.
     pub fn new_unlocked(
@@ -1,12 +1,21 @@
 use std::{
-    sync::atomic::{AtomicI64, Ordering},
+    fmt,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+/// A function returning the current time as a timestamp in milliseconds.
+type TimeSource = Arc<dyn Fn() -> u64 + Send + Sync>;
+
 /// Time with fixed offset from wall-clock, in milliseconds
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct OffsetTime {
     offset: AtomicI64,
+    /// Replaces the wall-clock if set.
+    source: Option<TimeSource>,
 }
 
 impl OffsetTime {
@@ -17,6 +26,16 @@ impl OffsetTime {
     pub fn with_offset(offset: i64) -> Self {
         OffsetTime {
             offset: AtomicI64::new(offset),
+            source: None,
+        }
+    }
+
+    /// Creates a time that follows the given source instead of the wall-clock, e.g. to run
+    /// deterministic simulations. The offset is applied on top of the source.
+    pub fn with_source(source: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        OffsetTime {
+            offset: AtomicI64::new(0),
+            source: Some(Arc::new(source)),
         }
     }
 
@@ -26,6 +45,10 @@ impl OffsetTime {
 
     pub fn now(&self) -> u64 {
         let offset = self.offset.load(Ordering::Relaxed);
+        if let Some(source) = &self.source {
+            return source().saturating_add_signed(offset);
+        }
+
         let abs_offset = offset.unsigned_abs();
         let system_time = if offset > 0 {
             SystemTime::now() + Duration::from_millis(abs_offset)
@@ -37,6 +60,15 @@ impl OffsetTime {
     }
 }
 
+impl fmt::Debug for OffsetTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OffsetTime")
+            .field("offset", &self.offset)
+            .field("source", &self.source.as_ref().map(|_| "custom"))
+            .finish()
+    }
+}
+
 pub fn systemtime_to_timestamp(time: SystemTime) -> u64 {
     match time.duration_since(UNIX_EPOCH) {
         Ok(duration) => duration.as_secs() * 1000 + u64::from(duration.subsec_nanos()) / 1_000_000,
@@ -1,3 +1,4 @@
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
 use nimiq_test_log::test;
 use nimiq_utils::otp::*;
 use serde::{Deserialize, Serialize};
@@ -88,3 +89,55 @@ fn create_locked_unchecked() {
     let unlocked = unlocked.ok().unwrap();
     assert!(unlocked.eq(&secret));
 }
+
+fn to_database_bytes<T: IntoDatabaseValue>(value: &T) -> Vec<u8> {
+    let mut bytes = vec![0; value.database_byte_size()];
+    value.copy_into_database(&mut bytes);
+    bytes
+}
+
+#[test]
+fn locks_with_custom_memory_cost() {
+    let secret = DummyU32::new(12345);
+    let password = "password";
+
+    let locked = Locked::new_with_memory_cost(
+        secret.clone(),
+        password.as_bytes(),
+        1,
+        64,
+        OtpLock::<DummyU32>::DEFAULT_SALT_LENGTH,
+        Algorithm::default(),
+    )
+    .unwrap();
+    assert_eq!(locked.memory_cost(), 64);
+
+    let locked = Locked::<DummyU32>::copy_from_database(&to_database_bytes(&locked)).unwrap();
+    assert_eq!(locked.memory_cost(), 64);
+    let unlocked = locked.unlock(password.as_bytes()).ok().unwrap();
+    assert!(Unlocked::unlocked_data(&unlocked).eq(&secret));
+}
+
+#[test]
+fn reads_locked_values_without_memory_cost() {
+    let secret = DummyU32::new(12345);
+    let password = "password";
+
+    let locked = Locked::with_defaults(secret.clone(), password.as_bytes()).unwrap();
+    assert_eq!(
+        locked.memory_cost(),
+        OtpLock::<DummyU32>::DEFAULT_MEMORY_COST
+    );
+
+    // Strip the trailing memory cost (512 is encoded as a two byte varint).
+    let mut bytes = to_database_bytes(&locked);
+    bytes.truncate(bytes.len() - 2);
+
+    let locked = Locked::<DummyU32>::copy_from_database(&bytes).unwrap();
+    assert_eq!(
+        locked.memory_cost(),
+        OtpLock::<DummyU32>::DEFAULT_MEMORY_COST
+    );
+    let unlocked = locked.unlock(password.as_bytes()).ok().unwrap();
+    assert!(Unlocked::unlocked_data(&unlocked).eq(&secret));
+}
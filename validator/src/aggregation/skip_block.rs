@@ -16,13 +16,13 @@ use nimiq_bls::{AggregateSignature, KeyPair};
 use nimiq_collections::BitSet;
 use nimiq_handel::{
     aggregation::Aggregation,
-    config::Config,
     contribution::{AggregatableContribution, ContributionError},
     evaluator::WeightedVote,
     identity::WeightRegistry,
     partitioner::BinomialPartitioner,
     protocol::Protocol,
     store::ReplaceStore,
+    tracker::AggregationTracker,
     update::LevelUpdate,
 };
 use nimiq_hash::Blake2sHash;
@@ -249,6 +249,7 @@ impl SkipBlockAggregation {
         validator_id: u16,
         active_validators: Validators,
         network: Arc<N>,
+        tracker: Arc<AggregationTracker>,
     ) -> (SkipBlockInfo, SkipBlockProof) {
         // TODO expose this somewehere else so we don't need to clone here.
         let weights = Arc::new(ValidatorRegistry::new(active_validators.clone()));
@@ -303,11 +304,12 @@ impl SkipBlockAggregation {
 
             let aggregation = Aggregation::new(
                 protocol,
-                Config::default(),
+                tracker.config(),
                 own_contribution,
                 Box::pin(input_switch),
                 NetworkWrapper::new(skip_block_info.clone(), Arc::clone(&network)),
-            );
+            )
+            .with_tracker(Arc::clone(&tracker));
 
             let mut stream = select(
                 aggregation.map(SkipBlockResult::SkipBlock),
@@ -0,0 +1,95 @@
+use nimiq_blockchain::Blockchain;
+use nimiq_mempool::{
+    block_building::{BlockBuildingConfig, BlockBuildingPolicy},
+    mempool::Mempool,
+};
+use nimiq_transaction::Transaction;
+
+/// Fills micro blocks with transactions from the mempool according to the configured block
+/// building policy and the space reserved for control and signaling transactions.
+pub(crate) struct BlockBuilder {
+    policy: Box<dyn BlockBuildingPolicy>,
+    reserved_control_bytes: Option<usize>,
+    reserved_signaling_bytes: Option<usize>,
+}
+
+impl BlockBuilder {
+    pub fn new(config: &BlockBuildingConfig) -> Self {
+        Self {
+            policy: config.policy.build(),
+            reserved_control_bytes: config.reserved_control_bytes,
+            reserved_signaling_bytes: config.reserved_signaling_bytes,
+        }
+    }
+
+    /// Selects the transactions for a block with `available_bytes` of space for transactions and
+    /// removes them from the mempool.
+    pub fn select_transactions(
+        &self,
+        mempool: &Mempool,
+        blockchain: &Blockchain,
+        available_bytes: usize,
+    ) -> Vec<Transaction> {
+        let policy = self.policy.as_ref();
+
+        if self.reserved_control_bytes.is_none() && self.reserved_signaling_bytes.is_none() {
+            // Without any reservation, control transactions are selected first and may fill the
+            // entire block. The remaining space is used for regular transactions.
+            let (mut transactions, control_size) = mempool
+                .get_control_transactions_for_block_with_policy_locked(
+                    blockchain,
+                    available_bytes,
+                    policy,
+                );
+            let (mut regular_transactions, _) = mempool
+                .get_transactions_for_block_with_policy_locked(
+                    blockchain,
+                    available_bytes.saturating_sub(control_size),
+                    policy,
+                );
+            transactions.append(&mut regular_transactions);
+            return transactions;
+        }
+
+        let reserved_control_bytes = self.reserved_control_bytes.unwrap_or_default();
+        let reserved_signaling_bytes = self.reserved_signaling_bytes.unwrap_or_default();
+
+        // First we fill the block with regular transactions, leaving the reserved space free.
+        let regular_bytes =
+            available_bytes.saturating_sub(reserved_control_bytes + reserved_signaling_bytes);
+        let (mut transactions, regular_size) = mempool
+            .get_transactions_for_block_with_policy_locked(blockchain, regular_bytes, policy);
+        let mut available_bytes = available_bytes.saturating_sub(regular_size);
+
+        // Then signaling transactions get their reserved space.
+        let (mut signaling_transactions, signaling_size) = mempool
+            .get_signaling_transactions_for_block_with_policy_locked(
+                blockchain,
+                reserved_signaling_bytes.min(available_bytes),
+                policy,
+            );
+        available_bytes = available_bytes.saturating_sub(signaling_size);
+        transactions.append(&mut signaling_transactions);
+
+        // Control transactions, including the signaling transactions that didn't fit into their
+        // reserved space, get the space reserved for them and whatever regular transactions left over.
+        let (mut control_transactions, control_size) = mempool
+            .get_control_transactions_for_block_with_policy_locked(
+                blockchain,
+                available_bytes,
+                policy,
+            );
+        available_bytes = available_bytes.saturating_sub(control_size);
+        transactions.append(&mut control_transactions);
+
+        // Finally, regular transactions may use the reserved space that control transactions
+        // didn't need.
+        if available_bytes > 0 {
+            let (mut regular_transactions, _) = mempool
+                .get_transactions_for_block_with_policy_locked(blockchain, available_bytes, policy);
+            transactions.append(&mut regular_transactions);
+        }
+
+        transactions
+    }
+}
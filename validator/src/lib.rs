@@ -2,6 +2,7 @@
 extern crate log;
 
 pub mod aggregation;
+mod block_building;
 mod jail;
 mod r#macro;
 mod micro;
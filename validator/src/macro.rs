@@ -11,6 +11,7 @@ use futures::{
 };
 use nimiq_block::MacroBlock;
 use nimiq_blockchain::{BlockProducer, Blockchain};
+use nimiq_handel::tracker::AggregationTracker;
 use nimiq_keys::Ed25519Signature as SchnorrSignature;
 use nimiq_network_interface::network::Topic;
 use nimiq_primitives::{networks::NetworkId, slots_allocation::Validators};
@@ -84,6 +85,7 @@ where
             'static,
             SignedProposalMessage<Header<PubsubId<TValidatorNetwork>>, (SchnorrSignature, u16)>,
         >,
+        aggregation_tracker: Arc<AggregationTracker>,
     ) -> Self {
         let input = network
             .receive::<TendermintUpdate>()
@@ -108,6 +110,7 @@ where
             validator_slot_band,
             network_id,
             block_height,
+            aggregation_tracker,
         );
 
         // create the Tendermint instance, which implements Stream
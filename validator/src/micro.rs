@@ -3,21 +3,21 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::{Duration, SystemTime},
+    time::Duration,
 };
 
 use futures::{future::BoxFuture, ready, FutureExt, Stream};
 use nimiq_block::{Block, EquivocationProof, MicroBlock, SkipBlockInfo};
 use nimiq_blockchain::{BlockProducer, Blockchain};
 use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+use nimiq_handel::tracker::AggregationTracker;
 use nimiq_mempool::mempool::Mempool;
-use nimiq_utils::time::systemtime_to_timestamp;
 use nimiq_validator_network::ValidatorNetwork;
 use nimiq_vrf::VrfSeed;
 use parking_lot::RwLock;
 use tokio::time;
 
-use crate::aggregation::skip_block::SkipBlockAggregation;
+use crate::{aggregation::skip_block::SkipBlockAggregation, block_building::BlockBuilder};
 
 // Ignoring this clippy warning since size difference is not that much (320
 // bytes) and we probably don't want the performance penalty of the allocation.
@@ -30,6 +30,7 @@ pub(crate) enum ProduceMicroBlockEvent {
 struct NextProduceMicroBlockEvent<TValidatorNetwork> {
     blockchain: Arc<RwLock<Blockchain>>,
     mempool: Arc<Mempool>,
+    block_builder: Arc<BlockBuilder>,
     network: Arc<TValidatorNetwork>,
     block_producer: BlockProducer,
     validator_slot_band: u16,
@@ -38,6 +39,7 @@ struct NextProduceMicroBlockEvent<TValidatorNetwork> {
     block_number: u32,
     producer_timeout: Duration,
     block_separation_time: Duration,
+    skip_block_tracker: Arc<AggregationTracker>,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<TValidatorNetwork> {
@@ -47,6 +49,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
     fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         mempool: Arc<Mempool>,
+        block_builder: Arc<BlockBuilder>,
         network: Arc<TValidatorNetwork>,
         block_producer: BlockProducer,
         validator_slot_band: u16,
@@ -55,10 +58,12 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
         block_number: u32,
         producer_timeout: Duration,
         block_separation_time: Duration,
+        skip_block_tracker: Arc<AggregationTracker>,
     ) -> Self {
         Self {
             blockchain,
             mempool,
+            block_builder,
             network,
             block_producer,
             validator_slot_band,
@@ -67,6 +72,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             block_number,
             producer_timeout,
             block_separation_time,
+            skip_block_tracker,
         }
     }
 
@@ -97,7 +103,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
                 } else if self.is_our_turn(&blockchain) {
                     // We want to produce a block at the expected timestamp for this block in this batch
                     // as it is calculated by the reward function and set the producer timeout accordingly
-                    let now = systemtime_to_timestamp(SystemTime::now());
+                    let now = blockchain.time.now();
 
                     // If the expected timestamp is already in the past, produce a block immediately.
                     // If the timestamp hasn't passed, wait until the expected block timestamp
@@ -168,7 +174,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
 
         // Wait for the block to be produced. We wait for at least `producer_timeout` here, but can
         // wait longer if the expected timestamp of the block is further in the future.
-        let now = systemtime_to_timestamp(SystemTime::now());
+        let now = self.blockchain.read().time.now();
         let wait_until_min = now + self.producer_timeout.as_millis() as u64;
         let wait_until_expected = expected_next_ts
             + (self.producer_timeout - self.block_separation_time).as_millis() as u64;
@@ -204,6 +210,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             self.validator_slot_band,
             active_validators.unwrap(),
             Arc::clone(&self.network),
+            Arc::clone(&self.skip_block_tracker),
         )
         .await;
 
@@ -275,26 +282,14 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
     }
 
     fn produce_micro_block(&self, blockchain: &Blockchain) -> MicroBlock {
-        let timestamp = u64::max(
-            blockchain.timestamp(),
-            systemtime_to_timestamp(SystemTime::now()),
-        );
-
-        // First we try to fill the block with control transactions
-        let mut block_available_bytes =
-            MicroBlock::get_available_bytes(self.equivocation_proofs.len());
-
-        let (mut transactions, txn_size) = self
-            .mempool
-            .get_control_transactions_for_block_locked(blockchain, block_available_bytes);
-
-        block_available_bytes = block_available_bytes.saturating_sub(txn_size);
+        let timestamp = u64::max(blockchain.timestamp(), blockchain.time.now());
 
-        let (mut regular_transactions, _) = self
-            .mempool
-            .get_transactions_for_block_locked(blockchain, block_available_bytes);
-
-        transactions.append(&mut regular_transactions);
+        let block_available_bytes = MicroBlock::get_available_bytes(self.equivocation_proofs.len());
+        let transactions = self.block_builder.select_transactions(
+            &self.mempool,
+            blockchain,
+            block_available_bytes,
+        );
 
         self.block_producer.next_micro_block(
             blockchain,
@@ -334,6 +329,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
     pub fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         mempool: Arc<Mempool>,
+        block_builder: Arc<BlockBuilder>,
         network: Arc<TValidatorNetwork>,
         block_producer: BlockProducer,
         validator_slot_band: u16,
@@ -342,10 +338,12 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
         block_number: u32,
         producer_timeout: Duration,
         block_separation_time: Duration,
+        skip_block_tracker: Arc<AggregationTracker>,
     ) -> Self {
         let next_event = NextProduceMicroBlockEvent::new(
             blockchain,
             mempool,
+            block_builder,
             network,
             block_producer,
             validator_slot_band,
@@ -354,6 +352,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
             block_number,
             producer_timeout,
             block_separation_time,
+            skip_block_tracker,
         )
         .next()
         .boxed();
@@ -10,7 +10,7 @@ use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_collections::BitSet;
 use nimiq_handel::{
     aggregation::Aggregation, identity::IdentityRegistry, protocol::Protocol as _,
-    verifier::VerificationResult,
+    tracker::AggregationTracker, verifier::VerificationResult,
 };
 use nimiq_hash::{Blake2sHash, Hash};
 use nimiq_keys::Ed25519Signature as SchnorrSignature;
@@ -115,6 +115,8 @@ pub struct TendermintProtocol<TValidatorNetwork: ValidatorNetwork> {
     pub blockchain: Arc<RwLock<Blockchain>>,
     // Validator registry on the heap for easy cloning into handel protocol.
     validator_registry: Arc<ValidatorRegistry>,
+    // Tracker for the vote aggregations, shared across macro blocks.
+    aggregation_tracker: Arc<AggregationTracker>,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidatorNetwork> {
@@ -128,6 +130,7 @@ impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidato
             current_validators: self.current_validators.clone(),
             blockchain: Arc::clone(&self.blockchain),
             validator_registry: Arc::clone(&self.validator_registry),
+            aggregation_tracker: Arc::clone(&self.aggregation_tracker),
         }
     }
 }
@@ -144,6 +147,7 @@ where
         validator_slot_band: u16,
         network_id: NetworkId,
         block_height: u32,
+        aggregation_tracker: Arc<AggregationTracker>,
     ) -> Self {
         Self {
             block_producer,
@@ -154,6 +158,7 @@ where
             validator_registry: Arc::new(ValidatorRegistry::new(current_validators.clone())),
             current_validators,
             network,
+            aggregation_tracker,
         }
     }
 }
@@ -407,11 +412,12 @@ where
 
         Aggregation::new(
             protocol,
-            nimiq_handel::config::Config::default(),
+            self.aggregation_tracker.config(),
             own_contribution,
             update_stream.map(|item| item.0).boxed(),
             network,
         )
+        .with_tracker(Arc::clone(&self.aggregation_tracker))
         .boxed()
     }
 
@@ -20,9 +20,10 @@ use nimiq_database::{
     traits::{Database, ReadTransaction, WriteTransaction},
     DatabaseProxy, TableProxy,
 };
+use nimiq_handel::{config::Config as HandelConfig, tracker::AggregationTracker};
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::{Address, KeyPair as SchnorrKeyPair};
-use nimiq_mempool::{config::MempoolConfig, mempool::Mempool};
+use nimiq_mempool::{block_building::BlockBuildingConfig, config::MempoolConfig, mempool::Mempool};
 use nimiq_network_interface::{
     network::{MsgAcceptance, Network, NetworkEvent, SubscribeEvents},
     request::request_handler,
@@ -37,6 +38,7 @@ use tokio_stream::wrappers::BroadcastStream;
 
 use crate::{
     aggregation::tendermint::{proposal::RequestProposal, state::MacroState},
+    block_building::BlockBuilder,
     jail::EquivocationProofPool,
     micro::{ProduceMicroBlock, ProduceMicroBlockEvent},
     proposal_buffer::{ProposalBuffer, ProposalReceiver},
@@ -119,6 +121,10 @@ where
     macro_state: Arc<RwLock<Option<MacroState>>>,
 
     micro_producer: Option<ProduceMicroBlock<TValidatorNetwork>>,
+    block_builder: Arc<BlockBuilder>,
+
+    skip_block_tracker: Arc<AggregationTracker>,
+    tendermint_tracker: Arc<AggregationTracker>,
 
     pub mempool: Arc<Mempool>,
     mempool_active: bool,
@@ -149,6 +155,7 @@ where
         voting_key: BlsKeyPair,
         fee_key: SchnorrKeyPair,
         mempool_config: MempoolConfig,
+        block_building: BlockBuildingConfig,
     ) -> Self {
         let consensus_event_rx = consensus.subscribe_events();
 
@@ -181,6 +188,7 @@ where
 
         let mempool = Arc::new(Mempool::new(Arc::clone(&blockchain), mempool_config));
         let mempool_active = false;
+        let block_builder = Arc::new(BlockBuilder::new(&block_building));
 
         let automatic_reactivate = Arc::new(AtomicBool::new(automatic_reactivate));
 
@@ -225,6 +233,16 @@ where
             macro_state: Arc::clone(&macro_state),
 
             micro_producer: None,
+            block_builder,
+
+            skip_block_tracker: Arc::new(AggregationTracker::new(
+                "skip_block",
+                HandelConfig::default(),
+            )),
+            tendermint_tracker: Arc::new(AggregationTracker::new(
+                "tendermint",
+                HandelConfig::default(),
+            )),
 
             mempool: Arc::clone(&mempool),
             mempool_active,
@@ -357,6 +375,7 @@ where
                     next_block_number,
                     self.macro_state.read().clone(),
                     proposal_stream,
+                    Arc::clone(&self.tendermint_tracker),
                 ));
             }
             BlockType::Micro => {
@@ -372,6 +391,7 @@ where
                 self.micro_producer = Some(ProduceMicroBlock::new(
                     Arc::clone(&self.blockchain),
                     Arc::clone(&self.mempool),
+                    Arc::clone(&self.block_builder),
                     Arc::clone(&self.network),
                     block_producer,
                     self.validator_slot_band(),
@@ -380,6 +400,7 @@ where
                     next_block_number,
                     Self::PRODUCER_TIMEOUT,
                     Self::BLOCK_SEPARATION_TIME,
+                    Arc::clone(&self.skip_block_tracker),
                 ));
             }
         }
@@ -823,6 +844,14 @@ where
         }
     }
 
+    /// Returns the trackers of the skip block and macro block vote aggregations.
+    pub fn aggregation_trackers(&self) -> Vec<Arc<AggregationTracker>> {
+        vec![
+            Arc::clone(&self.skip_block_tracker),
+            Arc::clone(&self.tendermint_tracker),
+        ]
+    }
+
     #[cfg(feature = "metrics")]
     pub fn get_mempool_monitor(&self) -> TaskMonitor {
         self.mempool_monitor.clone()
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_handel::{config::Config, tracker::AggregationTracker};
 use nimiq_network_libp2p::Network;
 use nimiq_network_mock::MockHub;
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
@@ -125,6 +126,7 @@ async fn it_verifies_inferior_chain_proposals() {
         0,
         NetworkId::UnitAlbatross,
         blockchain2.read().head().block_number() + 1,
+        Arc::new(AggregationTracker::new("tendermint", Config::default())),
     );
 
     // Make sure the main chain proposal is acceptable.
@@ -0,0 +1,201 @@
+use std::io::Write;
+
+use clear_on_drop::clear::Clear;
+use nimiq_hash::{
+    argon2kdf::{compute_argon2_kdf_with_memory_cost, Argon2Error},
+    Blake2bHash, Blake2bHasher, Hasher,
+};
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_utils::otp::{Algorithm, Verify};
+use rand::{rngs::OsRng, RngCore};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+use crate::wallet_account::WalletAccount;
+
+/// The parameters of the key derivation function used to encrypt a backup.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KdfParams {
+    pub algorithm: Algorithm,
+    pub iterations: u32,
+    /// The memory cost in KiB.
+    pub memory_cost: u32,
+    pub salt: Vec<u8>,
+}
+
+impl KdfParams {
+    pub const DEFAULT_ITERATIONS: u32 = 3;
+    pub const DEFAULT_MEMORY_COST: u32 = 64 * 1024;
+    pub const SALT_LENGTH: usize = 32;
+    /// The maximum number of iterations accepted, as backups might come from untrusted sources.
+    pub const MAX_ITERATIONS: u32 = 16;
+    /// The maximum memory cost in KiB accepted, as backups might come from untrusted sources.
+    pub const MAX_MEMORY_COST: u32 = 1024 * 1024;
+
+    /// Returns Argon2id parameters with the given costs and a random salt.
+    pub fn new(iterations: u32, memory_cost: u32) -> Self {
+        let mut salt = vec![0; Self::SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+
+        Self {
+            algorithm: Algorithm::Argon2id,
+            iterations,
+            memory_cost,
+            salt,
+        }
+    }
+
+    fn derive_key(&self, password: &[u8], length: usize) -> Result<Vec<u8>, BackupError> {
+        // Check the costs before deriving anything, a crafted backup could otherwise make us
+        // allocate huge amounts of memory or spin for a very long time.
+        if self.iterations > Self::MAX_ITERATIONS || self.memory_cost > Self::MAX_MEMORY_COST {
+            return Err(BackupError::KdfParamsTooHigh {
+                iterations: self.iterations,
+                memory_cost: self.memory_cost,
+            });
+        }
+
+        Ok(compute_argon2_kdf_with_memory_cost(
+            password,
+            &self.salt,
+            self.iterations,
+            self.memory_cost,
+            length,
+            self.algorithm.into(),
+        )?)
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_ITERATIONS, Self::DEFAULT_MEMORY_COST)
+    }
+}
+
+/// An encrypted, versioned backup of wallet accounts that can be imported into another node.
+/// The key derivation parameters are recorded in the backup, such that they can be increased
+/// over time without breaking older backups.
+///
+/// The ciphertext is authenticated with a keyed Blake2b hash over the whole backup, such that
+/// tampering is detected before anything gets decrypted.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WalletBackup {
+    version: u8,
+    pub kdf: KdfParams,
+    ciphertext: Vec<u8>,
+    mac: Blake2bHash,
+}
+
+impl WalletBackup {
+    /// The current version of the backup format.
+    pub const VERSION: u8 = 1;
+
+    /// The length of the MAC key, which is derived together with the encryption key.
+    const MAC_KEY_LENGTH: usize = 32;
+
+    /// Encrypts the accounts with the password, using the default key derivation parameters.
+    pub fn create(accounts: &[WalletAccount], password: &[u8]) -> Result<Self, BackupError> {
+        Self::create_with_params(accounts, password, KdfParams::default())
+    }
+
+    /// Encrypts the accounts with the password, using the given key derivation parameters.
+    pub fn create_with_params(
+        accounts: &[WalletAccount],
+        password: &[u8],
+        kdf: KdfParams,
+    ) -> Result<Self, BackupError> {
+        let mut ciphertext = accounts.to_vec().serialize_to_vec();
+        let mut key = kdf.derive_key(password, Self::MAC_KEY_LENGTH + ciphertext.len())?;
+        let (mac_key, encryption_key) = key.split_at(Self::MAC_KEY_LENGTH);
+        xor(&mut ciphertext, encryption_key);
+        let mac = Self::compute_mac(mac_key, Self::VERSION, &kdf, &ciphertext);
+
+        // Always overwrite the key material.
+        clear(&mut key);
+
+        Ok(Self {
+            version: Self::VERSION,
+            kdf,
+            ciphertext,
+            mac,
+        })
+    }
+
+    /// Decrypts the accounts of the backup.
+    pub fn open(&self, password: &[u8]) -> Result<Vec<WalletAccount>, BackupError> {
+        let mut key = self
+            .kdf
+            .derive_key(password, Self::MAC_KEY_LENGTH + self.ciphertext.len())?;
+        let (mac_key, encryption_key) = key.split_at(Self::MAC_KEY_LENGTH);
+
+        let mac = Self::compute_mac(mac_key, self.version, &self.kdf, &self.ciphertext);
+        if !bool::from(mac.as_bytes().ct_eq(self.mac.as_bytes())) {
+            clear(&mut key);
+            return Err(BackupError::WrongPassword);
+        }
+
+        let mut plaintext = self.ciphertext.clone();
+        xor(&mut plaintext, encryption_key);
+
+        let accounts = Vec::<WalletAccount>::deserialize_from_vec(&plaintext);
+
+        // Always overwrite the key material and the decrypted data.
+        clear(&mut key);
+        clear(&mut plaintext);
+
+        match accounts {
+            Ok(accounts) if accounts.iter().all(Verify::verify) => Ok(accounts),
+            _ => Err(BackupError::WrongPassword),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BackupError> {
+        // Check the version first, later versions might not be deserializable.
+        match bytes.first() {
+            Some(&Self::VERSION) => Ok(Self::deserialize_from_vec(bytes)?),
+            Some(&version) => Err(BackupError::UnsupportedVersion(version)),
+            None => Err(BackupError::UnsupportedVersion(0)),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.serialize_to_vec()
+    }
+
+    fn compute_mac(mac_key: &[u8], version: u8, kdf: &KdfParams, ciphertext: &[u8]) -> Blake2bHash {
+        let mut hasher = Blake2bHasher::with_key(mac_key);
+        hasher.write_all(&[version]).unwrap();
+        hasher.write_all(&kdf.serialize_to_vec()).unwrap();
+        hasher
+            .write_all(&ciphertext.to_vec().serialize_to_vec())
+            .unwrap();
+        hasher.finish()
+    }
+}
+
+fn clear(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        byte.clear();
+    }
+}
+
+fn xor(data: &mut [u8], key: &[u8]) {
+    for (data_byte, key_byte) in data.iter_mut().zip(key.iter()) {
+        *data_byte ^= key_byte;
+    }
+}
+
+/// Possible backup errors.
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("Unsupported backup version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Invalid backup: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Key derivation failed: {0}")]
+    Kdf(#[from] Argon2Error),
+    #[error("Invalid backup: KDF costs too high ({iterations} iterations, {memory_cost} KiB)")]
+    KdfParamsTooHigh { iterations: u32, memory_cost: u32 },
+    #[error("Wrong password or corrupted backup")]
+    WrongPassword,
+}
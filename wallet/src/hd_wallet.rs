@@ -0,0 +1,138 @@
+use std::{fmt, io, str::FromStr};
+
+use clear_on_drop::clear::InitializableFromZeroed;
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+use nimiq_key_derivation::ExtendedPrivateKey;
+use nimiq_keys::{Address, KeyPair};
+use nimiq_mnemonic::{Mnemonic, MnemonicType, WORDLIST_EN};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_utils::otp::Verify;
+use thiserror::Error;
+
+use crate::wallet_account::WalletAccount;
+
+/// The BIP44 path of the first Nimiq account (coin type 242). Accounts are derived by appending
+/// a hardened index to this path.
+pub const DEFAULT_ACCOUNT_PATH: &str = "m/44'/242'/0'";
+
+/// Returns the derivation path of the account with the given index below `DEFAULT_ACCOUNT_PATH`.
+pub fn account_path(index: u32) -> String {
+    format!("{DEFAULT_ACCOUNT_PATH}/{index}'")
+}
+
+/// The seed of a hierarchical deterministic wallet. It is stored encrypted in the wallet store
+/// and identified by the address of its master key.
+#[derive(Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct HdSeed {
+    seed: Vec<u8>,
+    id: Address,
+}
+
+impl fmt::Debug for HdSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HdSeed")
+            .field("seed", &"***")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+// Locking clears the seed from memory and needs a value to put in its place. Unlike `Default`,
+// this doesn't allow creating an empty seed outside of this crate.
+unsafe impl InitializableFromZeroed for HdSeed {
+    unsafe fn initialize(place: *mut Self) {
+        place.write(HdSeed {
+            seed: vec![],
+            id: Address::default(),
+        });
+    }
+}
+
+impl Verify for HdSeed {
+    fn verify(&self) -> bool {
+        // Check that the seed corresponds to the identifier.
+        ExtendedPrivateKey::from_seed(self.seed.clone()).to_address() == self.id
+    }
+}
+
+impl HdSeed {
+    /// Returns the seed for a 24 words BIP39 mnemonic, optionally protected by a password.
+    pub fn from_mnemonic(mnemonic: &str, password: Option<&str>) -> Result<Self, HdWalletError> {
+        let words: Vec<_> = mnemonic.split_whitespace().collect();
+        let mnemonic =
+            Mnemonic::from_str(&words.join(" ")).map_err(|_| HdWalletError::InvalidMnemonic)?;
+
+        match mnemonic.get_type(WORDLIST_EN) {
+            MnemonicType::BIP39 | MnemonicType::UNKNOWN => {}
+            MnemonicType::LEGACY | MnemonicType::INVALID => {
+                return Err(HdWalletError::InvalidMnemonic)
+            }
+        }
+
+        let seed = mnemonic
+            .to_seed(password)
+            .map_err(|_| HdWalletError::SeedDerivation)?;
+        Ok(Self::from_seed(seed))
+    }
+
+    pub fn from_seed(seed: Vec<u8>) -> Self {
+        let id = ExtendedPrivateKey::from_seed(seed.clone()).to_address();
+        Self { seed, id }
+    }
+
+    /// The identifier of this seed, i.e. the address of its master key.
+    pub fn id(&self) -> &Address {
+        &self.id
+    }
+
+    /// Derives the account at the given path, e.g. `m/44'/242'/0'/0'`.
+    pub fn derive(&self, path: &str) -> Result<WalletAccount, HdWalletError> {
+        let key = ExtendedPrivateKey::from_seed(self.seed.clone())
+            .derive_path(path)
+            .ok_or_else(|| HdWalletError::InvalidPath(path.to_string()))?;
+        Ok(WalletAccount::from(KeyPair::from(key.into_private_key())))
+    }
+}
+
+/// An account that has been derived from a stored seed. The key itself is stored as a regular
+/// (encrypted) `WalletAccount`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DerivedAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The identifier of the seed the account was derived from.
+    pub seed_id: Address,
+    /// The derivation path of the account.
+    pub path: String,
+}
+
+impl IntoDatabaseValue for DerivedAccount {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for DerivedAccount {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
+
+/// Possible HD wallet errors.
+#[derive(Debug, Error)]
+pub enum HdWalletError {
+    #[error("Invalid mnemonic, expected 24 words of the English BIP39 word list")]
+    InvalidMnemonic,
+    #[error("Failed to compute the seed of the mnemonic")]
+    SeedDerivation,
+    #[error("Invalid derivation path: {0}")]
+    InvalidPath(String),
+}
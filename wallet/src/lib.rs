@@ -1,9 +1,21 @@
-pub use multisig_account::MultiSigAccount;
+pub use backup::{BackupError, KdfParams, WalletBackup};
+pub use hd_wallet::{account_path, DerivedAccount, HdSeed, HdWalletError, DEFAULT_ACCOUNT_PATH};
+pub use multisig_account::{MultiSigAccount, MultiSigAccountError, MultiSigAccountInfo};
+pub use partially_signed_transaction::{
+    MultiSigSigners, PartiallySignedTransaction, PstError, PstSigner,
+};
+pub use scheduled_transaction::{
+    ScheduleState, ScheduleTrigger, ScheduledTransaction, TransactionIntent,
+};
 pub use wallet_account::WalletAccount;
 #[cfg(feature = "store")]
 pub use wallet_store::WalletStore;
 
+mod backup;
+mod hd_wallet;
 mod multisig_account;
+mod partially_signed_transaction;
+mod scheduled_transaction;
 mod wallet_account;
 #[cfg(feature = "store")]
 mod wallet_store;
@@ -1,5 +1,6 @@
-use std::num::NonZeroU8;
+use std::{io, num::NonZeroU8};
 
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
 use nimiq_hash::Blake2bHasher;
 use nimiq_keys::{
     multisig::{
@@ -13,11 +14,13 @@ use nimiq_keys::{
     Address, Ed25519PublicKey, KeyPair, PublicKey, SecureGenerate, Signature,
 };
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
-use nimiq_serde::Serialize;
+use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{SignatureProof, Transaction};
 use nimiq_utils::merkle::Blake2bMerklePath;
 use thiserror::Error;
 
+use crate::partially_signed_transaction::{PartiallySignedTransaction, PstError};
+
 /// A multi-signature account is an account that requires multiple signatures to authorize outgoing transactions.
 #[derive(Debug)]
 pub struct MultiSigAccount {
@@ -169,6 +172,103 @@ impl MultiSigAccount {
     }
 }
 
+/// The public description of a multi-signature account that is kept in the wallet store.
+/// It doesn't contain any secret: The key of the local participant is stored as a regular
+/// `WalletAccount` under the address of the `owner` public key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSigAccountInfo {
+    /// The address of the multi-signature account.
+    pub address: Address,
+    /// The public key of the local participant.
+    pub owner: Ed25519PublicKey,
+    /// Minimum number of required signatures.
+    pub min_signatures: NonZeroU8,
+    /// The sorted public keys of all participants.
+    pub public_keys: Vec<Ed25519PublicKey>,
+}
+
+impl MultiSigAccountInfo {
+    /// Returns the description of the k-of-n multi-signature account formed by the `public_keys`,
+    /// in which `owner` is the public key of the local participant.
+    pub fn new(
+        owner: Ed25519PublicKey,
+        min_signatures: NonZeroU8,
+        public_keys: &[Ed25519PublicKey],
+    ) -> Result<Self, MultiSigAccountError> {
+        if public_keys.is_empty() {
+            return Err(MultiSigAccountError::PublicKeysNotEmpty);
+        } else if !public_keys.contains(&owner) {
+            return Err(MultiSigAccountError::KeyPairNotPartOfList);
+        }
+
+        let mut public_keys = public_keys.to_vec();
+        public_keys.sort();
+        public_keys.dedup();
+
+        if min_signatures.get() as usize > public_keys.len() {
+            return Err(MultiSigAccountError::InvalidMinSignatures);
+        }
+
+        let multi_sig_keys =
+            combine_public_keys(public_keys.clone(), min_signatures.get() as usize);
+
+        Ok(Self {
+            address: compute_address(&multi_sig_keys),
+            owner,
+            min_signatures,
+            public_keys,
+        })
+    }
+
+    /// Returns the address of the wallet account holding the key of the local participant.
+    pub fn owner_address(&self) -> Address {
+        Address::from(&self.owner)
+    }
+
+    /// Returns the multi-signature account for the key pair of the local participant.
+    pub fn to_account(&self, key_pair: &KeyPair) -> Result<MultiSigAccount, MultiSigAccountError> {
+        if key_pair.public != self.owner {
+            return Err(MultiSigAccountError::KeyPairNotPartOfList);
+        }
+        MultiSigAccount::from_public_keys(key_pair, self.min_signatures, &self.public_keys)
+    }
+
+    /// Creates a partially signed transaction for a transaction sent from this account, that is
+    /// going to be signed by the given `signers`.
+    pub fn create_pst(
+        &self,
+        transaction: Transaction,
+        signers: &[Ed25519PublicKey],
+    ) -> Result<PartiallySignedTransaction, PstError> {
+        PartiallySignedTransaction::new_multisig(
+            transaction,
+            self.min_signatures,
+            &self.public_keys,
+            signers,
+        )
+    }
+}
+
+impl IntoDatabaseValue for MultiSigAccountInfo {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for MultiSigAccountInfo {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
+
 /// Possible multi-sig account errors.
 #[derive(Debug, Error)]
 pub enum MultiSigAccountError {
@@ -178,6 +278,8 @@ pub enum MultiSigAccountError {
     InvalidSignatureFromBytes(#[from] nimiq_keys::SignatureError),
     #[error("Number of signatures must be the same as the minimal signatures")]
     InvalidSignaturesLength,
+    #[error("The minimal number of signatures must not exceed the number of public keys")]
+    InvalidMinSignatures,
     #[error("The public key of keypair must be part of provided public keys")]
     KeyPairNotPartOfList,
     #[error("The provided public keys must not be empty")]
@@ -0,0 +1,463 @@
+use std::{collections::BTreeMap, num::NonZeroU8};
+
+use nimiq_hash::Blake2bHasher;
+use nimiq_keys::{
+    multisig::{
+        address::{combine_public_keys, compute_address},
+        commitment::{Commitment, CommitmentPair},
+        error::PartialSignatureError,
+        partial_signature::PartialSignature,
+        CommitmentsBuilder, CommitmentsData, MUSIG2_PARAMETER_V,
+    },
+    Ed25519PublicKey, KeyPair, PublicKey, SecureGenerate, Signature,
+};
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_transaction::{SignatureProof, Transaction, WebauthnExtraFields};
+use nimiq_utils::merkle::Blake2bMerklePath;
+use thiserror::Error;
+
+/// A transaction together with everything collected so far to sign it.
+///
+/// Partially signed transactions can be passed between the parties involved in signing, e.g.
+/// between an online node and an air-gapped signer, or between the owners of a multi-signature
+/// account. Every party adds its contribution, the results are combined and the transaction is
+/// finalized once all signatures are present.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartiallySignedTransaction {
+    /// The version of the format, see [`PartiallySignedTransaction::VERSION`].
+    pub version: u8,
+    /// The transaction to be signed. Its proof is ignored.
+    pub transaction: Transaction,
+    /// The signers of the transaction and their contributions.
+    pub signer: PstSigner,
+}
+
+/// The signers of a partially signed transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PstSigner {
+    /// The transaction is signed by a single key, which might be held by a webauthn authenticator.
+    Single {
+        public_key: PublicKey,
+        signature: Option<Signature>,
+        webauthn_fields: Option<WebauthnExtraFields>,
+    },
+    /// The transaction is signed by a subset of the owners of a multi-signature account using MuSig2.
+    MultiSig(MultiSigSigners),
+}
+
+/// The owners of a multi-signature account and the contributions of the ones signing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSigSigners {
+    /// Minimum number of required signatures.
+    pub min_signatures: NonZeroU8,
+    /// The public keys of all owners of the account.
+    pub public_keys: Vec<Ed25519PublicKey>,
+    /// The public keys of the owners signing the transaction.
+    pub signers: Vec<Ed25519PublicKey>,
+    /// The MuSig2 commitments of the signers, collected in the first round.
+    pub commitments: BTreeMap<Ed25519PublicKey, [Commitment; MUSIG2_PARAMETER_V]>,
+    /// The partial signatures of the signers, collected in the second round.
+    pub partial_signatures: BTreeMap<Ed25519PublicKey, PartialSignature>,
+}
+
+impl MultiSigSigners {
+    /// Returns the signers that didn't provide their commitments yet.
+    pub fn missing_commitments(&self) -> Vec<Ed25519PublicKey> {
+        self.signers
+            .iter()
+            .filter(|signer| !self.commitments.contains_key(signer))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the signers that didn't provide their partial signature yet.
+    pub fn missing_partial_signatures(&self) -> Vec<Ed25519PublicKey> {
+        self.signers
+            .iter()
+            .filter(|signer| !self.partial_signatures.contains_key(signer))
+            .copied()
+            .collect()
+    }
+
+    /// Builds the commitments data from the point of view of `own_public_key`.
+    /// All commitments must have been collected.
+    fn commitments_builder(
+        &self,
+        own_public_key: &Ed25519PublicKey,
+        own_commitments: Option<[CommitmentPair; MUSIG2_PARAMETER_V]>,
+    ) -> Result<CommitmentsBuilder, PstError> {
+        if !self.missing_commitments().is_empty() {
+            return Err(PstError::MissingCommitments);
+        }
+
+        let mut builder = match own_commitments {
+            Some(pairs) => CommitmentsBuilder::with_private_commitments(*own_public_key, pairs),
+            None => CommitmentsBuilder::with_public_commitments(
+                *own_public_key,
+                self.commitments[own_public_key],
+            ),
+        };
+        for (public_key, commitments) in &self.commitments {
+            if public_key != own_public_key {
+                builder.push_signer(*public_key, *commitments);
+            }
+        }
+        Ok(builder)
+    }
+
+    fn commitments_data(
+        &self,
+        own_public_key: &Ed25519PublicKey,
+        content: &[u8],
+    ) -> Result<CommitmentsData, PstError> {
+        Ok(self
+            .commitments_builder(own_public_key, None)?
+            .build(content))
+    }
+}
+
+impl PartiallySignedTransaction {
+    /// The current version of the format.
+    pub const VERSION: u8 = 1;
+
+    /// Creates a partially signed transaction to be signed by a single key.
+    pub fn new(transaction: Transaction, public_key: PublicKey) -> Self {
+        Self {
+            version: Self::VERSION,
+            transaction,
+            signer: PstSigner::Single {
+                public_key,
+                signature: None,
+                webauthn_fields: None,
+            },
+        }
+    }
+
+    /// Creates a partially signed transaction for a multi-signature account.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_signatures` - Number of signatures required.
+    /// * `public_keys` - The public keys of all owners of the account.
+    /// * `signers` - The public keys of the owners signing the transaction.
+    pub fn new_multisig(
+        transaction: Transaction,
+        min_signatures: NonZeroU8,
+        public_keys: &[Ed25519PublicKey],
+        signers: &[Ed25519PublicKey],
+    ) -> Result<Self, PstError> {
+        let mut public_keys = public_keys.to_vec();
+        public_keys.sort();
+        public_keys.dedup();
+        let mut signers = signers.to_vec();
+        signers.sort();
+        signers.dedup();
+
+        if signers.len() != min_signatures.get() as usize {
+            return Err(PstError::InvalidSignersLength);
+        }
+        if let Some(signer) = signers.iter().find(|signer| !public_keys.contains(signer)) {
+            return Err(PstError::UnknownSigner(*signer));
+        }
+
+        let multisig_keys = combine_public_keys(public_keys.clone(), min_signatures.get() as usize);
+        if compute_address(&multisig_keys) != transaction.sender {
+            return Err(PstError::SenderMismatch);
+        }
+
+        Ok(Self {
+            version: Self::VERSION,
+            transaction,
+            signer: PstSigner::MultiSig(MultiSigSigners {
+                min_signatures,
+                public_keys,
+                signers,
+                commitments: BTreeMap::new(),
+                partial_signatures: BTreeMap::new(),
+            }),
+        })
+    }
+
+    /// Deserializes a partially signed transaction and checks that its version is supported.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PstError> {
+        // The version is checked first, since later versions might not be deserializable.
+        match bytes.first() {
+            Some(&version) if version != Self::VERSION => {
+                Err(PstError::UnsupportedVersion(version))
+            }
+            _ => Ok(Deserialize::deserialize_from_vec(bytes)?),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.serialize_to_vec()
+    }
+
+    /// Adds the signature of the single signer, optionally created by a webauthn authenticator.
+    pub fn add_signature(
+        &mut self,
+        new_signature: Signature,
+        new_webauthn_fields: Option<WebauthnExtraFields>,
+    ) -> Result<(), PstError> {
+        match &mut self.signer {
+            PstSigner::Single {
+                signature,
+                webauthn_fields,
+                ..
+            } => {
+                *signature = Some(new_signature);
+                *webauthn_fields = new_webauthn_fields;
+                Ok(())
+            }
+            PstSigner::MultiSig(_) => Err(PstError::NotSingleSig),
+        }
+    }
+
+    /// Signs the transaction with the key of the single signer.
+    pub fn sign(&mut self, key_pair: &KeyPair) -> Result<(), PstError> {
+        match &self.signer {
+            PstSigner::Single {
+                public_key: PublicKey::Ed25519(public_key),
+                ..
+            } if *public_key == key_pair.public => {}
+            PstSigner::Single { .. } => return Err(PstError::UnknownSigner(key_pair.public)),
+            PstSigner::MultiSig(_) => return Err(PstError::NotSingleSig),
+        }
+
+        let signature = key_pair.sign(&self.transaction.serialize_content());
+        self.add_signature(Signature::Ed25519(signature), None)
+    }
+
+    /// Adds the MuSig2 commitments of a signer of a multi-signature account.
+    pub fn add_commitments(
+        &mut self,
+        public_key: Ed25519PublicKey,
+        commitments: [Commitment; MUSIG2_PARAMETER_V],
+    ) -> Result<(), PstError> {
+        let multisig = self.multisig_mut()?;
+        if !multisig.signers.contains(&public_key) {
+            return Err(PstError::UnknownSigner(public_key));
+        }
+        match multisig.commitments.get(&public_key) {
+            Some(existing) if *existing != commitments => {
+                Err(PstError::ConflictingCommitments(public_key))
+            }
+            _ => {
+                multisig.commitments.insert(public_key, commitments);
+                Ok(())
+            }
+        }
+    }
+
+    /// Generates the MuSig2 commitments of a signer and adds them. The returned commitment pairs
+    /// contain the secret nonces, which must be kept by the signer until the second round and must
+    /// never be used for more than one signature.
+    pub fn create_commitments(
+        &mut self,
+        public_key: Ed25519PublicKey,
+    ) -> Result<[CommitmentPair; MUSIG2_PARAMETER_V], PstError> {
+        let pairs: [CommitmentPair; MUSIG2_PARAMETER_V] = (0..MUSIG2_PARAMETER_V)
+            .map(|_| CommitmentPair::generate_default_csprng())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        self.add_commitments(public_key, CommitmentPair::to_commitments(&pairs))?;
+        Ok(pairs)
+    }
+
+    /// Creates the partial signature of a signer of a multi-signature account, using the
+    /// commitment pairs it generated in the first round. All commitments must have been collected.
+    pub fn partially_sign(
+        &mut self,
+        key_pair: &KeyPair,
+        commitment_pairs: [CommitmentPair; MUSIG2_PARAMETER_V],
+    ) -> Result<(), PstError> {
+        let content = self.transaction.serialize_content();
+        let multisig = self.multisig_mut()?;
+        match multisig.commitments.get(&key_pair.public) {
+            Some(commitments)
+                if *commitments == CommitmentPair::to_commitments(&commitment_pairs) => {}
+            Some(_) => return Err(PstError::ConflictingCommitments(key_pair.public)),
+            None => return Err(PstError::UnknownSigner(key_pair.public)),
+        }
+
+        let commitments_data = multisig
+            .commitments_builder(&key_pair.public, Some(commitment_pairs))?
+            .build(&content);
+        let partial_signature = key_pair.partial_sign(&commitments_data, &content)?;
+        multisig
+            .partial_signatures
+            .insert(key_pair.public, partial_signature);
+        Ok(())
+    }
+
+    /// Merges the contributions collected in another copy of this partially signed transaction.
+    pub fn combine(&mut self, other: &PartiallySignedTransaction) -> Result<(), PstError> {
+        if self.transaction.serialize_content() != other.transaction.serialize_content() {
+            return Err(PstError::TransactionMismatch);
+        }
+
+        match (&mut self.signer, &other.signer) {
+            (
+                PstSigner::Single {
+                    public_key,
+                    signature,
+                    webauthn_fields,
+                },
+                PstSigner::Single {
+                    public_key: other_public_key,
+                    signature: other_signature,
+                    webauthn_fields: other_webauthn_fields,
+                },
+            ) if public_key == other_public_key => {
+                if let Some(other_signature) = other_signature {
+                    if signature.as_ref().is_some_and(|sig| sig != other_signature) {
+                        return Err(PstError::ConflictingSignatures);
+                    }
+                    *signature = Some(other_signature.clone());
+                    *webauthn_fields = other_webauthn_fields.clone();
+                }
+                Ok(())
+            }
+            (PstSigner::MultiSig(multisig), PstSigner::MultiSig(other_multisig))
+                if multisig.min_signatures == other_multisig.min_signatures
+                    && multisig.public_keys == other_multisig.public_keys
+                    && multisig.signers == other_multisig.signers =>
+            {
+                for (public_key, commitments) in &other_multisig.commitments {
+                    match multisig.commitments.get(public_key) {
+                        Some(existing) if existing != commitments => {
+                            return Err(PstError::ConflictingCommitments(*public_key));
+                        }
+                        _ => {
+                            multisig.commitments.insert(*public_key, *commitments);
+                        }
+                    }
+                }
+                for (public_key, partial_signature) in &other_multisig.partial_signatures {
+                    match multisig.partial_signatures.get(public_key) {
+                        Some(existing) if existing != partial_signature => {
+                            return Err(PstError::ConflictingSignatures);
+                        }
+                        _ => {
+                            multisig
+                                .partial_signatures
+                                .insert(*public_key, *partial_signature);
+                        }
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(PstError::SignerMismatch),
+        }
+    }
+
+    /// Returns whether all signatures required to finalize the transaction are present.
+    pub fn is_complete(&self) -> bool {
+        match &self.signer {
+            PstSigner::Single { signature, .. } => signature.is_some(),
+            PstSigner::MultiSig(multisig) => multisig.missing_partial_signatures().is_empty(),
+        }
+    }
+
+    /// Creates the signature proof from the collected signatures and returns the signed transaction.
+    pub fn finalize(&self) -> Result<Transaction, PstError> {
+        let content = self.transaction.serialize_content();
+
+        let proof = match &self.signer {
+            PstSigner::Single {
+                public_key,
+                signature,
+                webauthn_fields,
+            } => SignatureProof::from(
+                public_key.clone(),
+                signature.clone().ok_or(PstError::MissingSignatures)?,
+                webauthn_fields.clone(),
+            ),
+            PstSigner::MultiSig(multisig) => {
+                if !multisig.missing_partial_signatures().is_empty() {
+                    return Err(PstError::MissingSignatures);
+                }
+
+                let commitments_data = multisig.commitments_data(&multisig.signers[0], &content)?;
+                for (public_key, partial_signature) in &multisig.partial_signatures {
+                    let commitments_data = multisig.commitments_data(public_key, &content)?;
+                    if !public_key.verify_partial(&commitments_data, partial_signature, &content) {
+                        return Err(PstError::InvalidPartialSignature(*public_key));
+                    }
+                }
+
+                let aggregated_signature: PartialSignature =
+                    multisig.partial_signatures.values().sum();
+                let multisig_keys = combine_public_keys(
+                    multisig.public_keys.clone(),
+                    multisig.min_signatures.get() as usize,
+                );
+
+                SignatureProof {
+                    merkle_path: Blake2bMerklePath::new::<Blake2bHasher, _>(
+                        &multisig_keys,
+                        &commitments_data.aggregate_public_key,
+                    ),
+                    public_key: PublicKey::Ed25519(commitments_data.aggregate_public_key),
+                    signature: Signature::Ed25519(
+                        aggregated_signature.to_signature(&commitments_data.aggregate_commitment),
+                    ),
+                    webauthn_fields: None,
+                }
+            }
+        };
+
+        if !proof.verify(&content) {
+            return Err(PstError::InvalidSignature);
+        }
+
+        let mut transaction = self.transaction.clone();
+        transaction.proof = proof.serialize_to_vec();
+        Ok(transaction)
+    }
+
+    fn multisig_mut(&mut self) -> Result<&mut MultiSigSigners, PstError> {
+        match &mut self.signer {
+            PstSigner::MultiSig(multisig) => Ok(multisig),
+            PstSigner::Single { .. } => Err(PstError::NotMultiSig),
+        }
+    }
+}
+
+/// Possible errors when working with partially signed transactions.
+#[derive(Debug, Error)]
+pub enum PstError {
+    #[error("Unsupported partially signed transaction version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Failed to deserialize partially signed transaction: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("The transaction is not signed by a single key")]
+    NotSingleSig,
+    #[error("The transaction is not signed by a multi-signature account")]
+    NotMultiSig,
+    #[error("Number of signers must be the same as the minimal signatures")]
+    InvalidSignersLength,
+    #[error("The transaction sender doesn't match the multi-signature account")]
+    SenderMismatch,
+    #[error("{0} is not a signer of the transaction")]
+    UnknownSigner(Ed25519PublicKey),
+    #[error("Conflicting commitments for signer {0}")]
+    ConflictingCommitments(Ed25519PublicKey),
+    #[error("Conflicting signatures")]
+    ConflictingSignatures,
+    #[error("Partially signed transactions are for different transactions")]
+    TransactionMismatch,
+    #[error("Partially signed transactions have different signers")]
+    SignerMismatch,
+    #[error("Not all commitments have been collected")]
+    MissingCommitments,
+    #[error("Not all signatures have been collected")]
+    MissingSignatures,
+    #[error("Invalid partial signature of signer {0}")]
+    InvalidPartialSignature(Ed25519PublicKey),
+    #[error("The collected signatures don't form a valid signature")]
+    InvalidSignature,
+    #[error("Failed to create partial signature: {0}")]
+    PartialSignature(#[from] PartialSignatureError),
+}
@@ -0,0 +1,129 @@
+use std::io;
+
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::Transaction;
+
+/// The condition that has to be met before a scheduled transaction is submitted.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum ScheduleTrigger {
+    /// Due once the head of the chain reached the given block number.
+    BlockNumber(u32),
+    /// Due once the timestamp of the head block reached the given time (in milliseconds).
+    Timestamp(u64),
+    /// Due once the timeout of the HTLC contract at the given address has been reached.
+    HtlcTimeout(Address),
+    /// Due once the given value can be withdrawn from the vesting contract at the given address.
+    VestingUnlocked { contract: Address, value: Coin },
+}
+
+/// The transaction to submit once a schedule is due. Transactions that are not signed in
+/// advance are built and signed at submission time, which requires the wallet to be unlocked.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum TransactionIntent {
+    /// A transaction that has already been signed and is submitted as is.
+    Signed(Transaction),
+    /// A basic transaction from the given wallet.
+    Basic {
+        wallet: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+    },
+    /// A transaction redeeming the funds of an HTLC contract after its timeout.
+    RedeemHtlcTimeout {
+        wallet: Address,
+        contract: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+    },
+    /// A transaction redeeming funds from a vesting contract.
+    RedeemVesting {
+        wallet: Address,
+        contract: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+    },
+}
+
+impl TransactionIntent {
+    /// The wallet that signs the transaction at submission time, if it is not signed yet.
+    pub fn wallet(&self) -> Option<&Address> {
+        match self {
+            TransactionIntent::Signed(_) => None,
+            TransactionIntent::Basic { wallet, .. }
+            | TransactionIntent::RedeemHtlcTimeout { wallet, .. }
+            | TransactionIntent::RedeemVesting { wallet, .. } => Some(wallet),
+        }
+    }
+}
+
+/// The lifecycle state of a scheduled transaction.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum ScheduleState {
+    /// Waiting for the trigger.
+    Scheduled,
+    /// The transaction with the given hash has been submitted at the given block number.
+    Submitted {
+        hash: Blake2bHash,
+        block_number: u32,
+    },
+    /// The transaction could not be submitted and will not be retried.
+    Failed(String),
+    /// The schedule has been cancelled before it was due.
+    Cancelled,
+}
+
+/// A transaction intent together with its trigger, as persisted in the wallet store.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ScheduledTransaction {
+    /// The identifier of the schedule, unique within the wallet store.
+    pub id: u32,
+    pub trigger: ScheduleTrigger,
+    pub intent: TransactionIntent,
+    pub state: ScheduleState,
+    /// The reason why the last submission attempt was postponed, e.g. a locked wallet.
+    pub last_error: Option<String>,
+}
+
+impl ScheduledTransaction {
+    pub fn new(id: u32, trigger: ScheduleTrigger, intent: TransactionIntent) -> Self {
+        ScheduledTransaction {
+            id,
+            trigger,
+            intent,
+            state: ScheduleState::Scheduled,
+            last_error: None,
+        }
+    }
+
+    /// Returns whether the transaction is still waiting to be submitted.
+    pub fn is_pending(&self) -> bool {
+        self.state == ScheduleState::Scheduled
+    }
+}
+
+impl IntoDatabaseValue for ScheduledTransaction {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for ScheduledTransaction {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
@@ -1,24 +1,49 @@
 use nimiq_database::{
     traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
-    DatabaseProxy, TableProxy, TransactionProxy, WriteTransactionProxy,
+    DatabaseProxy, TableFlags, TableProxy, TransactionProxy, WriteTransactionProxy,
 };
 use nimiq_keys::Address;
 use nimiq_utils::otp::Locked;
 
-use crate::wallet_account::WalletAccount;
+use crate::{
+    hd_wallet::{DerivedAccount, HdSeed},
+    multisig_account::MultiSigAccountInfo,
+    scheduled_transaction::ScheduledTransaction,
+    wallet_account::WalletAccount,
+};
 
 #[derive(Debug)]
 pub struct WalletStore {
     env: DatabaseProxy,
     wallet_db: TableProxy,
+    multisig_db: TableProxy,
+    seed_db: TableProxy,
+    derived_db: TableProxy,
+    scheduled_db: TableProxy,
 }
 
 impl WalletStore {
     const WALLET_DB_NAME: &'static str = "Wallet";
+    const MULTISIG_DB_NAME: &'static str = "MultiSigWallet";
+    const SEED_DB_NAME: &'static str = "HdSeed";
+    const DERIVED_DB_NAME: &'static str = "HdAccount";
+    const SCHEDULED_DB_NAME: &'static str = "ScheduledTransaction";
 
     pub fn new(env: DatabaseProxy) -> Self {
         let wallet_db = env.open_table(Self::WALLET_DB_NAME.to_string());
-        WalletStore { env, wallet_db }
+        let multisig_db = env.open_table(Self::MULTISIG_DB_NAME.to_string());
+        let seed_db = env.open_table(Self::SEED_DB_NAME.to_string());
+        let derived_db = env.open_table(Self::DERIVED_DB_NAME.to_string());
+        let scheduled_db =
+            env.open_table_with_flags(Self::SCHEDULED_DB_NAME.to_string(), TableFlags::UINT_KEYS);
+        WalletStore {
+            env,
+            wallet_db,
+            multisig_db,
+            seed_db,
+            derived_db,
+            scheduled_db,
+        }
     }
 
     pub fn create_read_transaction(&self) -> TransactionProxy {
@@ -65,4 +90,156 @@ impl WalletStore {
     ) {
         txn.put_reserve(&self.wallet_db, address, wallet);
     }
+
+    /// Returns the addresses of all registered multi-signature accounts.
+    pub fn list_multisig(&self, txn_option: Option<&TransactionProxy>) -> Vec<Address> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        let cursor = txn.cursor(&self.multisig_db);
+        cursor
+            .into_iter_start::<_, MultiSigAccountInfo>()
+            .map(|(address, _)| address)
+            .collect()
+    }
+
+    pub fn get_multisig(
+        &self,
+        address: &Address,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<MultiSigAccountInfo> {
+        match txn_option {
+            Some(txn) => txn.get(&self.multisig_db, address),
+            None => self.env.read_transaction().get(&self.multisig_db, address),
+        }
+    }
+
+    pub fn put_multisig(&self, account: &MultiSigAccountInfo, txn: &mut WriteTransactionProxy) {
+        txn.put_reserve(&self.multisig_db, &account.address, account);
+    }
+
+    pub fn remove_multisig(&self, address: &Address, txn: &mut WriteTransactionProxy) {
+        txn.remove(&self.multisig_db, address);
+    }
+
+    /// Returns the identifiers of all stored seeds.
+    pub fn list_seeds(&self, txn_option: Option<&TransactionProxy>) -> Vec<Address> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        let cursor = txn.cursor(&self.seed_db);
+        cursor
+            .into_iter_start::<_, Locked<HdSeed>>()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    pub fn get_seed(
+        &self,
+        id: &Address,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<Locked<HdSeed>> {
+        match txn_option {
+            Some(txn) => txn.get(&self.seed_db, id),
+            None => self.env.read_transaction().get(&self.seed_db, id),
+        }
+    }
+
+    pub fn put_seed(&self, id: &Address, seed: &Locked<HdSeed>, txn: &mut WriteTransactionProxy) {
+        txn.put_reserve(&self.seed_db, id, seed);
+    }
+
+    /// Returns the accounts derived from the given seed, or from any seed if `seed_id` is `None`.
+    pub fn list_derived(
+        &self,
+        seed_id: Option<&Address>,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<DerivedAccount> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        let cursor = txn.cursor(&self.derived_db);
+        cursor
+            .into_iter_start::<Address, DerivedAccount>()
+            .map(|(_, account)| account)
+            .filter(|account| seed_id.map_or(true, |id| &account.seed_id == id))
+            .collect()
+    }
+
+    pub fn get_derived(
+        &self,
+        address: &Address,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<DerivedAccount> {
+        match txn_option {
+            Some(txn) => txn.get(&self.derived_db, address),
+            None => self.env.read_transaction().get(&self.derived_db, address),
+        }
+    }
+
+    pub fn put_derived(&self, account: &DerivedAccount, txn: &mut WriteTransactionProxy) {
+        txn.put_reserve(&self.derived_db, &account.address, account);
+    }
+
+    /// Returns all scheduled transactions in the order they were scheduled.
+    pub fn list_scheduled(
+        &self,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<ScheduledTransaction> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        let cursor = txn.cursor(&self.scheduled_db);
+        cursor
+            .into_iter_start::<u32, ScheduledTransaction>()
+            .map(|(_, scheduled)| scheduled)
+            .collect()
+    }
+
+    pub fn get_scheduled(
+        &self,
+        id: u32,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<ScheduledTransaction> {
+        match txn_option {
+            Some(txn) => txn.get(&self.scheduled_db, &id),
+            None => self.env.read_transaction().get(&self.scheduled_db, &id),
+        }
+    }
+
+    /// Returns the identifier to use for the next scheduled transaction.
+    pub fn next_scheduled_id(&self, txn: &WriteTransactionProxy) -> u32 {
+        let mut cursor = txn.cursor(&self.scheduled_db);
+        cursor
+            .last::<u32, ScheduledTransaction>()
+            .map_or(0, |(id, _)| id + 1)
+    }
+
+    pub fn put_scheduled(&self, scheduled: &ScheduledTransaction, txn: &mut WriteTransactionProxy) {
+        txn.put_reserve(&self.scheduled_db, &scheduled.id, scheduled);
+    }
 }
@@ -0,0 +1,96 @@
+use nimiq_test_log::test;
+use nimiq_utils::otp::Algorithm;
+use nimiq_wallet::{BackupError, KdfParams, WalletAccount, WalletBackup};
+
+// Use cheap parameters to keep the tests fast.
+fn kdf_params() -> KdfParams {
+    KdfParams::new(1, 64)
+}
+
+#[test]
+fn it_can_create_and_open_backups() {
+    let accounts = vec![WalletAccount::generate(), WalletAccount::generate()];
+
+    let backup = WalletBackup::create_with_params(&accounts, b"password", kdf_params()).unwrap();
+    assert_eq!(backup.kdf.algorithm, Algorithm::Argon2id);
+    assert_eq!(backup.kdf.salt.len(), KdfParams::SALT_LENGTH);
+
+    let backup = WalletBackup::from_bytes(&backup.to_bytes()).unwrap();
+    assert_eq!(backup.open(b"password").unwrap(), accounts);
+    assert!(matches!(
+        backup.open(b"wrong password"),
+        Err(BackupError::WrongPassword)
+    ));
+}
+
+#[test]
+fn it_uses_a_random_salt() {
+    let accounts = vec![WalletAccount::generate()];
+
+    let backup_1 = WalletBackup::create_with_params(&accounts, b"password", kdf_params()).unwrap();
+    let backup_2 = WalletBackup::create_with_params(&accounts, b"password", kdf_params()).unwrap();
+    assert_ne!(backup_1.to_bytes(), backup_2.to_bytes());
+}
+
+#[test]
+fn it_rejects_unsupported_versions() {
+    let backup =
+        WalletBackup::create_with_params(&[WalletAccount::generate()], b"", kdf_params()).unwrap();
+
+    let mut bytes = backup.to_bytes();
+    bytes[0] = WalletBackup::VERSION + 1;
+    assert!(matches!(
+        WalletBackup::from_bytes(&bytes),
+        Err(BackupError::UnsupportedVersion(_))
+    ));
+}
+
+#[test]
+fn it_rejects_tampered_backups() {
+    let backup =
+        WalletBackup::create_with_params(&[WalletAccount::generate()], b"password", kdf_params())
+            .unwrap();
+    let bytes = backup.to_bytes();
+
+    // Flip a bit of the ciphertext, which is followed by the 32 byte MAC.
+    let mut tampered = bytes.clone();
+    let index = tampered.len() - 33;
+    tampered[index] ^= 1;
+    let tampered = WalletBackup::from_bytes(&tampered).unwrap();
+    assert!(matches!(
+        tampered.open(b"password"),
+        Err(BackupError::WrongPassword)
+    ));
+
+    // Flip a bit of the MAC.
+    let mut tampered = bytes;
+    *tampered.last_mut().unwrap() ^= 1;
+    let tampered = WalletBackup::from_bytes(&tampered).unwrap();
+    assert!(matches!(
+        tampered.open(b"password"),
+        Err(BackupError::WrongPassword)
+    ));
+}
+
+#[test]
+fn it_rejects_excessive_kdf_params() {
+    let accounts = vec![WalletAccount::generate()];
+
+    let mut backup =
+        WalletBackup::create_with_params(&accounts, b"password", kdf_params()).unwrap();
+    backup.kdf.memory_cost = KdfParams::MAX_MEMORY_COST + 1;
+    let backup = WalletBackup::from_bytes(&backup.to_bytes()).unwrap();
+    assert!(matches!(
+        backup.open(b"password"),
+        Err(BackupError::KdfParamsTooHigh { .. })
+    ));
+
+    assert!(matches!(
+        WalletBackup::create_with_params(
+            &accounts,
+            b"password",
+            KdfParams::new(KdfParams::MAX_ITERATIONS + 1, 64)
+        ),
+        Err(BackupError::KdfParamsTooHigh { .. })
+    ));
+}
@@ -0,0 +1,92 @@
+use nimiq_database::{traits::WriteTransaction, volatile::VolatileDatabase};
+use nimiq_key_derivation::ExtendedPrivateKey;
+use nimiq_test_log::test;
+use nimiq_utils::otp::Locked;
+use nimiq_wallet::{account_path, DerivedAccount, HdSeed, HdWalletError, WalletStore};
+
+// Test vector from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+const PASSWORD: &str = "TREZOR";
+const SEED: &str = "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8";
+
+#[test]
+fn it_derives_accounts_from_mnemonics() {
+    let seed = HdSeed::from_mnemonic(MNEMONIC, Some(PASSWORD)).unwrap();
+    assert_eq!(seed, HdSeed::from_seed(hex::decode(SEED).unwrap()));
+
+    let master_key = ExtendedPrivateKey::from_seed(hex::decode(SEED).unwrap());
+    assert_eq!(seed.id(), &master_key.to_address());
+
+    for index in 0..3 {
+        let path = account_path(index);
+        assert_eq!(path, format!("m/44'/242'/0'/{index}'"));
+
+        let account = seed.derive(&path).unwrap();
+        assert_eq!(
+            account.address,
+            master_key.derive_path(&path).unwrap().to_address()
+        );
+    }
+
+    assert!(matches!(
+        seed.derive("m/44/242"),
+        Err(HdWalletError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn it_does_not_reveal_seeds_in_debug_output() {
+    let seed_bytes = hex::decode(SEED).unwrap();
+    let seed = HdSeed::from_seed(seed_bytes.clone());
+    let debug = format!("{seed:?}");
+    assert!(!debug.contains(&format!("{:?}", &seed_bytes[..8]).trim_end_matches(']')));
+    assert!(debug.contains(&format!("{:?}", seed.id())));
+}
+
+#[test]
+fn it_rejects_invalid_mnemonics() {
+    assert!(matches!(
+        HdSeed::from_mnemonic("abandon abandon art", None),
+        Err(HdWalletError::InvalidMnemonic)
+    ));
+    assert!(matches!(
+        HdSeed::from_mnemonic(&MNEMONIC.replace("art", "abandon"), None),
+        Err(HdWalletError::InvalidMnemonic)
+    ));
+}
+
+#[test]
+fn it_stores_encrypted_seeds_and_derived_accounts() {
+    let store = WalletStore::new(VolatileDatabase::new(20).unwrap());
+    let seed = HdSeed::from_mnemonic(MNEMONIC, None).unwrap();
+    let id = seed.id().clone();
+    let path = account_path(0);
+    let derived = DerivedAccount {
+        address: seed.derive(&path).unwrap().address,
+        seed_id: id.clone(),
+        path,
+    };
+
+    let mut txn = store.create_write_transaction();
+    store.put_seed(
+        &id,
+        &Locked::with_defaults(seed.clone(), b"secret").unwrap(),
+        &mut txn,
+    );
+    store.put_derived(&derived, &mut txn);
+    txn.commit();
+
+    assert_eq!(store.list_seeds(None), vec![id.clone()]);
+    assert!(store.get_seed(&id, None).unwrap().unlock(b"wrong").is_err());
+    let unlocked = store
+        .get_seed(&id, None)
+        .unwrap()
+        .unlock(b"secret")
+        .unwrap_or_else(|_| panic!("Failed to unlock seed"));
+    assert_eq!(*unlocked, seed);
+
+    assert_eq!(store.list_derived(Some(&id), None), vec![derived.clone()]);
+    assert_eq!(store.list_derived(None, None), vec![derived.clone()]);
+    assert!(store.list_derived(Some(&derived.address), None).is_empty());
+    assert_eq!(store.get_derived(&derived.address, None), Some(derived));
+}
@@ -1,12 +1,13 @@
 use std::num::NonZeroU8;
 
 use hex::FromHex;
+use nimiq_database::{traits::WriteTransaction, volatile::VolatileDatabase};
 use nimiq_keys::{
     multisig::{commitment::CommitmentPair, CommitmentsBuilder},
     Address, KeyPair, PrivateKey,
 };
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
-use nimiq_wallet::MultiSigAccount;
+use nimiq_wallet::{MultiSigAccount, MultiSigAccountError, MultiSigAccountInfo, WalletStore};
 
 static PRIVATE_KEYS: &[&str] = &[
     "37f485f69a33e942b18b79602edb07481880d0b33a7d46adf693633bba7e85e0",
@@ -160,3 +161,61 @@ pub fn it_correctly_computes_merkle_root() {
         Address::from_any_str("4de9f6fe2e188b50eaef60f08322d455b65e51ea").unwrap()
     );
 }
+
+#[test]
+pub fn account_info_matches_account() {
+    let kp1 = KeyPair::from(PrivateKey::from_hex(PRIVATE_KEYS[0]).unwrap());
+    let kp2 = KeyPair::from(PrivateKey::from_hex(PRIVATE_KEYS[1]).unwrap());
+    let kp3 = KeyPair::from(PrivateKey::from_hex(PRIVATE_KEYS[2]).unwrap());
+    let public_keys = [kp3.public, kp1.public, kp2.public];
+    let min_signatures = NonZeroU8::new(2).unwrap();
+
+    let info = MultiSigAccountInfo::new(kp1.public, min_signatures, &public_keys).unwrap();
+    let account = MultiSigAccount::from_public_keys(&kp1, min_signatures, &public_keys).unwrap();
+
+    assert_eq!(info.address, account.address);
+    assert_eq!(info.owner_address(), Address::from(&kp1));
+    assert_eq!(info.to_account(&kp1).unwrap().address, account.address);
+    assert!(matches!(
+        info.to_account(&kp2),
+        Err(MultiSigAccountError::KeyPairNotPartOfList)
+    ));
+
+    assert!(matches!(
+        MultiSigAccountInfo::new(kp1.public, NonZeroU8::new(4).unwrap(), &public_keys),
+        Err(MultiSigAccountError::InvalidMinSignatures)
+    ));
+    assert!(matches!(
+        MultiSigAccountInfo::new(kp1.public, min_signatures, &[kp2.public, kp3.public]),
+        Err(MultiSigAccountError::KeyPairNotPartOfList)
+    ));
+}
+
+#[test]
+pub fn it_can_store_account_infos() {
+    let kp1 = KeyPair::from(PrivateKey::from_hex(PRIVATE_KEYS[0]).unwrap());
+    let kp2 = KeyPair::from(PrivateKey::from_hex(PRIVATE_KEYS[1]).unwrap());
+
+    let info = MultiSigAccountInfo::new(
+        kp1.public,
+        NonZeroU8::new(1).unwrap(),
+        &[kp1.public, kp2.public],
+    )
+    .unwrap();
+
+    let store = WalletStore::new(VolatileDatabase::new(20).unwrap());
+    let mut txn = store.create_write_transaction();
+    store.put_multisig(&info, &mut txn);
+    txn.commit();
+
+    assert_eq!(store.list_multisig(None), vec![info.address.clone()]);
+    assert_eq!(store.get_multisig(&info.address, None), Some(info.clone()));
+    assert!(store.list(None).is_empty());
+
+    let mut txn = store.create_write_transaction();
+    store.remove_multisig(&info.address, &mut txn);
+    txn.commit();
+
+    assert!(store.list_multisig(None).is_empty());
+    assert_eq!(store.get_multisig(&info.address, None), None);
+}
@@ -0,0 +1,207 @@
+use std::num::NonZeroU8;
+
+use hex::FromHex;
+use nimiq_keys::{Address, KeyPair, PrivateKey, PublicKey};
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_transaction::Transaction;
+use nimiq_wallet::{MultiSigAccount, PartiallySignedTransaction, PstError, WalletAccount};
+
+static PRIVATE_KEYS: &[&str] = &[
+    "37f485f69a33e942b18b79602edb07481880d0b33a7d46adf693633bba7e85e0",
+    "fb7789860ab2165b623cb4bda92f99247582320306ed1417bd6283d57d3694ed",
+    "122eb25a770f0dc0a1505fd540f518b72b8592b25fcac120f9c6e87ceb1e0274",
+];
+
+fn key_pair(i: usize) -> KeyPair {
+    KeyPair::from(PrivateKey::from_hex(PRIVATE_KEYS[i]).unwrap())
+}
+
+fn recipient() -> Address {
+    Address::from_any_str("NQ68 D40E KU4Q V8JV E96E X1M1 5NL6 KUYC SQXS").unwrap()
+}
+
+/// Simulates passing the partially signed transaction to another party.
+fn transfer(pst: &PartiallySignedTransaction) -> PartiallySignedTransaction {
+    PartiallySignedTransaction::from_bytes(&pst.to_bytes()).unwrap()
+}
+
+#[test]
+fn it_can_sign_with_a_single_key() {
+    let wallet = WalletAccount::from(key_pair(0));
+    let transaction = Transaction::new_basic(
+        wallet.address.clone(),
+        recipient(),
+        Coin::from_u64_unchecked(1),
+        Coin::ZERO,
+        1,
+        NetworkId::Dummy,
+    );
+
+    let pst =
+        PartiallySignedTransaction::new(transaction, PublicKey::Ed25519(wallet.key_pair.public));
+    assert!(!pst.is_complete());
+    assert!(matches!(pst.finalize(), Err(PstError::MissingSignatures)));
+
+    let mut signed = transfer(&pst);
+    signed.sign(&wallet.key_pair).unwrap();
+    assert!(signed.is_complete());
+
+    let tx = transfer(&signed).finalize().unwrap();
+    assert!(tx.verify(NetworkId::Dummy).is_ok());
+
+    // Other keys can't sign.
+    let mut pst = transfer(&pst);
+    assert!(matches!(
+        pst.sign(&key_pair(1)),
+        Err(PstError::UnknownSigner(_))
+    ));
+}
+
+#[test]
+fn it_can_sign_with_a_multisig_account() {
+    let key_pairs: Vec<_> = (0..3).map(key_pair).collect();
+    let public_keys: Vec<_> = key_pairs.iter().map(|kp| kp.public).collect();
+    let min_signatures = NonZeroU8::new(2).unwrap();
+
+    let account =
+        MultiSigAccount::from_public_keys(&key_pairs[0], min_signatures, &public_keys).unwrap();
+    let transaction = account.create_transaction(
+        recipient(),
+        Coin::from_u64_unchecked(1),
+        Coin::ZERO,
+        1,
+        NetworkId::Dummy,
+    );
+
+    let pst = PartiallySignedTransaction::new_multisig(
+        transaction,
+        min_signatures,
+        &public_keys,
+        &[key_pairs[2].public, key_pairs[0].public],
+    )
+    .unwrap();
+
+    // First round: Every signer adds its commitments to its own copy.
+    let mut pst_0 = transfer(&pst);
+    let pairs_0 = pst_0.create_commitments(key_pairs[0].public).unwrap();
+    let mut pst_2 = transfer(&pst);
+    let pairs_2 = pst_2.create_commitments(key_pairs[2].public).unwrap();
+
+    // Signing requires all commitments.
+    assert!(matches!(
+        pst_0.partially_sign(&key_pairs[0], pairs_0),
+        Err(PstError::MissingCommitments)
+    ));
+
+    let mut combined = transfer(&pst);
+    combined.combine(&pst_0).unwrap();
+    combined.combine(&pst_2).unwrap();
+
+    // Second round: Every signer adds its partial signature.
+    let mut pst_0 = transfer(&combined);
+    pst_0.partially_sign(&key_pairs[0], pairs_0).unwrap();
+    let mut pst_2 = transfer(&combined);
+    pst_2.partially_sign(&key_pairs[2], pairs_2).unwrap();
+    assert!(!pst_0.is_complete());
+
+    let mut combined = transfer(&combined);
+    combined.combine(&pst_2).unwrap();
+    combined.combine(&pst_0).unwrap();
+    assert!(combined.is_complete());
+
+    let tx = combined.finalize().unwrap();
+    assert!(tx.verify(NetworkId::Dummy).is_ok());
+}
+
+#[test]
+fn it_rejects_invalid_multisig_signers() {
+    let key_pairs: Vec<_> = (0..3).map(key_pair).collect();
+    let public_keys: Vec<_> = key_pairs.iter().map(|kp| kp.public).collect();
+    let min_signatures = NonZeroU8::new(2).unwrap();
+
+    let account =
+        MultiSigAccount::from_public_keys(&key_pairs[0], min_signatures, &public_keys).unwrap();
+    let transaction = account.create_transaction(
+        recipient(),
+        Coin::from_u64_unchecked(1),
+        Coin::ZERO,
+        1,
+        NetworkId::Dummy,
+    );
+
+    assert!(matches!(
+        PartiallySignedTransaction::new_multisig(
+            transaction.clone(),
+            min_signatures,
+            &public_keys,
+            &[key_pairs[0].public],
+        ),
+        Err(PstError::InvalidSignersLength)
+    ));
+    assert!(matches!(
+        PartiallySignedTransaction::new_multisig(
+            transaction.clone(),
+            NonZeroU8::new(1).unwrap(),
+            &public_keys,
+            &[key_pairs[0].public],
+        ),
+        Err(PstError::SenderMismatch)
+    ));
+
+    let mut pst = PartiallySignedTransaction::new_multisig(
+        transaction,
+        min_signatures,
+        &public_keys,
+        &[key_pairs[0].public, key_pairs[1].public],
+    )
+    .unwrap();
+    assert!(matches!(
+        pst.create_commitments(key_pairs[2].public),
+        Err(PstError::UnknownSigner(_))
+    ));
+}
+
+#[test]
+fn it_rejects_combining_different_transactions() {
+    let wallet = WalletAccount::from(key_pair(0));
+    let public_key = PublicKey::Ed25519(wallet.key_pair.public);
+    let transaction = |value| {
+        Transaction::new_basic(
+            wallet.address.clone(),
+            recipient(),
+            Coin::from_u64_unchecked(value),
+            Coin::ZERO,
+            1,
+            NetworkId::Dummy,
+        )
+    };
+
+    let mut pst_1 = PartiallySignedTransaction::new(transaction(1), public_key.clone());
+    let pst_2 = PartiallySignedTransaction::new(transaction(2), public_key);
+    assert!(matches!(
+        pst_1.combine(&pst_2),
+        Err(PstError::TransactionMismatch)
+    ));
+}
+
+#[test]
+fn it_rejects_unsupported_versions() {
+    let wallet = WalletAccount::from(key_pair(0));
+    let transaction = Transaction::new_basic(
+        wallet.address.clone(),
+        recipient(),
+        Coin::from_u64_unchecked(1),
+        Coin::ZERO,
+        1,
+        NetworkId::Dummy,
+    );
+    let pst =
+        PartiallySignedTransaction::new(transaction, PublicKey::Ed25519(wallet.key_pair.public));
+
+    let mut bytes = pst.to_bytes();
+    bytes[0] = PartiallySignedTransaction::VERSION + 1;
+    assert!(matches!(
+        PartiallySignedTransaction::from_bytes(&bytes),
+        Err(PstError::UnsupportedVersion(_))
+    ));
+}
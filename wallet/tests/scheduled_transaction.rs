@@ -0,0 +1,57 @@
+use nimiq_database::{traits::WriteTransaction, volatile::VolatileDatabase};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
+use nimiq_test_log::test;
+use nimiq_wallet::{
+    ScheduleState, ScheduleTrigger, ScheduledTransaction, TransactionIntent, WalletAccount,
+    WalletStore,
+};
+
+#[test]
+fn it_stores_scheduled_transactions() {
+    let store = WalletStore::new(VolatileDatabase::new(20).unwrap());
+    let wallet = WalletAccount::generate();
+    let intent = TransactionIntent::Basic {
+        wallet: wallet.address.clone(),
+        recipient: Address::burn_address(),
+        value: Coin::from_u64_unchecked(1000),
+        fee: Coin::ZERO,
+    };
+    assert_eq!(intent.wallet(), Some(&wallet.address));
+
+    let mut txn = store.create_write_transaction();
+    assert_eq!(store.next_scheduled_id(&txn), 0);
+    let first = ScheduledTransaction::new(
+        store.next_scheduled_id(&txn),
+        ScheduleTrigger::BlockNumber(1000),
+        intent.clone(),
+    );
+    store.put_scheduled(&first, &mut txn);
+    let second = ScheduledTransaction::new(
+        store.next_scheduled_id(&txn),
+        ScheduleTrigger::HtlcTimeout(Address::burn_address()),
+        intent,
+    );
+    store.put_scheduled(&second, &mut txn);
+    txn.commit();
+
+    assert_eq!(second.id, 1);
+    assert!(second.is_pending());
+    assert_eq!(store.list_scheduled(None), vec![first.clone(), second.clone()]);
+
+    // Update the state of the first schedule.
+    let mut submitted = first;
+    submitted.state = ScheduleState::Submitted {
+        hash: Blake2bHash::default(),
+        block_number: 1000,
+    };
+    let mut txn = store.create_write_transaction();
+    store.put_scheduled(&submitted, &mut txn);
+    txn.commit();
+
+    let stored = store.get_scheduled(0, None).unwrap();
+    assert!(!stored.is_pending());
+    assert_eq!(stored, submitted);
+    assert_eq!(store.get_scheduled(2, None), None);
+}
@@ -6,6 +6,8 @@ pub mod proof_store;
 pub mod proof_utils;
 #[cfg(feature = "zkp-prover")]
 pub mod prover_binary;
+#[cfg(feature = "zkp-prover")]
+pub mod remote_prover;
 pub mod types;
 pub mod zkp_component;
 #[cfg(feature = "zkp-prover")]
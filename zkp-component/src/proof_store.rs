@@ -1,39 +1,98 @@
 #[cfg(feature = "database-storage")]
 use nimiq_database::{
-    traits::{Database, ReadTransaction, WriteTransaction},
-    DatabaseProxy, TableProxy,
+    traits::{Database, ReadCursor, ReadTransaction, WriteCursor, WriteTransaction},
+    DatabaseProxy, TableFlags, TableProxy,
 };
+#[cfg(feature = "database-storage")]
+use nimiq_primitives::policy::Policy;
 
 use crate::types::*;
 
 /// Defines an interface for storing and retrieving ZK proofs.
-pub trait ProofStore: Send {
+pub trait ProofStore: Send + Sync {
     /// Gets a ZK proof.
     fn get_zkp(&self) -> Option<ZKProof>;
 
     /// Sets or stores a ZK proof.
     fn set_zkp(&self, zk_proof: &ZKProof);
+
+    /// Gets the ZK proof for the election block at the given block number.
+    /// Stores that don't keep historic proofs only return the latest proof.
+    fn get_zkp_at(&self, block_number: u32) -> Option<ZKProof> {
+        self.get_zkp()
+            .filter(|zk_proof| zk_proof.block_number == block_number)
+    }
 }
 
 #[cfg(feature = "database-storage")]
 /// DB implementation of a ProofStore meant for persistent storage
+///
+/// If created with a retention, it keeps the proofs of previous election blocks indexed by their
+/// block number besides the latest proof.
 #[derive(Debug)]
 pub struct DBProofStore {
     /// Environment for the DB creation and transaction handling.
     env: DatabaseProxy,
     // A database of the current zkp state.
     zkp_db: TableProxy,
+    // A database of the proofs of past election blocks, indexed by block number.
+    history_db: Option<TableProxy>,
+    /// The number of most recent election blocks for which the proof is kept.
+    /// If `None`, all proofs are kept.
+    retention: Option<u32>,
 }
 
 #[cfg(feature = "database-storage")]
 impl DBProofStore {
     const PROOF_DB_NAME: &'static str = "ZKPState";
+    const HISTORY_DB_NAME: &'static str = "ZKPHistory";
     const PROOF_KEY: &'static str = "proof";
 
+    /// Creates a proof store that only keeps the latest proof.
     pub fn new(env: DatabaseProxy) -> Self {
         let zkp_db = env.open_table(Self::PROOF_DB_NAME.to_string());
 
-        Self { env, zkp_db }
+        Self {
+            env,
+            zkp_db,
+            history_db: None,
+            retention: None,
+        }
+    }
+
+    /// Creates a proof store that keeps the proofs of the `retention` most recent election
+    /// blocks. The latest proof is always kept. If `retention` is `None`, all proofs are kept.
+    pub fn with_retention(env: DatabaseProxy, retention: Option<u32>) -> Self {
+        let zkp_db = env.open_table(Self::PROOF_DB_NAME.to_string());
+        let history_db =
+            env.open_table_with_flags(Self::HISTORY_DB_NAME.to_string(), TableFlags::UINT_KEYS);
+
+        Self {
+            env,
+            zkp_db,
+            history_db: Some(history_db),
+            retention,
+        }
+    }
+
+    /// Returns the block numbers of all proofs currently kept, in ascending order.
+    pub fn block_numbers(&self) -> Vec<u32> {
+        let history_db = match &self.history_db {
+            Some(history_db) => history_db,
+            None => {
+                return self
+                    .get_zkp()
+                    .map(|zkp| zkp.block_number)
+                    .into_iter()
+                    .collect()
+            }
+        };
+        let txn = self.env.read_transaction();
+        let cursor = txn.cursor(history_db);
+        cursor
+            .into_iter_start::<u32, ZKProof>()
+            .map(|(block_number, _)| block_number)
+            .collect()
     }
 }
 
@@ -48,6 +107,44 @@ impl ProofStore for DBProofStore {
     fn set_zkp(&self, zk_proof: &ZKProof) {
         let mut tx = self.env.write_transaction();
         tx.put(&self.zkp_db, Self::PROOF_KEY, zk_proof);
+
+        let history_db = match &self.history_db {
+            Some(history_db) => history_db,
+            None => {
+                tx.commit();
+                return;
+            }
+        };
+        tx.put(history_db, &zk_proof.block_number, zk_proof);
+
+        // Removes the proofs of election blocks that fell out of the retention window.
+        if let Some(retention) = self.retention {
+            let oldest_kept = zk_proof.block_number.saturating_sub(
+                retention
+                    .saturating_sub(1)
+                    .saturating_mul(Policy::blocks_per_epoch()),
+            );
+
+            let mut cursor = WriteTransaction::cursor(&tx, history_db);
+            let mut pos: Option<(u32, ZKProof)> = cursor.first();
+            while let Some((block_number, _)) = pos {
+                if block_number >= oldest_kept {
+                    break;
+                }
+                cursor.remove();
+                pos = cursor.next();
+            }
+        }
+
         tx.commit();
     }
+
+    fn get_zkp_at(&self, block_number: u32) -> Option<ZKProof> {
+        match &self.history_db {
+            Some(history_db) => self.env.read_transaction().get(history_db, &block_number),
+            None => self
+                .get_zkp()
+                .filter(|zk_proof| zk_proof.block_number == block_number),
+        }
+    }
 }
@@ -1,10 +1,15 @@
-use std::io::{self, BufReader, BufWriter, Error};
+use std::{
+    io::{self, BufReader, BufWriter, Error},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use ark_serialize::{Read, Write};
 use nimiq_serde::{Deserialize, Serialize};
 
 use crate::{
     proof_gen_utils::generate_new_proof,
+    remote_prover::{ProcessProofGenerator, RemoteProverServer},
     types::{ProofInput, ZKProofGenerationError, PROOF_GENERATION_OUTPUT_DELIMITER},
 };
 
@@ -43,3 +48,17 @@ pub async fn prover_main() -> Result<(), Error> {
 
     Ok(())
 }
+
+/// Runs a remote prover service on the given address. Every proof is generated in a new prover
+/// process, which is the current binary started with `--prove`.
+pub async fn prover_server_main(
+    address: String,
+    auth_key: Vec<u8>,
+    prover_keys_path: PathBuf,
+) -> Result<(), Error> {
+    let generator = Arc::new(ProcessProofGenerator::new(None));
+    let server = RemoteProverServer::bind(address, auth_key, prover_keys_path, generator).await?;
+    log::info!(address = %server.local_addr()?, "Remote prover service listening");
+
+    server.run().await
+}
@@ -0,0 +1,394 @@
+use std::{fmt, io, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
+
+use futures::{future::BoxFuture, FutureExt};
+use nimiq_hash::{hmac::compute_hmac_sha512, Blake2sHash};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_zkp::{verify::verify, ZKP_VERIFYING_DATA};
+use subtle::ConstantTimeEq;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        oneshot::{channel, Receiver},
+        Semaphore,
+    },
+    time::{sleep, timeout},
+};
+
+use crate::{proof_gen_utils::launch_generate_new_proof, types::*};
+
+/// The maximum size of a message of the remote prover protocol.
+const MAX_MESSAGE_SIZE: u32 = 16 * 1024 * 1024;
+
+/// The maximum size of a message exchanged before the node is authenticated.
+const MAX_AUTH_MESSAGE_SIZE: u32 = 1024;
+
+/// The configuration of the connection to a remote prover service.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RemoteProverConfig {
+    /// The address of the remote prover service.
+    pub address: String,
+    /// The key shared with the remote prover service to authenticate the node.
+    pub auth_key: Vec<u8>,
+    /// How often a failed proof request is retried before giving up.
+    pub max_retries: u32,
+    /// The time to wait before retrying a failed proof request.
+    pub retry_delay: Duration,
+}
+
+impl RemoteProverConfig {
+    pub fn new(address: String, auth_key: Vec<u8>) -> Self {
+        Self {
+            address,
+            auth_key,
+            max_retries: 3,
+            retry_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl fmt::Debug for RemoteProverConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteProverConfig")
+            .field("address", &self.address)
+            .field("auth_key", &"***")
+            .field("max_retries", &self.max_retries)
+            .field("retry_delay", &self.retry_delay)
+            .finish()
+    }
+}
+
+/// Delegates the proof generation to a remote prover service.
+///
+/// The protocol runs over a TCP connection and every message is prefixed with its length:
+///
+/// 1. The service sends a random challenge.
+/// 2. The node answers with the HMAC-SHA512 of the challenge under the shared key.
+/// 3. The service confirms the authentication or rejects it and closes the connection.
+/// 4. The node sends the [`ProofInput`]. The proving keys path is not sent, the service uses its own.
+/// 5. The service answers with the generated [`ZKProof`] or the error that occurred.
+///
+/// The node cancels a proof generation by closing the connection. The service doesn't need to
+/// be trusted, since every proof is verified before it is accepted.
+pub struct RemoteProver {
+    config: RemoteProverConfig,
+}
+
+impl RemoteProver {
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn new(config: RemoteProverConfig) -> Self {
+        Self { config }
+    }
+
+    /// Requests the proof for the given input from the remote prover service and verifies it.
+    /// Failed requests are retried as configured. Sending to or dropping the sender of `recv`
+    /// cancels the proof generation.
+    pub async fn generate_proof(
+        &self,
+        mut recv: Receiver<()>,
+        proof_input: ProofInput,
+    ) -> Result<ZKPState, ZKProofGenerationError> {
+        let mut attempt = 0;
+        loop {
+            let result = tokio::select! {
+                result = self.request_proof(&proof_input) => result,
+                _ = &mut recv => return Err(ZKProofGenerationError::ChannelError),
+            };
+
+            let result = match result {
+                Ok(zk_proof) => {
+                    // Verifying a proof takes a while, so don't block the executor.
+                    let proof_input = proof_input.clone();
+                    tokio::task::spawn_blocking(move || verify_proof(&proof_input, zk_proof))
+                        .await
+                        .unwrap_or_else(|error| {
+                            Err(ZKProofGenerationError::NanoZKP(error.to_string()))
+                        })
+                }
+                Err(error) => Err(error),
+            };
+            let error = match result {
+                Ok(zkp_state) => return Ok(zkp_state),
+                Err(error) => error,
+            };
+
+            // Only failures to communicate with the service are retried.
+            if !matches!(error, ZKProofGenerationError::Remote(_))
+                || attempt >= self.config.max_retries
+            {
+                return Err(error);
+            }
+            attempt += 1;
+            log::warn!(
+                %error,
+                attempt,
+                address = %self.config.address,
+                "Remote proof generation failed, retrying",
+            );
+
+            tokio::select! {
+                _ = sleep(self.config.retry_delay) => {},
+                _ = &mut recv => return Err(ZKProofGenerationError::ChannelError),
+            };
+        }
+    }
+
+    async fn request_proof(
+        &self,
+        proof_input: &ProofInput,
+    ) -> Result<ZKProof, ZKProofGenerationError> {
+        let mut stream = timeout(
+            Self::CONNECT_TIMEOUT,
+            TcpStream::connect(&self.config.address),
+        )
+        .await
+        .map_err(|_| ZKProofGenerationError::Remote("Connection timed out".to_owned()))?
+        .map_err(remote_error)?;
+
+        let challenge: [u8; 32] = read_message(&mut stream, MAX_AUTH_MESSAGE_SIZE).await?;
+        write_message(
+            &mut stream,
+            &authenticate(&self.config.auth_key, &challenge),
+        )
+        .await?;
+        let authenticated: Result<(), ZKProofGenerationError> =
+            read_message(&mut stream, MAX_AUTH_MESSAGE_SIZE).await?;
+        authenticated?;
+
+        // Our proving keys path is of no use to the service.
+        let proof_input = ProofInput {
+            prover_keys_path: PathBuf::new(),
+            ..proof_input.clone()
+        };
+        write_message(&mut stream, &proof_input).await?;
+
+        log::debug!(
+            address = %self.config.address,
+            block_number = proof_input.final_block.block_number(),
+            "Requested proof from remote prover",
+        );
+        read_message(&mut stream, MAX_MESSAGE_SIZE).await?
+    }
+}
+
+/// Verifies a proof returned by a remote prover service and returns the new zkp state.
+fn verify_proof(
+    proof_input: &ProofInput,
+    zk_proof: ZKProof,
+) -> Result<ZKPState, ZKProofGenerationError> {
+    if zk_proof.block_number != proof_input.final_block.block_number() {
+        return Err(ZKProofGenerationError::InvalidProof);
+    }
+    let proof = zk_proof.proof.ok_or(ZKProofGenerationError::InvalidProof)?;
+
+    if !verify(
+        Blake2sHash::from(proof_input.genesis_header_hash),
+        proof_input.final_block.hash_blake2s(),
+        proof.clone(),
+        &ZKP_VERIFYING_DATA,
+    )? {
+        return Err(ZKProofGenerationError::InvalidProof);
+    }
+
+    Ok(ZKPState {
+        latest_block: proof_input.final_block.clone(),
+        latest_proof: Some(proof),
+    })
+}
+
+/// Generates proofs for a [`RemoteProverServer`].
+pub trait ProofGenerator: Send + Sync {
+    /// Generates the proof for the given input. Sending to `recv` cancels the proof generation.
+    fn generate_proof(
+        &self,
+        recv: Receiver<()>,
+        proof_input: ProofInput,
+    ) -> BoxFuture<'static, Result<ZKPState, ZKProofGenerationError>>;
+}
+
+/// Generates proofs in a child process, like a node with an active local prover.
+pub struct ProcessProofGenerator {
+    prover_path: Option<PathBuf>,
+}
+
+impl ProcessProofGenerator {
+    pub fn new(prover_path: Option<PathBuf>) -> Self {
+        Self { prover_path }
+    }
+}
+
+impl ProofGenerator for ProcessProofGenerator {
+    fn generate_proof(
+        &self,
+        recv: Receiver<()>,
+        proof_input: ProofInput,
+    ) -> BoxFuture<'static, Result<ZKPState, ZKProofGenerationError>> {
+        launch_generate_new_proof(recv, proof_input, self.prover_path.clone()).boxed()
+    }
+}
+
+/// The remote prover service, generating proofs for authenticated nodes.
+/// See [`RemoteProver`] for the protocol.
+pub struct RemoteProverServer {
+    listener: TcpListener,
+    auth_key: Arc<Vec<u8>>,
+    prover_keys_path: PathBuf,
+    proof_permits: Arc<Semaphore>,
+    generator: Arc<dyn ProofGenerator>,
+}
+
+impl RemoteProverServer {
+    /// The default number of proofs generated at the same time. Further requests wait for one of
+    /// them to finish, since every proof generation needs a lot of memory.
+    pub const DEFAULT_MAX_CONCURRENT_PROOFS: usize = 1;
+
+    /// Binds the service to the given address. Proofs are generated with the proving keys at
+    /// `prover_keys_path`, regardless of what the nodes send.
+    pub async fn bind(
+        address: impl ToSocketAddrs,
+        auth_key: Vec<u8>,
+        prover_keys_path: PathBuf,
+        generator: Arc<dyn ProofGenerator>,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address).await?,
+            auth_key: Arc::new(auth_key),
+            prover_keys_path,
+            proof_permits: Arc::new(Semaphore::new(Self::DEFAULT_MAX_CONCURRENT_PROOFS)),
+            generator,
+        })
+    }
+
+    /// Sets the number of proofs generated at the same time.
+    pub fn with_max_concurrent_proofs(mut self, max_concurrent_proofs: usize) -> Self {
+        self.proof_permits = Arc::new(Semaphore::new(max_concurrent_proofs));
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections and serves their proof requests. Only returns on failure.
+    pub async fn run(self) -> io::Result<()> {
+        loop {
+            let (stream, peer_address) = self.listener.accept().await?;
+            let auth_key = Arc::clone(&self.auth_key);
+            let prover_keys_path = self.prover_keys_path.clone();
+            let proof_permits = Arc::clone(&self.proof_permits);
+            let generator = Arc::clone(&self.generator);
+
+            tokio::spawn(async move {
+                if let Err(error) = handle_connection(
+                    stream,
+                    &auth_key,
+                    prover_keys_path,
+                    proof_permits,
+                    generator,
+                )
+                .await
+                {
+                    log::warn!(%error, %peer_address, "Failed to serve proof request");
+                }
+            });
+        }
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    auth_key: &[u8],
+    prover_keys_path: PathBuf,
+    proof_permits: Arc<Semaphore>,
+    generator: Arc<dyn ProofGenerator>,
+) -> Result<(), ZKProofGenerationError> {
+    let challenge: [u8; 32] = rand::random();
+    write_message(&mut stream, &challenge).await?;
+
+    // Compare in constant time to not leak how much of the authentication was correct.
+    let authentication: Vec<u8> = read_message(&mut stream, MAX_AUTH_MESSAGE_SIZE).await?;
+    if !bool::from(authentication.ct_eq(&authenticate(auth_key, &challenge))) {
+        let error = ZKProofGenerationError::RemoteAuthentication;
+        write_message(&mut stream, &Err::<(), _>(error.clone())).await?;
+        return Err(error);
+    }
+    write_message(&mut stream, &Ok::<(), ZKProofGenerationError>(())).await?;
+
+    let proof_input = ProofInput {
+        prover_keys_path,
+        ..read_message(&mut stream, MAX_MESSAGE_SIZE).await?
+    };
+    let block_number = proof_input.final_block.block_number();
+
+    // The node doesn't send anything else, so any read returns once it closes the connection.
+    let (mut reader, mut writer) = stream.into_split();
+    let mut buffer = [0u8; 1];
+
+    let _permit = tokio::select! {
+        permit = proof_permits.acquire_owned() => permit.expect("Semaphore is never closed"),
+        _ = reader.read(&mut buffer) => {
+            log::info!(block_number, "Remote node cancelled the proof request while waiting");
+            return Ok(());
+        }
+    };
+    log::info!(block_number, "Starting proof generation for remote node");
+
+    let (sender, recv) = channel();
+    let mut proof = generator.generate_proof(recv, proof_input);
+
+    let result = tokio::select! {
+        result = &mut proof => result,
+        _ = reader.read(&mut buffer) => {
+            log::info!(block_number, "Remote node cancelled the proof generation");
+            let _ = sender.send(());
+            let _ = proof.await;
+            return Ok(());
+        }
+    };
+    log::info!(
+        block_number,
+        success = result.is_ok(),
+        "Finished proof generation for remote node",
+    );
+
+    write_message(&mut writer, &result.map(ZKProof::from)).await
+}
+
+fn authenticate(auth_key: &[u8], challenge: &[u8; 32]) -> Vec<u8> {
+    compute_hmac_sha512(auth_key, challenge).as_bytes().to_vec()
+}
+
+fn remote_error(error: io::Error) -> ZKProofGenerationError {
+    ZKProofGenerationError::Remote(error.to_string())
+}
+
+async fn write_message<W: AsyncWrite + Unpin, T: Serialize>(
+    writer: &mut W,
+    message: &T,
+) -> Result<(), ZKProofGenerationError> {
+    let data = message.serialize_to_vec();
+    writer
+        .write_u32(data.len() as u32)
+        .await
+        .map_err(remote_error)?;
+    writer.write_all(&data).await.map_err(remote_error)?;
+    writer.flush().await.map_err(remote_error)
+}
+
+async fn read_message<R: AsyncRead + Unpin, T: Deserialize>(
+    reader: &mut R,
+    max_size: u32,
+) -> Result<T, ZKProofGenerationError> {
+    let len = reader.read_u32().await.map_err(remote_error)?;
+    if len > max_size {
+        return Err(ZKProofGenerationError::Remote(format!(
+            "Message too large: {len} bytes"
+        )));
+    }
+
+    let mut data = vec![0u8; len as usize];
+    reader.read_exact(&mut data).await.map_err(remote_error)?;
+    Deserialize::deserialize_from_vec(&data)
+        .map_err(|e| ZKProofGenerationError::Remote(e.to_string()))
+}
@@ -16,7 +16,7 @@ use nimiq_zkp_primitives::NanoZKPError;
 use parking_lot::RwLock;
 use thiserror::Error;
 
-use crate::ZKPComponent;
+use crate::{proof_store::ProofStore, ZKPComponent};
 
 pub const PROOF_GENERATION_OUTPUT_DELIMITER: [u8; 2] = [242, 208];
 
@@ -181,7 +181,7 @@ pub enum Error {
     Request(#[from] RequestError),
 }
 
-#[derive(Error, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Error, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[repr(u8)]
 pub enum ZKProofGenerationError {
     #[error("Nano Zkp Error: {0}")]
@@ -198,6 +198,15 @@ pub enum ZKProofGenerationError {
 
     #[error("Process launching error: {0}")]
     ProcessError(String),
+
+    #[error("Remote prover error: {0}")]
+    Remote(String),
+
+    #[error("Invalid proof")]
+    InvalidProof,
+
+    #[error("Remote prover authentication failed")]
+    RemoteAuthentication,
 }
 
 impl From<DeserializeError> for ZKProofGenerationError {
@@ -249,6 +258,37 @@ pub enum RequestZKPResponse {
 pub(crate) struct ZKPStateEnvironment {
     pub(crate) zkp_state: Arc<RwLock<ZKPState>>,
     pub(crate) blockchain: BlockchainProxy,
+    pub(crate) proof_storage: Option<Arc<dyn ProofStore>>,
+}
+
+impl ZKPStateEnvironment {
+    /// Gets the proof for the election block at the given block number, either from the current
+    /// zkp state or from the proof storage.
+    pub(crate) fn get_zkp_at(&self, block_number: u32) -> Option<ZKProof> {
+        let zkp_state = self.zkp_state.read();
+        if zkp_state.latest_block.block_number() == block_number {
+            return Some((*zkp_state).clone().into());
+        }
+        drop(zkp_state);
+
+        self.proof_storage.as_ref()?.get_zkp_at(block_number)
+    }
+
+    /// Gets the election block at the given block number if it was requested.
+    fn get_election_block(
+        &self,
+        block_number: u32,
+        request_election_block: bool,
+    ) -> Option<MacroBlock> {
+        if !request_election_block {
+            return None;
+        }
+        self.blockchain
+            .read()
+            .get_block_at(block_number, true)
+            .ok()
+            .map(|block| block.unwrap_macro())
+    }
 }
 
 impl<N: Network> From<&ZKPComponent<N>> for ZKPStateEnvironment {
@@ -256,6 +296,7 @@ impl<N: Network> From<&ZKPComponent<N>> for ZKPStateEnvironment {
         ZKPStateEnvironment {
             zkp_state: Arc::clone(&component.zkp_state),
             blockchain: component.blockchain.clone(),
+            proof_storage: component.proof_storage.clone(),
         }
     }
 }
@@ -272,15 +313,7 @@ impl<N: Network> Handle<N, Arc<ZKPStateEnvironment>> for RequestZKP {
         drop(zkp_state);
 
         // Then get the corresponding block if necessary.
-        let block = if self.request_election_block {
-            env.blockchain
-                .read()
-                .get_block_at(latest_block_number, true)
-                .ok()
-                .map(|block| block.unwrap_macro())
-        } else {
-            None
-        };
+        let block = env.get_election_block(latest_block_number, self.request_election_block);
         RequestZKPResponse::Proof(zkp_proof, block)
     }
 }
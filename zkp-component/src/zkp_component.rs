@@ -24,7 +24,7 @@ use tokio::sync::{
 use tokio_stream::wrappers::BroadcastStream;
 
 #[cfg(feature = "zkp-prover")]
-use crate::zkp_prover::ZKProver;
+use crate::zkp_prover::{ProverBackend, ZKProver};
 use crate::{proof_store::ProofStore, proof_utils::*, types::*, zkp_requests::ZKPRequests};
 
 pub type ZKProofsStream<N> = BoxStream<'static, (ZKProof, <N as Network>::PubsubId)>;
@@ -34,6 +34,8 @@ pub(crate) const BROADCAST_MAX_CAPACITY: usize = 256;
 pub struct ZKPComponentProxy<N: Network> {
     network: Arc<N>,
     zkp_state: Arc<RwLock<ZKPState>>,
+    blockchain: BlockchainProxy,
+    proof_storage: Option<Arc<dyn ProofStore>>,
     zkp_requests: Arc<Mutex<ZKPRequests<N>>>,
     pub(crate) zkp_events_notifier: BroadcastSender<ZKPEvent<N>>,
 }
@@ -43,6 +45,8 @@ impl<N: Network> Clone for ZKPComponentProxy<N> {
         Self {
             network: Arc::clone(&self.network),
             zkp_state: Arc::clone(&self.zkp_state),
+            blockchain: self.blockchain.clone(),
+            proof_storage: self.proof_storage.clone(),
             zkp_requests: Arc::clone(&self.zkp_requests),
             zkp_events_notifier: self.zkp_events_notifier.clone(),
         }
@@ -55,6 +59,17 @@ impl<N: Network> ZKPComponentProxy<N> {
         self.zkp_state.read().clone()
     }
 
+    /// Gets the proof for the election block at the given block number. This is either the
+    /// current proof or a past one that is still kept in the proof storage.
+    pub fn get_zkp_by_block_number(&self, block_number: u32) -> Option<ZKProof> {
+        ZKPStateEnvironment {
+            zkp_state: Arc::clone(&self.zkp_state),
+            blockchain: self.blockchain.clone(),
+            proof_storage: self.proof_storage.clone(),
+        }
+        .get_zkp_at(block_number)
+    }
+
     /// Sends zkp request to all given peers. If no requests are ongoing, we request and return true,
     /// otherwise no requests will be sent.
     pub fn request_zkp_from_peers(
@@ -102,7 +117,7 @@ impl<N: Network> ZKPComponentProxy<N> {
 /// - The current zkp state
 /// - The proof generating component that can be activated by a client configuration
 /// - The zkp gossip stream
-/// - The db storage for the current proof
+/// - The db storage for the current and past proofs
 /// - The zkp requests component to fetch an up to date proof from our peers
 /// - The zkp events notifies newly stored proofs.
 ///
@@ -114,7 +129,7 @@ pub struct ZKPComponent<N: Network> {
     #[cfg(feature = "zkp-prover")]
     zk_prover: Option<ZKProver<N>>,
     zk_proofs_stream: ZKProofsStream<N>,
-    proof_storage: Option<Box<dyn ProofStore>>,
+    pub(crate) proof_storage: Option<Arc<dyn ProofStore>>,
     zkp_requests: Arc<Mutex<ZKPRequests<N>>>,
     zkp_events_notifier: BroadcastSender<ZKPEvent<N>>,
 }
@@ -146,7 +161,7 @@ impl<N: Network> ZKPComponent<N> {
             #[cfg(feature = "zkp-prover")]
             zk_prover: None,
             zk_proofs_stream,
-            proof_storage,
+            proof_storage: proof_storage.map(Arc::from),
             zkp_requests: Arc::new(Mutex::new(ZKPRequests::new(network))),
             zkp_events_notifier,
         };
@@ -168,6 +183,30 @@ impl<N: Network> ZKPComponent<N> {
         prover_path: Option<PathBuf>,
         prover_keys_path: PathBuf,
         proof_storage: Option<Box<dyn ProofStore>>,
+    ) -> Self {
+        Self::with_prover_backend(
+            blockchain,
+            network,
+            executor,
+            is_prover_active,
+            ProverBackend::Process { prover_path },
+            prover_keys_path,
+            proof_storage,
+        )
+        .await
+    }
+
+    /// Creates the ZKP component with a prover generating the proofs through the given backend.
+    /// The prover keys path is only used by local prover processes.
+    #[cfg(feature = "zkp-prover")]
+    pub async fn with_prover_backend(
+        blockchain: BlockchainProxy,
+        network: Arc<N>,
+        executor: impl TaskExecutor + Send + 'static,
+        is_prover_active: bool,
+        prover_backend: ProverBackend,
+        prover_keys_path: PathBuf,
+        proof_storage: Option<Box<dyn ProofStore>>,
     ) -> Self {
         let mut zkp_component = Self::new(blockchain, network, executor, proof_storage).await;
 
@@ -178,7 +217,7 @@ impl<N: Network> ZKPComponent<N> {
                     Arc::clone(blockchain),
                     Arc::clone(&zkp_component.network),
                     Arc::clone(&zkp_component.zkp_state),
-                    prover_path,
+                    prover_backend,
                     prover_keys_path,
                 )
                 .await,
@@ -195,8 +234,9 @@ impl<N: Network> ZKPComponent<N> {
 
     /// Launches thread that processes the zkp requests and replies to them.
     fn launch_request_handler(&self, executor: impl TaskExecutor + Send + 'static) {
-        let stream = self.network.receive_requests::<RequestZKP>();
         let env = Arc::new(ZKPStateEnvironment::from(self));
+
+        let stream = self.network.receive_requests::<RequestZKP>();
         executor.exec(Box::pin(request_handler(&self.network, stream, &env)));
     }
 
@@ -205,6 +245,8 @@ impl<N: Network> ZKPComponent<N> {
         ZKPComponentProxy {
             network: Arc::clone(&self.network),
             zkp_state: Arc::clone(&self.zkp_state),
+            blockchain: self.blockchain.clone(),
+            proof_storage: self.proof_storage.clone(),
             zkp_requests: Arc::clone(&self.zkp_requests),
             zkp_events_notifier: self.zkp_events_notifier.clone(),
         }
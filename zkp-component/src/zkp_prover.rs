@@ -18,7 +18,15 @@ use nimiq_primitives::policy::Policy;
 use parking_lot::{lock_api::RwLockUpgradableReadGuard, RwLock, RwLockWriteGuard};
 use tokio::sync::oneshot::{channel, Sender};
 
-use crate::{proof_gen_utils::*, types::*};
+use crate::{proof_gen_utils::*, remote_prover::RemoteProver, types::*};
+
+/// Where the proofs are generated.
+pub enum ProverBackend {
+    /// In a child process, launching the prover binary at the given path or the current binary.
+    Process { prover_path: Option<PathBuf> },
+    /// By a remote prover service.
+    Remote(Arc<RemoteProver>),
+}
 
 /// ZK Prover generates the zk proof for an election block. It has:
 ///
@@ -29,7 +37,7 @@ use crate::{proof_gen_utils::*, types::*};
 /// - The genesis state
 /// - The current proof generation future if a proof is being generated
 /// - The path of the proving keys directory
-/// - The path of the prover binary
+/// - The backend generating the proofs, either a child process or a remote prover service
 ///
 /// The proofs are returned by polling the components.
 pub struct ZKProver<N: Network> {
@@ -42,7 +50,7 @@ pub struct ZKProver<N: Network> {
     proof_future:
         Option<BoxFuture<'static, Result<(ZKPState, MacroBlock), ZKProofGenerationError>>>,
     prover_keys_path: PathBuf,
-    prover_path: Option<PathBuf>,
+    backend: ProverBackend,
 }
 
 impl<N: Network> ZKProver<N> {
@@ -50,7 +58,7 @@ impl<N: Network> ZKProver<N> {
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<N>,
         zkp_state: Arc<RwLock<ZKPState>>,
-        prover_path: Option<PathBuf>,
+        backend: ProverBackend,
         prover_keys_path: PathBuf,
     ) -> Self {
         let network_info = NetworkInfo::from_network_id(blockchain.read().network_id());
@@ -112,7 +120,7 @@ impl<N: Network> ZKProver<N> {
             election_stream: Box::pin(blockchain_election_rx),
             proof_future: None,
             prover_keys_path,
-            prover_path,
+            backend,
         }
     }
 
@@ -152,20 +160,26 @@ impl<N: Network> ZKProver<N> {
             == block.block_number() - Policy::blocks_per_epoch()
         {
             let (sender, recv) = channel();
+            let proof_input = ProofInput {
+                previous_block: zkp_state.latest_block.clone(),
+                previous_proof: zkp_state.latest_proof.clone(),
+                final_block: block.clone(),
+                genesis_header_hash: self.genesis_header_hash,
+                prover_keys_path: self.prover_keys_path.clone(),
+            };
+            let proof_future = match self.backend {
+                ProverBackend::Process { ref prover_path } => {
+                    launch_generate_new_proof(recv, proof_input, prover_path.clone()).boxed()
+                }
+                ProverBackend::Remote(ref remote_prover) => {
+                    let remote_prover = Arc::clone(remote_prover);
+                    async move { remote_prover.generate_proof(recv, proof_input).await }.boxed()
+                }
+            };
             self.proof_future = Some(
-                launch_generate_new_proof(
-                    recv,
-                    ProofInput {
-                        previous_block: zkp_state.latest_block.clone(),
-                        previous_proof: zkp_state.latest_proof.clone(),
-                        final_block: block.clone(),
-                        genesis_header_hash: self.genesis_header_hash,
-                        prover_keys_path: self.prover_keys_path.clone(),
-                    },
-                    self.prover_path.clone(),
-                )
-                .map(|res| res.map(|state| (state, block)))
-                .boxed(),
+                proof_future
+                    .map(|res| res.map(|state| (state, block)))
+                    .boxed(),
             );
             self.sender = Some(sender);
         } else {
@@ -178,3 +178,36 @@ async fn can_store_and_load_zkp_state_from_db() {
         "Load from db was not successful"
     );
 }
+
+#[test(tokio::test)]
+async fn can_retain_historic_zkps_in_db() {
+    let env = VolatileDatabase::new(2).unwrap();
+
+    let proof_store = DBProofStore::with_retention(env, Some(2));
+    let proofs: Vec<_> = (1..=3)
+        .map(|epoch| ZKProof {
+            block_number: epoch * Policy::blocks_per_epoch(),
+            proof: Some(Proof::default()),
+        })
+        .collect();
+
+    for proof in &proofs {
+        proof_store.set_zkp(proof);
+    }
+
+    // Only the proofs of the two most recent election blocks are kept.
+    assert_eq!(proof_store.get_zkp().unwrap(), proofs[2]);
+    assert_eq!(proof_store.get_zkp_at(proofs[0].block_number), None);
+    assert_eq!(
+        proof_store.get_zkp_at(proofs[1].block_number).unwrap(),
+        proofs[1]
+    );
+    assert_eq!(
+        proof_store.get_zkp_at(proofs[2].block_number).unwrap(),
+        proofs[2]
+    );
+    assert_eq!(
+        proof_store.block_numbers(),
+        vec![proofs[1].block_number, proofs[2].block_number]
+    );
+}
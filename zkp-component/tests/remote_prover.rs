@@ -0,0 +1,277 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use ark_groth16::Proof;
+use futures::{future::BoxFuture, FutureExt};
+use nimiq_block::MacroBlock;
+use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis::NetworkInfo;
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    blockchain::{signing_key, voting_key},
+    blockchain_with_rng::produce_macro_blocks_with_rng,
+    zkp_test_data::{get_base_seed, simulate_merger_wrapper, ZKP_TEST_KEYS_PATH},
+};
+use nimiq_utils::time::OffsetTime;
+use nimiq_zkp::ZKP_VERIFYING_DATA;
+use nimiq_zkp_component::{
+    remote_prover::{ProofGenerator, RemoteProver, RemoteProverConfig, RemoteProverServer},
+    types::{ProofInput, ZKPState, ZKProofGenerationError},
+};
+use parking_lot::RwLock;
+use tokio::sync::oneshot::{channel, Receiver};
+
+const AUTH_KEY: &[u8] = b"remote prover test key";
+const SERVER_KEYS_PATH: &str = "server/keys";
+
+/// Returns a precomputed proof instead of generating one.
+struct MockProofGenerator {
+    zkp_state: ZKPState,
+}
+
+impl ProofGenerator for MockProofGenerator {
+    fn generate_proof(
+        &self,
+        _recv: Receiver<()>,
+        proof_input: ProofInput,
+    ) -> BoxFuture<'static, Result<ZKPState, ZKProofGenerationError>> {
+        // The service must always use its own proving keys.
+        assert_eq!(
+            proof_input.prover_keys_path,
+            PathBuf::from(SERVER_KEYS_PATH)
+        );
+        let zkp_state = self.zkp_state.clone();
+        async move { Ok(zkp_state) }.boxed()
+    }
+}
+
+/// Takes some time for every proof and records how many proofs were generated at the same time.
+struct SlowProofGenerator {
+    zkp_state: ZKPState,
+    active: Arc<AtomicUsize>,
+    max_active: Arc<AtomicUsize>,
+}
+
+impl ProofGenerator for SlowProofGenerator {
+    fn generate_proof(
+        &self,
+        _recv: Receiver<()>,
+        _proof_input: ProofInput,
+    ) -> BoxFuture<'static, Result<ZKPState, ZKProofGenerationError>> {
+        let zkp_state = self.zkp_state.clone();
+        let active = Arc::clone(&self.active);
+        let max_active = Arc::clone(&self.max_active);
+        async move {
+            let now_active = active.fetch_add(1, Ordering::SeqCst) + 1;
+            max_active.fetch_max(now_active, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            active.fetch_sub(1, Ordering::SeqCst);
+            Ok(zkp_state)
+        }
+        .boxed()
+    }
+}
+
+/// Never finishes a proof, but records when it gets cancelled.
+struct PendingProofGenerator {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ProofGenerator for PendingProofGenerator {
+    fn generate_proof(
+        &self,
+        recv: Receiver<()>,
+        _proof_input: ProofInput,
+    ) -> BoxFuture<'static, Result<ZKPState, ZKProofGenerationError>> {
+        let cancelled = Arc::clone(&self.cancelled);
+        async move {
+            let _ = recv.await;
+            cancelled.store(true, Ordering::SeqCst);
+            Err(ZKProofGenerationError::ChannelError)
+        }
+        .boxed()
+    }
+}
+
+/// Produces the first epoch and returns the proof input for its election block together with
+/// a valid, simulated proof for it.
+fn proof_input_and_state() -> (ProofInput, ZKPState) {
+    let time = Arc::new(OffsetTime::new());
+    let env = VolatileDatabase::new(20).unwrap();
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            env,
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            time,
+        )
+        .unwrap(),
+    ));
+
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks_with_rng(
+        &producer,
+        &blockchain,
+        Policy::batches_per_epoch() as usize,
+        &mut get_base_seed(),
+    );
+
+    let zk_proof = simulate_merger_wrapper(
+        Path::new(ZKP_TEST_KEYS_PATH),
+        &blockchain,
+        &ZKP_VERIFYING_DATA,
+        &mut get_base_seed(),
+    );
+
+    let network_info = NetworkInfo::from_network_id(blockchain.read().network_id());
+    let genesis_block = network_info.genesis_block().unwrap_macro();
+    let final_block: MacroBlock = blockchain.read().state.election_head.clone();
+
+    let proof_input = ProofInput {
+        previous_block: genesis_block.clone(),
+        previous_proof: None,
+        final_block: final_block.clone(),
+        genesis_header_hash: genesis_block.hash_blake2s().0,
+        prover_keys_path: PathBuf::from("node/keys"),
+    };
+    let zkp_state = ZKPState {
+        latest_block: final_block,
+        latest_proof: zk_proof.proof,
+    };
+    (proof_input, zkp_state)
+}
+
+async fn start_server(generator: Arc<dyn ProofGenerator>) -> String {
+    let server = RemoteProverServer::bind(
+        "127.0.0.1:0",
+        AUTH_KEY.to_vec(),
+        PathBuf::from(SERVER_KEYS_PATH),
+        generator,
+    )
+    .await
+    .unwrap();
+    let address = server.local_addr().unwrap().to_string();
+    tokio::spawn(server.run());
+    address
+}
+
+fn remote_prover(address: String, auth_key: &[u8]) -> RemoteProver {
+    RemoteProver::new(RemoteProverConfig {
+        max_retries: 1,
+        retry_delay: Duration::from_millis(10),
+        ..RemoteProverConfig::new(address, auth_key.to_vec())
+    })
+}
+
+#[test(tokio::test)]
+async fn can_generate_proof_with_remote_prover() {
+    let (proof_input, zkp_state) = proof_input_and_state();
+    let address = start_server(Arc::new(MockProofGenerator {
+        zkp_state: zkp_state.clone(),
+    }))
+    .await;
+
+    let (_send, recv) = channel();
+    let result = remote_prover(address, AUTH_KEY)
+        .generate_proof(recv, proof_input)
+        .await;
+
+    assert_eq!(result, Ok(zkp_state));
+}
+
+#[test(tokio::test)]
+async fn rejects_invalid_remote_proofs() {
+    let (proof_input, mut zkp_state) = proof_input_and_state();
+    zkp_state.latest_proof = Some(Proof::default());
+    let address = start_server(Arc::new(MockProofGenerator { zkp_state })).await;
+
+    let (_send, recv) = channel();
+    let result = remote_prover(address, AUTH_KEY)
+        .generate_proof(recv, proof_input)
+        .await;
+
+    assert_eq!(result, Err(ZKProofGenerationError::InvalidProof));
+}
+
+#[test(tokio::test)]
+async fn rejects_unauthenticated_nodes() {
+    let (proof_input, zkp_state) = proof_input_and_state();
+    let address = start_server(Arc::new(MockProofGenerator { zkp_state })).await;
+
+    let (_send, recv) = channel();
+    let result = remote_prover(address, b"wrong key")
+        .generate_proof(recv, proof_input)
+        .await;
+
+    assert_eq!(result, Err(ZKProofGenerationError::RemoteAuthentication));
+}
+
+#[test(tokio::test)]
+async fn can_cancel_remote_proof_generation() {
+    let (proof_input, _) = proof_input_and_state();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let address = start_server(Arc::new(PendingProofGenerator {
+        cancelled: Arc::clone(&cancelled),
+    }))
+    .await;
+
+    let (send, recv) = channel();
+    let prover = remote_prover(address, AUTH_KEY);
+    let result = tokio::spawn(async move { prover.generate_proof(recv, proof_input).await });
+
+    // Give the request some time to reach the server before cancelling it.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    send.send(()).unwrap();
+    assert_eq!(
+        result.await.unwrap(),
+        Err(ZKProofGenerationError::ChannelError)
+    );
+
+    // The server stops the proof generation once the connection is closed.
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !cancelled.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Proof generation should have been cancelled");
+}
+
+#[test(tokio::test)]
+async fn limits_concurrent_remote_proof_generations() {
+    let (proof_input, zkp_state) = proof_input_and_state();
+    let max_active = Arc::new(AtomicUsize::new(0));
+    let address = start_server(Arc::new(SlowProofGenerator {
+        zkp_state: zkp_state.clone(),
+        active: Arc::new(AtomicUsize::new(0)),
+        max_active: Arc::clone(&max_active),
+    }))
+    .await;
+
+    let requests = (0..3).map(|_| {
+        let prover = remote_prover(address.clone(), AUTH_KEY);
+        let proof_input = proof_input.clone();
+        tokio::spawn(async move {
+            let (_send, recv) = channel();
+            prover.generate_proof(recv, proof_input).await
+        })
+    });
+    for result in futures::future::join_all(requests).await {
+        assert_eq!(result.unwrap(), Ok(zkp_state.clone()));
+    }
+
+    // The requests were served one after the other.
+    assert_eq!(
+        max_active.load(Ordering::SeqCst),
+        RemoteProverServer::DEFAULT_MAX_CONCURRENT_PROOFS
+    );
+}